@@ -1,4 +1,7 @@
-use geop_topology::topology::face::Face;
+use geop_topology::{
+    naming::{trace_faces, EntityId, History, NamedFace},
+    topology::face::Face,
+};
 
 use crate::intersections::face_face::face_face_same_surface_intersection;
 
@@ -6,3 +9,41 @@ pub fn face_face_difference(face_self: &Face, face_other: &Face) -> Vec<Face> {
     assert!(face_self.surface == face_other.surface);
     return face_face_same_surface_intersection(&face_self, &face_other.neg());
 }
+
+// Like face_face_difference, but names the resulting faces and edges after the inputs.
+pub fn face_face_difference_named(
+    face_self: &NamedFace,
+    face_other: &NamedFace,
+    name: EntityId,
+) -> (Vec<NamedFace>, History) {
+    let faces = face_face_difference(&face_self.face, &face_other.face);
+    trace_faces(&name, &[face_self, face_other], faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_topology::naming::{find_duplicate_id, HistoryKind};
+
+    use super::*;
+    use crate::test_util::rectangle;
+
+    #[test]
+    fn test_face_face_difference_named() {
+        let a = NamedFace::new(EntityId::new("a"), rectangle(0.0, 2.0, 0.0, 1.0));
+        let b = NamedFace::new(EntityId::new("b"), rectangle(1.0, 3.0, 0.5, 1.5));
+        let (named, history) = face_face_difference_named(&a, &b, EntityId::new("difference"));
+        assert_eq!(find_duplicate_id(&named), None);
+
+        assert_eq!(named.len(), 1);
+        assert_eq!(named[0].id, a.id);
+        assert_eq!(history.kind(&a.id), Some(HistoryKind::Modified));
+        assert!(history.is_deleted(&b.id));
+
+        // The notch is bounded by the parts of the left and bottom edges of b that are inside of a
+        for id in ["b/edge1/split0", "b/edge2/split0", "a/edge1", "a/edge2"] {
+            assert!(named[0].edge_ids.contains(&EntityId::new(id)), "{}", id);
+        }
+        assert!(history.is_deleted(&EntityId::new("b/edge0")));
+        assert!(history.is_deleted(&EntityId::new("b/edge3")));
+    }
+}
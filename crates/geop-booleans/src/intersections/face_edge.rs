@@ -7,7 +7,10 @@ use geop_geometry::{
 };
 
 use geop_topology::{
-    contains::face_point::{face_point_contains, FacePointContains},
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        face_point::{face_point_contains, FacePointContains},
+    },
     topology::{edge::Edge, face::Face},
 };
 
@@ -56,8 +59,15 @@ pub fn face_edge_intersection(face: &Face, edge: &Edge) -> FaceEdgeIntersection
 
             let mut edges = Vec::<Edge>::new();
             for (p1, p2) in points.iter().zip(points.iter().skip(1)) {
+                // Only a curve without any split points is one piece from end to end.
+                if p1.is_none() && p2.is_none() && points.len() > 2 {
+                    continue;
+                }
                 let m = curve.get_midpoint(*p1, *p2).unwrap();
-                if face_point_contains(face, m) == FacePointContains::Inside {
+                // The pieces beyond the ends of the edge are not part of it, even if the face extends further.
+                if face_point_contains(face, m) == FacePointContains::Inside
+                    && edge_point_contains(edge, m) != EdgePointContains::Outside
+                {
                     edges.push(Edge::new(*p1, *p2, curve.clone()));
                 }
             }
@@ -67,3 +77,49 @@ pub fn face_edge_intersection(face: &Face, edge: &Edge) -> FaceEdgeIntersection
         CurveSurfaceIntersection::None => FaceEdgeIntersection::None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::surfaces::{plane::Plane, surface::Surface};
+    use geop_topology::primitive_objects::{
+        edges::line::{primitive_infinite_line, primitive_line},
+        faces::rectangle::primitive_rectangle,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_face_edge_intersection_unbounded_line() {
+        // Only the piece between the two crossings of the boundary is inside, the line beyond them and the line as a whole are not.
+        let face = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let line = primitive_infinite_line(Point::zero(), Point::unit_x());
+        let FaceEdgeIntersection::Edges(edges) = face_edge_intersection(&face, &line) else {
+            panic!("Expected edges");
+        };
+        assert_eq!(edges.len(), 1);
+        assert_eq!(
+            edges[0],
+            primitive_line(-Point::unit_x(), Point::unit_x()).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_face_edge_intersection_ends_of_edge() {
+        // The plane extends beyond the edge, but the pieces beyond its ends are not part of it.
+        let face = Face::new(
+            vec![],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_y(),
+            ))),
+        );
+        let edge = primitive_line(Point::zero(), Point::unit_x()).unwrap();
+        let FaceEdgeIntersection::Edges(edges) = face_edge_intersection(&face, &edge) else {
+            panic!("Expected edges");
+        };
+        assert_eq!(edges, vec![edge]);
+    }
+}
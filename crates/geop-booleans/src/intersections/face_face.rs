@@ -8,6 +8,7 @@ use geop_geometry::{
 use geop_topology::{
     contains::face_point::{face_point_contains, FacePointContains},
     topology::{edge::Edge, face::Face},
+    topology_error::TopologyResult,
 };
 
 use crate::remesh::face::{face_remesh, face_split, normalize_faces, FaceSplit};
@@ -67,59 +68,66 @@ pub enum FaceFaceIntersection {
 //     todo!()
 // }
 
-pub fn face_face_intersection(face_self: &Face, face_other: &Face) -> FaceFaceIntersection {
-    match surface_surface_intersection(&face_self.surface, &face_other.surface) {
-        FaceSurfaceIntersection::None => FaceFaceIntersection::None,
-        FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
-            let mut points = points
-                .iter()
-                .filter(|p| {
-                    face_point_contains(face_self, **p) == FacePointContains::Inside
-                        && face_point_contains(face_other, **p) == FacePointContains::Inside
-                })
-                .cloned()
-                .collect::<Vec<Point>>();
+// Fails for pairs of surfaces whose intersection is not supported yet.
+pub fn face_face_intersection(
+    face_self: &Face,
+    face_other: &Face,
+) -> TopologyResult<FaceFaceIntersection> {
+    Ok(
+        match surface_surface_intersection(&face_self.surface, &face_other.surface)? {
+            FaceSurfaceIntersection::None => FaceFaceIntersection::None,
+            FaceSurfaceIntersection::CurvesAndPoints(curves, points) => {
+                let mut points = points
+                    .iter()
+                    .filter(|p| {
+                        face_point_contains(face_self, **p) == FacePointContains::Inside
+                            && face_point_contains(face_other, **p) == FacePointContains::Inside
+                    })
+                    .cloned()
+                    .collect::<Vec<Point>>();
 
-            let mut edges = Vec::<Edge>::new();
-            for curve in curves.iter() {
-                match face_edge_intersection(face_self, &Edge::from_curve(curve.clone())) {
-                    FaceEdgeIntersection::Points(ps) => {
-                        for p in ps.iter() {
-                            if face_point_contains(face_other, *p) == FacePointContains::Inside {
-                                points.push(*p);
+                let mut edges = Vec::<Edge>::new();
+                for curve in curves.iter() {
+                    match face_edge_intersection(face_self, &Edge::from_curve(curve.clone())) {
+                        FaceEdgeIntersection::Points(ps) => {
+                            for p in ps.iter() {
+                                if face_point_contains(face_other, *p) == FacePointContains::Inside
+                                {
+                                    points.push(*p);
+                                }
                             }
                         }
-                    }
-                    FaceEdgeIntersection::Edges(es) => {
-                        for e in es.iter() {
-                            match face_edge_intersection(face_other, e) {
-                                FaceEdgeIntersection::Points(ps) => {
-                                    points.extend(ps);
-                                }
-                                FaceEdgeIntersection::Edges(es) => {
-                                    edges.extend(es);
+                        FaceEdgeIntersection::Edges(es) => {
+                            for e in es.iter() {
+                                match face_edge_intersection(face_other, e) {
+                                    FaceEdgeIntersection::Points(ps) => {
+                                        points.extend(ps);
+                                    }
+                                    FaceEdgeIntersection::Edges(es) => {
+                                        edges.extend(es);
+                                    }
+                                    FaceEdgeIntersection::None => {}
                                 }
-                                FaceEdgeIntersection::None => {}
                             }
                         }
+                        FaceEdgeIntersection::None => {}
                     }
-                    FaceEdgeIntersection::None => {}
                 }
-            }
 
-            FaceFaceIntersection::EdgesAndPoints(points, edges)
-        }
-        FaceSurfaceIntersection::Surface(_surface) => {
-            if face_self.surface == face_other.surface {
-                FaceFaceIntersection::Faces(face_face_same_surface_intersection(
-                    face_self, face_other,
-                ))
-            } else {
-                FaceFaceIntersection::Faces(face_face_same_surface_intersection(
-                    face_self,
-                    &face_other.flip(),
-                ))
+                FaceFaceIntersection::EdgesAndPoints(points, edges)
+            }
+            FaceSurfaceIntersection::Surface(_surface) => {
+                if face_self.surface == face_other.surface {
+                    FaceFaceIntersection::Faces(face_face_same_surface_intersection(
+                        face_self, face_other,
+                    ))
+                } else {
+                    FaceFaceIntersection::Faces(face_face_same_surface_intersection(
+                        face_self,
+                        &face_other.flip(),
+                    ))
+                }
             }
-        }
-    }
+        },
+    )
 }
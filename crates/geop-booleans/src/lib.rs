@@ -2,7 +2,10 @@ pub mod contains;
 pub mod difference;
 pub mod intersections;
 pub mod remesh;
+pub mod split;
 pub mod split_if_necessary;
+#[cfg(test)]
+mod test_util;
 pub mod union;
 
 // use topology::scene::Scene;
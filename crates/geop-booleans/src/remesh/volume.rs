@@ -3,14 +3,29 @@ use crate::{
     intersections::face_face::{face_face_intersection, FaceFaceIntersection},
     split_if_necessary::edge_split_face::split_faces_by_edges_if_necessary,
 };
-use geop_topology::topology::{edge::Edge, face::Face, volume::Volume};
+use geop_topology::{
+    naming::{trace_faces, EntityId, History, NamedFace, NamedVolume},
+    topology::{edge::Edge, face::Face, volume::Volume},
+    topology_error::TopologyResult,
+};
 
 // Points are ignored for now.
-pub fn volume_split_edges(volume_self: &Volume, volume_other: &Volume) -> Vec<Edge> {
+// Faces whose bounding boxes are apart are skipped, so that volumes made of several parts only intersect the parts that overlap.
+pub fn volume_split_edges(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<Edge>> {
     let mut edges = Vec::<Edge>::new();
     for face_self in volume_self.all_faces().iter() {
         for face_other in volume_other.all_faces().iter() {
-            match face_face_intersection(face_self, face_other) {
+            if let (Ok(box_self), Ok(box_other)) =
+                (face_self.bounding_box(), face_other.bounding_box())
+            {
+                if !box_self.intersects(&box_other) {
+                    continue;
+                }
+            }
+            match face_face_intersection(face_self, face_other)? {
                 FaceFaceIntersection::EdgesAndPoints(_points, new_edges) => {
                     edges.extend(new_edges);
                 }
@@ -23,7 +38,7 @@ pub fn volume_split_edges(volume_self: &Volume, volume_other: &Volume) -> Vec<Ed
             }
         }
     }
-    edges
+    Ok(edges)
 }
 
 #[derive(Debug)]
@@ -53,13 +68,16 @@ impl VolumeSplit {
     }
 }
 
-pub fn volume_split(volume_self: &Volume, volume_other: &Volume) -> Vec<VolumeSplit> {
-    let intersections = volume_split_edges(volume_self, volume_other);
+pub fn volume_split(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<VolumeSplit>> {
+    let intersections = volume_split_edges(volume_self, volume_other)?;
 
     let faces_self = split_faces_by_edges_if_necessary(volume_self.all_faces(), &intersections);
     let faces_other = split_faces_by_edges_if_necessary(volume_other.all_faces(), &intersections);
 
-    faces_self
+    Ok(faces_self
         .into_iter()
         .map(|face| match volume_face_contains(volume_other, &face) {
             VolumeFaceContains::Inside => VolumeSplit::AinB(face),
@@ -77,5 +95,104 @@ pub fn volume_split(volume_self: &Volume, volume_other: &Volume) -> Vec<VolumeSp
                     VolumeFaceContains::Outside => VolumeSplit::BoutA(face),
                 }),
         )
-        .collect()
+        .collect())
+}
+
+impl VolumeSplit {
+    fn is_self(&self) -> bool {
+        matches!(
+            self,
+            VolumeSplit::AinB(_)
+                | VolumeSplit::AonBSameSide(_)
+                | VolumeSplit::AonBOpSide(_)
+                | VolumeSplit::AoutB(_)
+        )
+    }
+}
+
+// Like volume_split, but names the split faces after the faces of the volume they come from.
+// The faces of both volumes are traced separately, so an intersection edge gets one name on each side.
+pub fn volume_split_named(
+    volume_self: &NamedVolume,
+    volume_other: &NamedVolume,
+    name: EntityId,
+) -> TopologyResult<(Vec<(VolumeSplit, NamedFace)>, History)> {
+    let (splits_self, splits_other): (Vec<VolumeSplit>, Vec<VolumeSplit>) =
+        volume_split(&volume_self.volume, &volume_other.volume)?
+            .into_iter()
+            .partition(|split| split.is_self());
+
+    let mut history = History::new();
+    let mut result = Vec::<(VolumeSplit, NamedFace)>::new();
+    for (volume, splits, role) in [
+        (volume_self, splits_self, "a"),
+        (volume_other, splits_other, "b"),
+    ] {
+        let inputs: Vec<&NamedFace> = volume.faces.iter().collect();
+        let faces = splits.iter().map(|split| split.face().clone()).collect();
+        let (named, traced) = trace_faces(&name.child(role), &inputs, faces);
+        history.extend(traced);
+        result.extend(splits.into_iter().zip(named));
+    }
+    Ok((result, history))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::{efloat::EFloat64, point::Point, transforms::Transform};
+    use geop_topology::{
+        naming::{find_duplicate_id, HistoryKind},
+        primitive_objects::volumes::cube::primitive_cube,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_volume_split_named() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let a = NamedVolume::new(EntityId::new("a"), cube.clone());
+        let b = NamedVolume::new(
+            EntityId::new("b"),
+            cube.transform(Transform::from_translation(Point::from_f64(1.0, 0.5, 0.25))),
+        );
+        let (splits, history) = volume_split_named(&a, &b, EntityId::new("split")).unwrap();
+
+        for (volume, role) in [(&a, "a"), (&b, "b")] {
+            let named: Vec<NamedFace> = splits
+                .iter()
+                .filter(|(split, _)| split.is_self() == (role == "a"))
+                .map(|(_, face)| face.clone())
+                .collect();
+            assert_eq!(find_duplicate_id(&named), None);
+
+            // Each cube has three faces that are cut by the other cube, and three faces that are not touched
+            let kinds: Vec<HistoryKind> = volume
+                .faces
+                .iter()
+                .map(|face| history.kind(&history.trace(&face.id)[0]).unwrap())
+                .collect();
+            assert_eq!(
+                kinds.iter().filter(|k| **k == HistoryKind::Split).count(),
+                3
+            );
+            assert_eq!(
+                kinds
+                    .iter()
+                    .filter(|k| **k == HistoryKind::Modified)
+                    .count(),
+                3
+            );
+            for face in named.iter() {
+                assert_eq!(history.inputs(&face.id).len(), 1);
+            }
+
+            // The intersection of the cubes is a loop of six new edges on each side
+            let generated = EntityId::new("split").child(role);
+            for k in 0..6 {
+                let edge = generated.child(&format!("edge{}", k));
+                assert_eq!(history.kind(&edge), Some(HistoryKind::Generated));
+                assert!(named.iter().any(|face| face.edge_ids.contains(&edge)));
+            }
+        }
+    }
 }
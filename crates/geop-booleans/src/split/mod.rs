@@ -0,0 +1,2 @@
+pub mod section;
+pub mod volume;
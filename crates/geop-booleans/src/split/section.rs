@@ -0,0 +1,294 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::CurveLike,
+    efloat::EFloat64,
+    surfaces::{plane::Plane, surface::Surface, SurfaceLike},
+};
+use geop_topology::{
+    mass_properties::face_area,
+    topology::{contour::Contour, edge::Edge, face::Face, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use crate::{
+    contains::face_contour::{face_contour_contains, FaceContourContains},
+    intersections::face_face::{face_face_intersection, FaceFaceIntersection},
+    remesh::face::normalize_faces,
+    split_if_necessary::edge_split_face::closed_loops,
+};
+
+fn edge_on_surface(edge: &Edge, surface: &Surface) -> bool {
+    [0.0, 0.25, 0.5, 0.75, 1.0].iter().all(|t| {
+        edge.curve
+            .interpolate(edge.start, edge.end, *t)
+            .is_ok_and(|p| surface.on_surface(p))
+    })
+}
+
+// Which side of the surface the face leaves the edge to. Zero if the face runs along the surface.
+fn side_of_edge(face: &Face, edge: &Edge, surface: &Surface) -> Option<EFloat64> {
+    let p = edge.get_midpoint();
+    let tangent = edge.curve.tangent(p).ok()?;
+    // The face is on the left of its boundary.
+    let inwards = face.surface.normal(p).cross(tangent);
+    Some(inwards.dot(surface.normal(p)))
+}
+
+// Edges of the volume on the surface, where the faces on both sides of the edge leave to opposite sides of the surface.
+// Edges where the surface only touches the volume are not part of the cut.
+fn edges_on_surface(volume: &Volume, surface: &Surface) -> Vec<Edge> {
+    let mut sides = Vec::<(Edge, EFloat64)>::new();
+    for face in volume.all_faces().iter() {
+        for edge in face.all_edges() {
+            if !edge_on_surface(&edge, surface) {
+                continue;
+            }
+            if let Some(side) = side_of_edge(face, &edge, surface) {
+                sides.push((edge, side));
+            }
+        }
+    }
+    let mut edges = Vec::<Edge>::new();
+    for (edge, side) in sides.iter() {
+        let crossing = sides
+            .iter()
+            .any(|(other, other_side)| other == edge && *side > 0.0 && *other_side < 0.0);
+        if crossing {
+            edges.push(edge.clone());
+        }
+    }
+    edges
+}
+
+// The edges where the faces of the volume cross the surface. The surface is taken as a face without boundaries.
+// Edges of the volume that the surface cuts through are included, as the faces next to them do not report them.
+pub(crate) fn section_edges(volume: &Volume, surface: Rc<Surface>) -> TopologyResult<Vec<Edge>> {
+    let mut edges = edges_on_surface(volume, &surface);
+    let tool = Face::new(vec![], surface);
+    for face in volume.all_faces().iter() {
+        if let FaceFaceIntersection::EdgesAndPoints(_points, new_edges) =
+            face_face_intersection(face, &tool)?
+        {
+            edges.extend(new_edges);
+        }
+    }
+    Ok(edges)
+}
+
+// Chains the section edges into loops. Edges that are left over mean that a cut could not be followed, so the section would be incomplete.
+pub(crate) fn section_loops(edges: Vec<Edge>) -> TopologyResult<Vec<Contour>> {
+    let (loops, open) = closed_loops(edges);
+    if let Some(edge) = open.first() {
+        return Err(TopologyError::new(format!(
+            "Section does not close into loops at {}",
+            edge
+        )));
+    }
+    Ok(loops)
+}
+
+// The faces on the surface that are bounded by the loops, with the normal of the surface.
+// Nested loops alternate between outer boundaries and holes.
+pub(crate) fn section_faces(loops: Vec<Contour>, surface: Rc<Surface>) -> Vec<Face> {
+    let loops: Vec<Contour> = loops
+        .into_iter()
+        .map(
+            |contour| match face_area(&Face::new(vec![contour.clone()], surface.clone())) > 0.0 {
+                true => contour,
+                false => contour.flip(),
+            },
+        )
+        .collect();
+    let contours = loops
+        .iter()
+        .map(|contour| {
+            let depth = loops
+                .iter()
+                .filter(|other| {
+                    face_contour_contains(
+                        &Face::new(vec![(*other).clone()], surface.clone()),
+                        contour,
+                    ) == FaceContourContains::Inside
+                })
+                .count();
+            match depth % 2 {
+                0 => contour.clone(),
+                _ => contour.flip(),
+            }
+        })
+        .collect();
+    normalize_faces(contours, surface)
+}
+
+// The cross-section of the volume with the plane. The faces lie on the plane and have holes, where the volume has.
+// A plane that only touches the volume, e.g. along one of its faces, gives no section.
+// Fails if the plane cuts a face along a curve that is not supported, like the parabola on a cone, or if the cut does not close into loops.
+pub fn section(volume: &Volume, plane: &Plane) -> TopologyResult<Vec<Face>> {
+    let surface = Rc::new(Surface::Plane(plane.clone()));
+    let loops = section_loops(section_edges(volume, surface.clone())?)?;
+    Ok(section_faces(loops, surface))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use geop_geometry::{curves::curve::Curve, efloat::EFloat64, point::Point};
+    use geop_topology::{
+        operations::{extrude::extrude, loft::loft},
+        primitive_objects::{
+            edges::{circle::primitive_circle, ellipse::primitive_ellipse},
+            faces::{rectangle::primitive_rectangle, sphere::primitive_sphere},
+            volumes::cube::primitive_cube,
+        },
+        topology::shell::Shell,
+    };
+
+    use super::*;
+
+    fn plane_at(z: f64) -> Plane {
+        Plane::new(
+            Point::from_f64(0.0, 0.0, z),
+            Point::unit_x(),
+            Point::unit_y(),
+        )
+    }
+
+    fn areas(faces: &[Face]) -> Vec<f64> {
+        faces.iter().map(face_area).collect()
+    }
+
+    #[test]
+    fn test_section_with_hole() {
+        // A square plate with a round hole, extruded from z = 0 to z = 1.
+        let square = primitive_rectangle(Point::zero(), Point::unit_y(), Point::unit_x());
+        let hole = Contour::new(vec![primitive_circle(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(0.5),
+        )]);
+        let plate = Face::new(
+            vec![square.boundaries[0].clone(), hole],
+            square.surface.clone(),
+        );
+        let volume = extrude(plate, Point::unit_z()).unwrap();
+
+        let faces = section(&volume, &plane_at(0.5)).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].boundaries.len(), 2);
+        assert!((areas(&faces)[0] - (4.0 - PI / 4.0)).abs() < 1e-6);
+
+        assert!(section(&volume, &plane_at(2.0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_section_sphere() {
+        let sphere = Volume::new(
+            Shell::new(vec![primitive_sphere(Point::zero(), EFloat64::one())]),
+            vec![],
+        );
+        let faces = section(&sphere, &plane_at(0.5)).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!((areas(&faces)[0] - PI * 0.75).abs() < 1e-6);
+
+        assert!(section(&sphere, &plane_at(2.0)).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_section_cone() {
+        let circle = |radius: f64, z: f64| {
+            Contour::new(vec![primitive_circle(
+                Point::from_f64(0.0, 0.0, z),
+                Point::unit_z(),
+                EFloat64::from(radius),
+            )])
+        };
+        let frustum = loft(&[circle(1.0, 0.0), circle(2.0, 2.0)], false).unwrap();
+        let faces = section(&frustum, &plane_at(1.0)).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!((areas(&faces)[0] - PI * 2.25).abs() < 1e-6);
+
+        // A tilted plane cuts the cone along an ellipse.
+        let tilted = Plane::new(
+            Point::unit_z(),
+            Point::from_f64(1.0, 0.0, 0.2),
+            Point::unit_y(),
+        );
+        let faces = section(&frustum, &tilted).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!(matches!(
+            faces[0].boundaries[0].edges[0].curve,
+            Curve::Ellipse(_)
+        ));
+
+        // A plane parallel to the axis cuts the cone along a hyperbola, which is not supported.
+        let side = Plane::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        assert!(section(&frustum, &side).is_err());
+    }
+
+    #[test]
+    fn test_section_elliptic_cylinder() {
+        let ellipse = primitive_ellipse(
+            Point::zero(),
+            -Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let base = Face::new(
+            vec![Contour::new(vec![ellipse])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(base, Point::from_f64(0.0, 0.0, 3.0)).unwrap();
+        let faces = section(&cylinder, &plane_at(1.0)).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!((areas(&faces)[0] - 2.0 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_section_cube_through_vertices_and_edges() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+
+        // The plane x = y cuts along two vertical edges, which bound a 2 x 2 sqrt(2) rectangle.
+        let diagonal = Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 1.0, 0.0),
+            Point::unit_z(),
+        );
+        let faces = section(&cube, &diagonal).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!((areas(&faces)[0] - 4.0 * 2.0f64.sqrt()).abs() < 1e-6);
+
+        // The plane x + y + z = 1 goes through three vertices, which span a triangle with sides of 2 sqrt(2).
+        let corners = Plane::new(
+            Point::from_f64(1.0, 1.0, -1.0),
+            Point::from_f64(1.0, -1.0, 0.0),
+            Point::from_f64(1.0, 0.0, -1.0),
+        );
+        let faces = section(&cube, &corners).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert!((areas(&faces)[0] - 2.0 * 3.0f64.sqrt()).abs() < 1e-6);
+
+        // Planes that only touch a vertex or an edge give no section.
+        let vertex = Plane::new(
+            Point::from_f64(1.0, 1.0, 1.0),
+            Point::from_f64(1.0, -1.0, 0.0),
+            Point::from_f64(1.0, 0.0, -1.0),
+        );
+        assert!(section(&cube, &vertex).unwrap().is_empty());
+        let edge = Plane::new(
+            Point::from_f64(1.0, 1.0, 0.0),
+            Point::from_f64(1.0, -1.0, 0.0),
+            Point::unit_z(),
+        );
+        assert!(section(&cube, &edge).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,350 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{surface::Surface, SurfaceLike},
+};
+use geop_topology::{
+    contains::volume_point::{volume_point_contains, VolumePointContains},
+    mass_properties::volume_mass_properties,
+    topology::{contour::Contour, face::Face, shell::Shell, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+use crate::{
+    contains::face_edge::{face_edge_contains, FaceEdgeContains},
+    split_if_necessary::edge_split_face::{split_face_by_loop, split_faces_by_edges_if_necessary},
+};
+
+use super::section::{section_edges, section_loops};
+
+#[derive(Debug, PartialEq)]
+enum Side {
+    Below,
+    Above,
+}
+
+// The normal of the surface at the point closest to p. Planes have the same normal everywhere, so p does not have to be projected onto them.
+fn normal_near(surface: &Surface, p: Point) -> Point {
+    match surface {
+        Surface::Plane(plane) => plane.u_slope.cross(plane.v_slope),
+        _ => surface.normal(surface.project(p)),
+    }
+}
+
+// How far p is above the surface, along its normal. Only the sign is meaningful.
+fn signed_offset(surface: &Surface, p: Point) -> EFloat64 {
+    match surface {
+        Surface::Plane(plane) => (p - plane.basis).dot(normal_near(surface, p)),
+        _ => (p - surface.project(p)).dot(normal_near(surface, p)),
+    }
+}
+
+// The side of the surface the face is on. Faces on the surface are below, if their normal points along the normal of the surface.
+fn face_side(face: &Face, surface: &Surface) -> Side {
+    let p = face.inner_point();
+    let offset = signed_offset(surface, p);
+    if offset > 0.0 {
+        return Side::Above;
+    }
+    if offset < 0.0 {
+        return Side::Below;
+    }
+    match face.surface.normal(p).dot(normal_near(surface, p)) > 0.0 {
+        true => Side::Below,
+        false => Side::Above,
+    }
+}
+
+// Groups the faces into closed shells, which are connected by common edges. Neighbours use an edge in opposite directions.
+fn connected_shells(mut faces: Vec<Face>) -> Vec<Shell> {
+    let mut shells = Vec::<Shell>::new();
+    while let Some(face) = faces.pop() {
+        let mut group = vec![face];
+        let mut i = 0;
+        while i < group.len() {
+            let edges = group[i].all_edges();
+            let (connected, rest): (Vec<Face>, Vec<Face>) = faces.into_iter().partition(|f| {
+                f.all_edges()
+                    .iter()
+                    .any(|e| edges.contains(e) || edges.contains(&e.flip()))
+            });
+            group.extend(connected);
+            faces = rest;
+            i += 1;
+        }
+        shells.push(Shell::new(group));
+    }
+    shells
+}
+
+// Every shell that encloses a negative volume is a cavity of the body around it.
+fn assemble_volumes(faces: Vec<Face>) -> Vec<Volume> {
+    let (boundaries, cavities): (Vec<Shell>, Vec<Shell>) =
+        connected_shells(faces).into_iter().partition(|shell| {
+            volume_mass_properties(&Volume::new(shell.clone(), vec![]))
+                .map_or(true, |properties| properties.volume > 0.0)
+        });
+    let mut volumes: Vec<Volume> = boundaries
+        .into_iter()
+        .map(|shell| Volume::new(shell, vec![]))
+        .collect();
+    for cavity in cavities {
+        let p = cavity.faces[0].inner_point();
+        match volumes
+            .iter_mut()
+            .find(|v| matches!(volume_point_contains(v, p), VolumePointContains::Inside))
+        {
+            Some(volume) => volume.cavities.push(cavity),
+            None => volumes.push(Volume::new(cavity, vec![])),
+        }
+    }
+    volumes
+}
+
+// The loop runs against the face below the surface that shares its edges, so that the cap closes the part below.
+fn orient_loop(contour: Contour, below: &[Face]) -> Contour {
+    let edge = &contour.edges[0];
+    let same_direction = below
+        .iter()
+        .flat_map(|face| face.all_edges())
+        .any(|e| e == *edge);
+    match same_direction {
+        true => contour.flip(),
+        false => contour,
+    }
+}
+
+// The faces on the surface that close the part below it. The surface is cut along every loop, starting with the whole surface.
+// The caps are the pieces that are on the left of all of their loops. This works on surfaces, where loops do not bound a disk, like a loop around a cylinder.
+fn cap_faces(loops: Vec<Contour>, below: &[Face], surface: Rc<Surface>) -> Vec<Face> {
+    // Each piece remembers if it is on the right of one of its loops.
+    let mut pieces = vec![(Face::new(vec![], surface), false)];
+    for contour in loops.into_iter().map(|c| orient_loop(c, below)) {
+        let i = pieces
+            .iter()
+            .position(|(piece, _)| {
+                face_edge_contains(piece, &contour.edges[0]) == FaceEdgeContains::Inside
+            })
+            .unwrap_or(0);
+        let (piece, right) = pieces.remove(i);
+        let [left_piece, right_piece]: [Face; 2] =
+            split_face_by_loop(&piece, &contour).try_into().unwrap();
+        pieces.push((left_piece, right));
+        pieces.push((right_piece, true));
+    }
+    pieces
+        .into_iter()
+        .filter(|(_, right)| !right)
+        .map(|(piece, _)| piece)
+        .collect()
+}
+
+// Splits the volume by the surface into the parts below and above it, where above is the side the normal points to.
+// The cut is closed with faces on the surface. Each side can consist of several bodies, or none at all.
+// Fails if the surface cuts a face along a curve that is not supported, or if the cut does not close into loops.
+pub fn split_volume(
+    volume: &Volume,
+    surface: &Surface,
+) -> TopologyResult<(Vec<Volume>, Vec<Volume>)> {
+    let surface = Rc::new(surface.clone());
+    let edges = section_edges(volume, surface.clone())?;
+
+    let (mut below, mut above): (Vec<Face>, Vec<Face>) =
+        split_faces_by_edges_if_necessary(volume.all_faces(), &edges)
+            .into_iter()
+            .partition(|face| face_side(face, &surface) == Side::Below);
+    if below.is_empty() || above.is_empty() {
+        return Ok(match below.is_empty() {
+            true => (vec![], vec![volume.clone()]),
+            false => (vec![volume.clone()], vec![]),
+        });
+    }
+
+    let loops = section_loops(edges)?;
+    let caps = cap_faces(loops, &below, surface);
+    below.extend(caps.iter().cloned());
+    above.extend(caps.iter().map(|face| face.flip()));
+    Ok((assemble_volumes(below), assemble_volumes(above)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use geop_geometry::{
+        efloat::EFloat64,
+        point::Point,
+        surfaces::{cylinder::Cylinder, plane::Plane},
+    };
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::{
+            edges::circle::primitive_circle, faces::sphere::primitive_sphere,
+            volumes::cube::primitive_cube,
+        },
+        topology::shell::Shell,
+        validation::check_volume,
+    };
+
+    use super::*;
+
+    // Both sides have to be a single valid body with the given volume.
+    fn assert_split(split: (Vec<Volume>, Vec<Volume>), below: f64, above: f64) {
+        for (volumes, expected) in [(split.0, below), (split.1, above)] {
+            assert_eq!(volumes.len(), 1);
+            assert!(check_volume(&volumes[0]).is_valid());
+            let volume = volume_mass_properties(&volumes[0]).unwrap().volume;
+            assert!((volume - expected).abs() < 1e-6);
+        }
+    }
+
+    fn cube() -> Volume {
+        primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two())
+    }
+
+    // A cylinder of radius 1 from z = 0 to z = 2.
+    fn cylinder() -> Volume {
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap()
+    }
+
+    #[test]
+    fn test_split_cube() {
+        let plane = Surface::Plane(Plane::new(Point::zero(), Point::unit_x(), Point::unit_y()));
+        assert_split(split_volume(&cube(), &plane).unwrap(), 4.0, 4.0);
+
+        // A plane that misses the cube leaves it on one side.
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        let (below, above) = split_volume(&cube(), &plane).unwrap();
+        assert_eq!((below.len(), above.len()), (1, 0));
+    }
+
+    #[test]
+    fn test_split_cylinder() {
+        let plane = Surface::Plane(Plane::new(
+            Point::unit_z(),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        let split = split_volume(&cylinder(), &plane).unwrap();
+        assert_eq!(split.0[0].all_faces().len(), 3);
+        assert_split(split, PI, PI);
+
+        // A tilted plane through the center cuts the side along ellipses and halves the cylinder.
+        let plane = Surface::Plane(Plane::new(
+            Point::unit_z(),
+            Point::unit_x(),
+            Point::from_f64(0.0, 1.0, 0.3),
+        ));
+        assert_split(split_volume(&cylinder(), &plane).unwrap(), PI, PI);
+    }
+
+    #[test]
+    fn test_split_ball() {
+        // A plane above the center cuts a cap of height h = 1 / 2 off the unit ball, with a volume of pi h^2 (3 r - h) / 3.
+        let ball = Volume::new(
+            Shell::new(vec![primitive_sphere(Point::zero(), EFloat64::one())]),
+            vec![],
+        );
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(0.0, 0.0, 0.5),
+            Point::unit_x(),
+            Point::unit_y(),
+        ));
+        let cap = PI * 0.25 * 2.5 / 3.0;
+        assert_split(
+            split_volume(&ball, &plane).unwrap(),
+            4.0 / 3.0 * PI - cap,
+            cap,
+        );
+    }
+
+    #[test]
+    fn test_split_by_cylinder() {
+        // The cylinder cuts a round bar out of the cube, which leaves a cube with a hole.
+        let surface = Surface::Cylinder(Cylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(0.5),
+            true,
+        ));
+        let split = split_volume(&cube(), &surface).unwrap();
+        assert_eq!(split.0[0].all_faces().len(), 3);
+        assert_eq!(split.1[0].all_faces().len(), 7);
+        assert_split(split, PI / 4.0 * 2.0, 8.0 - PI / 4.0 * 2.0);
+    }
+
+    #[test]
+    fn test_split_cube_through_vertices_and_edges() {
+        // The plane x = y cuts through two vertical edges and along the diagonals of the top and bottom faces.
+        let plane = Surface::Plane(Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 1.0, 0.0),
+            Point::unit_z(),
+        ));
+        assert_split(split_volume(&cube(), &plane).unwrap(), 4.0, 4.0);
+
+        // The plane x + y + z = 1 goes through three vertices and cuts off the corner at (1, 1, 1).
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(1.0, 1.0, -1.0),
+            Point::from_f64(1.0, -1.0, 0.0),
+            Point::from_f64(1.0, 0.0, -1.0),
+        ));
+        assert_split(
+            split_volume(&cube(), &plane).unwrap(),
+            8.0 - 4.0 / 3.0,
+            4.0 / 3.0,
+        );
+
+        // The plane x + 2 y + 4 z = -1 goes through the vertex (1, 1, -1) only.
+        let plane = Surface::Plane(Plane::new(
+            Point::from_f64(1.0, 1.0, -1.0),
+            Point::from_f64(2.0, -1.0, 0.0),
+            Point::from_f64(4.0, 0.0, -1.0),
+        ));
+        let (below, above) = split_volume(&cube(), &plane).unwrap();
+        assert_eq!((below.len(), above.len()), (1, 1));
+        let total: f64 = below
+            .iter()
+            .chain(above.iter())
+            .map(|volume| {
+                assert!(check_volume(volume).is_valid());
+                volume_mass_properties(volume).unwrap().volume
+            })
+            .sum();
+        assert!((total - 8.0).abs() < 1e-6, "{}", total);
+
+        // Planes that only touch the cube at a vertex or along an edge leave it on one side.
+        let corner = Surface::Plane(Plane::new(
+            Point::from_f64(1.0, 1.0, 1.0),
+            Point::from_f64(1.0, -1.0, 0.0),
+            Point::from_f64(1.0, 0.0, -1.0),
+        ));
+        let (below, above) = split_volume(&cube(), &corner).unwrap();
+        assert_eq!((below.len(), above.len()), (1, 0));
+        let edge = Surface::Plane(Plane::new(
+            Point::from_f64(1.0, 1.0, 0.0),
+            Point::from_f64(1.0, -1.0, 0.0),
+            Point::unit_z(),
+        ));
+        let (below, above) = split_volume(&cube(), &edge).unwrap();
+        assert_eq!(below.len() + above.len(), 1);
+    }
+}
@@ -3,13 +3,17 @@
 //     split_if_necessary::point_split_edge::split_contours_by_points_if_necessary,
 // };
 
+use geop_geometry::curves::curve::Curve;
 use geop_topology::{
     contains::{contour_point::contour_point_contains, edge_point::EdgePointContains},
     topology::{contour::Contour, edge::Edge, face::Face},
 };
 
 use crate::{
-    contains::face_edge::{face_edge_contains, FaceEdgeContains},
+    contains::{
+        face_contour::{face_contour_contains, FaceContourContains},
+        face_edge::{face_edge_contains, FaceEdgeContains},
+    },
     remesh::face::normalize_faces,
 };
 
@@ -145,9 +149,102 @@ pub fn split_face_by_edge_if_necessary(face: &Face, edge: &Edge) -> Vec<Face> {
     }
 }
 
+// Whether the edge runs through the inside of the face without touching its boundaries.
+// Edges without start and end have to be closed curves.
+fn is_interior_edge(face: &Face, edge: &Edge) -> bool {
+    let bounded = matches!(
+        (&edge.curve, edge.start, edge.end),
+        (_, Some(_), Some(_)) | (Curve::Circle(_) | Curve::Ellipse(_), None, None)
+    );
+    bounded
+        && face_edge_contains(face, edge) == FaceEdgeContains::Inside
+        && [edge.start, edge.end].iter().flatten().all(|p| {
+            face.boundaries
+                .iter()
+                .all(|c| contour_point_contains(c, *p) == EdgePointContains::Outside)
+        })
+}
+
+// Chains the edges into closed loops. Edges that do not close a loop are returned separately.
+pub(crate) fn closed_loops(edges: Vec<Edge>) -> (Vec<Contour>, Vec<Edge>) {
+    // Faces that meet at a cut can both report it, in either direction.
+    let mut unique = Vec::<Edge>::new();
+    for edge in edges {
+        if !unique.contains(&edge) {
+            unique.push(edge);
+        }
+    }
+    let mut loops = Vec::<Contour>::new();
+    let mut rest = Vec::<Edge>::new();
+    while let Some(first) = unique.pop() {
+        let mut chain = vec![first];
+        while chain[0].start != chain[chain.len() - 1].end {
+            let end = chain[chain.len() - 1].end;
+            match unique.iter().position(|e| e.start == end || e.end == end) {
+                Some(i) => {
+                    let next = unique.remove(i);
+                    match next.start == end {
+                        true => chain.push(next),
+                        false => chain.push(next.flip()),
+                    }
+                }
+                None => break,
+            }
+        }
+        match chain[0].start == chain[chain.len() - 1].end {
+            true => loops.push(Contour::new(chain)),
+            false => rest.extend(chain),
+        }
+    }
+    (loops, rest)
+}
+
+// Chains the interior edges into closed loops. All other edges are returned separately.
+fn interior_loops(face: &Face, edges: &[Edge]) -> (Vec<Contour>, Vec<Edge>) {
+    let (interior, mut rest): (Vec<Edge>, Vec<Edge>) = edges
+        .iter()
+        .cloned()
+        .partition(|e| is_interior_edge(face, e));
+    let (loops, open) = closed_loops(interior);
+    rest.extend(open);
+    (loops, rest)
+}
+
+// Cuts the face along the loop. The face on the left of the loop gets the loop and the boundaries on its side, the other face gets the flipped loop and the remaining boundaries.
+// On a plane this is the face with a hole and the face inside of the loop. On a cylinder, a loop around the axis separates the two ends.
+pub(crate) fn split_face_by_loop(face: &Face, contour: &Contour) -> Vec<Face> {
+    let left = Face::new(vec![contour.clone()], face.surface.clone());
+    let (left_boundaries, right_boundaries): (Vec<Contour>, Vec<Contour>) = face
+        .boundaries
+        .iter()
+        .cloned()
+        .partition(|c| face_contour_contains(&left, c) == FaceContourContains::Inside);
+
+    let mut left_face = vec![contour.clone()];
+    left_face.extend(left_boundaries);
+    let mut right_face = vec![contour.flip()];
+    right_face.extend(right_boundaries);
+    vec![
+        Face::new(left_face, face.surface.clone()),
+        Face::new(right_face, face.surface.clone()),
+    ]
+}
+
 pub fn split_face_by_edges_if_necessary(face: &Face, edges: &[Edge]) -> Vec<Face> {
+    // Closed loops inside of the face, like the footprint of a boss, cannot be split off one edge at a time.
+    let (loops, edges) = interior_loops(face, edges);
     let mut result = vec![face.clone()];
-    for c in edges {
+    for contour in loops.iter() {
+        let mut new_result = Vec::<Face>::new();
+        for face in result.iter() {
+            match contour.edges.iter().all(|e| is_interior_edge(face, e)) {
+                true => new_result.extend(split_face_by_loop(face, contour)),
+                false => new_result.push(face.clone()),
+            }
+        }
+        result = new_result;
+    }
+    for c in edges.iter() {
         let mut new_result = Vec::<Face>::new();
         for face in result.iter() {
             new_result.extend(split_face_by_edge_if_necessary(face, c));
@@ -164,3 +261,99 @@ pub fn split_faces_by_edges_if_necessary(faces: Vec<Face>, edges: &Vec<Edge>) ->
     }
     result
 }
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use geop_geometry::{efloat::EFloat64, point::Point};
+    use geop_topology::{
+        mass_properties::face_area,
+        primitive_objects::{
+            edges::{circle::primitive_circle, line::primitive_line},
+            faces::rectangle::primitive_rectangle,
+        },
+    };
+
+    use super::*;
+
+    fn face() -> Face {
+        primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y())
+    }
+
+    fn square_edges(size: f64) -> Vec<Edge> {
+        let corners = [(1.0, 1.0), (-1.0, 1.0), (-1.0, -1.0), (1.0, -1.0)]
+            .map(|(x, y)| Point::from_f64(x * size, y * size, 0.0));
+        (0..4)
+            .map(|i| primitive_line(corners[i], corners[(i + 1) % 4]).unwrap())
+            .collect()
+    }
+
+    // Areas of the faces, with the number of boundaries, sorted by area.
+    fn areas(faces: &[Face]) -> Vec<(usize, f64)> {
+        let mut areas: Vec<(usize, f64)> = faces
+            .iter()
+            .map(|face| (face.boundaries.len(), face_area(face)))
+            .collect();
+        areas.sort_by(|a, b| a.1.partial_cmp(&b.1).unwrap());
+        areas
+    }
+
+    fn assert_areas(faces: &[Face], expected: &[(usize, f64)]) {
+        let areas = areas(faces);
+        assert_eq!(areas.len(), expected.len());
+        for ((boundaries, area), (expected_boundaries, expected_area)) in
+            areas.iter().zip(expected.iter())
+        {
+            assert_eq!(boundaries, expected_boundaries);
+            assert!(
+                (area - expected_area).abs() < 1e-6,
+                "{} {}",
+                area,
+                expected_area
+            );
+        }
+    }
+
+    #[test]
+    fn test_split_face_by_inner_square() {
+        // The edges are given out of order and in both directions, and are chained into a loop.
+        let mut edges = square_edges(0.5);
+        edges.swap(0, 2);
+        edges[1] = edges[1].flip();
+        let faces = split_face_by_edges_if_necessary(&face(), &edges);
+        assert_areas(&faces, &[(1, 1.0), (2, 3.0)]);
+    }
+
+    #[test]
+    fn test_split_face_by_inner_circle() {
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::from(0.5));
+        let faces = split_face_by_edges_if_necessary(&face(), &[circle]);
+        assert_areas(&faces, &[(1, PI / 4.0), (2, 4.0 - PI / 4.0)]);
+    }
+
+    #[test]
+    fn test_split_face_by_nested_loops() {
+        // A circle inside of a square gives a ring between them.
+        let mut edges = square_edges(0.75);
+        edges.push(primitive_circle(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::from(0.5),
+        ));
+        let faces = split_face_by_edges_if_necessary(&face(), &edges);
+        assert_areas(
+            &faces,
+            &[(1, PI / 4.0), (2, 2.25 - PI / 4.0), (2, 4.0 - 2.25)],
+        );
+    }
+
+    #[test]
+    fn test_split_face_by_open_edges() {
+        // Edges that do not close a loop are not split off as a loop.
+        let edges = square_edges(0.5)[..3].to_vec();
+        let (loops, rest) = interior_loops(&face(), &edges);
+        assert!(loops.is_empty());
+        assert_eq!(rest.len(), 3);
+    }
+}
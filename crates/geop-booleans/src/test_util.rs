@@ -0,0 +1,13 @@
+use geop_geometry::point::Point;
+use geop_topology::{
+    primitive_objects::faces::rectangle::primitive_rectangle, topology::face::Face,
+};
+
+// The rectangle [x0, x1] x [y0, y1] in the xy plane.
+pub fn rectangle(x0: f64, x1: f64, y0: f64, y1: f64) -> Face {
+    primitive_rectangle(
+        Point::from_f64((x0 + x1) / 2.0, (y0 + y1) / 2.0, 0.0),
+        Point::from_f64((x1 - x0) / 2.0, 0.0, 0.0),
+        Point::from_f64(0.0, (y1 - y0) / 2.0, 0.0),
+    )
+}
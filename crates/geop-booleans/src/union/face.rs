@@ -1,5 +1,8 @@
 use crate::remesh::face::{face_remesh, face_split, normalize_faces, FaceSplit};
-use geop_topology::topology::face::Face;
+use geop_topology::{
+    naming::{trace_faces, EntityId, History, NamedFace},
+    topology::face::Face,
+};
 
 pub fn face_face_union(face_self: &Face, face_other: &Face) -> Vec<Face> {
     assert!(
@@ -24,3 +27,42 @@ pub fn face_face_union(face_self: &Face, face_other: &Face) -> Vec<Face> {
     let contours = face_remesh(edges);
     return normalize_faces(contours, face_self.surface.clone());
 }
+
+// Like face_face_union, but names the resulting faces and edges after the inputs.
+pub fn face_face_union_named(
+    face_self: &NamedFace,
+    face_other: &NamedFace,
+    name: EntityId,
+) -> (Vec<NamedFace>, History) {
+    let faces = face_face_union(&face_self.face, &face_other.face);
+    trace_faces(&name, &[face_self, face_other], faces)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_topology::naming::{find_duplicate_id, HistoryKind};
+
+    use super::*;
+    use crate::test_util::rectangle;
+
+    #[test]
+    fn test_face_face_union_named() {
+        let a = NamedFace::new(EntityId::new("a"), rectangle(0.0, 2.0, 0.0, 1.0));
+        let b = NamedFace::new(EntityId::new("b"), rectangle(1.0, 3.0, 0.5, 1.5));
+        let (named, history) = face_face_union_named(&a, &b, EntityId::new("union"));
+        assert_eq!(find_duplicate_id(&named), None);
+
+        assert_eq!(named.len(), 1);
+        assert_eq!(named[0].id, a.id);
+        assert_eq!(history.kind(&a.id), Some(HistoryKind::Merged));
+        assert_eq!(history.trace(&b.id), vec![a.id.clone()]);
+
+        // The left edge of a is outside of b and kept. The top edge of a is cut where it enters b.
+        assert!(named[0].edge_ids.contains(&EntityId::new("a/edge1")));
+        assert_eq!(
+            history.trace(&EntityId::new("a/edge0")),
+            vec![EntityId::new("a/edge0/split0")]
+        );
+        assert!(named[0].edge_ids.contains(&EntityId::new("a/edge0/split0")));
+    }
+}
@@ -0,0 +1,238 @@
+use geop_geometry::{curves::line::Line, efloat::EFloat64, point::Point};
+use geop_topology::{
+    contains::volume_point::{volume_point_contains, VolumePointContains},
+    operations::pattern::{circular_pattern, linear_pattern},
+    topology::{shell::Shell, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+use crate::remesh::volume::{volume_split, volume_split_edges, VolumeSplit};
+
+fn contains(volume: &Volume, other: &Volume) -> bool {
+    let p = other.all_faces()[0].inner_point();
+    matches!(
+        volume_point_contains(volume, p),
+        VolumePointContains::Inside
+    )
+}
+
+// The union of two volumes without cavities. It is a single volume if they overlap or touch, and both volumes otherwise.
+// Only planar faces are supported yet, and cuts through the boundary of a face are not always split cleanly.
+pub fn volume_volume_union(
+    volume_self: &Volume,
+    volume_other: &Volume,
+) -> TopologyResult<Vec<Volume>> {
+    let disjoint = vec![volume_self.clone(), volume_other.clone()];
+    if let (Ok(box_self), Ok(box_other)) = (volume_self.bounding_box(), volume_other.bounding_box())
+    {
+        if !box_self.intersects(&box_other) {
+            return Ok(disjoint);
+        }
+    }
+    if volume_split_edges(volume_self, volume_other)?.is_empty() {
+        if contains(volume_self, volume_other) {
+            return Ok(vec![volume_self.clone()]);
+        }
+        if contains(volume_other, volume_self) {
+            return Ok(vec![volume_other.clone()]);
+        }
+        return Ok(disjoint);
+    }
+
+    let faces = volume_split(volume_self, volume_other)?
+        .into_iter()
+        .filter(|split| match split {
+            VolumeSplit::AinB(_) => false,
+            VolumeSplit::AonBSameSide(_) => true,
+            VolumeSplit::AonBOpSide(_) => false,
+            VolumeSplit::AoutB(_) => true,
+            VolumeSplit::BinA(_) => false,
+            VolumeSplit::BonASameSide(_) => false,
+            VolumeSplit::BonAOpSide(_) => false,
+            VolumeSplit::BoutA(_) => true,
+        })
+        .map(|split| split.face().clone())
+        .collect();
+    Ok(vec![Volume::new(Shell::new(faces), vec![])])
+}
+
+// Unions all volumes. Every volume is merged with all bodies it overlaps, so the result has one body per connected group.
+pub fn volumes_union(volumes: &[Volume]) -> TopologyResult<Vec<Volume>> {
+    let mut bodies = Vec::<Volume>::new();
+    for volume in volumes.iter() {
+        let mut merged = volume.clone();
+        let mut rest = Vec::<Volume>::new();
+        for body in bodies.drain(..) {
+            match volume_volume_union(&merged, &body)?.as_slice() {
+                [union] => merged = union.clone(),
+                _ => rest.push(body),
+            }
+        }
+        rest.push(merged);
+        bodies = rest;
+    }
+    Ok(bodies)
+}
+
+// Like linear_pattern, but unions the copies into the base body. Overlapping copies are merged with each other first.
+pub fn linear_pattern_union(
+    base: &Volume,
+    volume: &Volume,
+    direction: Point,
+    count: usize,
+    spacing: EFloat64,
+) -> TopologyResult<Vec<Volume>> {
+    let mut volumes = volumes_union(&linear_pattern(volume, direction, count, spacing)?)?;
+    volumes.push(base.clone());
+    volumes_union(&volumes)
+}
+
+// Like circular_pattern, but unions the copies into the base body.
+pub fn circular_pattern_union(
+    base: &Volume,
+    volume: &Volume,
+    axis: &Line,
+    count: usize,
+    angle: EFloat64,
+) -> TopologyResult<Vec<Volume>> {
+    let mut volumes = volumes_union(&circular_pattern(volume, axis, count, angle)?)?;
+    volumes.push(base.clone());
+    volumes_union(&volumes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64::consts::PI, rc::Rc};
+
+    use geop_geometry::{
+        surfaces::{plane::Plane, surface::Surface},
+        transforms::Transform,
+    };
+    use geop_topology::{
+        mass_properties::volume_mass_properties,
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::{contour::Contour, face::Face},
+        validation::check_volume,
+    };
+
+    use super::*;
+
+    // A cube with edges of length 2 around the center.
+    fn cube(x: f64, y: f64, z: f64) -> Volume {
+        primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two())
+            .transform(Transform::from_translation(Point::from_f64(x, y, z)))
+    }
+
+    // The bodies have to be valid and have the given volumes, in any order.
+    fn assert_volumes(volumes: &[Volume], expected: &[f64]) {
+        let mut volumes: Vec<f64> = volumes
+            .iter()
+            .map(|volume| {
+                let report = check_volume(volume);
+                assert!(report.is_valid(), "{}", report);
+                volume_mass_properties(volume).unwrap().volume
+            })
+            .collect();
+        volumes.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert_eq!(volumes.len(), expected.len());
+        for (volume, expected) in volumes.iter().zip(expected.iter()) {
+            assert!((volume - expected).abs() < 1e-6, "{} {}", volume, expected);
+        }
+    }
+
+    #[test]
+    fn test_volume_volume_union_overlapping() {
+        let union = volume_volume_union(&cube(0.0, 0.0, 0.0), &cube(1.0, 1.0, 1.0)).unwrap();
+        assert_volumes(&union, &[15.0]);
+    }
+
+    #[test]
+    fn test_volume_volume_union_touching() {
+        // The cubes share a whole face, which is removed from the union.
+        let union = volume_volume_union(&cube(0.0, 0.0, 0.0), &cube(2.0, 0.0, 0.0)).unwrap();
+        assert_volumes(&union, &[16.0]);
+    }
+
+    #[test]
+    fn test_volume_volume_union_disjoint() {
+        let union = volume_volume_union(&cube(0.0, 0.0, 0.0), &cube(3.0, 0.0, 0.0)).unwrap();
+        assert_volumes(&union, &[8.0, 8.0]);
+
+        // A volume inside of the other one is swallowed.
+        let small = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one());
+        let union = volume_volume_union(&small, &cube(0.0, 0.0, 0.0)).unwrap();
+        assert_volumes(&union, &[8.0]);
+    }
+
+    #[test]
+    fn test_volumes_union() {
+        let volumes = [
+            cube(0.0, 0.0, 0.0),
+            cube(5.0, 0.0, 0.0),
+            cube(1.0, 1.0, 1.0),
+        ];
+        assert_volumes(&volumes_union(&volumes).unwrap(), &[8.0, 15.0]);
+    }
+
+    #[test]
+    fn test_linear_pattern_union() {
+        // Three pins on a plate. The first two stand on the plate, the last one is too far away.
+        let plate = primitive_cube(EFloat64::from(6.0), EFloat64::two(), EFloat64::two());
+        let pin = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one()).transform(
+            Transform::from_translation(Point::from_f64(-2.0, 0.25, 1.25)),
+        );
+        let union =
+            linear_pattern_union(&plate, &pin, Point::unit_x(), 3, EFloat64::from(3.0)).unwrap();
+        assert_volumes(&union, &[1.0, 24.0 + 2.0 * 0.75]);
+
+        // Pins that touch each other merge into a wall, and so do pins that overlap.
+        let union =
+            linear_pattern_union(&plate, &pin, Point::unit_x(), 3, EFloat64::one()).unwrap();
+        assert_volumes(&union, &[24.0 + 3.0 * 0.75]);
+        let union =
+            linear_pattern_union(&plate, &pin, Point::unit_x(), 3, EFloat64::from(0.5)).unwrap();
+        assert_volumes(&union, &[24.0 + 2.0 * 0.75]);
+    }
+
+    #[test]
+    fn test_circular_pattern_union() {
+        // Four teeth around a hub. Each overlaps the hub, but not the other teeth.
+        let hub = cube(0.0, 0.0, 0.0);
+        let tooth = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one())
+            .transform(Transform::from_translation(Point::from_f64(1.25, 0.1, 0.1)));
+        let axis = Line::new(Point::zero(), Point::unit_z()).unwrap();
+        let union =
+            circular_pattern_union(&hub, &tooth, &axis, 4, EFloat64::from(PI / 2.0)).unwrap();
+        assert_volumes(&union, &[8.0 + 4.0 * 0.75]);
+
+        // Teeth away from the hub stay separate bodies.
+        let tooth = tooth.transform(Transform::from_translation(Point::from_f64(3.0, 0.0, 0.0)));
+        let union =
+            circular_pattern_union(&hub, &tooth, &axis, 4, EFloat64::from(PI / 2.0)).unwrap();
+        assert_volumes(&union, &[1.0, 1.0, 1.0, 1.0, 8.0]);
+    }
+
+    #[test]
+    fn test_linear_pattern_union_cylinders() {
+        // Three disjoint bosses stand on a plate. Each boss only overlaps the plate, not the bosses merged before it.
+        let plate = primitive_cube(EFloat64::from(4.0), EFloat64::two(), EFloat64::two());
+        let circle = primitive_circle(
+            Point::from_f64(-1.0, 0.0, 1.0),
+            -Point::unit_z(),
+            EFloat64::from(0.3),
+        );
+        let disk = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::from_f64(0.0, 0.0, 1.0),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let boss = extrude(disk, Point::unit_z()).unwrap();
+        let union =
+            linear_pattern_union(&plate, &boss, Point::unit_x(), 3, EFloat64::one()).unwrap();
+        assert_volumes(&union, &[16.0 + 3.0 * PI * 0.09]);
+    }
+}
@@ -0,0 +1,100 @@
+use crate::{
+    curve_surface_intersection::{
+        circle_plane::{circle_plane_intersection, CirclePlaneIntersection},
+        conic_quadric::{conic_quadric_intersection, ConicQuadricIntersection},
+    },
+    curves::{circle::Circle, ellipse::Ellipse, CurveLike},
+    point::Point,
+    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface},
+};
+
+#[derive(Debug)]
+pub enum CircleEllipseIntersection {
+    Circle(Circle),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn circle_ellipse_intersection(
+    circle: &Circle,
+    ellipse: &Ellipse,
+) -> CircleEllipseIntersection {
+    let plane = Plane::new(ellipse.basis, ellipse.major_radius, ellipse.minor_radius);
+    let points = match circle_plane_intersection(circle, &plane) {
+        CirclePlaneIntersection::None => vec![],
+        CirclePlaneIntersection::OnePoint(p) => vec![p],
+        CirclePlaneIntersection::TwoPoints(p1, p2) => vec![p1, p2],
+        // In the same plane, the points of the ellipse on the cylinder through the circle are on the circle.
+        CirclePlaneIntersection::Circle(_) => {
+            let cylinder = Surface::Cylinder(Cylinder::new(
+                circle.basis,
+                circle.normal,
+                circle.radius.norm(),
+                true,
+            ));
+            match conic_quadric_intersection(
+                ellipse.basis,
+                ellipse.major_radius,
+                ellipse.minor_radius,
+                &cylinder,
+            ) {
+                ConicQuadricIntersection::Conic => {
+                    return CircleEllipseIntersection::Circle(circle.clone())
+                }
+                ConicQuadricIntersection::Points(points) => points,
+                ConicQuadricIntersection::None => vec![],
+            }
+        }
+    };
+    let points: Vec<Point> = points
+        .into_iter()
+        .filter(|p| ellipse.on_curve(*p))
+        .collect();
+    match points.is_empty() {
+        true => CircleEllipseIntersection::None,
+        false => CircleEllipseIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::efloat::EFloat64;
+
+    use super::*;
+
+    #[test]
+    fn test_circle_ellipse_intersection() {
+        // A tilted cut through a cylinder of radius 1 crosses the circle at its top twice.
+        let circle = Circle::try_new(Point::unit_z(), Point::unit_z(), EFloat64::one()).unwrap();
+        let ellipse = Ellipse::try_new(
+            Point::unit_z(),
+            Point::from_f64(-1.0, 0.0, 1.0).normalize().unwrap(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        )
+        .unwrap();
+        let CircleEllipseIntersection::Points(points) =
+            circle_ellipse_intersection(&circle, &ellipse)
+        else {
+            panic!("Expected points");
+        };
+        assert_eq!(points.len(), 2);
+        assert!(points.iter().all(|p| p.x == 0.0 && p.y.abs() == 1.0));
+
+        // In the same plane, the ellipse touches the circle at the ends of its minor axis.
+        let ellipse = Ellipse::try_new(
+            Point::unit_z(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+        )
+        .unwrap();
+        let CircleEllipseIntersection::Points(points) =
+            circle_ellipse_intersection(&circle, &ellipse)
+        else {
+            panic!("Expected points");
+        };
+        assert_eq!(points.len(), 2);
+        assert!(points.iter().all(|p| p.y.abs() == 1.0));
+    }
+}
@@ -21,10 +21,15 @@ pub fn circle_line_intersection(circle: &Circle, line: &Line) -> CircleLineInter
         return CircleLineIntersection::None;
     }
 
-    assert!(
-        circle.normal.is_perpendicular(line.direction),
-        "3D circle-line intersection is not implemented yet"
-    );
+    // The line crosses the plane of the circle in a single point.
+    if !circle.normal.is_perpendicular(line.direction) {
+        let t = (circle.basis - line.basis).dot(circle.normal) / line.direction.dot(circle.normal);
+        let point = line.basis + line.direction * t.unwrap();
+        if ((point - circle.basis).norm() - circle.radius.norm()) == 0.0 {
+            return CircleLineIntersection::OnePoint(point);
+        }
+        return CircleLineIntersection::None;
+    }
 
     let v = circle.basis - line.basis;
     let dir = line.direction.normalize().unwrap();
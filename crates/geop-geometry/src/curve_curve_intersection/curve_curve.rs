@@ -2,8 +2,10 @@ use crate::{curves::curve::Curve, point::Point};
 
 use super::{
     circle_circle::{circle_circle_intersection, CircleCircleIntersection},
+    circle_ellipse::{circle_ellipse_intersection, CircleEllipseIntersection},
     circle_line::{circle_line_intersection, CircleLineIntersection},
     ellipse_ellipse::{ellipse_ellipse_intersection, EllipseEllipseIntersection},
+    ellipse_line::{ellipse_line_intersection, EllipseLineIntersection},
     helix_circle::{helix_circle_intersection, HelixCircleIntersection},
     helix_line::{helix_line_intersection, HelixLineIntersection},
     line_line::{line_line_intersection, LineLineIntersection},
@@ -41,7 +43,15 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     CurveCurveIntersection::FinitePoints(vec![p1, p2])
                 }
             },
-            Curve::Ellipse(_) => todo!("Implement this"),
+            Curve::Ellipse(ellipse) => match ellipse_line_intersection(ellipse, line) {
+                EllipseLineIntersection::None => CurveCurveIntersection::None,
+                EllipseLineIntersection::OnePoint(p) => {
+                    CurveCurveIntersection::FinitePoints(vec![p])
+                }
+                EllipseLineIntersection::TwoPoint(p1, p2) => {
+                    CurveCurveIntersection::FinitePoints(vec![p1, p2])
+                }
+            },
             Curve::Helix(helix) => match helix_line_intersection(helix, line) {
                 HelixLineIntersection::PointArray(point_array) => {
                     CurveCurveIntersection::InfiniteDiscretePoints(point_array)
@@ -73,7 +83,17 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
                     CurveCurveIntersection::Curve(Curve::Circle(c))
                 }
             },
-            Curve::Ellipse(_) => todo!("Implement this"),
+            Curve::Ellipse(other_ellipse) => {
+                match circle_ellipse_intersection(circle, other_ellipse) {
+                    CircleEllipseIntersection::Circle(c) => {
+                        CurveCurveIntersection::Curve(Curve::Circle(c))
+                    }
+                    CircleEllipseIntersection::Points(points) => {
+                        CurveCurveIntersection::FinitePoints(points)
+                    }
+                    CircleEllipseIntersection::None => CurveCurveIntersection::None,
+                }
+            }
             Curve::Helix(helix) => match helix_circle_intersection(helix, circle) {
                 HelixCircleIntersection::TwoPoints(p1, p2) => {
                     CurveCurveIntersection::FinitePoints(vec![p1, p2])
@@ -85,8 +105,24 @@ pub fn curve_curve_intersection(edge_self: &Curve, edge_other: &Curve) -> CurveC
             },
         },
         Curve::Ellipse(ellipse) => match edge_other {
-            Curve::Line(_) => todo!("Implement this"),
-            Curve::Circle(_) => todo!("Implement this"),
+            Curve::Line(other_line) => match ellipse_line_intersection(ellipse, other_line) {
+                EllipseLineIntersection::None => CurveCurveIntersection::None,
+                EllipseLineIntersection::OnePoint(p) => {
+                    CurveCurveIntersection::FinitePoints(vec![p])
+                }
+                EllipseLineIntersection::TwoPoint(p1, p2) => {
+                    CurveCurveIntersection::FinitePoints(vec![p1, p2])
+                }
+            },
+            Curve::Circle(circle) => match circle_ellipse_intersection(circle, ellipse) {
+                CircleEllipseIntersection::Circle(c) => {
+                    CurveCurveIntersection::Curve(Curve::Circle(c))
+                }
+                CircleEllipseIntersection::Points(points) => {
+                    CurveCurveIntersection::FinitePoints(points)
+                }
+                CircleEllipseIntersection::None => CurveCurveIntersection::None,
+            },
             Curve::Ellipse(other_ellipse) => {
                 match ellipse_ellipse_intersection(ellipse, other_ellipse) {
                     EllipseEllipseIntersection::Ellipse(ellipse) => {
@@ -1,6 +1,14 @@
-use crate::{curves::ellipse::Ellipse, point::Point};
-
-use super::numerical::curve_curve_intersection_numerical;
+use crate::{
+    curve_surface_intersection::{
+        ellipse_elliptic_cylinder::{
+            ellipse_elliptic_cylinder_intersection, EllipseEllipticCylinderIntersection,
+        },
+        ellipse_plane::{ellipse_plane_intersection, EllipsePlaneIntersection},
+    },
+    curves::{ellipse::Ellipse, CurveLike},
+    point::Point,
+    surfaces::{elliptic_cylinder::EllipticCylinder, plane::Plane},
+};
 
 pub enum EllipseEllipseIntersection {
     Ellipse(Ellipse),
@@ -19,22 +27,104 @@ pub fn ellipse_ellipse_intersection(
         return EllipseEllipseIntersection::Ellipse(ellipse_self.clone());
     }
 
-    let intersection_points = curve_curve_intersection_numerical(ellipse_self, ellipse_other);
-    match intersection_points.len() {
+    let plane = Plane::new(
+        ellipse_other.basis,
+        ellipse_other.major_radius,
+        ellipse_other.minor_radius,
+    );
+    let points = match ellipse_plane_intersection(ellipse_self, &plane) {
+        EllipsePlaneIntersection::None => vec![],
+        EllipsePlaneIntersection::OnePoint(p) => vec![p],
+        EllipsePlaneIntersection::TwoPoints(p1, p2) => vec![p1, p2],
+        // In the same plane, the points of the ellipse on the cylinder through the other ellipse are on the other ellipse.
+        EllipsePlaneIntersection::Ellipse(_) => {
+            let cylinder = EllipticCylinder::new(
+                ellipse_other.basis,
+                ellipse_other.normal,
+                ellipse_other.major_radius,
+                ellipse_other.minor_radius,
+                true,
+            );
+            match ellipse_elliptic_cylinder_intersection(ellipse_self, &cylinder) {
+                EllipseEllipticCylinderIntersection::Ellipse(_) => {
+                    return EllipseEllipseIntersection::Ellipse(ellipse_self.clone())
+                }
+                EllipseEllipticCylinderIntersection::Points(points) => points,
+                EllipseEllipticCylinderIntersection::None => vec![],
+            }
+        }
+    };
+    let points: Vec<Point> = points
+        .into_iter()
+        .filter(|p| ellipse_other.on_curve(*p))
+        .collect();
+    match points.len() {
         0 => EllipseEllipseIntersection::None,
-        1 => EllipseEllipseIntersection::OnePoint(intersection_points[0]),
-        2 => EllipseEllipseIntersection::TwoPoint(intersection_points[0], intersection_points[1]),
-        3 => EllipseEllipseIntersection::ThreePoint(
-            intersection_points[0],
-            intersection_points[1],
-            intersection_points[2],
-        ),
-        4 => EllipseEllipseIntersection::FourPoint(
-            intersection_points[0],
-            intersection_points[1],
-            intersection_points[2],
-            intersection_points[3],
-        ),
+        1 => EllipseEllipseIntersection::OnePoint(points[0]),
+        2 => EllipseEllipseIntersection::TwoPoint(points[0], points[1]),
+        3 => EllipseEllipseIntersection::ThreePoint(points[0], points[1], points[2]),
+        4 => EllipseEllipseIntersection::FourPoint(points[0], points[1], points[2], points[3]),
         _ => panic!("Unexpected number of intersection points"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipse_ellipse_intersection() {
+        // An ellipse and the same ellipse turned by 90 degrees cross on the diagonals.
+        let ellipse = Ellipse::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+        )
+        .unwrap();
+        let turned = Ellipse::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(0.0, 2.0, 0.0),
+            -Point::unit_x(),
+        )
+        .unwrap();
+        let EllipseEllipseIntersection::FourPoint(p1, p2, p3, p4) =
+            ellipse_ellipse_intersection(&ellipse, &turned)
+        else {
+            panic!("Expected four points");
+        };
+        let coordinate = 2.0 / 5.0_f64.sqrt();
+        for p in [p1, p2, p3, p4] {
+            assert!(p.x.abs() == coordinate && p.y.abs() == coordinate);
+        }
+
+        // In different planes, the ellipses cross where the plane of one cuts the other.
+        let upright = Ellipse::try_new(
+            Point::zero(),
+            Point::unit_x(),
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_y(),
+        )
+        .unwrap();
+        let EllipseEllipseIntersection::TwoPoint(p1, p2) =
+            ellipse_ellipse_intersection(&ellipse, &upright)
+        else {
+            panic!("Expected two points");
+        };
+        assert!(p1.x == 0.0 && p1.y.abs() == 1.0 && p1.z == 0.0);
+        assert!(p2.x == 0.0 && p2.y.abs() == 1.0 && p2.z == 0.0);
+
+        let shifted = Ellipse::try_new(
+            Point::from_f64(5.0, 0.0, 0.0),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+        )
+        .unwrap();
+        assert!(matches!(
+            ellipse_ellipse_intersection(&ellipse, &shifted),
+            EllipseEllipseIntersection::None
+        ));
+    }
+}
@@ -0,0 +1,93 @@
+use crate::{
+    curves::{ellipse::Ellipse, line::Line, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+};
+
+#[derive(Debug)]
+pub enum EllipseLineIntersection {
+    TwoPoint(Point, Point),
+    OnePoint(Point),
+    None,
+}
+
+pub fn ellipse_line_intersection(ellipse: &Ellipse, line: &Line) -> EllipseLineIntersection {
+    // The line crosses the plane of the ellipse in a single point.
+    if !ellipse.normal.is_perpendicular(line.direction) {
+        let t =
+            (ellipse.basis - line.basis).dot(ellipse.normal) / line.direction.dot(ellipse.normal);
+        let point = line.basis + line.direction * t.unwrap();
+        if ellipse.on_curve(point) {
+            return EllipseLineIntersection::OnePoint(point);
+        }
+        return EllipseLineIntersection::None;
+    }
+    if (line.basis - ellipse.basis).dot(ellipse.normal) != 0.0 {
+        return EllipseLineIntersection::None;
+    }
+
+    // In the coordinates of the major and minor radius, the ellipse is the unit circle.
+    let coordinates = |v: Point| {
+        (
+            (v.dot(ellipse.major_radius) / ellipse.major_radius.norm_sq()).unwrap(),
+            (v.dot(ellipse.minor_radius) / ellipse.minor_radius.norm_sq()).unwrap(),
+        )
+    };
+    let (x, y) = coordinates(line.basis - ellipse.basis);
+    let (dx, dy) = coordinates(line.direction);
+    let a = dx * dx + dy * dy;
+    let b = EFloat64::two() * (x * dx + y * dy);
+    let c = x * x + y * y - EFloat64::one();
+    let determinant_sq = b * b - EFloat64::from(4.0) * a * c;
+    if determinant_sq < 0.0 {
+        return EllipseLineIntersection::None;
+    }
+    if determinant_sq == 0.0 {
+        let t = (-b / (EFloat64::two() * a)).unwrap();
+        return EllipseLineIntersection::OnePoint(line.basis + line.direction * t);
+    }
+    let determinant = determinant_sq.sqrt().unwrap();
+    let t1 = ((-b - determinant) / (EFloat64::two() * a)).unwrap();
+    let t2 = ((-b + determinant) / (EFloat64::two() * a)).unwrap();
+    EllipseLineIntersection::TwoPoint(
+        line.basis + line.direction * t1,
+        line.basis + line.direction * t2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ellipse_line_intersection() {
+        let ellipse = Ellipse::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        )
+        .unwrap();
+
+        let line = Line::new(Point::zero(), Point::unit_x()).unwrap();
+        match ellipse_line_intersection(&ellipse, &line) {
+            EllipseLineIntersection::TwoPoint(p1, p2) => {
+                assert_eq!(p1, Point::from_f64(-2.0, 0.0, 0.0));
+                assert_eq!(p2, Point::from_f64(2.0, 0.0, 0.0));
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 1.0, 0.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            ellipse_line_intersection(&ellipse, &line),
+            EllipseLineIntersection::OnePoint(_)
+        ));
+
+        let line = Line::new(Point::from_f64(0.0, 1.0, 5.0), Point::unit_z()).unwrap();
+        match ellipse_line_intersection(&ellipse, &line) {
+            EllipseLineIntersection::OnePoint(p) => assert_eq!(p, Point::unit_y()),
+            _ => panic!("Intersection should be one point"),
+        }
+    }
+}
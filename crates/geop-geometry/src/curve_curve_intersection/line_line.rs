@@ -13,7 +13,9 @@ pub fn line_line_intersection(a: &Line, b: &Line) -> LineLineIntersection {
     let p1 = a.basis;
     let p2 = b.basis;
 
-    if v1.is_parallel(v2) {
+    // Lines that are so close to parallel that the cross product cannot be divided by are treated as parallel.
+    let cross_product = v1.cross(v2);
+    if v1.is_parallel(v2) || cross_product.norm_sq() == 0.0 {
         if (p1 - p2).is_parallel(v1) {
             return LineLineIntersection::Line(Line::new(p1, v1).unwrap());
         } else {
@@ -21,7 +23,10 @@ pub fn line_line_intersection(a: &Line, b: &Line) -> LineLineIntersection {
         }
     }
 
-    let cross_product = v1.cross(v2);
+    // Skew lines do not intersect. Close to parallel, the intersection would be too uncertain to tell this apart.
+    if (p2 - p1).dot(cross_product) != 0.0 {
+        return LineLineIntersection::None;
+    }
     let t = (p2 - p1).cross(v2).dot(cross_product) / cross_product.norm_sq();
     let t = t.unwrap();
     let p = p1 + v1 * t;
@@ -1,8 +1,10 @@
 // Alphabetical order
 pub mod circle_circle;
+pub mod circle_ellipse;
 pub mod circle_line;
 pub mod curve_curve;
 pub mod ellipse_ellipse;
+pub mod ellipse_line;
 pub mod helix_circle;
 pub mod helix_line;
 pub mod line_line;
@@ -0,0 +1,88 @@
+use crate::{
+    curves::circle::Circle,
+    point::Point,
+    surfaces::{cone::Cone, surface::Surface},
+};
+
+use super::conic_quadric::{conic_quadric_intersection, ConicQuadricIntersection};
+
+pub enum CircleConeIntersection {
+    Circle(Circle),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn circle_cone_intersection(circle: &Circle, cone: &Cone) -> CircleConeIntersection {
+    if circle.normal.is_parallel(cone.extend_dir) {
+        let distance = circle.basis - cone.basis;
+        let height = distance.dot(cone.extend_dir);
+        let distance = distance - height * cone.extend_dir;
+        if distance.norm() == 0.0 {
+            // Both are around the same axis, so the circle is either on the cone or does not touch it.
+            if height >= 0.0 && (circle.radius.norm() - height * cone.slope) == 0.0 {
+                return CircleConeIntersection::Circle(circle.clone());
+            }
+            return CircleConeIntersection::None;
+        }
+    }
+
+    match conic_quadric_intersection(
+        circle.basis,
+        circle.radius,
+        circle.normal.cross(circle.radius),
+        &Surface::Cone(cone.clone()),
+    ) {
+        ConicQuadricIntersection::Conic => CircleConeIntersection::Circle(circle.clone()),
+        ConicQuadricIntersection::Points(points) => CircleConeIntersection::Points(points),
+        ConicQuadricIntersection::None => CircleConeIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::CurveLike, efloat::EFloat64};
+
+    use super::*;
+
+    #[test]
+    fn test_circle_cone_intersection_coaxial() {
+        let cone = Cone::new(
+            Point::from_f64(0.0, 0.0, -2.0),
+            Point::unit_z(),
+            EFloat64::from(0.5),
+            true,
+        );
+        let circle = Circle::try_new(Point::zero(), -Point::unit_z(), EFloat64::from(1.0)).unwrap();
+        assert!(matches!(
+            circle_cone_intersection(&circle, &cone),
+            CircleConeIntersection::Circle(_)
+        ));
+
+        let circle = Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::from(2.0)).unwrap();
+        assert!(matches!(
+            circle_cone_intersection(&circle, &cone),
+            CircleConeIntersection::None
+        ));
+    }
+
+    #[test]
+    fn test_circle_cone_intersection_tilted() {
+        // A horizontal circle next to the axis crosses the circle of the cone at its height twice.
+        let cone = Cone::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        let circle = Circle::try_new(
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_z(),
+            EFloat64::from(1.0),
+        )
+        .unwrap();
+        let CircleConeIntersection::Points(points) = circle_cone_intersection(&circle, &cone)
+        else {
+            panic!("Expected points");
+        };
+        assert_eq!(points.len(), 2);
+        for p in points {
+            assert!(circle.on_curve(p));
+            assert!(p.x == 0.5);
+        }
+    }
+}
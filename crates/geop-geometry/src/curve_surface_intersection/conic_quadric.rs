@@ -0,0 +1,216 @@
+use std::f64::consts::PI;
+
+use crate::{
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{surface::Surface, SurfaceLike},
+};
+
+// Number of samples around the conic that are checked for sign changes of the implicit function.
+const SAMPLES: usize = 360;
+
+pub enum ConicQuadricIntersection {
+    Conic,
+    Points(Vec<Point>),
+    None,
+}
+
+// The implicit function of the surface, which is zero exactly on the surface. Cones include the nappe below the apex.
+fn implicit(surface: &Surface, p: Point) -> f64 {
+    match surface {
+        Surface::Plane(plane) => (p - plane.basis)
+            .dot(plane.u_slope.cross(plane.v_slope))
+            .to_f64(),
+        Surface::Sphere(sphere) => {
+            ((p - sphere.basis).norm_sq() - sphere.radius * sphere.radius).to_f64()
+        }
+        Surface::Cylinder(cylinder) => {
+            let d = p - cylinder.basis;
+            let radial = d - d.dot(cylinder.extend_dir) * cylinder.extend_dir;
+            (radial.norm_sq() - cylinder.radius.norm_sq()).to_f64()
+        }
+        Surface::Cone(cone) => {
+            let d = p - cone.basis;
+            let height = d.dot(cone.extend_dir);
+            ((EFloat64::one() + cone.slope * cone.slope) * height * height - d.norm_sq()).to_f64()
+        }
+        Surface::EllipticCylinder(cylinder) => {
+            let (x, y, _) = cylinder.coordinates(p);
+            (x * x + y * y - EFloat64::one()).to_f64()
+        }
+        Surface::HyperbolicParaboloid(patch) => {
+            let (x, y, z) = patch.coordinates(p);
+            (z - x * y).to_f64()
+        }
+    }
+}
+
+fn conic_point(basis: Point, u: Point, v: Point, t: EFloat64) -> Point {
+    basis + u * t.cos() + v * t.sin()
+}
+
+// The point of the root as an interval that contains the exact intersection, which has to lie on the surface.
+fn root_point(
+    surface: &Surface,
+    basis: Point,
+    u: Point,
+    v: Point,
+    lower: f64,
+    upper: f64,
+) -> Option<Point> {
+    for margin in [0.0, 1e-12, 1e-9] {
+        let p = conic_point(basis, u, v, EFloat64::new(upper + margin, lower - margin));
+        if surface.on_surface(p) {
+            return Some(p);
+        }
+    }
+    None
+}
+
+// Intersection of the conic basis + cos(t) * u + sin(t) * v, i.e. a circle or an ellipse, with a plane, sphere, cylinder, cone, elliptic cylinder or hyperbolic paraboloid.
+// Along the conic, the implicit function of the surface is a trigonometric polynomial of degree 2, so there are at most 4 intersections.
+// They are bracketed by sign changes and refined by bisection. Points where the conic only touches the surface are found as minima of the implicit function.
+pub fn conic_quadric_intersection(
+    basis: Point,
+    u: Point,
+    v: Point,
+    surface: &Surface,
+) -> ConicQuadricIntersection {
+    let on_surface = (0..8).all(|i| {
+        surface.on_surface(conic_point(
+            basis,
+            u,
+            v,
+            EFloat64::from(i as f64 * PI / 4.0),
+        ))
+    });
+    if on_surface {
+        return ConicQuadricIntersection::Conic;
+    }
+
+    let f = |t: f64| implicit(surface, conic_point(basis, u, v, EFloat64::from(t)));
+    let step = 2.0 * PI / SAMPLES as f64;
+    let values: Vec<f64> = (0..SAMPLES).map(|i| f(i as f64 * step)).collect();
+    let scale = values.iter().fold(0.0_f64, |m, v| m.max(v.abs()));
+
+    let mut roots = Vec::<(f64, f64)>::new();
+    for i in 0..SAMPLES {
+        let (a, b) = (values[i], values[(i + 1) % SAMPLES]);
+        let (mut lower, mut upper) = (i as f64 * step, (i + 1) as f64 * step);
+        if a == 0.0 {
+            roots.push((lower, lower));
+            continue;
+        }
+        if a.signum() != b.signum() && b != 0.0 {
+            for _ in 0..64 {
+                let middle = (lower + upper) / 2.0;
+                if middle <= lower || middle >= upper {
+                    break;
+                }
+                match f(middle).signum() == a.signum() {
+                    true => lower = middle,
+                    false => upper = middle,
+                }
+            }
+            roots.push((lower, upper));
+            continue;
+        }
+        // The conic touches the surface without crossing it, if |f| has a minimum close to zero.
+        let previous = values[(i + SAMPLES - 1) % SAMPLES];
+        if a.abs() <= previous.abs() && a.abs() <= b.abs() && previous.signum() == a.signum() {
+            let (mut lower, mut upper) = ((i as f64 - 1.0) * step, (i as f64 + 1.0) * step);
+            for _ in 0..100 {
+                let left = lower + (upper - lower) / 3.0;
+                let right = upper - (upper - lower) / 3.0;
+                match f(left).abs() < f(right).abs() {
+                    true => upper = right,
+                    false => lower = left,
+                }
+            }
+            let t = (lower + upper) / 2.0;
+            if f(t).abs() <= 1e-12 * scale.max(1.0) {
+                roots.push((t, t));
+            }
+        }
+    }
+
+    let mut points = Vec::<Point>::new();
+    for (lower, upper) in roots {
+        if let Some(p) = root_point(surface, basis, u, v, lower, upper) {
+            if !points.contains(&p) {
+                points.push(p);
+            }
+        }
+    }
+    match points.is_empty() {
+        true => ConicQuadricIntersection::None,
+        false => ConicQuadricIntersection::Points(points),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::{cone::Cone, elliptic_cylinder::EllipticCylinder};
+
+    use super::*;
+
+    #[test]
+    fn test_circle_cone_general_position() {
+        // A vertical circle above the apex crosses each of the two generators in the xz-plane twice.
+        let cone = Cone::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+        let surface = Surface::Cone(cone);
+        let intersection = conic_quadric_intersection(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::from_f64(1.5, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 1.5),
+            &surface,
+        );
+        let ConicQuadricIntersection::Points(points) = intersection else {
+            panic!("Expected points");
+        };
+        assert_eq!(points.len(), 4);
+        for p in points {
+            assert!(surface.on_surface(p));
+            assert!(p.x.abs() == p.z);
+        }
+    }
+
+    #[test]
+    fn test_conic_on_and_touching_surface() {
+        let cylinder = Surface::EllipticCylinder(EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+            true,
+        ));
+        // The cross section lies on the cylinder.
+        assert!(matches!(
+            conic_quadric_intersection(
+                Point::unit_z(),
+                Point::from_f64(2.0, 0.0, 0.0),
+                Point::from_f64(0.0, 1.0, 0.0),
+                &cylinder,
+            ),
+            ConicQuadricIntersection::Conic
+        ));
+        // A circle of radius 1 around the axis touches the cylinder at the ends of the minor axis.
+        let ConicQuadricIntersection::Points(points) =
+            conic_quadric_intersection(Point::zero(), Point::unit_x(), Point::unit_y(), &cylinder)
+        else {
+            panic!("Expected points");
+        };
+        assert_eq!(points.len(), 2);
+        assert!(points.iter().all(|p| p.y.abs() == 1.0));
+        // A smaller circle misses it.
+        assert!(matches!(
+            conic_quadric_intersection(
+                Point::zero(),
+                Point::from_f64(0.5, 0.0, 0.0),
+                Point::from_f64(0.0, 0.5, 0.0),
+                &cylinder,
+            ),
+            ConicQuadricIntersection::None
+        ));
+    }
+}
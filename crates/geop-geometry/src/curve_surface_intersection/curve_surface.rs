@@ -1,10 +1,23 @@
 use crate::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
 
 use super::{
+    circle_cone::{circle_cone_intersection, CircleConeIntersection},
     circle_cylinder::{circle_cylinder_intersection, CircleCylinderIntersection},
     circle_plane::{circle_plane_intersection, CirclePlaneIntersection},
     circle_sphere::{circle_sphere_intersection, CircleSphereIntersection},
+    conic_quadric::{conic_quadric_intersection, ConicQuadricIntersection},
+    ellipse_elliptic_cylinder::{
+        ellipse_elliptic_cylinder_intersection, EllipseEllipticCylinderIntersection,
+    },
+    ellipse_plane::{ellipse_plane_intersection, EllipsePlaneIntersection},
+    line_cone::{line_cone_intersection, ConeLineIntersection},
     line_cylinder::{line_cylinder_intersection, CylinderLineIntersection},
+    line_elliptic_cylinder::{
+        line_elliptic_cylinder_intersection, EllipticCylinderLineIntersection,
+    },
+    line_hyperbolic_paraboloid::{
+        line_hyperbolic_paraboloid_intersection, HyperbolicParaboloidLineIntersection,
+    },
     line_plane::{line_plane_intersection, LinePlaneIntersection},
     line_sphere::{line_sphere_intersection, LineSphereIntersection},
 };
@@ -71,6 +84,44 @@ pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurf
                 }
                 CylinderLineIntersection::None => CurveSurfaceIntersection::None,
             },
+            Surface::Cone(cone) => match line_cone_intersection(line, cone) {
+                ConeLineIntersection::Line(line) => {
+                    CurveSurfaceIntersection::Curve(Curve::Line(line))
+                }
+                ConeLineIntersection::TwoPoints(point1, point2) => {
+                    CurveSurfaceIntersection::Points(vec![point1, point2])
+                }
+                ConeLineIntersection::Point(point) => CurveSurfaceIntersection::Points(vec![point]),
+                ConeLineIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::EllipticCylinder(cylinder) => {
+                match line_elliptic_cylinder_intersection(line, cylinder) {
+                    EllipticCylinderLineIntersection::Line(line) => {
+                        CurveSurfaceIntersection::Curve(Curve::Line(line))
+                    }
+                    EllipticCylinderLineIntersection::TwoPoints(point1, point2) => {
+                        CurveSurfaceIntersection::Points(vec![point1, point2])
+                    }
+                    EllipticCylinderLineIntersection::Point(point) => {
+                        CurveSurfaceIntersection::Points(vec![point])
+                    }
+                    EllipticCylinderLineIntersection::None => CurveSurfaceIntersection::None,
+                }
+            }
+            Surface::HyperbolicParaboloid(patch) => {
+                match line_hyperbolic_paraboloid_intersection(line, patch) {
+                    HyperbolicParaboloidLineIntersection::Line(line) => {
+                        CurveSurfaceIntersection::Curve(Curve::Line(line))
+                    }
+                    HyperbolicParaboloidLineIntersection::TwoPoints(point1, point2) => {
+                        CurveSurfaceIntersection::Points(vec![point1, point2])
+                    }
+                    HyperbolicParaboloidLineIntersection::Point(point) => {
+                        CurveSurfaceIntersection::Points(vec![point])
+                    }
+                    HyperbolicParaboloidLineIntersection::None => CurveSurfaceIntersection::None,
+                }
+            }
         },
         Curve::Circle(circle) => match surface {
             Surface::Plane(plane) => match circle_plane_intersection(circle, plane) {
@@ -107,8 +158,72 @@ pub fn curve_surface_intersection(curve: &Curve, surface: &Surface) -> CurveSurf
                 }
                 CircleCylinderIntersection::None => CurveSurfaceIntersection::None,
             },
+            Surface::Cone(cone) => match circle_cone_intersection(circle, cone) {
+                CircleConeIntersection::Circle(circle) => {
+                    CurveSurfaceIntersection::Curve(Curve::Circle(circle))
+                }
+                CircleConeIntersection::Points(points) => CurveSurfaceIntersection::Points(points),
+                CircleConeIntersection::None => CurveSurfaceIntersection::None,
+            },
+            Surface::EllipticCylinder(_) | Surface::HyperbolicParaboloid(_) => {
+                match conic_quadric_intersection(
+                    circle.basis,
+                    circle.radius,
+                    circle.normal.cross(circle.radius),
+                    surface,
+                ) {
+                    ConicQuadricIntersection::Conic => {
+                        CurveSurfaceIntersection::Curve(Curve::Circle(circle.clone()))
+                    }
+                    ConicQuadricIntersection::Points(points) => {
+                        CurveSurfaceIntersection::Points(points)
+                    }
+                    ConicQuadricIntersection::None => CurveSurfaceIntersection::None,
+                }
+            }
+        },
+        Curve::Ellipse(ellipse) => match surface {
+            Surface::Plane(plane) => match ellipse_plane_intersection(ellipse, plane) {
+                EllipsePlaneIntersection::None => CurveSurfaceIntersection::None,
+                EllipsePlaneIntersection::TwoPoints(p1, p2) => {
+                    CurveSurfaceIntersection::Points(vec![p1, p2])
+                }
+                EllipsePlaneIntersection::OnePoint(p) => CurveSurfaceIntersection::Points(vec![p]),
+                EllipsePlaneIntersection::Ellipse(ellipse) => {
+                    CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                }
+            },
+            Surface::EllipticCylinder(cylinder) => {
+                match ellipse_elliptic_cylinder_intersection(ellipse, cylinder) {
+                    EllipseEllipticCylinderIntersection::Ellipse(ellipse) => {
+                        CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse))
+                    }
+                    EllipseEllipticCylinderIntersection::Points(points) => {
+                        CurveSurfaceIntersection::Points(points)
+                    }
+                    EllipseEllipticCylinderIntersection::None => CurveSurfaceIntersection::None,
+                }
+            }
+            Surface::Sphere(_)
+            | Surface::Cylinder(_)
+            | Surface::Cone(_)
+            | Surface::HyperbolicParaboloid(_) => {
+                match conic_quadric_intersection(
+                    ellipse.basis,
+                    ellipse.major_radius,
+                    ellipse.minor_radius,
+                    surface,
+                ) {
+                    ConicQuadricIntersection::Conic => {
+                        CurveSurfaceIntersection::Curve(Curve::Ellipse(ellipse.clone()))
+                    }
+                    ConicQuadricIntersection::Points(points) => {
+                        CurveSurfaceIntersection::Points(points)
+                    }
+                    ConicQuadricIntersection::None => CurveSurfaceIntersection::None,
+                }
+            }
         },
-        Curve::Ellipse(_) => todo!("Implement this"),
         Curve::Helix(_) => todo!("Implement this"),
     }
 }
@@ -0,0 +1,82 @@
+use crate::{
+    curves::ellipse::Ellipse,
+    point::Point,
+    surfaces::{elliptic_cylinder::EllipticCylinder, surface::Surface},
+};
+
+use super::conic_quadric::{conic_quadric_intersection, ConicQuadricIntersection};
+
+pub enum EllipseEllipticCylinderIntersection {
+    Ellipse(Ellipse),
+    Points(Vec<Point>),
+    None,
+}
+
+pub fn ellipse_elliptic_cylinder_intersection(
+    ellipse: &Ellipse,
+    cylinder: &EllipticCylinder,
+) -> EllipseEllipticCylinderIntersection {
+    match conic_quadric_intersection(
+        ellipse.basis,
+        ellipse.major_radius,
+        ellipse.minor_radius,
+        &Surface::EllipticCylinder(cylinder.clone()),
+    ) {
+        ConicQuadricIntersection::Conic => {
+            EllipseEllipticCylinderIntersection::Ellipse(ellipse.clone())
+        }
+        ConicQuadricIntersection::Points(points) => {
+            EllipseEllipticCylinderIntersection::Points(points)
+        }
+        ConicQuadricIntersection::None => EllipseEllipticCylinderIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::CurveLike, efloat::EFloat64, surfaces::SurfaceLike};
+
+    use super::*;
+
+    #[test]
+    fn test_ellipse_elliptic_cylinder_intersection() {
+        let cylinder = EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+            true,
+        );
+        let cross_section = Ellipse::try_new(
+            Point::from_f64(0.0, 0.0, 3.0),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        )
+        .unwrap();
+        assert!(matches!(
+            ellipse_elliptic_cylinder_intersection(&cross_section, &cylinder),
+            EllipseEllipticCylinderIntersection::Ellipse(_)
+        ));
+
+        // The same ellipse turned by 90 degrees crosses the cylinder four times.
+        let turned = Ellipse::try_new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(0.0, 2.0, 0.0),
+            Point::from_f64(-1.0, 0.0, 0.0),
+        )
+        .unwrap();
+        let EllipseEllipticCylinderIntersection::Points(points) =
+            ellipse_elliptic_cylinder_intersection(&turned, &cylinder)
+        else {
+            panic!("Expected points");
+        };
+        assert_eq!(points.len(), 4);
+        for p in points {
+            assert!(turned.on_curve(p));
+            assert!(cylinder.on_surface(p));
+            assert!(p.x.abs() == EFloat64::from(0.8).sqrt().unwrap());
+        }
+    }
+}
@@ -0,0 +1,31 @@
+use crate::{
+    curve_curve_intersection::ellipse_line::{ellipse_line_intersection, EllipseLineIntersection},
+    curves::ellipse::Ellipse,
+    point::Point,
+    surface_surface_intersection::plane_plane::{plane_plane_intersection, PlanePlaneIntersection},
+    surfaces::plane::Plane,
+};
+
+pub enum EllipsePlaneIntersection {
+    None,
+    TwoPoints(Point, Point),
+    OnePoint(Point),
+    Ellipse(Ellipse),
+}
+
+pub fn ellipse_plane_intersection(ellipse: &Ellipse, plane: &Plane) -> EllipsePlaneIntersection {
+    // The plane that contains the ellipse
+    let plane_ellipse = Plane::new(ellipse.basis, ellipse.major_radius, ellipse.minor_radius);
+
+    match plane_plane_intersection(plane, &plane_ellipse) {
+        PlanePlaneIntersection::Plane(_plane) => EllipsePlaneIntersection::Ellipse(ellipse.clone()),
+        PlanePlaneIntersection::None => EllipsePlaneIntersection::None,
+        PlanePlaneIntersection::Line(line) => match ellipse_line_intersection(ellipse, &line) {
+            EllipseLineIntersection::TwoPoint(p1, p2) => {
+                EllipsePlaneIntersection::TwoPoints(p1, p2)
+            }
+            EllipseLineIntersection::OnePoint(p) => EllipsePlaneIntersection::OnePoint(p),
+            EllipseLineIntersection::None => EllipsePlaneIntersection::None,
+        },
+    }
+}
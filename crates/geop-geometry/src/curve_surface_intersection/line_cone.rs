@@ -0,0 +1,103 @@
+use crate::{curves::line::Line, efloat::EFloat64, point::Point, surfaces::cone::Cone};
+
+pub enum ConeLineIntersection {
+    Line(Line),
+    TwoPoints(Point, Point),
+    Point(Point),
+    None,
+}
+
+pub fn line_cone_intersection(line: &Line, cone: &Cone) -> ConeLineIntersection {
+    // A point p is on the double cone if (1 + slope^2) * ((p - apex) . axis)^2 = |p - apex|^2.
+    // Along the line, this is a quadratic equation in the line parameter.
+    let w = line.basis - cone.basis;
+    let d = line.direction;
+    let a = cone.extend_dir;
+    let k = EFloat64::one() + cone.slope * cone.slope;
+    let qa = k * d.dot(a) * d.dot(a) - d.dot(d);
+    let qb = EFloat64::two() * (k * w.dot(a) * d.dot(a) - w.dot(d));
+    let qc = k * w.dot(a) * w.dot(a) - w.dot(w);
+
+    // Only the nappe in the direction of the axis belongs to the cone.
+    let on_nappe = |t: EFloat64| {
+        let p = line.basis + d * t;
+        match (p - cone.basis).dot(a) >= 0.0 {
+            true => Some(p),
+            false => None,
+        }
+    };
+
+    if qa == 0.0 {
+        if qb == 0.0 {
+            if qc == 0.0 {
+                return ConeLineIntersection::Line(line.clone());
+            }
+            return ConeLineIntersection::None;
+        }
+        return match on_nappe((-qc / qb).unwrap()) {
+            Some(p) => ConeLineIntersection::Point(p),
+            None => ConeLineIntersection::None,
+        };
+    }
+
+    let determinant_sq = qb * qb - EFloat64::from(4.0) * qa * qc;
+    if determinant_sq < 0.0 {
+        return ConeLineIntersection::None;
+    }
+    if determinant_sq == 0.0 {
+        return match on_nappe((-qb / (EFloat64::two() * qa)).unwrap()) {
+            Some(p) => ConeLineIntersection::Point(p),
+            None => ConeLineIntersection::None,
+        };
+    }
+    let determinant = determinant_sq.sqrt().unwrap();
+    let p1 = on_nappe(((-qb + determinant) / (EFloat64::two() * qa)).unwrap());
+    let p2 = on_nappe(((-qb - determinant) / (EFloat64::two() * qa)).unwrap());
+    match (p1, p2) {
+        (Some(p1), Some(p2)) => ConeLineIntersection::TwoPoints(p1, p2),
+        (Some(p), None) | (None, Some(p)) => ConeLineIntersection::Point(p),
+        (None, None) => ConeLineIntersection::None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_cone_intersection() {
+        let cone = Cone::new(Point::zero(), Point::unit_z(), EFloat64::from(1.0), true);
+
+        // A generator of the cone
+        let line = Line::new(
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::from_f64(1.0, 0.0, 1.0).normalize().unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            line_cone_intersection(&line, &cone),
+            ConeLineIntersection::Line(_)
+        ));
+
+        // A horizontal line through the axis
+        let line = Line::new(Point::from_f64(0.0, 0.0, 2.0), Point::unit_x()).unwrap();
+        match line_cone_intersection(&line, &cone) {
+            ConeLineIntersection::TwoPoints(p1, p2) => {
+                assert!(
+                    p1 == Point::from_f64(2.0, 0.0, 2.0) || p1 == Point::from_f64(-2.0, 0.0, 2.0)
+                );
+                assert!(
+                    p2 == Point::from_f64(2.0, 0.0, 2.0) || p2 == Point::from_f64(-2.0, 0.0, 2.0)
+                );
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        // The axis only touches the apex, below the cone there is nothing
+        let line = Line::new(Point::from_f64(0.0, 0.0, -1.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            line_cone_intersection(&line, &cone),
+            ConeLineIntersection::None
+        ));
+    }
+}
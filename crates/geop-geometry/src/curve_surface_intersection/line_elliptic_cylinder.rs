@@ -0,0 +1,90 @@
+use crate::{
+    curves::line::Line, efloat::EFloat64, point::Point,
+    surfaces::elliptic_cylinder::EllipticCylinder,
+};
+
+pub enum EllipticCylinderLineIntersection {
+    Line(Line),
+    TwoPoints(Point, Point),
+    Point(Point),
+    None,
+}
+
+pub fn line_elliptic_cylinder_intersection(
+    line: &Line,
+    cylinder: &EllipticCylinder,
+) -> EllipticCylinderLineIntersection {
+    // In the coordinates of the cross section, the cylinder is x^2 + y^2 = 1. Along the line, this is a quadratic equation in the line parameter.
+    let (x, y, _) = cylinder.coordinates(line.basis);
+    let (dx, dy, _) = cylinder.coordinates(cylinder.basis + line.direction);
+    let qa = dx * dx + dy * dy;
+    let qb = EFloat64::two() * (x * dx + y * dy);
+    let qc = x * x + y * y - EFloat64::one();
+
+    // The line is parallel to the axis.
+    if qa == 0.0 {
+        if qc == 0.0 {
+            return EllipticCylinderLineIntersection::Line(line.clone());
+        }
+        return EllipticCylinderLineIntersection::None;
+    }
+
+    let determinant_sq = qb * qb - EFloat64::from(4.0) * qa * qc;
+    if determinant_sq < 0.0 {
+        return EllipticCylinderLineIntersection::None;
+    }
+    if determinant_sq == 0.0 {
+        let t = (-qb / (EFloat64::two() * qa)).unwrap();
+        return EllipticCylinderLineIntersection::Point(line.basis + line.direction * t);
+    }
+    let determinant = determinant_sq.sqrt().unwrap();
+    let t1 = ((-qb - determinant) / (EFloat64::two() * qa)).unwrap();
+    let t2 = ((-qb + determinant) / (EFloat64::two() * qa)).unwrap();
+    EllipticCylinderLineIntersection::TwoPoints(
+        line.basis + line.direction * t1,
+        line.basis + line.direction * t2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_line_elliptic_cylinder_intersection() {
+        let cylinder = EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+            true,
+        );
+
+        let line = Line::new(Point::from_f64(2.0, 0.0, 0.0), Point::unit_z()).unwrap();
+        assert!(matches!(
+            line_elliptic_cylinder_intersection(&line, &cylinder),
+            EllipticCylinderLineIntersection::Line(_)
+        ));
+
+        let line = Line::new(Point::from_f64(0.0, 0.0, 1.0), Point::unit_x()).unwrap();
+        match line_elliptic_cylinder_intersection(&line, &cylinder) {
+            EllipticCylinderLineIntersection::TwoPoints(p1, p2) => {
+                assert_eq!(p1, Point::from_f64(-2.0, 0.0, 1.0));
+                assert_eq!(p2, Point::from_f64(2.0, 0.0, 1.0));
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 1.0, 0.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            line_elliptic_cylinder_intersection(&line, &cylinder),
+            EllipticCylinderLineIntersection::Point(_)
+        ));
+
+        let line = Line::new(Point::from_f64(0.0, 2.0, 0.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            line_elliptic_cylinder_intersection(&line, &cylinder),
+            EllipticCylinderLineIntersection::None
+        ));
+    }
+}
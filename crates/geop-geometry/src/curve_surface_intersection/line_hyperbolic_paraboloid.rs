@@ -0,0 +1,108 @@
+use crate::{
+    curves::line::Line, efloat::EFloat64, point::Point,
+    surfaces::hyperbolic_paraboloid::HyperbolicParaboloid,
+};
+
+pub enum HyperbolicParaboloidLineIntersection {
+    Line(Line),
+    TwoPoints(Point, Point),
+    Point(Point),
+    None,
+}
+
+pub fn line_hyperbolic_paraboloid_intersection(
+    line: &Line,
+    surface: &HyperbolicParaboloid,
+) -> HyperbolicParaboloidLineIntersection {
+    // In the coordinates of the surface, it is z = x * y. Along the line, this is a quadratic equation in the line parameter.
+    let (x, y, z) = surface.coordinates(line.basis);
+    let (dx, dy, dz) = surface.coordinates(surface.basis + line.direction);
+    let qa = -(dx * dy);
+    let qb = dz - x * dy - y * dx;
+    let qc = z - x * y;
+
+    // The line is parallel to one of the rulings, so it crosses the surface once or is a ruling.
+    if qa == 0.0 {
+        if qb == 0.0 {
+            if qc == 0.0 {
+                return HyperbolicParaboloidLineIntersection::Line(line.clone());
+            }
+            return HyperbolicParaboloidLineIntersection::None;
+        }
+        let t = (-qc / qb).unwrap();
+        return HyperbolicParaboloidLineIntersection::Point(line.basis + line.direction * t);
+    }
+
+    let determinant_sq = qb * qb - EFloat64::from(4.0) * qa * qc;
+    if determinant_sq < 0.0 {
+        return HyperbolicParaboloidLineIntersection::None;
+    }
+    if determinant_sq == 0.0 {
+        let t = (-qb / (EFloat64::two() * qa)).unwrap();
+        return HyperbolicParaboloidLineIntersection::Point(line.basis + line.direction * t);
+    }
+    let determinant = determinant_sq.sqrt().unwrap();
+    let t1 = ((-qb - determinant) / (EFloat64::two() * qa)).unwrap();
+    let t2 = ((-qb + determinant) / (EFloat64::two() * qa)).unwrap();
+    HyperbolicParaboloidLineIntersection::TwoPoints(
+        line.basis + line.direction * t1,
+        line.basis + line.direction * t2,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::surfaces::SurfaceLike;
+
+    use super::*;
+
+    #[test]
+    fn test_line_hyperbolic_paraboloid_intersection() {
+        // z = x * y
+        let surface = HyperbolicParaboloid::from_corners(
+            Point::zero(),
+            Point::unit_x(),
+            Point::unit_y(),
+            Point::from_f64(1.0, 1.0, 1.0),
+            true,
+        )
+        .unwrap();
+
+        let line = Line::new(Point::from_f64(0.0, 2.0, 0.0), Point::unit_x()).unwrap();
+        assert!(matches!(
+            line_hyperbolic_paraboloid_intersection(&line, &surface),
+            HyperbolicParaboloidLineIntersection::Point(_)
+        ));
+
+        let line = Line::new(
+            Point::from_f64(0.0, 2.0, 0.0),
+            Point::from_f64(1.0, 0.0, 2.0).normalize().unwrap(),
+        )
+        .unwrap();
+        assert!(matches!(
+            line_hyperbolic_paraboloid_intersection(&line, &surface),
+            HyperbolicParaboloidLineIntersection::Line(_)
+        ));
+
+        // Along the diagonal, the surface is z = x^2.
+        let line = Line::new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::from_f64(1.0, 1.0, 0.0).normalize().unwrap(),
+        )
+        .unwrap();
+        match line_hyperbolic_paraboloid_intersection(&line, &surface) {
+            HyperbolicParaboloidLineIntersection::TwoPoints(p1, p2) => {
+                assert!(surface.on_surface(p1) && surface.on_surface(p2));
+                assert!(p1.x.abs() == 1.0 && p1.y == p1.x && p1.z == 1.0);
+                assert!(p2 == -p1 + Point::from_f64(0.0, 0.0, 2.0));
+            }
+            _ => panic!("Intersection should be two points"),
+        }
+
+        let line = Line::new(Point::from_f64(0.0, 0.0, -1.0), line.direction).unwrap();
+        assert!(matches!(
+            line_hyperbolic_paraboloid_intersection(&line, &surface),
+            HyperbolicParaboloidLineIntersection::None
+        ));
+    }
+}
@@ -2,11 +2,18 @@ use crate::efloat::EFloat64;
 
 use crate::{curves::CurveLike, point::Point, surfaces::SurfaceLike};
 
+pub mod circle_cone;
 pub mod circle_cylinder;
 pub mod circle_plane;
 pub mod circle_sphere;
+pub mod conic_quadric;
 pub mod curve_surface;
+pub mod ellipse_elliptic_cylinder;
+pub mod ellipse_plane;
+pub mod line_cone;
 pub mod line_cylinder;
+pub mod line_elliptic_cylinder;
+pub mod line_hyperbolic_paraboloid;
 pub mod line_plane;
 pub mod line_sphere;
 
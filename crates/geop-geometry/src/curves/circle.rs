@@ -350,14 +350,29 @@ impl CurveLike for Circle {
         todo!("Implement this")
     }
 
-    fn sort(&self, _points: Vec<Option<Point>>) -> Vec<Option<Point>> {
-        todo!("Implement this")
+    // Sorts the points by their angle, starting at the radius. Unbounded ends are sorted to the end.
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        let angle = |p: Point| {
+            let p = p - self.basis;
+            self.dir_cross.dot(p).atan2(self.radius.dot(p)).to_f64()
+        };
+        let mut points = points;
+        points.sort_unstable_by(|a, b| match (a, b) {
+            (Some(a), Some(b)) => angle(*a).partial_cmp(&angle(*b)).unwrap(),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        points
     }
 }
 
 impl PartialEq for Circle {
     fn eq(&self, other: &Circle) -> bool {
-        self.basis == other.basis && self.normal == other.normal && self.radius == other.radius
+        // The direction of the radius only defines where the parameterization starts, so it is not part of the shape.
+        self.basis == other.basis
+            && self.normal == other.normal
+            && self.radius.norm() == other.radius.norm()
     }
 }
 
@@ -370,3 +385,24 @@ impl Display for Circle {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_circle_eq_ignores_radius_direction() {
+        let circle = Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::from(2.0)).unwrap();
+        let radius = Point::new(EFloat64::zero(), EFloat64::from(2.0), EFloat64::zero());
+        let rotated = Circle {
+            radius,
+            dir_cross: circle.normal.cross(radius),
+            ..circle.clone()
+        };
+        assert!(circle.radius != rotated.radius);
+        assert_eq!(circle, rotated);
+
+        let larger = Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::from(3.0)).unwrap();
+        assert!(circle != larger);
+    }
+}
@@ -64,6 +64,11 @@ impl Ellipse {
             ))
             .with_context(&error_context);
         }
+        // The ellipse runs from the major to the minor radius, which is counter-clockwise around the normal.
+        let minor_radius = match major_radius.cross(minor_radius).dot(normal) < 0.0 {
+            true => -minor_radius,
+            false => minor_radius,
+        };
         Ok(Ellipse {
             basis,
             normal,
@@ -72,6 +77,39 @@ impl Ellipse {
         })
     }
 
+    // The ellipse basis + cos(t) * a + sin(t) * b, where a and b are conjugate radii that do not have to be orthogonal.
+    // The principal axes are where the conjugate radii are turned to the longest and the shortest radius.
+    pub fn from_conjugate_radii(
+        basis: Point,
+        normal: Point,
+        a: Point,
+        b: Point,
+    ) -> GeometryResult<Ellipse> {
+        let t = ((EFloat64::two() * a.dot(b)).atan2(a.norm_sq() - b.norm_sq()) / EFloat64::two())?;
+        let longest = a * t.cos() + b * t.sin();
+        let shortest = b * t.cos() - a * t.sin();
+        let across = normal.cross(longest).normalize()?;
+        let along = across.cross(normal);
+        Ellipse::try_new(
+            basis,
+            normal,
+            along * longest.norm(),
+            across * shortest.norm(),
+        )
+    }
+
+    // The parameter of a point on the ellipse, which is basis + cos(angle) * major_radius + sin(angle) * minor_radius.
+    fn angle(&self, p: Point) -> EFloat64 {
+        let p = p - self.basis;
+        let x = (self.major_radius.dot(p) / self.major_radius.norm_sq()).unwrap();
+        let y = (self.minor_radius.dot(p) / self.minor_radius.norm_sq()).unwrap();
+        y.atan2(x)
+    }
+
+    fn point_at(&self, angle: EFloat64) -> Point {
+        angle.cos() * self.major_radius + angle.sin() * self.minor_radius + self.basis
+    }
+
     fn assert_on_curve(&self, p: Point, variable_name: &str) -> GeometryResult<()> {
         if !self.on_curve(p) {
             return Err(GeometryError::new(format!(
@@ -176,10 +214,8 @@ impl CurveLike for Ellipse {
 
     fn tangent(&self, p: Point) -> GeometryResult<Point> {
         self.assert_on_curve(p, "p")?;
-        let p = p - self.basis;
-        let x = self.major_radius.dot(p) / self.major_radius.norm();
-        let y = self.minor_radius.dot(p) / self.minor_radius.norm();
-        let tangent = y.unwrap() * self.major_radius - x.unwrap() * self.minor_radius;
+        let angle = self.angle(p);
+        let tangent = -angle.sin() * self.major_radius + angle.cos() * self.minor_radius;
         Ok(tangent.normalize().unwrap())
     }
 
@@ -209,8 +245,26 @@ impl CurveLike for Ellipse {
 
         self.assert_on_curve(x, "x").with_context(&error_context)?;
         self.assert_on_curve(y, "y").with_context(&error_context)?;
-        let angle = (x - self.basis).angle(y - self.basis).unwrap();
-        Ok(self.major_radius.norm() * angle)
+        // Arc length from x to y, by Simpson's rule.
+        let start = self.angle(x).to_f64();
+        let mut end = self.angle(y).to_f64();
+        if end < start {
+            end += std::f64::consts::PI * 2.0;
+        }
+        let speed = |angle: f64| {
+            let angle = EFloat64::from(angle);
+            (-angle.sin() * self.major_radius + angle.cos() * self.minor_radius)
+                .norm()
+                .to_f64()
+        };
+        let n = 64;
+        let h = (end - start) / n as f64;
+        let mut sum = speed(start) + speed(end);
+        for i in 1..n {
+            let weight = if i % 2 == 1 { 4.0 } else { 2.0 };
+            sum += weight * speed(start + h * i as f64);
+        }
+        Ok(EFloat64::from(sum * h / 3.0))
     }
 
     fn interpolate(
@@ -244,45 +298,28 @@ impl CurveLike for Ellipse {
                     .with_context(&error_context)?;
                 self.assert_on_curve(end, "end")
                     .with_context(&error_context)?;
-                let start = start - self.basis;
-                let end = end - self.basis;
-                let x_start = self.major_radius.dot(start);
-                let x_end = self.major_radius.dot(end);
-                let y_start = self.minor_radius.dot(start);
-                let y_end = self.minor_radius.dot(end);
-                let angle1 = y_start.atan2(x_start);
-                let mut angle2 = y_end.atan2(x_end);
+                let angle1 = self.angle(start);
+                let mut angle2 = self.angle(end);
                 assert!(angle2 != angle1);
                 if angle2.upper_bound < angle1.lower_bound {
-                    angle2 = EFloat64::two_pi();
+                    angle2 = angle2 + EFloat64::two_pi();
                 }
                 let angle = angle1 + EFloat64::from(t) * (angle2 - angle1);
-                Ok(angle.cos() * self.major_radius + angle.sin() * self.minor_radius + self.basis)
+                Ok(self.point_at(angle))
             }
             (Some(start), None) => {
                 self.assert_on_curve(start, "start")
                     .with_context(&error_context)?;
-                let start = start - self.basis;
-                let x_start = self.major_radius.dot(start);
-                let y_start = self.minor_radius.dot(start);
-                let angle1 = y_start.atan2(x_start);
-                let angle = angle1 + EFloat64::from(t * std::f64::consts::PI * 2.0);
-                Ok(angle.cos() * self.major_radius + angle.sin() * self.minor_radius + self.basis)
+                let angle = self.angle(start) + EFloat64::from(t * std::f64::consts::PI * 2.0);
+                Ok(self.point_at(angle))
             }
             (None, Some(end)) => {
                 self.assert_on_curve(end, "end")
                     .with_context(&error_context)?;
-                let end = end - self.basis;
-                let x_end = self.major_radius.dot(end);
-                let y_end = self.minor_radius.dot(end);
-                let angle2 = y_end.atan2(x_end);
-                let angle = angle2 + EFloat64::from(t * std::f64::consts::PI * 2.0);
-                Ok(angle.cos() * self.major_radius + angle.sin() * self.minor_radius + self.basis)
-            }
-            (None, None) => {
-                let angle = EFloat64::from(t * std::f64::consts::PI * 2.0);
-                Ok(angle.cos() * self.major_radius + angle.sin() * self.minor_radius + self.basis)
+                let angle = self.angle(end) + EFloat64::from(t * std::f64::consts::PI * 2.0);
+                Ok(self.point_at(angle))
             }
+            (None, None) => Ok(self.point_at(EFloat64::from(t * std::f64::consts::PI * 2.0))),
         }
     }
 
@@ -312,15 +349,9 @@ impl CurveLike for Ellipse {
                     .with_context(&error_context)?;
                 self.assert_on_curve(end, "end")
                     .with_context(&error_context)?;
-                let start = start - self.basis;
-                let end = end - self.basis;
-                let m = m - self.basis;
-                let angle_start = self
-                    .minor_radius
-                    .dot(start)
-                    .atan2(self.major_radius.dot(start));
-                let mut angle_end = self.minor_radius.dot(end).atan2(self.major_radius.dot(end));
-                let mut angle_m = self.minor_radius.dot(m).atan2(self.major_radius.dot(m));
+                let angle_start = self.angle(start);
+                let mut angle_end = self.angle(end);
+                let mut angle_m = self.angle(m);
                 if angle_end.upper_bound < angle_start.lower_bound {
                     angle_end = angle_end + EFloat64::two_pi();
                 }
@@ -398,7 +429,9 @@ impl CurveLike for Ellipse {
                 if self.between(p1, Some(start), Some(end)).unwrap() {
                     return Ok(p1);
                 } else {
-                    return Ok(-mid + self.basis);
+                    return Ok(self
+                        .transform_point_from_circle(-mid)
+                        .expect("Mid is on curve"));
                 }
             }
             (Some(start), None) => {
@@ -485,8 +518,19 @@ impl CurveLike for Ellipse {
         todo!("Implement this")
     }
 
-    fn sort(&self, _points: Vec<Option<Point>>) -> Vec<Option<Point>> {
-        todo!("Implement this")
+    fn sort(&self, points: Vec<Option<Point>>) -> Vec<Option<Point>> {
+        let mut points = points;
+        points.sort_unstable_by(|a, b| match (a, b) {
+            (Some(a), Some(b)) => self
+                .angle(*a)
+                .to_f64()
+                .partial_cmp(&self.angle(*b).to_f64())
+                .unwrap(),
+            (Some(_), None) => std::cmp::Ordering::Less,
+            (None, Some(_)) => std::cmp::Ordering::Greater,
+            (None, None) => std::cmp::Ordering::Equal,
+        });
+        points
     }
 }
 
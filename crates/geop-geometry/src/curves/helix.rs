@@ -41,7 +41,9 @@ impl Helix {
         let basis = transform * self.basis;
         let pitch = transform * (self.pitch + basis_old) - basis;
         let radius = transform * (self.radius + basis_old) - basis;
-        Helix::new(basis, pitch, radius, self.right_winding)
+        // A reflection turns a right winding helix into a left winding one.
+        let dir_cross = transform * (self.dir_cross + basis_old) - basis;
+        Helix::new(basis, pitch, radius, pitch.cross(radius).dot(dir_cross) > 0.0)
     }
 
     pub fn neg(&self) -> Helix {
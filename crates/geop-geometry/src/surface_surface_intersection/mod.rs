@@ -1,3 +1,6 @@
+pub mod plane_cone;
+pub mod plane_cylinder;
+pub mod plane_elliptic_cylinder;
 pub mod plane_plane;
 pub mod plane_sphere;
 pub mod surface_surface;
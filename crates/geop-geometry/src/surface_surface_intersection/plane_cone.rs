@@ -0,0 +1,139 @@
+use crate::{
+    curves::{circle::Circle, ellipse::Ellipse, line::Line},
+    efloat::EFloat64,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    surfaces::{cone::Cone, plane::Plane, SurfaceLike},
+};
+
+pub enum PlaneConeIntersection {
+    Circle(Circle),
+    Ellipse(Ellipse),
+    TwoLines(Line, Line),
+    Line(Line),
+    Point(Point),
+    None,
+}
+
+// Parabolas and hyperbolas are not supported curves, so planes that cut them out of the cone return an error.
+pub fn plane_cone_intersection(a: &Cone, b: &Plane) -> GeometryResult<PlaneConeIntersection> {
+    let n = b.normal(b.basis).normalize().unwrap();
+    let cos = a.extend_dir.dot(n);
+    let distance = (b.basis - a.basis).dot(n);
+
+    // In the plane, the cone is a x^2 + y^2 = s^2 (h + x sin)^2, with x along the axis tilted into the plane, and y across it.
+    let tilt = a.extend_dir - n * cos;
+    let sin = tilt.norm();
+    let k = EFloat64::one() + a.slope * a.slope;
+    let quadratic = EFloat64::one() - k * sin * sin;
+
+    // The plane runs through the apex.
+    if distance == 0.0 {
+        if tilt.norm() == 0.0 || quadratic > 0.0 {
+            return Ok(PlaneConeIntersection::Point(a.basis));
+        }
+        let w = tilt.normalize().unwrap();
+        let m = n.cross(w);
+        if quadratic == 0.0 {
+            return Ok(PlaneConeIntersection::Line(Line::new(a.basis, w).unwrap()));
+        }
+        let spread = (-quadratic).sqrt().unwrap();
+        return Ok(PlaneConeIntersection::TwoLines(
+            Line::new(a.basis, (w + m * spread).normalize().unwrap()).unwrap(),
+            Line::new(a.basis, (w - m * spread).normalize().unwrap()).unwrap(),
+        ));
+    }
+
+    if quadratic <= 0.0 {
+        return Err(GeometryError::new(format!(
+            "The intersection of {:?} and {:?} is a parabola or hyperbola, which is not supported",
+            a, b
+        )));
+    }
+
+    // Otherwise, the plane cuts the axis at the height h. Below the apex, it misses the cone.
+    let height = (distance / cos).unwrap();
+    if height < 0.0 {
+        return Ok(PlaneConeIntersection::None);
+    }
+    let center = a.basis + a.extend_dir * height;
+    if tilt.norm() == 0.0 {
+        return Ok(PlaneConeIntersection::Circle(
+            Circle::try_new(center, n, a.slope * height).unwrap(),
+        ));
+    }
+    let w = tilt.normalize().unwrap();
+    let m = n.cross(w);
+    let radius = a.slope * height * cos.abs();
+    let offset = a.slope * a.slope * sin * height;
+    let center = center + w * (offset / quadratic).unwrap();
+    let major = w * (radius / quadratic).unwrap();
+    let minor = m * (radius / quadratic.sqrt().unwrap()).unwrap();
+    Ok(PlaneConeIntersection::Ellipse(
+        Ellipse::try_new(center, n, major, minor).unwrap(),
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::curves::CurveLike;
+
+    use super::*;
+
+    #[test]
+    fn test_plane_cone_intersection() {
+        let cone = Cone::new(Point::zero(), Point::unit_z(), EFloat64::from(0.5), true);
+
+        let top = Plane::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        match plane_cone_intersection(&cone, &top).unwrap() {
+            PlaneConeIntersection::Circle(circle) => {
+                assert!(circle.basis == Point::from_f64(0.0, 0.0, 2.0));
+                assert!(circle.radius.norm() == 1.0);
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        let oblique = Plane::new(
+            Point::from_f64(0.0, 0.0, 2.0),
+            Point::from_f64(1.0, 0.0, 0.5),
+            Point::unit_y(),
+        );
+        match plane_cone_intersection(&cone, &oblique).unwrap() {
+            PlaneConeIntersection::Ellipse(ellipse) => {
+                for t in [0.0, 0.3, 0.5, 0.8] {
+                    let p = ellipse.interpolate(None, None, t).unwrap();
+                    assert!(cone.on_surface(p));
+                    assert!(oblique.on_surface(p));
+                }
+            }
+            _ => panic!("Intersection should be an ellipse"),
+        }
+
+        let below = Plane::new(
+            Point::from_f64(0.0, 0.0, -1.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        assert!(matches!(
+            plane_cone_intersection(&cone, &below).unwrap(),
+            PlaneConeIntersection::None
+        ));
+
+        let apex = Plane::new(Point::zero(), Point::unit_x(), Point::unit_z());
+        match plane_cone_intersection(&cone, &apex).unwrap() {
+            PlaneConeIntersection::TwoLines(l1, l2) => {
+                for l in [l1, l2] {
+                    assert!(cone.on_surface(l.basis + l.direction));
+                }
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        let side = Plane::new(Point::unit_y(), Point::unit_x(), Point::unit_z());
+        assert!(plane_cone_intersection(&cone, &side).is_err());
+    }
+}
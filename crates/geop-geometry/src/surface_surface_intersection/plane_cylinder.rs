@@ -0,0 +1,111 @@
+use crate::{
+    curves::{circle::Circle, ellipse::Ellipse, line::Line},
+    surfaces::{cylinder::Cylinder, plane::Plane, SurfaceLike},
+};
+
+pub enum PlaneCylinderIntersection {
+    Circle(Circle),
+    Ellipse(Ellipse),
+    TwoLines(Line, Line),
+    Line(Line),
+    None,
+}
+
+pub fn plane_cylinder_intersection(a: &Cylinder, b: &Plane) -> PlaneCylinderIntersection {
+    let n = b.normal(b.basis).normalize().unwrap();
+    let r = a.radius.norm();
+    let cos = a.extend_dir.dot(n);
+
+    // The plane is parallel to the axis, and cuts the cylinder along the axis.
+    if cos == 0.0 {
+        let rho = (a.basis - b.basis).dot(n);
+        let foot = a.basis - n * rho;
+        if rho < r.upper_bound && rho > -r.upper_bound {
+            let side =
+                n.cross(a.extend_dir).normalize().unwrap() * (r * r - rho * rho).sqrt().unwrap();
+            return PlaneCylinderIntersection::TwoLines(
+                Line::new(foot + side, a.extend_dir).unwrap(),
+                Line::new(foot - side, a.extend_dir).unwrap(),
+            );
+        }
+        if rho == r || rho == -r {
+            return PlaneCylinderIntersection::Line(Line::new(foot, a.extend_dir).unwrap());
+        }
+        return PlaneCylinderIntersection::None;
+    }
+
+    // Otherwise, the plane cuts the axis, which is the center of the circle or ellipse.
+    let center = a.basis + a.extend_dir * ((b.basis - a.basis).dot(n) / cos).unwrap();
+    let minor = n.cross(a.extend_dir);
+    if minor.norm() == 0.0 {
+        return PlaneCylinderIntersection::Circle(Circle::try_new(center, n, r).unwrap());
+    }
+    let minor = minor.normalize().unwrap();
+    let major = n.cross(minor) * (r / cos.abs()).unwrap();
+    PlaneCylinderIntersection::Ellipse(Ellipse::try_new(center, n, major, minor * r).unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::CurveLike, efloat::EFloat64, point::Point};
+
+    use super::*;
+
+    #[test]
+    fn test_plane_cylinder_intersection() {
+        let cylinder = Cylinder::new(Point::zero(), Point::unit_z(), EFloat64::one(), true);
+
+        let top = Plane::new(Point::unit_z(), Point::unit_x(), Point::unit_y());
+        match plane_cylinder_intersection(&cylinder, &top) {
+            PlaneCylinderIntersection::Circle(circle) => {
+                assert_eq!(circle.basis, Point::unit_z());
+                assert_eq!(circle.radius.norm(), 1.0);
+            }
+            _ => panic!("Intersection should be a circle"),
+        }
+
+        let oblique = Plane::new(
+            Point::zero(),
+            Point::from_f64(1.0, 0.0, 1.0),
+            Point::unit_y(),
+        );
+        match plane_cylinder_intersection(&cylinder, &oblique) {
+            PlaneCylinderIntersection::Ellipse(ellipse) => {
+                assert!((ellipse.major_radius.norm().to_f64() - 2.0f64.sqrt()).abs() < 1e-9);
+                assert!((ellipse.minor_radius.norm().to_f64() - 1.0).abs() < 1e-9);
+                assert!(ellipse.on_curve(Point::from_f64(1.0, 0.0, 1.0)));
+            }
+            _ => panic!("Intersection should be an ellipse"),
+        }
+
+        let side = Plane::new(
+            Point::from_f64(0.5, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        match plane_cylinder_intersection(&cylinder, &side) {
+            PlaneCylinderIntersection::TwoLines(l1, l2) => {
+                assert!(cylinder.on_surface(l1.basis));
+                assert!(cylinder.on_surface(l2.basis));
+                assert!(side.on_surface(l1.basis));
+                assert!(side.on_surface(l2.basis));
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        let tangent = Plane::new(Point::unit_x(), Point::unit_y(), Point::unit_z());
+        assert!(matches!(
+            plane_cylinder_intersection(&cylinder, &tangent),
+            PlaneCylinderIntersection::Line(_)
+        ));
+        let outside = Plane::new(
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        assert!(matches!(
+            plane_cylinder_intersection(&cylinder, &outside),
+            PlaneCylinderIntersection::None
+        ));
+    }
+}
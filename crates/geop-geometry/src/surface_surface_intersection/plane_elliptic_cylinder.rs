@@ -0,0 +1,106 @@
+use crate::{
+    curve_curve_intersection::ellipse_line::{ellipse_line_intersection, EllipseLineIntersection},
+    curves::{ellipse::Ellipse, line::Line},
+    surfaces::{elliptic_cylinder::EllipticCylinder, plane::Plane, SurfaceLike},
+};
+
+use super::plane_plane::{plane_plane_intersection, PlanePlaneIntersection};
+
+pub enum PlaneEllipticCylinderIntersection {
+    Ellipse(Ellipse),
+    TwoLines(Line, Line),
+    Line(Line),
+    None,
+}
+
+pub fn plane_elliptic_cylinder_intersection(
+    a: &EllipticCylinder,
+    b: &Plane,
+) -> PlaneEllipticCylinderIntersection {
+    let n = b.normal(b.basis).normalize().unwrap();
+    let cos = a.extend_dir.dot(n);
+
+    // The plane is parallel to the axis, so it cuts the cylinder along the rulings through the points where it crosses the cross section.
+    if cos == 0.0 {
+        let cross_section =
+            Ellipse::try_new(a.basis, a.extend_dir, a.major_radius, a.minor_radius).unwrap();
+        let section_plane = Plane::new(a.basis, a.major_radius, a.minor_radius);
+        let PlanePlaneIntersection::Line(line) = plane_plane_intersection(&section_plane, b) else {
+            return PlaneEllipticCylinderIntersection::None;
+        };
+        return match ellipse_line_intersection(&cross_section, &line) {
+            EllipseLineIntersection::TwoPoint(p1, p2) => {
+                PlaneEllipticCylinderIntersection::TwoLines(
+                    Line::new(p1, a.extend_dir).unwrap(),
+                    Line::new(p2, a.extend_dir).unwrap(),
+                )
+            }
+            EllipseLineIntersection::OnePoint(p) => {
+                PlaneEllipticCylinderIntersection::Line(Line::new(p, a.extend_dir).unwrap())
+            }
+            EllipseLineIntersection::None => PlaneEllipticCylinderIntersection::None,
+        };
+    }
+
+    // Otherwise, the radii of the cross section slide along the axis into the plane. They are conjugate radii of the ellipse in the plane.
+    let center = a.basis + a.extend_dir * ((b.basis - a.basis).dot(n) / cos).unwrap();
+    let major = a.major_radius - a.extend_dir * (a.major_radius.dot(n) / cos).unwrap();
+    let minor = a.minor_radius - a.extend_dir * (a.minor_radius.dot(n) / cos).unwrap();
+
+    PlaneEllipticCylinderIntersection::Ellipse(
+        Ellipse::from_conjugate_radii(center, n, major, minor).unwrap(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{curves::CurveLike, point::Point};
+
+    use super::*;
+
+    #[test]
+    fn test_plane_elliptic_cylinder_intersection() {
+        let cylinder = EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+            true,
+        );
+
+        let oblique = Plane::new(
+            Point::from_f64(0.0, 0.0, 1.0),
+            Point::from_f64(1.0, 1.0, 0.5),
+            Point::from_f64(-1.0, 1.0, 0.0),
+        );
+        match plane_elliptic_cylinder_intersection(&cylinder, &oblique) {
+            PlaneEllipticCylinderIntersection::Ellipse(ellipse) => {
+                for t in [0.0, 0.3, 0.5, 0.8] {
+                    let p = ellipse.interpolate(None, None, t).unwrap();
+                    assert!(cylinder.on_surface(p));
+                    assert!(oblique.on_surface(p));
+                }
+            }
+            _ => panic!("Intersection should be an ellipse"),
+        }
+
+        let side = Plane::new(Point::unit_x(), Point::unit_y(), Point::unit_z());
+        match plane_elliptic_cylinder_intersection(&cylinder, &side) {
+            PlaneEllipticCylinderIntersection::TwoLines(l1, l2) => {
+                assert!(cylinder.on_surface(l1.basis));
+                assert!(cylinder.on_surface(l2.basis));
+            }
+            _ => panic!("Intersection should be two lines"),
+        }
+
+        let outside = Plane::new(
+            Point::from_f64(3.0, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        );
+        assert!(matches!(
+            plane_elliptic_cylinder_intersection(&cylinder, &outside),
+            PlaneEllipticCylinderIntersection::None
+        ));
+    }
+}
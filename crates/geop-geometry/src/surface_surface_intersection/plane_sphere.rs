@@ -17,7 +17,7 @@ pub fn plane_sphere_intersection(a: &Sphere, b: &Plane) -> PlaneSphereIntersecti
     let r = a.radius;
 
     if rho < r.upper_bound && rho > -r.upper_bound {
-        let new_circle_center = a.basis + n * rho;
+        let new_circle_center = a.basis - n * rho;
         let new_circle_radius = (r * r - rho * rho).sqrt();
         return PlaneSphereIntersection::Circle(
             Circle::try_new(new_circle_center, n, new_circle_radius.unwrap()).unwrap(),
@@ -87,6 +87,23 @@ mod tests {
             _ => panic!("Intersection should be a circle"),
         }
 
+        // Planes above and below the center cut circles around the foot of the center on the plane.
+        let sphere = Sphere::new(Point::zero(), EFloat64::from(2.0), true);
+        for z in [1.0, -1.0] {
+            let plane = Plane::new(
+                Point::from_f64(3.0, 0.0, z),
+                Point::unit_x(),
+                Point::unit_y(),
+            );
+            match plane_sphere_intersection(&sphere, &plane) {
+                PlaneSphereIntersection::Circle(circle) => {
+                    assert_eq!(circle.basis, Point::from_f64(0.0, 0.0, z));
+                    assert_eq!(circle.radius.norm(), 3.0_f64.sqrt());
+                }
+                _ => panic!("Intersection should be a circle"),
+            }
+        }
+
         // Move the sphere far enough that there is no intersection
         let sphere = Sphere::new(Point::from_f64(1.0, 1.0, 5.0), EFloat64::one(), true);
 
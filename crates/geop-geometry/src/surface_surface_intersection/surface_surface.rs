@@ -1,6 +1,87 @@
-use crate::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+use crate::{
+    curves::curve::Curve,
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    surfaces::surface::Surface,
+};
 
-use super::plane_plane::{plane_plane_intersection, PlanePlaneIntersection};
+use super::{
+    plane_cone::{plane_cone_intersection, PlaneConeIntersection},
+    plane_cylinder::{plane_cylinder_intersection, PlaneCylinderIntersection},
+    plane_elliptic_cylinder::{
+        plane_elliptic_cylinder_intersection, PlaneEllipticCylinderIntersection,
+    },
+    plane_plane::{plane_plane_intersection, PlanePlaneIntersection},
+    plane_sphere::{plane_sphere_intersection, PlaneSphereIntersection},
+};
+
+fn plane_cylinder_curves(intersection: PlaneCylinderIntersection) -> FaceSurfaceIntersection {
+    match intersection {
+        PlaneCylinderIntersection::Circle(circle) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(circle)], vec![])
+        }
+        PlaneCylinderIntersection::Ellipse(ellipse) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Ellipse(ellipse)], vec![])
+        }
+        PlaneCylinderIntersection::TwoLines(l1, l2) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l1), Curve::Line(l2)], vec![])
+        }
+        PlaneCylinderIntersection::Line(l) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+        }
+        PlaneCylinderIntersection::None => FaceSurfaceIntersection::None,
+    }
+}
+
+fn plane_sphere_curves(intersection: PlaneSphereIntersection) -> FaceSurfaceIntersection {
+    match intersection {
+        PlaneSphereIntersection::Circle(circle) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(circle)], vec![])
+        }
+        PlaneSphereIntersection::Point(p) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+        }
+        PlaneSphereIntersection::None => FaceSurfaceIntersection::None,
+    }
+}
+
+fn plane_cone_curves(intersection: PlaneConeIntersection) -> FaceSurfaceIntersection {
+    match intersection {
+        PlaneConeIntersection::Circle(circle) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Circle(circle)], vec![])
+        }
+        PlaneConeIntersection::Ellipse(ellipse) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Ellipse(ellipse)], vec![])
+        }
+        PlaneConeIntersection::TwoLines(l1, l2) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l1), Curve::Line(l2)], vec![])
+        }
+        PlaneConeIntersection::Line(l) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+        }
+        PlaneConeIntersection::Point(p) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![], vec![p])
+        }
+        PlaneConeIntersection::None => FaceSurfaceIntersection::None,
+    }
+}
+
+fn plane_elliptic_cylinder_curves(
+    intersection: PlaneEllipticCylinderIntersection,
+) -> FaceSurfaceIntersection {
+    match intersection {
+        PlaneEllipticCylinderIntersection::Ellipse(ellipse) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Ellipse(ellipse)], vec![])
+        }
+        PlaneEllipticCylinderIntersection::TwoLines(l1, l2) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l1), Curve::Line(l2)], vec![])
+        }
+        PlaneEllipticCylinderIntersection::Line(l) => {
+            FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+        }
+        PlaneEllipticCylinderIntersection::None => FaceSurfaceIntersection::None,
+    }
+}
 
 pub enum FaceSurfaceIntersection {
     None,
@@ -8,51 +89,42 @@ pub enum FaceSurfaceIntersection {
     Surface(Surface),
 }
 
+// Intersections between two curved surfaces are not supported yet and return an error.
 pub fn surface_surface_intersection(
     face_self: &Surface,
     face_other: &Surface,
-) -> FaceSurfaceIntersection {
-    match face_self {
-        Surface::Plane(plane_self) => match face_other {
-            Surface::Plane(plane_other) => {
-                match plane_plane_intersection(plane_self, plane_other) {
-                    PlanePlaneIntersection::None => FaceSurfaceIntersection::None,
-                    PlanePlaneIntersection::Line(l) => {
-                        FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
-                    }
-                    PlanePlaneIntersection::Plane(p) => {
-                        FaceSurfaceIntersection::Surface(Surface::Plane(p))
-                    }
+) -> GeometryResult<FaceSurfaceIntersection> {
+    match (face_self, face_other) {
+        (Surface::Plane(plane_self), Surface::Plane(plane_other)) => {
+            Ok(match plane_plane_intersection(plane_self, plane_other) {
+                PlanePlaneIntersection::None => FaceSurfaceIntersection::None,
+                PlanePlaneIntersection::Line(l) => {
+                    FaceSurfaceIntersection::CurvesAndPoints(vec![Curve::Line(l)], vec![])
+                }
+                PlanePlaneIntersection::Plane(p) => {
+                    FaceSurfaceIntersection::Surface(Surface::Plane(p))
                 }
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Plane-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Plane-Cylinder intersection")
-            }
-        },
-        Surface::Sphere(_sphere_self) => match face_other {
-            Surface::Plane(_plane_other) => {
-                todo!("Sphere-Plane intersection")
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Sphere-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Sphere-Cylinder intersection")
-            }
-        },
-        Surface::Cylinder(_cylinder_self) => match face_other {
-            Surface::Plane(_plane_other) => {
-                todo!("Cylinder-Plane intersection")
-            }
-            Surface::Sphere(_sphere_other) => {
-                todo!("Cylinder-Sphere intersection")
-            }
-            Surface::Cylinder(_cylinder_other) => {
-                todo!("Cylinder-Cylinder intersection")
-            }
-        },
+            })
+        }
+        (Surface::Plane(plane), Surface::Sphere(sphere))
+        | (Surface::Sphere(sphere), Surface::Plane(plane)) => Ok(plane_sphere_curves(
+            plane_sphere_intersection(sphere, plane),
+        )),
+        (Surface::Plane(plane), Surface::Cylinder(cylinder))
+        | (Surface::Cylinder(cylinder), Surface::Plane(plane)) => Ok(plane_cylinder_curves(
+            plane_cylinder_intersection(cylinder, plane),
+        )),
+        (Surface::Plane(plane), Surface::Cone(cone))
+        | (Surface::Cone(cone), Surface::Plane(plane)) => {
+            Ok(plane_cone_curves(plane_cone_intersection(cone, plane)?))
+        }
+        (Surface::Plane(plane), Surface::EllipticCylinder(cylinder))
+        | (Surface::EllipticCylinder(cylinder), Surface::Plane(plane)) => Ok(
+            plane_elliptic_cylinder_curves(plane_elliptic_cylinder_intersection(cylinder, plane)),
+        ),
+        _ => Err(GeometryError::new(format!(
+            "The intersection of {:?} and {:?} is not supported",
+            face_self, face_other
+        ))),
     }
 }
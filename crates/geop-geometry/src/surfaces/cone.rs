@@ -0,0 +1,303 @@
+use crate::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, line::Line},
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    transforms::Transform,
+    HORIZON_DIST,
+};
+
+use super::{
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// A single nappe of a circular cone. It starts at the apex in basis and opens in extend_dir, with the radius growing by slope per unit of height.
+#[derive(Clone, Debug)]
+pub struct Cone {
+    pub basis: Point,
+    pub extend_dir: Point,
+    pub slope: EFloat64,
+    pub normal_outwards: bool,
+    radius_dir: Point,
+    dir_cross: Point,
+}
+
+impl Cone {
+    pub fn new(basis: Point, extend_dir: Point, slope: EFloat64, normal_outwards: bool) -> Cone {
+        assert!(slope > 0.0, "Slope of a cone has to be positive");
+        let extend_dir = extend_dir.normalize().unwrap();
+        let radius_dir = match Point::unit_x().cross(extend_dir).norm_sq()
+            > Point::unit_y().cross(extend_dir).norm_sq().lower_bound
+        {
+            true => Point::unit_x().cross(extend_dir).normalize().unwrap(),
+            false => Point::unit_y().cross(extend_dir).normalize().unwrap(),
+        };
+        Cone {
+            basis,
+            extend_dir,
+            slope,
+            normal_outwards,
+            radius_dir,
+            dir_cross: extend_dir.cross(radius_dir),
+        }
+    }
+
+    fn transform(&self, transform: Transform) -> Self {
+        let basis = transform * self.basis;
+        let extend_dir = transform * (self.extend_dir + self.basis) - basis;
+        Cone::new(basis, extend_dir, self.slope, self.normal_outwards)
+    }
+
+    fn neg(&self) -> Self {
+        Cone::new(
+            self.basis,
+            self.extend_dir,
+            self.slope,
+            !self.normal_outwards,
+        )
+    }
+
+    // The radius of the circle at the height of p.
+    pub fn radius_at(&self, p: Point) -> EFloat64 {
+        (p - self.basis).dot(self.extend_dir) * self.slope
+    }
+
+    // Sine and cosine of the half opening angle.
+    fn half_angle(&self) -> (EFloat64, EFloat64) {
+        let hypotenuse = (EFloat64::one() + self.slope * self.slope).sqrt().unwrap();
+        (
+            (self.slope / hypotenuse).unwrap(),
+            (EFloat64::one() / hypotenuse).unwrap(),
+        )
+    }
+
+    // The unit direction from the axis to p, or None on the axis.
+    fn radial_dir(&self, p: Point) -> Option<Point> {
+        let d = p - self.basis;
+        let radial = d - d.dot(self.extend_dir) * self.extend_dir;
+        radial.normalize().ok()
+    }
+
+    // Signed angle around the axis from x to y.
+    fn angle_between(&self, x: Point, y: Point) -> EFloat64 {
+        match (self.radial_dir(x), self.radial_dir(y)) {
+            (Some(rx), Some(ry)) => rx.cross(ry).dot(self.extend_dir).atan2(rx.dot(ry)),
+            _ => EFloat64::zero(),
+        }
+    }
+
+    // A cone can be unrolled into the plane. There, the distance to the apex is kept and the angles around the axis shrink by the sine of the half angle.
+    fn unrolled(&self, x: Point, y: Point) -> (EFloat64, EFloat64, EFloat64) {
+        let (sin, _) = self.half_angle();
+        (
+            (x - self.basis).norm(),
+            (y - self.basis).norm(),
+            self.angle_between(x, y) * sin,
+        )
+    }
+
+    // The generator through p, which runs from the apex through p and is the only straight line through p on the cone.
+    pub fn generator(&self, p: Point) -> Line {
+        let radial = self.radial_dir(p).unwrap_or(self.radius_dir);
+        let (sin, cos) = self.half_angle();
+        Line::new(
+            p,
+            (self.extend_dir * cos + radial * sin).normalize().unwrap(),
+        )
+        .unwrap()
+    }
+}
+
+impl SurfaceLike for Cone {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::Cone(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::Cone(self.neg())
+    }
+
+    fn normal(&self, p: Point) -> Point {
+        let radial = self
+            .radial_dir(p)
+            .expect("Normal is not defined at the apex");
+        let normal = (radial - self.extend_dir * self.slope).normalize().unwrap();
+        match self.normal_outwards {
+            true => normal,
+            false => -normal,
+        }
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let d = p - self.basis;
+        let height = d.dot(self.extend_dir);
+        let radial = d - height * self.extend_dir;
+        height >= 0.0 && (radial.norm() - height * self.slope) == 0.0
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x), "{:?} has to be on {:?}", x, self);
+        assert!(self.on_surface(y), "{:?} has to be on {:?}", y, self);
+        let (sx, sy, angle) = self.unrolled(x, y);
+        (sx * sx + sy * sy - EFloat64::two() * sx * sy * angle.cos())
+            .max(EFloat64::zero())
+            .sqrt()
+            .unwrap()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let Some(radial) = self.radial_dir(x) else {
+            return self.project(x + u);
+        };
+        let (sin, cos) = self.half_angle();
+        let generator = self.extend_dir * cos + radial * sin;
+        let around = self.extend_dir.cross(radial);
+        // Walk along the straight line in the unrolled cone.
+        let s = (x - self.basis).norm() + u.dot(generator);
+        let t = u.dot(around);
+        let distance = (s * s + t * t).sqrt().unwrap();
+        let angle = (t.atan2(s) / sin).unwrap();
+        let radial = radial * angle.cos() + around * angle.sin();
+        self.basis + (self.extend_dir * cos + radial * sin) * distance
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x), "{:?} {:?}", x, y);
+        assert!(self.on_surface(y), "{:?} {:?}", x, y);
+        let Some(radial) = self.radial_dir(x) else {
+            return Some(y - x);
+        };
+        let (sin, cos) = self.half_angle();
+        let generator = self.extend_dir * cos + radial * sin;
+        let around = self.extend_dir.cross(radial);
+        let (sx, sy, angle) = self.unrolled(x, y);
+        Some(generator * (sy * angle.cos() - sx) + around * (sy * angle.sin()))
+    }
+
+    // Parallel transport along the geodesic is the identity in the unrolled cone. There, the frame of generator and direction around the axis turns by the unrolled angle between x and y.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        let (Some(radial_x), Some(radial_y)) = (self.radial_dir(x), self.radial_dir(y)) else {
+            return None;
+        };
+        let (sin, cos) = self.half_angle();
+        let frame = |radial: Point| {
+            (
+                self.extend_dir * cos + radial * sin,
+                self.extend_dir.cross(radial),
+            )
+        };
+        let (generator_x, around_x) = frame(radial_x);
+        let (generator_y, around_y) = frame(radial_y);
+        let (_, _, angle) = self.unrolled(x, y);
+        let (along, across) = (v.dot(generator_x), v.dot(around_x));
+        Some(
+            generator_y * (along * angle.cos() + across * angle.sin())
+                + around_y * (across * angle.cos() - along * angle.sin()),
+        )
+    }
+
+    // Geodesics are only straight along a generator. Otherwise, they are straight lines in the unrolled cone, which are not one of the supported curves.
+    fn geodesic(&self, p: Point, q: Point) -> GeometryResult<Curve> {
+        assert!(self.on_surface(p));
+        assert!(self.on_surface(q));
+        assert!(p != q);
+        let same_generator = match (self.radial_dir(p), self.radial_dir(q)) {
+            (Some(rp), Some(rq)) => rp.cross(rq).norm() == 0.0 && rp.dot(rq) > 0.0,
+            _ => true,
+        };
+        if same_generator {
+            return Ok(Curve::Line(
+                Line::new(p, (q - p).normalize().unwrap()).unwrap(),
+            ));
+        }
+        Err(GeometryError::new(format!(
+            "The geodesic from {:?} to {:?} on the cone is not a supported curve, because they are on different generators",
+            p, q
+        )))
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 1..=m {
+                let theta = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                let theta = EFloat64::from(theta);
+                let height = EFloat64::from(j as f64 / m as f64 * HORIZON_DIST / 2.0);
+                let point = self.basis
+                    + height * self.extend_dir
+                    + (theta.cos() * self.radius_dir + theta.sin() * self.dir_cross)
+                        * (height * self.slope);
+                assert!(self.on_surface(point));
+                points.push(point);
+            }
+        }
+        points
+    }
+
+    fn project(&self, point: Point) -> Point {
+        let d = point - self.basis;
+        let radial = self.radial_dir(point).unwrap_or(self.radius_dir);
+        let (sin, cos) = self.half_angle();
+        let generator = self.extend_dir * cos + radial * sin;
+        let t = d.dot(generator).max(EFloat64::zero());
+        self.basis + generator * t
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        let grad = point - self.project(point);
+        if grad.norm() <= 0.0 {
+            return None;
+        }
+        Some(grad)
+    }
+}
+
+impl PartialEq for Cone {
+    fn eq(&self, other: &Cone) -> bool {
+        self.basis == other.basis
+            && self.extend_dir == other.extend_dir
+            && self.slope == other.slope
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cone_exp_log() {
+        let cone = Cone::new(Point::zero(), Point::unit_z(), EFloat64::from(0.5), true);
+        let x = Point::from_f64(1.0, 0.0, 2.0);
+        let y = Point::from_f64(0.0, 1.5, 3.0);
+        assert!(cone.on_surface(x));
+        assert!(cone.on_surface(y));
+        assert!(!cone.on_surface(Point::from_f64(1.0, 0.0, -2.0)));
+
+        let u = cone.log(x, y).unwrap();
+        assert!(cone.exp(x, u) == y);
+        assert!(u.norm() == cone.distance(x, y));
+
+        let normal = cone.normal(x);
+        assert!(normal.dot(u) == 0.0);
+        assert!(normal.dot(Point::unit_x()) > 0.0);
+        assert!(normal.dot(Point::unit_z()) < 0.0);
+    }
+}
@@ -2,6 +2,7 @@ use crate::efloat::EFloat64;
 
 use crate::{
     curves::{circle::Circle, curve::Curve, helix::Helix, line::Line, CurveLike},
+    geometry_error::GeometryResult,
     point::Point,
     transforms::Transform,
     HORIZON_DIST,
@@ -164,7 +165,7 @@ impl SurfaceLike for Cylinder {
         todo!()
     }
 
-    fn geodesic(&self, p: Point, q: Point) -> Curve {
+    fn geodesic(&self, p: Point, q: Point) -> GeometryResult<Curve> {
         assert!(self.on_surface(p));
         assert!(self.on_surface(q));
         assert!(p != q);
@@ -176,30 +177,32 @@ impl SurfaceLike for Cylinder {
         let q_proj = q_loc - self.extend_dir * q_height;
         let angle = p_proj.angle(q_proj).unwrap();
         if angle <= 0.0 {
-            return Curve::Line(Line::new(p, (q - p).normalize().unwrap()).unwrap());
+            return Ok(Curve::Line(
+                Line::new(p, (q - p).normalize().unwrap()).unwrap(),
+            ));
         }
         let helix_basis = self.basis + p_height * self.extend_dir;
         let helix_radius = p_proj;
         let helix_pitch =
             (self.extend_dir * (q_height - p_height) * EFloat64::two_pi() / angle).unwrap();
         if helix_pitch.norm() == 0.0 {
-            return Curve::Circle(
+            return Ok(Curve::Circle(
                 Circle::try_new(
                     self.basis + p_height * self.extend_dir,
                     self.extend_dir.normalize().unwrap(),
                     helix_radius.norm(),
                 )
                 .unwrap(),
-            );
+            ));
         }
         let helix = Curve::Helix(Helix::new(helix_basis, helix_pitch, helix_radius, true));
         assert!(helix.on_curve(p));
         if !helix.on_curve(q) {
             let helix = Helix::new(helix_basis, helix_pitch, helix_radius, false);
             assert!(helix.on_curve(q));
-            return Curve::Helix(helix);
+            return Ok(Curve::Helix(helix));
         }
-        helix
+        Ok(helix)
     }
 
     fn point_grid(&self, density: f64) -> Vec<Point> {
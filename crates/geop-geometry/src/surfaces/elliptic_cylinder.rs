@@ -0,0 +1,338 @@
+use crate::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, ellipse::Ellipse, line::Line},
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    transforms::Transform,
+    HORIZON_DIST,
+};
+
+use super::{
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// A right elliptic cylinder. The cross sections are ellipses around the axis through basis, which run from the major
+// to the minor radius counter-clockwise around extend_dir.
+#[derive(Clone, Debug)]
+pub struct EllipticCylinder {
+    pub basis: Point,
+    pub extend_dir: Point,
+    pub major_radius: Point,
+    pub minor_radius: Point,
+    pub normal_outwards: bool,
+}
+
+impl EllipticCylinder {
+    pub fn new(
+        basis: Point,
+        extend_dir: Point,
+        major_radius: Point,
+        minor_radius: Point,
+        normal_outwards: bool,
+    ) -> EllipticCylinder {
+        let extend_dir = extend_dir.normalize().unwrap();
+        assert!(
+            extend_dir.dot(major_radius) == 0.0 && extend_dir.dot(minor_radius) == 0.0,
+            "Radii of an elliptic cylinder have to be perpendicular to its axis"
+        );
+        assert!(
+            major_radius.dot(minor_radius) == 0.0,
+            "Major and minor radius of an elliptic cylinder have to be orthogonal"
+        );
+        let minor_radius = match major_radius.cross(minor_radius).dot(extend_dir) < 0.0 {
+            true => -minor_radius,
+            false => minor_radius,
+        };
+        EllipticCylinder {
+            basis,
+            extend_dir,
+            major_radius,
+            minor_radius,
+            normal_outwards,
+        }
+    }
+
+    fn transform(&self, transform: Transform) -> Self {
+        let basis = transform * self.basis;
+        let extend_dir = transform * (self.extend_dir + self.basis) - basis;
+        let major_radius = transform * (self.major_radius + self.basis) - basis;
+        let minor_radius = transform * (self.minor_radius + self.basis) - basis;
+        EllipticCylinder::new(
+            basis,
+            extend_dir,
+            major_radius,
+            minor_radius,
+            self.normal_outwards,
+        )
+    }
+
+    fn neg(&self) -> Self {
+        EllipticCylinder::new(
+            self.basis,
+            self.extend_dir,
+            self.major_radius,
+            self.minor_radius,
+            !self.normal_outwards,
+        )
+    }
+
+    // Coordinates of p, in which the cross section is the unit circle, and the height along the axis.
+    pub fn coordinates(&self, p: Point) -> (EFloat64, EFloat64, EFloat64) {
+        let d = p - self.basis;
+        (
+            (d.dot(self.major_radius) / self.major_radius.norm_sq()).unwrap(),
+            (d.dot(self.minor_radius) / self.minor_radius.norm_sq()).unwrap(),
+            d.dot(self.extend_dir),
+        )
+    }
+
+    fn angle(&self, p: Point) -> f64 {
+        let (x, y, _) = self.coordinates(p);
+        y.atan2(x).to_f64()
+    }
+
+    fn point_at(&self, angle: f64, height: EFloat64) -> Point {
+        let angle = EFloat64::from(angle);
+        self.basis
+            + angle.cos() * self.major_radius
+            + angle.sin() * self.minor_radius
+            + height * self.extend_dir
+    }
+
+    // Unit tangent of the cross section at the given angle.
+    fn tangent_at(&self, angle: f64) -> Point {
+        let angle = EFloat64::from(angle);
+        (-angle.sin() * self.major_radius + angle.cos() * self.minor_radius)
+            .normalize()
+            .unwrap()
+    }
+
+    fn speed(&self, angle: f64) -> f64 {
+        let a = self.major_radius.norm().to_f64();
+        let b = self.minor_radius.norm().to_f64();
+        (a * a * angle.sin().powi(2) + b * b * angle.cos().powi(2)).sqrt()
+    }
+
+    // Signed arc length of the cross section between two angles, by Simpson's rule.
+    fn arc_length(&self, from: f64, to: f64) -> f64 {
+        let n = 64;
+        let h = (to - from) / n as f64;
+        let mut sum = self.speed(from) + self.speed(to);
+        for i in 1..n {
+            let weight = match i % 2 {
+                0 => 2.0,
+                _ => 4.0,
+            };
+            sum += weight * self.speed(from + h * i as f64);
+        }
+        sum * h / 3.0
+    }
+
+    // The angle that is the given signed arc length away from the start angle.
+    fn angle_after(&self, from: f64, length: f64) -> f64 {
+        let mut angle = from + length / self.speed(from);
+        for _ in 0..32 {
+            let step = (self.arc_length(from, angle) - length) / self.speed(angle);
+            angle -= step;
+            if step.abs() < 1e-15 {
+                break;
+            }
+        }
+        angle
+    }
+
+    // The ruling through p, which is the only straight line through p on the cylinder.
+    pub fn ruling(&self, p: Point) -> Line {
+        Line::new(p, self.extend_dir).unwrap()
+    }
+
+    // Angle from x to y along the shorter way around the axis.
+    fn angle_between(&self, x: Point, y: Point) -> (f64, f64) {
+        let two_pi = 2.0 * std::f64::consts::PI;
+        let from = self.angle(x);
+        let mut to = self.angle(y);
+        to -= two_pi * ((to - from) / two_pi).round();
+        (from, to)
+    }
+}
+
+impl SurfaceLike for EllipticCylinder {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::EllipticCylinder(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::EllipticCylinder(self.neg())
+    }
+
+    // The gradient of x^2 + y^2 in the coordinates of the cross section.
+    fn normal(&self, p: Point) -> Point {
+        let (x, y, _) = self.coordinates(p);
+        let normal = (x * self.major_radius / self.major_radius.norm_sq()).unwrap()
+            + (y * self.minor_radius / self.minor_radius.norm_sq()).unwrap();
+        let normal = normal.normalize().unwrap();
+        match self.normal_outwards {
+            true => normal,
+            false => -normal,
+        }
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let (x, y, _) = self.coordinates(p);
+        x * x + y * y == 1.0
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    // The cylinder can be unrolled into the plane, where the arc length of the cross section is one coordinate.
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        assert!(self.on_surface(x), "{:?} has to be on {:?}", x, self);
+        assert!(self.on_surface(y), "{:?} has to be on {:?}", y, self);
+        let (from, to) = self.angle_between(x, y);
+        let arc_length = EFloat64::from(self.arc_length(from, to));
+        let height_diff = (y - x).dot(self.extend_dir);
+        (arc_length * arc_length + height_diff * height_diff)
+            .sqrt()
+            .unwrap()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let angle = self.angle(x);
+        let (_, _, height) = self.coordinates(x);
+        let height_diff = u.dot(self.extend_dir);
+        let length = u.dot(self.tangent_at(angle)).to_f64();
+        if length == 0.0 {
+            return x + height_diff * self.extend_dir;
+        }
+        self.point_at(self.angle_after(angle, length), height + height_diff)
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x), "{:?} {:?}", x, y);
+        assert!(self.on_surface(y), "{:?} {:?}", x, y);
+        let (from, to) = self.angle_between(x, y);
+        let arc_length = EFloat64::from(self.arc_length(from, to));
+        let height_diff = (y - x).dot(self.extend_dir);
+        Some(self.tangent_at(from) * arc_length + self.extend_dir * height_diff)
+    }
+
+    // Parallel transport along the geodesic is the identity in the unrolled cylinder, where the tangent of the cross section and the axis are the frame at every point.
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        let (from, to) = self.angle_between(x, y);
+        Some(
+            self.tangent_at(to) * v.dot(self.tangent_at(from))
+                + self.extend_dir * v.dot(self.extend_dir),
+        )
+    }
+
+    // Geodesics are straight along a ruling and the cross section at a constant height. Otherwise, they are helices with an elliptic cross section, which are not one of the supported curves.
+    fn geodesic(&self, p: Point, q: Point) -> GeometryResult<Curve> {
+        assert!(self.on_surface(p));
+        assert!(self.on_surface(q));
+        assert!(p != q);
+        let (from, to) = self.angle_between(p, q);
+        let height_diff = (q - p).dot(self.extend_dir);
+        if (q - p - height_diff * self.extend_dir).norm() == 0.0 {
+            return Ok(Curve::Line(
+                Line::new(p, (q - p).normalize().unwrap()).unwrap(),
+            ));
+        }
+        if height_diff == 0.0 {
+            let (_, _, height) = self.coordinates(p);
+            let normal = match to > from {
+                true => self.extend_dir,
+                false => -self.extend_dir,
+            };
+            return Ok(Curve::Ellipse(Ellipse::try_new(
+                self.basis + height * self.extend_dir,
+                normal,
+                self.major_radius,
+                self.minor_radius,
+            )?));
+        }
+        Err(GeometryError::new(format!(
+            "The geodesic from {:?} to {:?} on the elliptic cylinder is not a supported curve, because it winds around the axis",
+            p, q
+        )))
+    }
+
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = (16.0 * density) as usize;
+        let m = (16.0 * density) as usize;
+        let mut points = Vec::with_capacity(n * m);
+        for i in 0..n {
+            for j in 0..m {
+                let theta = 2.0 * std::f64::consts::PI * i as f64 / n as f64;
+                let v = j as f64 / (m as f64 - 1.0);
+                let point = self.point_at(theta, EFloat64::from((v - 0.5) * HORIZON_DIST));
+                assert!(self.on_surface(point));
+                points.push(point);
+            }
+        }
+        points
+    }
+
+    // Scales the point radially onto the surface. This is the closest point only on the axes of the cross section.
+    fn project(&self, point: Point) -> Point {
+        let (_, _, height) = self.coordinates(point);
+        self.point_at(self.angle(point), height)
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        let grad = point - self.project(point);
+        if grad.norm() <= 0.0 {
+            return None;
+        }
+        Some(grad)
+    }
+}
+
+impl PartialEq for EllipticCylinder {
+    fn eq(&self, other: &EllipticCylinder) -> bool {
+        self.basis == other.basis
+            && self.extend_dir == other.extend_dir
+            && self.major_radius == other.major_radius
+            && self.minor_radius == other.minor_radius
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_elliptic_cylinder_exp_log() {
+        let cylinder = EllipticCylinder::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+            true,
+        );
+        let x = Point::from_f64(2.0, 0.0, 1.0);
+        let y = Point::from_f64(0.0, 1.0, 3.0);
+        assert!(cylinder.on_surface(x));
+        assert!(cylinder.on_surface(y));
+        assert!(!cylinder.on_surface(Point::from_f64(1.0, 0.0, 1.0)));
+
+        let u = cylinder.log(x, y).unwrap();
+        assert!((cylinder.exp(x, u) - y).norm() < 1e-9);
+        assert!(u.norm() == cylinder.distance(x, y));
+        assert!(cylinder.normal(x).dot(u) == 0.0);
+        assert!(cylinder.normal(y) == Point::unit_y());
+    }
+}
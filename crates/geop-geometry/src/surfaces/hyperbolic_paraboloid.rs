@@ -0,0 +1,409 @@
+use crate::efloat::EFloat64;
+
+use crate::{
+    curves::{curve::Curve, line::Line},
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+    transforms::Transform,
+};
+
+use super::{
+    surface::{Surface, TangentPoint},
+    SurfaceLike,
+};
+
+// Steps of the Runge-Kutta integration along geodesics.
+const GEODESIC_STEPS: usize = 32;
+// Newton iterations for shooting geodesics and for projecting points.
+const NEWTON_ITERATIONS: usize = 16;
+
+// A hyperbolic paraboloid, given as the bilinear patch basis + u * u_slope + v * v_slope + u * v * twist. The lines of constant u and of constant v are rulings.
+// It is the ruled surface between two skew segments, which are the rulings at v = 0 and v = 1. The normal points in the direction of d/du x d/dv if normal_outwards is true.
+#[derive(Clone, Debug)]
+pub struct HyperbolicParaboloid {
+    pub basis: Point,
+    pub u_slope: Point,
+    pub v_slope: Point,
+    pub twist: Point,
+    pub normal_outwards: bool,
+}
+
+impl HyperbolicParaboloid {
+    pub fn try_new(
+        basis: Point,
+        u_slope: Point,
+        v_slope: Point,
+        twist: Point,
+        normal_outwards: bool,
+    ) -> GeometryResult<HyperbolicParaboloid> {
+        if u_slope.cross(v_slope).dot(twist) == 0.0 {
+            return Err(GeometryError::new(format!(
+                "The twist {} of a hyperbolic paraboloid has to leave the plane of the slopes {} and {}",
+                twist, u_slope, v_slope
+            )));
+        }
+        Ok(HyperbolicParaboloid {
+            basis,
+            u_slope,
+            v_slope,
+            twist,
+            normal_outwards,
+        })
+    }
+
+    // The patch from the segment a0 -> b0 at v = 0 to the segment a1 -> b1 at v = 1. Fails if the segments are coplanar.
+    pub fn from_corners(
+        a0: Point,
+        b0: Point,
+        a1: Point,
+        b1: Point,
+        normal_outwards: bool,
+    ) -> GeometryResult<HyperbolicParaboloid> {
+        HyperbolicParaboloid::try_new(a0, b0 - a0, a1 - a0, b1 - b0 - a1 + a0, normal_outwards)
+    }
+
+    fn transform(&self, transform: Transform) -> Self {
+        // Affine transforms keep the corners of the unit patch.
+        let a0 = transform * self.basis;
+        let b0 = transform * (self.basis + self.u_slope);
+        let a1 = transform * (self.basis + self.v_slope);
+        let b1 = transform * (self.basis + self.u_slope + self.v_slope + self.twist);
+        HyperbolicParaboloid::from_corners(a0, b0, a1, b1, self.normal_outwards)
+            .expect("Affine transforms keep the twist out of the plane of the slopes")
+    }
+
+    fn neg(&self) -> Self {
+        HyperbolicParaboloid {
+            normal_outwards: !self.normal_outwards,
+            ..self.clone()
+        }
+    }
+
+    // Coordinates of p in the frame of the slopes and the twist. The point is on the surface if z = x * y, and then it is at u = x and v = y.
+    pub fn coordinates(&self, p: Point) -> (EFloat64, EFloat64, EFloat64) {
+        let d = p - self.basis;
+        let det = self.u_slope.dot(self.v_slope.cross(self.twist));
+        (
+            (d.dot(self.v_slope.cross(self.twist)) / det).unwrap(),
+            (d.dot(self.twist.cross(self.u_slope)) / det).unwrap(),
+            (d.dot(self.u_slope.cross(self.v_slope)) / det).unwrap(),
+        )
+    }
+
+    fn parameters(&self, p: Point) -> (f64, f64) {
+        let (x, y, _) = self.coordinates(p);
+        (x.to_f64(), y.to_f64())
+    }
+
+    pub fn point_at(&self, u: EFloat64, v: EFloat64) -> Point {
+        self.basis + u * self.u_slope + v * self.v_slope + u * v * self.twist
+    }
+
+    // Derivatives d/du and d/dv at the parameters, which are the directions of the rulings through the point.
+    pub fn slopes(&self, u: EFloat64, v: EFloat64) -> (Point, Point) {
+        (self.u_slope + v * self.twist, self.v_slope + u * self.twist)
+    }
+
+    // The ruling of constant v through p.
+    pub fn ruling(&self, p: Point) -> Line {
+        let (_, v, _) = self.coordinates(p);
+        let (direction, _) = self.slopes(EFloat64::zero(), v);
+        Line::new(p, direction.normalize().unwrap()).unwrap()
+    }
+
+    // Components of the tangent vector w in the directions d/du and d/dv at the parameters.
+    fn tangent_components(&self, u: f64, v: f64, w: Point) -> (f64, f64) {
+        let (pu, pv) = self.slopes(EFloat64::from(u), EFloat64::from(v));
+        let (e, f, g) = (
+            pu.dot(pu).to_f64(),
+            pu.dot(pv).to_f64(),
+            pv.dot(pv).to_f64(),
+        );
+        let (a, b) = (pu.dot(w).to_f64(), pv.dot(w).to_f64());
+        let det = e * g - f * f;
+        ((g * a - f * b) / det, (e * b - f * a) / det)
+    }
+
+    fn tangent_vector(&self, u: f64, v: f64, a: f64, b: f64) -> TangentPoint {
+        let (pu, pv) = self.slopes(EFloat64::from(u), EFloat64::from(v));
+        pu * EFloat64::from(a) + pv * EFloat64::from(b)
+    }
+
+    // Derivative of the state (u, v, u', v', a, b) along a geodesic, where a * d/du + b * d/dv is transported parallel along it.
+    // The only second derivative of the patch is d^2/dudv = twist, so the tangential part of the acceleration has to cancel 2 u' v' twist,
+    // and the one of the derivative of the transported vector has to cancel (a v' + b u') twist.
+    fn geodesic_derivative(&self, state: [f64; 6]) -> [f64; 6] {
+        let [u, v, du, dv, a, b] = state;
+        let (pu, pv) = self.slopes(EFloat64::from(u), EFloat64::from(v));
+        let (e, f, g) = (
+            pu.dot(pu).to_f64(),
+            pu.dot(pv).to_f64(),
+            pv.dot(pv).to_f64(),
+        );
+        let (tu, tv) = (pu.dot(self.twist).to_f64(), pv.dot(self.twist).to_f64());
+        let det = e * g - f * f;
+        let cancel = |c: f64| (-(g * tu - f * tv) * c / det, -(e * tv - f * tu) * c / det);
+        let (ddu, ddv) = cancel(2.0 * du * dv);
+        let (da, db) = cancel(a * dv + b * du);
+        [du, dv, ddu, ddv, da, db]
+    }
+
+    // Follows the geodesic from the given state for unit time with the classical Runge-Kutta method.
+    fn follow_geodesic(&self, mut state: [f64; 6]) -> [f64; 6] {
+        let h = 1.0 / GEODESIC_STEPS as f64;
+        let step = |s: [f64; 6], k: [f64; 6], t: f64| -> [f64; 6] {
+            let mut result = s;
+            for i in 0..6 {
+                result[i] += k[i] * t;
+            }
+            result
+        };
+        for _ in 0..GEODESIC_STEPS {
+            let k1 = self.geodesic_derivative(state);
+            let k2 = self.geodesic_derivative(step(state, k1, h / 2.0));
+            let k3 = self.geodesic_derivative(step(state, k2, h / 2.0));
+            let k4 = self.geodesic_derivative(step(state, k3, h));
+            for i in 0..6 {
+                state[i] += h / 6.0 * (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]);
+            }
+        }
+        state
+    }
+
+    // Parameters of x and the initial velocity in the parameters of the geodesic that reaches y at unit time. Found by Newton's method on the end point.
+    fn shoot(&self, x: Point, y: Point) -> (f64, f64, f64, f64) {
+        let (u0, v0) = self.parameters(x);
+        let (u1, v1) = self.parameters(y);
+        let end = |du: f64, dv: f64| {
+            let state = self.follow_geodesic([u0, v0, du, dv, 0.0, 0.0]);
+            (state[0], state[1])
+        };
+        let (mut du, mut dv) = (u1 - u0, v1 - v0);
+        for _ in 0..NEWTON_ITERATIONS {
+            let (u, v) = end(du, dv);
+            let (ru, rv) = (u1 - u, v1 - v);
+            if ru.abs().max(rv.abs()) < 1e-14 {
+                break;
+            }
+            let h = 1e-7;
+            let (uu, vu) = end(du + h, dv);
+            let (uv, vv) = end(du, dv + h);
+            let (j11, j21, j12, j22) = ((uu - u) / h, (vu - v) / h, (uv - u) / h, (vv - v) / h);
+            let det = j11 * j22 - j12 * j21;
+            du += (j22 * ru - j12 * rv) / det;
+            dv += (j11 * rv - j21 * ru) / det;
+        }
+        (u0, v0, du, dv)
+    }
+}
+
+impl SurfaceLike for HyperbolicParaboloid {
+    fn transform(&self, transform: Transform) -> Surface {
+        Surface::HyperbolicParaboloid(self.transform(transform))
+    }
+
+    fn neg(&self) -> Surface {
+        Surface::HyperbolicParaboloid(self.neg())
+    }
+
+    // d/du x d/dv only vanishes if the twist is in the plane of the slopes, so the normal is defined everywhere.
+    fn normal(&self, p: Point) -> Point {
+        let (u, v, _) = self.coordinates(p);
+        let (pu, pv) = self.slopes(u, v);
+        let normal = pu.cross(pv).normalize().unwrap();
+        match self.normal_outwards {
+            true => normal,
+            false => -normal,
+        }
+    }
+
+    fn on_surface(&self, p: Point) -> bool {
+        let (x, y, z) = self.coordinates(p);
+        z == x * y
+    }
+
+    fn metric(&self, _x: Point, u: TangentPoint, v: TangentPoint) -> EFloat64 {
+        u.dot(v)
+    }
+
+    fn distance(&self, x: Point, y: Point) -> EFloat64 {
+        self.log(x, y).unwrap().norm()
+    }
+
+    fn exp(&self, x: Point, u: TangentPoint) -> Point {
+        assert!(self.on_surface(x));
+        let (u0, v0) = self.parameters(x);
+        let (du, dv) = self.tangent_components(u0, v0, u);
+        let state = self.follow_geodesic([u0, v0, du, dv, 0.0, 0.0]);
+        self.point_at(EFloat64::from(state[0]), EFloat64::from(state[1]))
+    }
+
+    fn log(&self, x: Point, y: Point) -> Option<TangentPoint> {
+        assert!(self.on_surface(x), "{:?} {:?}", x, y);
+        assert!(self.on_surface(y), "{:?} {:?}", x, y);
+        let (u0, v0, du, dv) = self.shoot(x, y);
+        Some(self.tangent_vector(u0, v0, du, dv))
+    }
+
+    fn parallel_transport(
+        &self,
+        v: Option<TangentPoint>,
+        x: Point,
+        y: Point,
+    ) -> Option<TangentPoint> {
+        assert!(self.on_surface(x));
+        assert!(self.on_surface(y));
+        let v = v?;
+        let (u0, v0, du, dv) = self.shoot(x, y);
+        let (a, b) = self.tangent_components(u0, v0, v);
+        let state = self.follow_geodesic([u0, v0, du, dv, a, b]);
+        Some(self.tangent_vector(state[0], state[1], state[4], state[5]))
+    }
+
+    // Geodesics are straight along the rulings. Otherwise, they are not one of the supported curves.
+    fn geodesic(&self, p: Point, q: Point) -> GeometryResult<Curve> {
+        assert!(self.on_surface(p));
+        assert!(self.on_surface(q));
+        assert!(p != q);
+        let (pu, pv, _) = self.coordinates(p);
+        let (qu, qv, _) = self.coordinates(q);
+        if pu == qu || pv == qv {
+            return Ok(Curve::Line(Line::new(p, (q - p).normalize()?)?));
+        }
+        Err(GeometryError::new(format!(
+            "The geodesic from {:?} to {:?} on the hyperbolic paraboloid is not a supported curve, because they are not on a common ruling",
+            p, q
+        )))
+    }
+
+    // The grid covers the patch between the rulings at u, v = 0 and u, v = 1.
+    fn point_grid(&self, density: f64) -> Vec<Point> {
+        let n = ((8.0 * density) as usize).max(2);
+        let mut points = Vec::with_capacity(n * n);
+        for i in 0..n {
+            for j in 0..n {
+                let u = EFloat64::from(i as f64 / (n as f64 - 1.0));
+                let v = EFloat64::from(j as f64 / (n as f64 - 1.0));
+                points.push(self.point_at(u, v));
+            }
+        }
+        points
+    }
+
+    // Newton's method on the squared distance, starting at the parameters of the point.
+    fn project(&self, point: Point) -> Point {
+        let (mut u, mut v) = self.parameters(point);
+        for _ in 0..NEWTON_ITERATIONS {
+            let r = self.point_at(EFloat64::from(u), EFloat64::from(v)) - point;
+            let (pu, pv) = self.slopes(EFloat64::from(u), EFloat64::from(v));
+            let (gu, gv) = (r.dot(pu).to_f64(), r.dot(pv).to_f64());
+            let huu = pu.dot(pu).to_f64();
+            let hvv = pv.dot(pv).to_f64();
+            let huv = (pu.dot(pv) + r.dot(self.twist)).to_f64();
+            let det = huu * hvv - huv * huv;
+            if det <= 0.0 {
+                break;
+            }
+            let step_u = (hvv * gu - huv * gv) / det;
+            let step_v = (huu * gv - huv * gu) / det;
+            u -= step_u;
+            v -= step_v;
+            if step_u.abs().max(step_v.abs()) < 1e-15 {
+                break;
+            }
+        }
+        self.point_at(EFloat64::from(u), EFloat64::from(v))
+    }
+
+    fn unsigned_l2_squared_distance_gradient(&self, point: Point) -> Option<Point> {
+        let grad = point - self.project(point);
+        if grad.norm() <= 0.0 {
+            return None;
+        }
+        Some(grad)
+    }
+}
+
+impl PartialEq for HyperbolicParaboloid {
+    fn eq(&self, other: &HyperbolicParaboloid) -> bool {
+        self.basis == other.basis
+            && self.u_slope == other.u_slope
+            && self.v_slope == other.v_slope
+            && self.twist == other.twist
+            && self.normal_outwards == other.normal_outwards
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // The saddle z = x * y over the unit square.
+    fn saddle() -> HyperbolicParaboloid {
+        HyperbolicParaboloid::from_corners(
+            Point::from_f64(0.0, 0.0, 0.0),
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+            Point::from_f64(1.0, 1.0, 1.0),
+            true,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_hyperbolic_paraboloid_exp_log() {
+        let surface = saddle();
+        let x = Point::from_f64(0.2, 0.3, 0.06);
+        let y = Point::from_f64(0.9, 0.7, 0.63);
+        assert!(surface.on_surface(x));
+        assert!(surface.on_surface(y));
+        assert!(!surface.on_surface(Point::from_f64(0.2, 0.3, 0.0)));
+
+        let u = surface.log(x, y).unwrap();
+        assert!((surface.exp(x, u) - y).norm() < 1e-9);
+        assert!(surface.normal(x).dot(u) == 0.0);
+        // The geodesic is shorter than the path along the rulings, but not shorter than the chord.
+        let distance = surface.distance(x, y).to_f64();
+        let corner = Point::from_f64(0.9, 0.3, 0.27);
+        assert!(distance >= (y - x).norm().to_f64());
+        assert!(distance <= ((corner - x).norm() + (y - corner).norm()).to_f64());
+
+        // Parallel transport keeps the length of the vector and its angle to the geodesic.
+        let w = surface.normal(x).cross(u);
+        let transported = surface.parallel_transport(Some(w), x, y).unwrap();
+        let v = -surface.log(y, x).unwrap();
+        assert!((transported.norm() - w.norm()).abs() < 1e-9);
+        assert!(transported.dot(v).abs() < 1e-9);
+        assert!(surface.normal(y).dot(transported).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_hyperbolic_paraboloid_rulings() {
+        let surface = saddle();
+        let p = Point::from_f64(0.5, 0.5, 0.25);
+        assert!(surface.normal(p) == Point::from_f64(-0.5, -0.5, 1.0).normalize().unwrap());
+
+        // Points on a common ruling are connected by a line, which lies on the surface.
+        let q = Point::from_f64(1.0, 0.5, 0.5);
+        let Curve::Line(line) = surface.geodesic(p, q).unwrap() else {
+            panic!("Geodesic along a ruling should be a line");
+        };
+        assert!(surface.on_surface(line.basis + line.direction * EFloat64::from(3.0)));
+        assert!(surface.ruling(p).direction.is_parallel(q - p));
+        assert!(surface.geodesic(p, Point::from_f64(1.0, 1.0, 1.0)).is_err());
+
+        let projected = surface.project(Point::from_f64(0.5, 0.5, 1.0));
+        assert!(surface.on_surface(projected));
+        assert!((projected - Point::from_f64(0.5, 0.5, 1.0)).is_parallel(surface.normal(projected)));
+
+        // Coplanar segments do not span a hyperbolic paraboloid.
+        assert!(HyperbolicParaboloid::from_corners(
+            Point::zero(),
+            Point::unit_x(),
+            Point::unit_y(),
+            Point::from_f64(1.0, 1.0, 0.0),
+            true,
+        )
+        .is_err());
+    }
+}
@@ -1,9 +1,14 @@
 use crate::efloat::EFloat64;
 use surface::{Surface, TangentPoint};
 
-use crate::{curves::curve::Curve, point::Point, transforms::Transform};
+use crate::{
+    curves::curve::Curve, geometry_error::GeometryResult, point::Point, transforms::Transform,
+};
 
+pub mod cone;
 pub mod cylinder;
+pub mod elliptic_cylinder;
+pub mod hyperbolic_paraboloid;
 pub mod plane;
 pub mod sphere;
 pub mod surface;
@@ -41,8 +46,8 @@ pub trait SurfaceLike {
         y: Point,
     ) -> Option<TangentPoint>;
 
-    // Returns the geodesic between p and q.
-    fn geodesic(&self, x: Point, y: Point) -> Curve;
+    // Returns the geodesic between p and q. Fails if the geodesic is not one of the supported curves.
+    fn geodesic(&self, x: Point, y: Point) -> GeometryResult<Curve>;
 
     // Returns a point grid on the surface, which can be used for visualization.
     fn point_grid(&self, density: f64) -> Vec<Point>;
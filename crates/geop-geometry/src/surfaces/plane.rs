@@ -2,6 +2,7 @@ use crate::efloat::EFloat64;
 
 use crate::{
     curves::{curve::Curve, line::Line},
+    geometry_error::GeometryResult,
     point::Point,
     transforms::Transform,
     HORIZON_DIST,
@@ -120,9 +121,11 @@ impl SurfaceLike for Plane {
         v
     }
 
-    fn geodesic(&self, p: Point, q: Point) -> Curve {
+    fn geodesic(&self, p: Point, q: Point) -> GeometryResult<Curve> {
         assert!(p != q);
-        Curve::Line(Line::new(p, (q - p).normalize().unwrap()).unwrap())
+        Ok(Curve::Line(
+            Line::new(p, (q - p).normalize().unwrap()).unwrap(),
+        ))
     }
 
     fn point_grid(&self, density: f64) -> Vec<Point> {
@@ -2,6 +2,7 @@ use crate::efloat::EFloat64;
 
 use crate::{
     curves::{circle::Circle, curve::Curve},
+    geometry_error::GeometryResult,
     point::Point,
     transforms::Transform,
 };
@@ -150,13 +151,13 @@ impl SurfaceLike for Sphere {
         }
     }
 
-    fn geodesic(&self, p: Point, q: Point) -> Curve {
+    fn geodesic(&self, p: Point, q: Point) -> GeometryResult<Curve> {
         assert!(self.on_surface(p));
         assert!(self.on_surface(q));
         assert!(p != q);
         let normal = (p - self.basis).cross(q - self.basis).normalize().unwrap();
         let circle = Circle::try_new(self.basis, normal, self.radius);
-        Curve::Circle(circle.unwrap())
+        Ok(Curve::Circle(circle.unwrap()))
     }
 
     fn point_grid(&self, density: f64) -> Vec<Point> {
@@ -1,8 +1,13 @@
 use crate::efloat::EFloat64;
 
-use crate::{curves::curve::Curve, point::Point, transforms::Transform};
+use crate::{
+    curves::curve::Curve, geometry_error::GeometryResult, point::Point, transforms::Transform,
+};
 
-use super::{cylinder::Cylinder, plane::Plane, sphere::Sphere, SurfaceLike};
+use super::{
+    cone::Cone, cylinder::Cylinder, elliptic_cylinder::EllipticCylinder,
+    hyperbolic_paraboloid::HyperbolicParaboloid, plane::Plane, sphere::Sphere, SurfaceLike,
+};
 
 pub type TangentPoint = Point;
 
@@ -11,6 +16,9 @@ pub enum Surface {
     Plane(Plane),
     Sphere(Sphere),
     Cylinder(Cylinder),
+    Cone(Cone),
+    EllipticCylinder(EllipticCylinder),
+    HyperbolicParaboloid(HyperbolicParaboloid),
 }
 
 impl SurfaceLike for Surface {
@@ -20,6 +28,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.transform(transform),
             Surface::Sphere(sphere) => sphere.transform(transform),
             Surface::Cylinder(cylinder) => cylinder.transform(transform),
+            Surface::Cone(cone) => cone.transform(transform),
+            Surface::EllipticCylinder(cylinder) => cylinder.transform(transform),
+            Surface::HyperbolicParaboloid(patch) => patch.transform(transform),
         }
     }
 
@@ -29,6 +40,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.neg(),
             Surface::Sphere(sphere) => sphere.neg(),
             Surface::Cylinder(cylinder) => cylinder.neg(),
+            Surface::Cone(cone) => cone.neg(),
+            Surface::EllipticCylinder(cylinder) => cylinder.neg(),
+            Surface::HyperbolicParaboloid(patch) => patch.neg(),
         }
     }
 
@@ -38,6 +52,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.normal(p),
             Surface::Sphere(sphere) => sphere.normal(p),
             Surface::Cylinder(cylinder) => cylinder.normal(p),
+            Surface::Cone(cone) => cone.normal(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.normal(p),
+            Surface::HyperbolicParaboloid(patch) => patch.normal(p),
         }
     }
     // Checks if the point p is on the surface.
@@ -46,6 +63,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.on_surface(p),
             Surface::Sphere(sphere) => sphere.on_surface(p),
             Surface::Cylinder(cylinder) => cylinder.on_surface(p),
+            Surface::Cone(cone) => cone.on_surface(p),
+            Surface::EllipticCylinder(cylinder) => cylinder.on_surface(p),
+            Surface::HyperbolicParaboloid(patch) => patch.on_surface(p),
         }
     }
 
@@ -55,6 +75,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.metric(x, u, v),
             Surface::Sphere(sphere) => sphere.metric(x, u, v),
             Surface::Cylinder(cylinder) => cylinder.metric(x, u, v),
+            Surface::Cone(cone) => cone.metric(x, u, v),
+            Surface::EllipticCylinder(cylinder) => cylinder.metric(x, u, v),
+            Surface::HyperbolicParaboloid(patch) => patch.metric(x, u, v),
         }
     }
     // Returns the Riemannian distance between x and y.
@@ -63,6 +86,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.distance(x, y),
             Surface::Sphere(sphere) => sphere.distance(x, y),
             Surface::Cylinder(cylinder) => cylinder.distance(x, y),
+            Surface::Cone(cone) => cone.distance(x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.distance(x, y),
+            Surface::HyperbolicParaboloid(patch) => patch.distance(x, y),
         }
     }
     // Exponential of u at base x. u_z is ignored.
@@ -71,6 +97,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.exp(x, u),
             Surface::Sphere(sphere) => sphere.exp(x, u),
             Surface::Cylinder(cylinder) => cylinder.exp(x, u),
+            Surface::Cone(cone) => cone.exp(x, u),
+            Surface::EllipticCylinder(cylinder) => cylinder.exp(x, u),
+            Surface::HyperbolicParaboloid(patch) => patch.exp(x, u),
         }
     }
     // Log of y at base x. Z coordinate is set to 0.
@@ -79,6 +108,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.log(x, y),
             Surface::Sphere(sphere) => sphere.log(x, y),
             Surface::Cylinder(cylinder) => cylinder.log(x, y),
+            Surface::Cone(cone) => cone.log(x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.log(x, y),
+            Surface::HyperbolicParaboloid(patch) => patch.log(x, y),
         }
     }
     // Parallel transport of v from x to y.
@@ -92,14 +124,20 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.parallel_transport(v, x, y),
             Surface::Sphere(sphere) => sphere.parallel_transport(v, x, y),
             Surface::Cylinder(cylinder) => cylinder.parallel_transport(v, x, y),
+            Surface::Cone(cone) => cone.parallel_transport(v, x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.parallel_transport(v, x, y),
+            Surface::HyperbolicParaboloid(patch) => patch.parallel_transport(v, x, y),
         }
     }
     // Returns the geodesic between p and q.
-    fn geodesic(&self, x: Point, y: Point) -> Curve {
+    fn geodesic(&self, x: Point, y: Point) -> GeometryResult<Curve> {
         match self {
             Surface::Plane(plane) => plane.geodesic(x, y),
             Surface::Sphere(sphere) => sphere.geodesic(x, y),
             Surface::Cylinder(cylinder) => cylinder.geodesic(x, y),
+            Surface::Cone(cone) => cone.geodesic(x, y),
+            Surface::EllipticCylinder(cylinder) => cylinder.geodesic(x, y),
+            Surface::HyperbolicParaboloid(patch) => patch.geodesic(x, y),
         }
     }
     // Returns a point grid on the surface, which can be used for visualization.
@@ -108,6 +146,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.point_grid(density),
             Surface::Sphere(sphere) => sphere.point_grid(density),
             Surface::Cylinder(cylinder) => cylinder.point_grid(density),
+            Surface::Cone(cone) => cone.point_grid(density),
+            Surface::EllipticCylinder(cylinder) => cylinder.point_grid(density),
+            Surface::HyperbolicParaboloid(patch) => patch.point_grid(density),
         }
     }
     // Finds the closest point on the surface to the given point.
@@ -116,6 +157,9 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.project(point),
             Surface::Sphere(sphere) => sphere.project(point),
             Surface::Cylinder(cylinder) => cylinder.project(point),
+            Surface::Cone(cone) => cone.project(point),
+            Surface::EllipticCylinder(cylinder) => cylinder.project(point),
+            Surface::HyperbolicParaboloid(patch) => patch.project(point),
         }
     }
 
@@ -125,6 +169,13 @@ impl SurfaceLike for Surface {
             Surface::Plane(plane) => plane.unsigned_l2_squared_distance_gradient(point),
             Surface::Sphere(sphere) => sphere.unsigned_l2_squared_distance_gradient(point),
             Surface::Cylinder(cylinder) => cylinder.unsigned_l2_squared_distance_gradient(point),
+            Surface::Cone(cone) => cone.unsigned_l2_squared_distance_gradient(point),
+            Surface::EllipticCylinder(cylinder) => {
+                cylinder.unsigned_l2_squared_distance_gradient(point)
+            }
+            Surface::HyperbolicParaboloid(patch) => {
+                patch.unsigned_l2_squared_distance_gradient(point)
+            }
         }
     }
 }
@@ -98,6 +98,46 @@ impl Transform {
         Transform { matrix }
     }
 
+    // Rotation by angle around the axis through basis, counter-clockwise when looking against the axis.
+    pub fn from_axis_angle(basis: Point, axis: Point, angle: EFloat64) -> Transform {
+        let k = axis.normalize().unwrap();
+        let k = [k.x, k.y, k.z];
+        let (sin, cos) = (angle.sin(), angle.cos());
+        // Rodrigues' rotation formula: R = cos I + sin [k]x + (1 - cos) k k^T
+        let cross = [
+            [EFloat64::zero(), -k[2], k[1]],
+            [k[2], EFloat64::zero(), -k[0]],
+            [-k[1], k[0], EFloat64::zero()],
+        ];
+        let mut matrix = [[EFloat64::zero(); 4]; 4];
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix[i][j] = sin * cross[i][j] + (EFloat64::one() - cos) * k[i] * k[j];
+            }
+            matrix[i][i] = matrix[i][i] + cos;
+        }
+        matrix[3][3] = EFloat64::one();
+        let rotation = Transform { matrix };
+        Transform::from_translation(basis) * rotation * Transform::from_translation(-basis)
+    }
+
+    // Reflection at the plane through basis with the given normal.
+    pub fn from_mirror(basis: Point, normal: Point) -> Transform {
+        let n = normal.normalize().unwrap();
+        let n = [n.x, n.y, n.z];
+        // Householder reflection: R = I - 2 n n^T
+        let mut matrix = [[EFloat64::zero(); 4]; 4];
+        for i in 0..3 {
+            for j in 0..3 {
+                matrix[i][j] = -EFloat64::two() * n[i] * n[j];
+            }
+            matrix[i][i] = matrix[i][i] + EFloat64::one();
+        }
+        matrix[3][3] = EFloat64::one();
+        let reflection = Transform { matrix };
+        Transform::from_translation(basis) * reflection * Transform::from_translation(-basis)
+    }
+
     pub fn from_scale(scale: Point) -> Transform {
         let mut matrix = [[EFloat64::zero(); 4]; 4];
         matrix[0][0] = scale.x;
@@ -108,9 +148,10 @@ impl Transform {
     }
 
     pub fn uniform_scale_factor(&self) -> EFloat64 {
-        let scale_x = self.matrix[0][0] + self.matrix[0][1] + self.matrix[0][2];
-        let scale_y = self.matrix[1][0] + self.matrix[1][1] + self.matrix[1][2];
-        let scale_z = self.matrix[2][0] + self.matrix[2][1] + self.matrix[2][2];
+        // The length of the image of each unit vector, so that rotations have a scale of one.
+        let column =
+            |j: usize| Point::new(self.matrix[0][j], self.matrix[1][j], self.matrix[2][j]).norm();
+        let (scale_x, scale_y, scale_z) = (column(0), column(1), column(2));
         assert!(
             (scale_x.abs() - scale_y.abs()) == 0.0,
             "Scale must be uniform"
@@ -155,4 +196,36 @@ mod tests {
             Point::from_f64(0.0, 0.0, 1.0)
         );
     }
+
+    #[test]
+    fn test_axis_angle() {
+        let t = Transform::from_axis_angle(
+            Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.0, 2.0),
+            EFloat64::half_pi(),
+        );
+        assert_eq!(
+            t * Point::from_f64(2.0, 0.0, 5.0),
+            Point::from_f64(1.0, 1.0, 5.0)
+        );
+        assert_eq!(
+            t * Point::from_f64(1.0, 0.0, 0.0),
+            Point::from_f64(1.0, 0.0, 0.0)
+        );
+        assert!(t.uniform_scale_factor() == 1.0);
+    }
+
+    #[test]
+    fn test_mirror() {
+        let t = Transform::from_mirror(Point::from_f64(0.0, 0.0, 1.0), Point::unit_z());
+        assert_eq!(
+            t * Point::from_f64(1.0, 2.0, 3.0),
+            Point::from_f64(1.0, 2.0, -1.0)
+        );
+        assert_eq!(
+            t * Point::from_f64(1.0, 2.0, 1.0),
+            Point::from_f64(1.0, 2.0, 1.0)
+        );
+        assert!(t.uniform_scale_factor() == 1.0);
+    }
 }
@@ -0,0 +1,136 @@
+use super::{settings::MillingSettings, toolpath::Toolpath};
+use crate::gcode::edge_moves;
+
+// G-code that cuts the toolpaths one after another with absolute positions.
+// Every contour is entered from above: a rapid move to the safe height, a plunge to the depth of the pass, and a retract at the end.
+// Starting the spindle and changing the tool are left to the machine.
+pub fn gcode(toolpaths: &[Toolpath], settings: &MillingSettings) -> String {
+    let safe_height = settings.stock_top + settings.safe_height;
+    let mut lines = vec![
+        "G21 ; millimeters".to_string(),
+        "G90 ; absolute positions".to_string(),
+        format!("G0 Z{:.3}", safe_height),
+    ];
+    for pass in toolpaths.iter().flat_map(|toolpath| toolpath.passes.iter()) {
+        for contour in pass.contours.iter() {
+            let mut position = contour.edges[0].interpolate(0.0);
+            lines.push(format!(
+                "G0 X{:.3} Y{:.3}",
+                position.x.to_f64(),
+                position.y.to_f64()
+            ));
+            lines.push(format!("G1 Z{:.3} F{:.0}", pass.z, settings.plunge_rate));
+            let mut feed = Some(settings.feed_rate);
+            for edge in contour.edges.iter() {
+                for m in edge_moves(edge, position) {
+                    let command = m.command(position);
+                    lines.push(match feed.take() {
+                        Some(feed) => format!("{} F{:.0}", command, feed),
+                        None => command,
+                    });
+                    position = m.end();
+                }
+            }
+            lines.push(format!("G0 Z{:.3}", safe_height));
+        }
+    }
+    lines.join("\n") + "\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        efloat::EFloat64,
+        point::Point,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+    use geop_topology::{
+        primitive_objects::{
+            edges::{circle::primitive_circle, line::primitive_line},
+            faces::rectangle::primitive_rectangle,
+        },
+        topology::{contour::Contour, edge::Edge, face::Face},
+    };
+
+    use super::*;
+    use crate::cam::toolpath::{pocket, profile};
+
+    #[test]
+    fn test_gcode_profile() {
+        let settings = MillingSettings {
+            tool_radius: 1.0,
+            ..MillingSettings::default()
+        };
+        let square = primitive_rectangle(
+            Point::from_f64(0.0, 0.0, -1.0),
+            Point::from_f64(5.0, 0.0, 0.0),
+            Point::from_f64(0.0, 5.0, 0.0),
+        );
+        let gcode = gcode(&[profile(&square, &settings).unwrap()], &settings);
+        let expected = "G21 ; millimeters
+G90 ; absolute positions
+G0 Z5.000
+G0 X6.000 Y6.000
+G1 Z-1.000 F200
+G1 X-6.000 Y6.000 F600
+G1 X-6.000 Y-6.000
+G1 X6.000 Y-6.000
+G1 X6.000 Y6.000
+G0 Z5.000
+";
+        assert_eq!(gcode, expected);
+    }
+
+    #[test]
+    fn test_gcode_arcs() {
+        // A slot with two straight sides and round ends of radius 2, with a round hole in the middle.
+        let (p1, p2) = (
+            Point::from_f64(5.0, -2.0, -1.0),
+            Point::from_f64(5.0, 2.0, -1.0),
+        );
+        let (p3, p4) = (
+            Point::from_f64(-5.0, 2.0, -1.0),
+            Point::from_f64(-5.0, -2.0, -1.0),
+        );
+        let arc = |from: Point, to: Point, center: Point| {
+            let circle = primitive_circle(center, Point::unit_z(), EFloat64::from(2.0));
+            Edge::new(Some(from), Some(to), circle.curve)
+        };
+        let slot = Face::new(
+            vec![
+                Contour::new(vec![
+                    arc(p1, p2, Point::from_f64(5.0, 0.0, -1.0)),
+                    primitive_line(p2, p3).unwrap(),
+                    arc(p3, p4, Point::from_f64(-5.0, 0.0, -1.0)),
+                    primitive_line(p4, p1).unwrap(),
+                ]),
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, -1.0),
+                    -Point::unit_z(),
+                    EFloat64::from(0.5),
+                )]),
+            ],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::from_f64(0.0, 0.0, -1.0),
+                Point::unit_x(),
+                Point::unit_y(),
+            ))),
+        );
+        let settings = MillingSettings {
+            tool_radius: 0.5,
+            ..MillingSettings::default()
+        };
+        let toolpaths = [
+            pocket(&slot, &settings).unwrap(),
+            profile(&slot, &settings).unwrap(),
+        ];
+        let gcode = gcode(&toolpaths, &settings);
+        // The tool runs around the island clockwise, and around the round ends counter-clockwise.
+        assert!(gcode.contains("G2 X-1.000 Y0.000 I1.000 J0.000"));
+        assert!(gcode.contains("G3 X5.000 Y1.500 I0.000 J1.500"));
+        assert!(gcode.contains("G3 X5.000 Y2.500 I0.000 J2.500"));
+        assert_eq!(gcode.matches("G1 Z-1.000").count(), 3);
+    }
+}
@@ -0,0 +1,3 @@
+pub mod gcode;
+pub mod settings;
+pub mod toolpath;
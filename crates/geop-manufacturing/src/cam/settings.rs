@@ -0,0 +1,29 @@
+// Settings of the tool and the stock. Lengths are in millimeters, feed rates in millimeters per minute.
+#[derive(Debug, Clone)]
+pub struct MillingSettings {
+    pub tool_radius: f64,
+    // Distance between neighbouring pocketing passes as a fraction of the tool diameter.
+    pub stepover: f64,
+    // Largest depth that is cut in a single pass.
+    pub step_down: f64,
+    // Height of the top of the stock, where the cutting starts.
+    pub stock_top: f64,
+    // Height above the stock, where the tool moves rapidly between passes.
+    pub safe_height: f64,
+    pub feed_rate: f64,
+    pub plunge_rate: f64,
+}
+
+impl Default for MillingSettings {
+    fn default() -> MillingSettings {
+        MillingSettings {
+            tool_radius: 3.0,
+            stepover: 0.4,
+            step_down: 1.0,
+            stock_top: 0.0,
+            safe_height: 5.0,
+            feed_rate: 600.0,
+            plunge_rate: 200.0,
+        }
+    }
+}
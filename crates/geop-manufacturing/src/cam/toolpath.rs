@@ -0,0 +1,229 @@
+use geop_booleans::intersections::edge_edge::{edge_edge_intersection, EdgeEdgeIntersection};
+use geop_geometry::{
+    efloat::EFloat64, point::Point, surfaces::surface::Surface, transforms::Transform,
+};
+use geop_topology::{
+    mass_properties::face_area,
+    operations::offset::{offset_contour, offset_face},
+    topology::{contour::Contour, face::Face},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use super::settings::MillingSettings;
+
+// The contours that the center of the tool follows at one depth, in the order they are cut.
+#[derive(Debug, Clone)]
+pub struct Pass {
+    pub z: f64,
+    pub contours: Vec<Contour>,
+}
+
+// Passes from the top of the stock down to the face.
+#[derive(Debug, Clone)]
+pub struct Toolpath {
+    pub passes: Vec<Pass>,
+}
+
+// The height of the face, which has to face upwards towards the tool.
+fn face_height(face: &Face) -> TopologyResult<f64> {
+    match face.surface.as_ref() {
+        Surface::Plane(plane) => {
+            let normal = plane.u_slope.cross(plane.v_slope);
+            if !normal.is_parallel(Point::unit_z()) || normal.z <= 0.0 {
+                return Err(TopologyError::new(
+                    "Only horizontal faces that face upwards can be milled".to_string(),
+                ));
+            }
+            Ok(plane.basis.z.to_f64())
+        }
+        _ => Err(TopologyError::new(
+            "Only planar faces can be milled".to_string(),
+        )),
+    }
+}
+
+// The contours at the face moved to the depths between the top of the stock and the face.
+// The depths are evenly spaced, so that no pass is deeper than the step down.
+fn passes(
+    face: &Face,
+    contours: Vec<Contour>,
+    settings: &MillingSettings,
+) -> TopologyResult<Toolpath> {
+    let height = face_height(face)?;
+    let depth = settings.stock_top - height;
+    if depth <= 0.0 {
+        return Err(TopologyError::new(format!(
+            "The face at {} is not below the top of the stock at {}",
+            height, settings.stock_top
+        )));
+    }
+    if settings.step_down <= 0.0 {
+        return Err(TopologyError::new(
+            "The step down must be positive".to_string(),
+        ));
+    }
+    let count = (depth / settings.step_down).ceil() as usize;
+    let passes = (1..=count)
+        .map(|i| {
+            let z = settings.stock_top - depth * i as f64 / count as f64;
+            let shift = Transform::from_translation(Point::from_f64(0.0, 0.0, z - height));
+            Pass {
+                z,
+                contours: contours.iter().map(|c| c.transform(shift)).collect(),
+            }
+        })
+        .collect();
+    Ok(Toolpath { passes })
+}
+
+// Whether two boundaries of the face cross each other, or an outer boundary has shrunk past the holes.
+fn boundaries_overlap(face: &Face) -> bool {
+    if face_area(face) <= 0.0 {
+        return true;
+    }
+    let edges: Vec<_> = face.boundaries.iter().map(|c| &c.edges).collect();
+    edges.iter().enumerate().any(|(i, a)| {
+        edges[i + 1..].iter().any(|b| {
+            a.iter().any(|e1| {
+                b.iter()
+                    .any(|e2| !matches!(edge_edge_intersection(e1, e2), EdgeEdgeIntersection::None))
+            })
+        })
+    })
+}
+
+// Clears the face as a pocket with offset contours, starting with the innermost and ending at the walls.
+// The tool keeps its radius away from the boundaries, so the holes of the face stay as islands.
+// Offsetting stops once the boundaries grow into each other. Boundaries that overlap themselves are not detected.
+pub fn pocket(face: &Face, settings: &MillingSettings) -> TopologyResult<Toolpath> {
+    let stepover = 2.0 * settings.tool_radius * settings.stepover;
+    if settings.tool_radius <= 0.0 || stepover <= 0.0 {
+        return Err(TopologyError::new(
+            "Tool radius and stepover must be positive".to_string(),
+        ));
+    }
+    let mut rings = Vec::<Vec<Contour>>::new();
+    let mut distance = settings.tool_radius;
+    while let Some(offset) = offset_face(face, EFloat64::from(distance))? {
+        if boundaries_overlap(&offset) {
+            break;
+        }
+        rings.push(offset.boundaries);
+        distance += stepover;
+    }
+    if rings.is_empty() {
+        return Err(TopologyError::new(
+            "The tool does not fit into the pocket".to_string(),
+        ));
+    }
+    rings.reverse();
+    passes(face, rings.into_iter().flatten().collect(), settings)
+}
+
+// Cuts around the outside of the face, with the tool moved outwards by its radius.
+pub fn profile(face: &Face, settings: &MillingSettings) -> TopologyResult<Toolpath> {
+    let normal = Point::unit_z();
+    let mut contours = Vec::<Contour>::new();
+    for contour in face.boundaries.iter() {
+        // Outer boundaries run counter-clockwise, so they enclose a positive area on their own.
+        if face_area(&Face::new(vec![contour.clone()], face.surface.clone())) <= 0.0 {
+            continue;
+        }
+        match offset_contour(contour, normal, EFloat64::from(-settings.tool_radius))? {
+            Some(contour) => contours.push(contour),
+            None => {
+                return Err(TopologyError::new(
+                    "The tool path around the face vanishes".to_string(),
+                ))
+            }
+        }
+    }
+    passes(face, contours, settings)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        curves::curve::Curve,
+        efloat::EFloat64,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+    use geop_topology::primitive_objects::{
+        edges::circle::primitive_circle, faces::rectangle::primitive_rectangle,
+    };
+
+    use super::*;
+
+    fn floor() -> Face {
+        primitive_rectangle(
+            Point::from_f64(0.0, 0.0, -2.0),
+            Point::from_f64(10.0, 0.0, 0.0),
+            Point::from_f64(0.0, 10.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_pocket_rectangle() {
+        // Offsets of 3.0, 5.4 and 7.8 fit into the square of 20 x 20.
+        let toolpath = pocket(&floor(), &MillingSettings::default()).unwrap();
+        assert_eq!(toolpath.passes.len(), 2);
+        assert_eq!(toolpath.passes[0].z, -1.0);
+        assert_eq!(toolpath.passes[1].z, -2.0);
+        let pass = &toolpath.passes[1];
+        assert_eq!(pass.contours.len(), 3);
+        // The last contour runs along the walls.
+        assert!(pass.contours[2]
+            .all_points()
+            .contains(&Point::from_f64(7.0, 7.0, -2.0)));
+        assert!(pass.contours[0]
+            .all_points()
+            .contains(&Point::from_f64(2.2, 2.2, -2.0)));
+
+        let settings = MillingSettings {
+            tool_radius: 11.0,
+            ..MillingSettings::default()
+        };
+        assert!(pocket(&floor(), &settings).is_err());
+    }
+
+    #[test]
+    fn test_pocket_and_profile_ring() {
+        // A ring with an outer radius of 10 and an island of radius 2.
+        let ring = Face::new(
+            vec![
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, -1.0),
+                    Point::unit_z(),
+                    EFloat64::from(10.0),
+                )]),
+                Contour::new(vec![primitive_circle(
+                    Point::from_f64(0.0, 0.0, -1.0),
+                    -Point::unit_z(),
+                    EFloat64::from(2.0),
+                )]),
+            ],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::from_f64(0.0, 0.0, -1.0),
+                Point::unit_x(),
+                Point::unit_y(),
+            ))),
+        );
+        let radius = |contour: &Contour| match &contour.edges[0].curve {
+            Curve::Circle(circle) => circle.radius.norm().to_f64(),
+            _ => panic!("Expected a circle"),
+        };
+
+        // The second ring would have the outer boundary at 10 - 5.4 = 4.6 inside of the island at 2 + 5.4 = 7.4.
+        let toolpath = pocket(&ring, &MillingSettings::default()).unwrap();
+        assert_eq!(toolpath.passes.len(), 1);
+        let radii: Vec<f64> = toolpath.passes[0].contours.iter().map(radius).collect();
+        assert_eq!(radii.len(), 2);
+        assert!((radii[0] - 7.0).abs() < 1e-9 && (radii[1] - 5.0).abs() < 1e-9);
+
+        let toolpath = profile(&ring, &MillingSettings::default()).unwrap();
+        assert_eq!(toolpath.passes[0].contours.len(), 1);
+        assert!((radius(&toolpath.passes[0].contours[0]) - 13.0).abs() < 1e-9);
+    }
+}
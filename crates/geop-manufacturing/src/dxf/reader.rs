@@ -0,0 +1,474 @@
+use std::{f64::consts::PI, rc::Rc};
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse, CurveLike},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{plane::Plane, surface::Surface},
+};
+use geop_topology::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    mass_properties::face_area,
+    primitive_objects::edges::{circle::primitive_circle, line::primitive_line},
+    topology::{contour::Contour, edge::Edge, face::Face},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+// Endpoints closer than this are the same vertex. DXF files store rounded coordinates, so the ends of neighbouring entities rarely match exactly.
+const SNAP_TOLERANCE: f64 = 1e-6;
+
+// Group codes of an entity, e.g. (10, "1.5") for the x coordinate of the first point.
+type Groups<'a> = Vec<(i32, &'a str)>;
+
+// How an open piece of the profile gets from its start to its end.
+enum Segment {
+    Line,
+    // Arc of a circle, with the angle that it sweeps counter-clockwise. Negative angles sweep clockwise.
+    Arc(f64),
+    Ellipse(Ellipse),
+}
+
+struct Piece {
+    segment: Segment,
+    start: [f64; 2],
+    end: [f64; 2],
+}
+
+// The entities of the drawing. Open pieces still have to be joined into loops, closed curves are loops on their own.
+#[derive(Default)]
+struct Profile {
+    pieces: Vec<Piece>,
+    closed: Vec<Edge>,
+}
+
+fn groups(dxf: &str) -> TopologyResult<Groups<'_>> {
+    let lines: Vec<&str> = dxf.lines().map(|l| l.trim()).collect();
+    lines
+        .chunks(2)
+        .filter(|pair| pair.len() == 2)
+        .map(|pair| match pair[0].parse::<i32>() {
+            Ok(code) => Ok((code, pair[1])),
+            Err(_) => Err(TopologyError::new(format!(
+                "Invalid DXF group code {}",
+                pair[0]
+            ))),
+        })
+        .collect()
+}
+
+// The entities in the ENTITIES section, with their type and group codes.
+fn entities<'a>(groups: &[(i32, &'a str)]) -> TopologyResult<Vec<(&'a str, Groups<'a>)>> {
+    let start = groups
+        .windows(2)
+        .position(|w| w[0] == (0, "SECTION") && w[1] == (2, "ENTITIES"))
+        .ok_or_else(|| TopologyError::new("The DXF has no ENTITIES section".to_string()))?;
+    let mut entities = Vec::<(&str, Groups)>::new();
+    for &(code, value) in groups[start + 2..].iter() {
+        match code {
+            0 if value == "ENDSEC" => break,
+            0 => entities.push((value, vec![])),
+            _ => {
+                if let Some((_, entity)) = entities.last_mut() {
+                    entity.push((code, value));
+                }
+            }
+        }
+    }
+    Ok(entities)
+}
+
+fn value(groups: &Groups, code: i32) -> TopologyResult<Option<f64>> {
+    match groups.iter().find(|(c, _)| *c == code) {
+        Some((_, v)) => v.parse::<f64>().map(Some).map_err(|_| {
+            TopologyError::new(format!("Invalid DXF value {} for group code {}", v, code))
+        }),
+        None => Ok(None),
+    }
+}
+
+fn required(groups: &Groups, code: i32, entity: &str) -> TopologyResult<f64> {
+    value(groups, code)?
+        .ok_or_else(|| TopologyError::new(format!("{} is missing the group code {}", entity, code)))
+}
+
+// Entities that are drawn upside down, with an extrusion direction of -z, mirror their x coordinates and turn clockwise.
+fn upside_down(groups: &Groups) -> TopologyResult<bool> {
+    Ok(value(groups, 230)?.unwrap_or(1.0) < 0.0)
+}
+
+fn point(p: [f64; 2]) -> Point {
+    Point::from_f64(p[0], p[1], 0.0)
+}
+
+fn normal(counter_clockwise: bool) -> Point {
+    match counter_clockwise {
+        true => Point::unit_z(),
+        false => -Point::unit_z(),
+    }
+}
+
+fn read_line(groups: &Groups, profile: &mut Profile) -> TopologyResult<()> {
+    profile.pieces.push(Piece {
+        segment: Segment::Line,
+        start: [required(groups, 10, "LINE")?, required(groups, 20, "LINE")?],
+        end: [required(groups, 11, "LINE")?, required(groups, 21, "LINE")?],
+    });
+    Ok(())
+}
+
+fn read_circle(groups: &Groups, profile: &mut Profile) -> TopologyResult<()> {
+    let center = [
+        required(groups, 10, "CIRCLE")?,
+        required(groups, 20, "CIRCLE")?,
+    ];
+    let radius = required(groups, 40, "CIRCLE")?;
+    let sign = if upside_down(groups)? { -1.0 } else { 1.0 };
+    profile.closed.push(primitive_circle(
+        point([sign * center[0], center[1]]),
+        Point::unit_z(),
+        EFloat64::from(radius),
+    ));
+    Ok(())
+}
+
+// Arcs run counter-clockwise from the start angle to the end angle, which are given in degrees.
+fn read_arc(groups: &Groups, profile: &mut Profile) -> TopologyResult<()> {
+    let center = [required(groups, 10, "ARC")?, required(groups, 20, "ARC")?];
+    let radius = required(groups, 40, "ARC")?;
+    let start = required(groups, 50, "ARC")?.to_radians();
+    let end = required(groups, 51, "ARC")?.to_radians();
+    let sign = if upside_down(groups)? { -1.0 } else { 1.0 };
+    let at = |angle: f64| {
+        [
+            sign * (center[0] + radius * angle.cos()),
+            center[1] + radius * angle.sin(),
+        ]
+    };
+    let sweep = (end - start).rem_euclid(2.0 * PI);
+    if sweep == 0.0 {
+        return read_circle(groups, profile);
+    }
+    profile.pieces.push(Piece {
+        segment: Segment::Arc(sign * sweep),
+        start: at(start),
+        end: at(end),
+    });
+    Ok(())
+}
+
+// Ellipses are given by the end of the major axis relative to the center, the ratio of the minor to the major axis, and the parameters where they start and end.
+fn read_ellipse(groups: &Groups, profile: &mut Profile) -> TopologyResult<()> {
+    let center = [
+        required(groups, 10, "ELLIPSE")?,
+        required(groups, 20, "ELLIPSE")?,
+    ];
+    let major = Point::from_f64(
+        required(groups, 11, "ELLIPSE")?,
+        required(groups, 21, "ELLIPSE")?,
+        0.0,
+    );
+    let ratio = EFloat64::from(required(groups, 40, "ELLIPSE")?);
+    let start = value(groups, 41)?.unwrap_or(0.0);
+    let end = value(groups, 42)?.unwrap_or(2.0 * PI);
+    let normal = normal(!upside_down(groups)?);
+    let ellipse = Ellipse::try_new(point(center), normal, major, normal.cross(major) * ratio)?;
+
+    let sweep = (end - start).rem_euclid(2.0 * PI);
+    if !(1e-9..=2.0 * PI - 1e-9).contains(&sweep) {
+        profile
+            .closed
+            .push(Edge::new(None, None, Curve::Ellipse(ellipse)));
+        return Ok(());
+    }
+    let at = |t: f64| {
+        let p = ellipse.basis
+            + ellipse.major_radius * EFloat64::from(t.cos())
+            + ellipse.minor_radius * EFloat64::from(t.sin());
+        [p.x.to_f64(), p.y.to_f64()]
+    };
+    profile.pieces.push(Piece {
+        start: at(start),
+        end: at(end),
+        segment: Segment::Ellipse(ellipse),
+    });
+    Ok(())
+}
+
+// Lightweight polylines list their vertices, each with a bulge for the segment to the next vertex.
+// The bulge is the tangent of a quarter of the angle that the segment sweeps, so a bulge of 0 is a straight line.
+fn read_lwpolyline(groups: &Groups, profile: &mut Profile) -> TopologyResult<()> {
+    let mut vertices = Vec::<([f64; 2], f64)>::new();
+    let mut closed = false;
+    for &(code, v) in groups.iter() {
+        let number = || {
+            v.parse::<f64>().map_err(|_| {
+                TopologyError::new(format!("Invalid DXF value {} for group code {}", v, code))
+            })
+        };
+        match code {
+            10 => vertices.push(([number()?, 0.0], 0.0)),
+            20 | 42 => match vertices.last_mut() {
+                Some(vertex) if code == 20 => vertex.0[1] = number()?,
+                Some(vertex) => vertex.1 = number()?,
+                None => {
+                    return Err(TopologyError::new(format!(
+                        "LWPOLYLINE has the group code {} before its first vertex",
+                        code
+                    )))
+                }
+            },
+            70 => closed = number()? as i64 & 1 == 1,
+            _ => {}
+        }
+    }
+    let sign = if upside_down(groups)? { -1.0 } else { 1.0 };
+    let count = if closed {
+        vertices.len()
+    } else {
+        vertices.len().saturating_sub(1)
+    };
+    for i in 0..count {
+        let (start, bulge) = vertices[i];
+        let (end, _) = vertices[(i + 1) % vertices.len()];
+        profile.pieces.push(Piece {
+            segment: if bulge == 0.0 {
+                Segment::Line
+            } else {
+                Segment::Arc(sign * 4.0 * bulge.atan())
+            },
+            start: [sign * start[0], start[1]],
+            end: [sign * end[0], end[1]],
+        });
+    }
+    Ok(())
+}
+
+// The arc from start to end that sweeps the angle. Its center is placed exactly between the endpoints, so that both lie on the circle.
+fn arc_edge(start: Point, end: Point, sweep: f64) -> TopologyResult<Edge> {
+    let normal = normal(sweep > 0.0);
+    let middle = ((start + end) / EFloat64::two()).map_err(GeometryError::from)?;
+    let center =
+        middle + normal.cross(end - start) * EFloat64::from(0.5 / (sweep.abs() / 2.0).tan());
+    let circle = Circle::try_new(center, normal, (start - center).norm())?;
+    Ok(Edge::new(Some(start), Some(end), Curve::Circle(circle)))
+}
+
+// Joins the pieces at their common endpoints into loops. Pieces may be given in any order and direction.
+fn close_loops(profile: Profile) -> TopologyResult<Vec<Contour>> {
+    let mut vertices = Vec::<[f64; 2]>::new();
+    let mut vertex = |p: [f64; 2]| match vertices
+        .iter()
+        .position(|v| (v[0] - p[0]).hypot(v[1] - p[1]) < SNAP_TOLERANCE)
+    {
+        Some(i) => i,
+        None => {
+            vertices.push(p);
+            vertices.len() - 1
+        }
+    };
+    let pieces: Vec<(usize, usize, Segment)> = profile
+        .pieces
+        .into_iter()
+        .map(|piece| (vertex(piece.start), vertex(piece.end), piece.segment))
+        .filter(|(start, end, _)| start != end)
+        .collect();
+
+    // Ellipses cannot be bent through a given point, so their ends are moved onto the ellipse, and lines and arcs follow.
+    let mut positions: Vec<Point> = vertices.iter().map(|v| point(*v)).collect();
+    for (start, end, segment) in pieces.iter() {
+        if let Segment::Ellipse(ellipse) = segment {
+            positions[*start] = ellipse.project(positions[*start]);
+            positions[*end] = ellipse.project(positions[*end]);
+        }
+    }
+    let mut edges = Vec::<(usize, usize, Edge)>::new();
+    for (start, end, segment) in pieces.into_iter() {
+        let (p, q) = (positions[start], positions[end]);
+        let edge = match segment {
+            Segment::Line => primitive_line(p, q)?,
+            Segment::Arc(sweep) => arc_edge(p, q, sweep)?,
+            Segment::Ellipse(ellipse) => Edge::new(Some(p), Some(q), Curve::Ellipse(ellipse)),
+        };
+        edges.push((start, end, edge));
+    }
+
+    let mut contours: Vec<Contour> = profile
+        .closed
+        .into_iter()
+        .map(|edge| Contour::new(vec![edge]))
+        .collect();
+    let mut used = vec![false; edges.len()];
+    for i in 0..edges.len() {
+        if used[i] {
+            continue;
+        }
+        used[i] = true;
+        let (first, mut current) = (edges[i].0, edges[i].1);
+        let mut contour = vec![edges[i].2.clone()];
+        while current != first {
+            let next = (0..edges.len())
+                .find(|j| !used[*j] && (edges[*j].0 == current || edges[*j].1 == current))
+                .ok_or_else(|| {
+                    TopologyError::new(format!(
+                        "The profile is not closed at ({}, {})",
+                        vertices[current][0], vertices[current][1]
+                    ))
+                })?;
+            used[next] = true;
+            let (start, end, edge) = &edges[next];
+            match *start == current {
+                true => {
+                    contour.push(edge.clone());
+                    current = *end;
+                }
+                false => {
+                    contour.push(edge.flip());
+                    current = *start;
+                }
+            }
+        }
+        contours.push(Contour::new(contour));
+    }
+    Ok(contours)
+}
+
+fn xy_plane() -> Rc<Surface> {
+    Rc::new(Surface::Plane(Plane::new(
+        Point::zero(),
+        Point::unit_x(),
+        Point::unit_y(),
+    )))
+}
+
+// The closed loops of the drawing in the xy-plane, each running counter-clockwise around the z-axis.
+// LINE, ARC, CIRCLE, ELLIPSE and LWPOLYLINE entities are read, other entities like text and dimensions are ignored. Z coordinates are ignored as well.
+pub fn read_contours(dxf: &str) -> TopologyResult<Vec<Contour>> {
+    let groups = groups(dxf)?;
+    let mut profile = Profile::default();
+    for (entity, groups) in entities(&groups)? {
+        match entity {
+            "LINE" => read_line(&groups, &mut profile)?,
+            "ARC" => read_arc(&groups, &mut profile)?,
+            "CIRCLE" => read_circle(&groups, &mut profile)?,
+            "ELLIPSE" => read_ellipse(&groups, &mut profile)?,
+            "LWPOLYLINE" => read_lwpolyline(&groups, &mut profile)?,
+            _ => {}
+        }
+    }
+    Ok(close_loops(profile)?
+        .into_iter()
+        .map(
+            |contour| match face_area(&Face::new(vec![contour.clone()], xy_plane())) < 0.0 {
+                true => contour.flip(),
+                false => contour,
+            },
+        )
+        .collect())
+}
+
+// The faces of the drawing in the xy-plane. Loops inside of an outer boundary become its holes, and loops inside of a hole are outer boundaries of new faces.
+pub fn read_faces(dxf: &str) -> TopologyResult<Vec<Face>> {
+    let contours = read_contours(dxf)?;
+    let regions: Vec<Face> = contours
+        .iter()
+        .map(|contour| Face::new(vec![contour.clone()], xy_plane()))
+        .collect();
+    let inside = |outer: usize, inner: usize| {
+        outer != inner
+            && matches!(
+                face_point_contains(&regions[outer], contours[inner].edges[0].get_midpoint()),
+                FacePointContains::Inside
+            )
+    };
+    let depth: Vec<usize> = (0..contours.len())
+        .map(|i| (0..contours.len()).filter(|j| inside(*j, i)).count())
+        .collect();
+
+    Ok((0..contours.len())
+        .filter(|i| depth[*i].is_multiple_of(2))
+        .map(|i| {
+            let mut boundaries = vec![contours[i].clone()];
+            for j in 0..contours.len() {
+                if depth[j] == depth[i] + 1 && inside(i, j) {
+                    boundaries.push(contours[j].flip());
+                }
+            }
+            Face::new(boundaries, xy_plane())
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dxf(entities: &str) -> String {
+        format!("0\nSECTION\n2\nENTITIES\n{}0\nENDSEC\n0\nEOF\n", entities)
+    }
+
+    fn line(x1: f64, y1: f64, x2: f64, y2: f64) -> String {
+        format!(
+            "0\nLINE\n8\n0\n10\n{}\n20\n{}\n11\n{}\n21\n{}\n",
+            x1, y1, x2, y2
+        )
+    }
+
+    #[test]
+    fn test_read_plate() {
+        // A plate of 100 x 50 with a round hole and a slot with round ends, whose outline is given in any order and direction.
+        let entities = [
+            line(0.0, 0.0, 100.0, 0.0),
+            line(0.0, 50.0, 100.0, 50.0000001),
+            line(100.0, 50.0, 100.0, 0.0),
+            line(0.0, 50.0, 0.0, 0.0),
+            "0\nCIRCLE\n8\n0\n10\n20\n20\n25\n40\n5\n".to_string(),
+            "0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n10\n50\n20\n22\n10\n70\n20\n22\n42\n1\n10\n70\n20\n28\n10\n50\n20\n28\n42\n1\n".to_string(),
+        ];
+        let faces = read_faces(&dxf(&entities.concat())).unwrap();
+        assert_eq!(faces.len(), 1);
+        assert_eq!(faces[0].boundaries.len(), 3);
+        let area = 5000.0 - 25.0 * PI - (120.0 + 9.0 * PI);
+        assert!((face_area(&faces[0]) - area).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_read_nested_loops() {
+        // A square frame with an island in the middle, and a text that is not part of the profile.
+        let entities = [
+            "0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n10\n0\n20\n0\n10\n10\n20\n0\n10\n10\n20\n10\n10\n0\n20\n10\n".to_string(),
+            "0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n10\n2\n20\n2\n10\n2\n20\n8\n10\n8\n20\n8\n10\n8\n20\n2\n".to_string(),
+            "0\nCIRCLE\n8\n0\n10\n5\n20\n5\n40\n1\n".to_string(),
+            "0\nTEXT\n8\n0\n10\n0\n20\n-5\n40\n2\n1\nFrame\n".to_string(),
+        ];
+        let faces = read_faces(&dxf(&entities.concat())).unwrap();
+        assert_eq!(faces.len(), 2);
+        let mut areas: Vec<f64> = faces.iter().map(face_area).collect();
+        areas.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        assert!((areas[0] - PI).abs() < 1e-6);
+        assert!((areas[1] - 64.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_read_arcs_and_ellipses() {
+        // Half of an ellipse with axes of 4 and 2, and half of a circle with a radius of 2, both closed by a line.
+        let entities = [
+            "0\nELLIPSE\n8\n0\n10\n0\n20\n0\n11\n4\n21\n0\n40\n0.5\n41\n0\n42\n3.141592653589793\n"
+                .to_string(),
+            line(-4.0, 0.0, 4.0, 0.0),
+            "0\nARC\n8\n0\n10\n10\n20\n0\n40\n2\n50\n0\n51\n180\n".to_string(),
+            line(8.0, 0.0, 12.0, 0.0),
+        ];
+        let faces = read_faces(&dxf(&entities.concat())).unwrap();
+        assert_eq!(faces.len(), 2);
+        assert!((face_area(&faces[0]) - 4.0 * PI).abs() < 1e-6);
+        assert!((face_area(&faces[1]) - 2.0 * PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_read_open_profile() {
+        let entities = [line(0.0, 0.0, 10.0, 0.0), line(10.0, 0.0, 10.0, 10.0)];
+        assert!(read_contours(&dxf(&entities.concat())).is_err());
+        assert!(read_contours("0\nEOF\n").is_err());
+    }
+}
@@ -0,0 +1,217 @@
+use std::{f64::consts::PI, fmt::Display};
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse},
+    point::Point,
+    surfaces::surface::Surface,
+};
+use geop_topology::{
+    topology::{contour::Contour, edge::Edge, face::Face},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+struct DxfWriter {
+    dxf: String,
+}
+
+impl DxfWriter {
+    fn group(&mut self, code: i32, value: impl Display) {
+        self.dxf += &format!("{}\n{}\n", code, value);
+    }
+
+    fn entity(&mut self, entity: &str) {
+        self.group(0, entity);
+        self.group(8, "0");
+    }
+
+    fn point(&mut self, code: i32, p: Point) {
+        self.group(code, p.x.to_f64());
+        self.group(code + 10, p.y.to_f64());
+        self.group(code + 20, p.z.to_f64());
+    }
+
+    fn line(&mut self, start: Point, end: Point) {
+        self.entity("LINE");
+        self.point(10, start);
+        self.point(11, end);
+    }
+
+    fn circle(&mut self, circle: &Circle) {
+        self.entity("CIRCLE");
+        self.point(10, circle.basis);
+        self.group(40, circle.radius.norm().to_f64());
+    }
+
+    // Arcs always run counter-clockwise, so clockwise arcs are written from their end to their start.
+    fn arc(&mut self, circle: &Circle, start: Point, end: Point) {
+        let (start, end) = match circle.normal.z > 0.0 {
+            true => (start, end),
+            false => (end, start),
+        };
+        let angle = |p: Point| {
+            let d = p - circle.basis;
+            d.y.to_f64().atan2(d.x.to_f64()).to_degrees()
+        };
+        self.entity("ARC");
+        self.point(10, circle.basis);
+        self.group(40, circle.radius.norm().to_f64());
+        self.group(50, angle(start));
+        self.group(51, angle(end));
+    }
+
+    // The minor axis of the ellipse is the major axis turned counter-clockwise, so the parameters are measured from the major axis.
+    fn ellipse(&mut self, ellipse: &Ellipse, start: Option<Point>, end: Option<Point>) {
+        let (major, minor) = match ellipse.major_radius.norm() >= ellipse.minor_radius.norm() {
+            true => (ellipse.major_radius, ellipse.minor_radius),
+            false => (ellipse.minor_radius, ellipse.major_radius),
+        };
+        let ratio = minor.norm().to_f64() / major.norm().to_f64();
+        let minor = Point::unit_z().cross(major);
+        let parameter = |p: Point| {
+            let d = p - ellipse.basis;
+            let t = f64::atan2(d.dot(minor).to_f64() / ratio, d.dot(major).to_f64());
+            t.rem_euclid(2.0 * PI)
+        };
+        let (start, end) = match (start, end, ellipse.normal.z > 0.0) {
+            (Some(start), Some(end), true) => (parameter(start), parameter(end)),
+            (Some(start), Some(end), false) => (parameter(end), parameter(start)),
+            _ => (0.0, 2.0 * PI),
+        };
+        self.entity("ELLIPSE");
+        self.point(10, ellipse.basis);
+        self.point(11, major);
+        self.group(40, ratio);
+        self.group(41, start);
+        self.group(42, end);
+    }
+
+    // Loops of lines and arcs are written as a closed polyline, with the bulges of the arcs.
+    fn lwpolyline(&mut self, contour: &Contour) {
+        self.entity("LWPOLYLINE");
+        self.group(90, contour.edges.len());
+        self.group(70, 1);
+        self.group(38, contour.edges[0].start.unwrap().z.to_f64());
+        for edge in contour.edges.iter() {
+            let start = edge.start.unwrap();
+            self.group(10, start.x.to_f64());
+            self.group(20, start.y.to_f64());
+            if let Curve::Circle(circle) = &edge.curve {
+                let (s, e) = (start - circle.basis, edge.end.unwrap() - circle.basis);
+                let angle = f64::atan2(s.cross(e).z.to_f64(), s.dot(e).to_f64());
+                let sweep = match circle.normal.z > 0.0 {
+                    true => angle.rem_euclid(2.0 * PI),
+                    false => -(-angle).rem_euclid(2.0 * PI),
+                };
+                self.group(42, (sweep / 4.0).tan());
+            }
+        }
+    }
+
+    fn edge(&mut self, edge: &Edge) -> TopologyResult<()> {
+        match (&edge.curve, edge.start, edge.end) {
+            (Curve::Line(_), Some(start), Some(end)) => self.line(start, end),
+            (Curve::Circle(circle), None, None) => self.circle(circle),
+            (Curve::Circle(circle), Some(start), Some(end)) => self.arc(circle, start, end),
+            (Curve::Ellipse(ellipse), start, end) => self.ellipse(ellipse, start, end),
+            _ => return Err(TopologyError::new(format!("Cannot write {} to DXF", edge))),
+        }
+        Ok(())
+    }
+
+    fn contour(&mut self, contour: &Contour) -> TopologyResult<()> {
+        let polyline = contour.edges.iter().all(|edge| {
+            edge.start.is_some()
+                && edge.end.is_some()
+                && matches!(edge.curve, Curve::Line(_) | Curve::Circle(_))
+        });
+        if polyline {
+            self.lwpolyline(contour);
+            return Ok(());
+        }
+        for edge in contour.edges.iter() {
+            self.edge(edge)?;
+        }
+        Ok(())
+    }
+}
+
+// DXF with the boundaries of the faces, which have to lie in planes parallel to the xy-plane.
+// Loops of lines and arcs become closed LWPOLYLINEs, full circles become CIRCLEs, and loops with ellipses are written as LINE, ARC and ELLIPSE entities.
+pub fn write_dxf(faces: &[Face]) -> TopologyResult<String> {
+    let mut writer = DxfWriter { dxf: String::new() };
+    writer.group(0, "SECTION");
+    writer.group(2, "HEADER");
+    writer.group(9, "$ACADVER");
+    writer.group(1, "AC1015");
+    writer.group(0, "ENDSEC");
+    writer.group(0, "SECTION");
+    writer.group(2, "ENTITIES");
+    for face in faces.iter() {
+        match face.surface.as_ref() {
+            Surface::Plane(plane)
+                if plane
+                    .u_slope
+                    .cross(plane.v_slope)
+                    .is_parallel(Point::unit_z()) => {}
+            _ => {
+                return Err(TopologyError::new(
+                    "Only faces parallel to the xy-plane can be written to DXF".to_string(),
+                ))
+            }
+        }
+        for contour in face.boundaries.iter() {
+            writer.contour(contour)?;
+        }
+    }
+    writer.group(0, "ENDSEC");
+    writer.group(0, "EOF");
+    Ok(writer.dxf)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_topology::mass_properties::face_area;
+
+    use super::*;
+    use crate::dxf::reader::read_faces;
+
+    fn total_area(faces: &[Face]) -> f64 {
+        faces.iter().map(face_area).sum()
+    }
+
+    #[test]
+    fn test_write_polylines_and_circles() {
+        // A plate with a round hole and a slot with round ends, which runs clockwise as a hole.
+        let dxf = "0\nSECTION\n2\nENTITIES\n\
+0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n10\n0\n20\n0\n10\n100\n20\n0\n10\n100\n20\n50\n10\n0\n20\n50\n\
+0\nCIRCLE\n8\n0\n10\n20\n20\n25\n40\n5\n\
+0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n10\n50\n20\n22\n10\n70\n20\n22\n42\n1\n10\n70\n20\n28\n10\n50\n20\n28\n42\n1\n\
+0\nENDSEC\n0\nEOF\n";
+        let faces = read_faces(dxf).unwrap();
+        let written = write_dxf(&faces).unwrap();
+        assert_eq!(written.matches("\nLWPOLYLINE\n").count(), 2);
+        assert_eq!(written.matches("\nCIRCLE\n").count(), 1);
+        assert_eq!(written.matches("\n42\n-").count(), 2);
+        assert!((total_area(&read_faces(&written).unwrap()) - total_area(&faces)).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_write_ellipses() {
+        // Half of an ellipse closed by a line, and a full ellipse as a hole in a square.
+        let dxf = "0\nSECTION\n2\nENTITIES\n\
+0\nELLIPSE\n8\n0\n10\n0\n20\n0\n11\n4\n21\n0\n40\n0.5\n41\n0\n42\n3.141592653589793\n\
+0\nLINE\n8\n0\n10\n-4\n20\n0\n11\n4\n21\n0\n\
+0\nLWPOLYLINE\n8\n0\n90\n4\n70\n1\n10\n10\n20\n-5\n10\n20\n20\n-5\n10\n20\n20\n5\n10\n10\n20\n5\n\
+0\nELLIPSE\n8\n0\n10\n15\n20\n0\n11\n0\n21\n3\n40\n0.5\n\
+0\nENDSEC\n0\nEOF\n";
+        let faces = read_faces(dxf).unwrap();
+        assert_eq!(faces.len(), 2);
+        let written = write_dxf(&faces).unwrap();
+        assert_eq!(written.matches("\nELLIPSE\n").count(), 2);
+        assert_eq!(written.matches("\nLINE\n").count(), 1);
+        let read = read_faces(&written).unwrap();
+        assert_eq!(read.len(), 2);
+        let area = 4.0 * PI + 100.0 - 4.5 * PI;
+        assert!((total_area(&read) - area).abs() < 1e-6);
+    }
+}
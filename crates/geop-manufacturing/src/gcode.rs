@@ -0,0 +1,99 @@
+use std::f64::consts::PI;
+
+use geop_geometry::{curves::curve::Curve, point::Point};
+use geop_topology::topology::edge::Edge;
+
+// Number of straight moves that approximate curves other than lines and circles.
+const POLYLINE_SEGMENTS: usize = 32;
+
+// A move along a part of an edge. Arcs are seen from above, so G2 runs clockwise and G3 counter-clockwise.
+pub(crate) enum GCodeMove {
+    Line {
+        end: Point,
+        length: f64,
+    },
+    Arc {
+        end: Point,
+        center: Point,
+        clockwise: bool,
+        length: f64,
+    },
+}
+
+impl GCodeMove {
+    pub(crate) fn end(&self) -> Point {
+        match self {
+            GCodeMove::Line { end, .. } => *end,
+            GCodeMove::Arc { end, .. } => *end,
+        }
+    }
+
+    pub(crate) fn length(&self) -> f64 {
+        match self {
+            GCodeMove::Line { length, .. } => *length,
+            GCodeMove::Arc { length, .. } => *length,
+        }
+    }
+
+    // The command without feed rate or extrusion. The center of arcs is relative to the start.
+    pub(crate) fn command(&self, start: Point) -> String {
+        match self {
+            GCodeMove::Line { end, .. } => {
+                format!("G1 X{:.3} Y{:.3}", end.x.to_f64(), end.y.to_f64())
+            }
+            GCodeMove::Arc {
+                end,
+                center,
+                clockwise,
+                ..
+            } => {
+                let offset = *center - start;
+                format!(
+                    "{} X{:.3} Y{:.3} I{:.3} J{:.3}",
+                    match clockwise {
+                        true => "G2",
+                        false => "G3",
+                    },
+                    end.x.to_f64(),
+                    end.y.to_f64(),
+                    offset.x.to_f64(),
+                    offset.y.to_f64()
+                )
+            }
+        }
+    }
+}
+
+// The moves along the edge, which starts at the given point if it is a closed circle.
+// Lines and circles are kept exact, other curves are approximated by lines.
+pub(crate) fn edge_moves(edge: &Edge, start: Point) -> Vec<GCodeMove> {
+    match &edge.curve {
+        Curve::Line(_) => {
+            let end = edge.end.unwrap();
+            vec![GCodeMove::Line {
+                end,
+                length: (end - start).norm().to_f64(),
+            }]
+        }
+        Curve::Circle(circle) => vec![GCodeMove::Arc {
+            end: edge.end.unwrap_or(start),
+            center: circle.basis,
+            clockwise: circle.normal.z < 0.0,
+            length: match edge.length() {
+                Some(length) => length.to_f64(),
+                None => 2.0 * PI * circle.radius.norm().to_f64(),
+            },
+        }],
+        _ => {
+            let mut previous = start;
+            (1..=POLYLINE_SEGMENTS)
+                .map(|i| {
+                    let end = edge.interpolate(i as f64 / POLYLINE_SEGMENTS as f64);
+                    let length = (end - previous).norm().to_f64();
+                    previous = end;
+                    GCodeMove::Line { end, length }
+                })
+                .collect()
+        }
+    }
+}
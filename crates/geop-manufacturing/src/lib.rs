@@ -0,0 +1,4 @@
+pub mod cam;
+pub mod dxf;
+pub(crate) mod gcode;
+pub mod slicer;
@@ -0,0 +1,166 @@
+use geop_geometry::point::Point;
+use geop_topology::topology::{contour::Contour, edge::Edge};
+
+use super::{layer::Layer, settings::SlicerSettings};
+use crate::gcode::edge_moves;
+
+struct GCodeWriter<'a> {
+    settings: &'a SlicerSettings,
+    gcode: String,
+    // Absolute position of the extruder, which grows with every printed line.
+    extruded: f64,
+    position: Option<Point>,
+}
+
+impl<'a> GCodeWriter<'a> {
+    fn new(settings: &'a SlicerSettings) -> GCodeWriter<'a> {
+        GCodeWriter {
+            settings,
+            gcode: String::new(),
+            extruded: 0.0,
+            position: None,
+        }
+    }
+
+    fn line(&mut self, line: String) {
+        self.gcode.push_str(&line);
+        self.gcode.push('\n');
+    }
+
+    fn travel(&mut self, p: Point) {
+        if self.position == Some(p) {
+            return;
+        }
+        self.line(format!(
+            "G0 F{:.0} X{:.3} Y{:.3}",
+            self.settings.travel_speed * 60.0,
+            p.x.to_f64(),
+            p.y.to_f64()
+        ));
+        self.position = Some(p);
+    }
+
+    // Prints the edge, starting at the current position.
+    fn print_edge(&mut self, edge: &Edge) {
+        let mut position = self.position.expect("Printing starts with a travel move");
+        for m in edge_moves(edge, position) {
+            self.extruded += m.length() * self.settings.extrusion_per_mm();
+            self.line(format!("{} E{:.5}", m.command(position), self.extruded));
+            position = m.end();
+        }
+        self.position = Some(position);
+    }
+
+    fn print_contour(&mut self, contour: &Contour) {
+        self.travel(contour.edges[0].interpolate(0.0));
+        for edge in contour.edges.iter() {
+            self.print_edge(edge);
+        }
+    }
+}
+
+// G-code for the layers with absolute positions and absolute extrusion. Heating and homing are left to the start code of the printer.
+// Every layer prints its perimeters from the outside to the inside, and then the infill.
+pub fn gcode(layers: &[Layer], settings: &SlicerSettings) -> String {
+    let mut writer = GCodeWriter::new(settings);
+    writer.line("G21 ; millimeters".to_string());
+    writer.line("G90 ; absolute positions".to_string());
+    writer.line("M82 ; absolute extrusion".to_string());
+    writer.line("G92 E0".to_string());
+    for (i, layer) in layers.iter().enumerate() {
+        writer.line(format!(";LAYER:{}", i));
+        // The nozzle is at the top of the layer, which is sliced in its middle.
+        writer.line(format!(
+            "G0 F{:.0} Z{:.3}",
+            settings.travel_speed * 60.0,
+            layer.z + settings.layer_height / 2.0
+        ));
+        writer.line(format!("G1 F{:.0}", settings.print_speed * 60.0));
+        for island in layer.islands.iter() {
+            for contour in island.perimeters.iter().flatten() {
+                writer.print_contour(contour);
+            }
+            for edge in island.infill.iter() {
+                writer.travel(edge.start.unwrap());
+                writer.print_edge(edge);
+            }
+        }
+    }
+    writer.gcode
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64::consts::PI, rc::Rc};
+
+    use geop_geometry::{
+        efloat::EFloat64,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::face::Face,
+    };
+
+    use super::*;
+    use crate::slicer::layer::slice;
+
+    #[test]
+    fn test_gcode_cube() {
+        let settings = SlicerSettings {
+            infill_density: 0.0,
+            perimeters: 1,
+            ..SlicerSettings::default()
+        };
+        let cube = primitive_cube(
+            EFloat64::from(4.0),
+            EFloat64::from(4.0),
+            EFloat64::from(0.4),
+        );
+        let gcode = gcode(&slice(&cube, &settings).unwrap(), &settings);
+        assert_eq!(gcode.matches(";LAYER:").count(), 2);
+        assert_eq!(gcode.matches("\nG1 X").count(), 8);
+        assert!(!gcode.contains("G2 ") && !gcode.contains("G3 "));
+
+        // A single perimeter of 4 x 3.6 mm, with the filament of 1.75 mm.
+        let filament = 2.0 * 4.0 * 3.6 * 0.2 * 0.4 / (PI * 0.875 * 0.875);
+        let last = gcode.lines().rfind(|l| l.starts_with("G1 X")).unwrap();
+        let e: f64 = last.split(" E").nth(1).unwrap().parse().unwrap();
+        assert!((e - filament).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_gcode_arcs() {
+        // A tube, whose outer wall is printed counter-clockwise and whose inner wall is printed clockwise.
+        let bottom = Face::new(
+            vec![
+                Contour::new(vec![primitive_circle(
+                    Point::zero(),
+                    -Point::unit_z(),
+                    EFloat64::from(5.0),
+                )]),
+                Contour::new(vec![primitive_circle(
+                    Point::zero(),
+                    Point::unit_z(),
+                    EFloat64::from(2.0),
+                )]),
+            ],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let settings = SlicerSettings {
+            infill_density: 0.0,
+            ..SlicerSettings::default()
+        };
+        let tube = extrude(bottom, Point::from_f64(0.0, 0.0, 0.2)).unwrap();
+        let gcode = gcode(&slice(&tube, &settings).unwrap(), &settings);
+        assert_eq!(gcode.matches("\nG3 ").count(), 2);
+        assert_eq!(gcode.matches("\nG2 ").count(), 2);
+        assert!(!gcode.contains("\nG1 X"));
+        assert!(gcode.contains("G3 X4.800 Y0.000 I-4.800 J0.000"));
+    }
+}
@@ -0,0 +1,87 @@
+use geop_booleans::intersections::face_edge::{face_edge_intersection, FaceEdgeIntersection};
+use geop_geometry::{
+    curves::{curve::Curve, line::Line},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::surface::Surface,
+};
+use geop_topology::{
+    topology::{edge::Edge, face::Face},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+// The range that the face covers along the direction.
+fn face_extent(face: &Face, direction: Point) -> TopologyResult<(f64, f64)> {
+    let mut extent = (f64::INFINITY, f64::NEG_INFINITY);
+    let mut add = |center: Point, radius: f64| {
+        let d = center.dot(direction).to_f64();
+        extent = (extent.0.min(d - radius), extent.1.max(d + radius));
+    };
+    for edge in face.all_edges() {
+        match (&edge.curve, edge.start, edge.end) {
+            (Curve::Line(_), Some(start), Some(end)) => {
+                add(start, 0.0);
+                add(end, 0.0);
+            }
+            (Curve::Circle(circle), _, _) => add(circle.basis, circle.radius.norm().to_f64()),
+            (Curve::Ellipse(ellipse), _, _) => {
+                add(ellipse.basis, ellipse.major_radius.norm().to_f64())
+            }
+            _ => {
+                return Err(TopologyError::new(format!(
+                    "Cannot fill a face bounded by {}",
+                    edge
+                )))
+            }
+        }
+    }
+    Ok(extent)
+}
+
+// Rectilinear infill of a planar face with parallel lines along the direction, which have the spacing to each other.
+// The lines lie on a grid through the origin, so that the lines of different layers are stacked on top of each other.
+// Every second line runs backwards, so that the lines are printed as a zigzag.
+pub fn rectilinear_infill(
+    face: &Face,
+    direction: Point,
+    spacing: f64,
+) -> TopologyResult<Vec<Edge>> {
+    let plane = match face.surface.as_ref() {
+        Surface::Plane(plane) => plane,
+        _ => {
+            return Err(TopologyError::new(
+                "Only planar faces can be filled".to_string(),
+            ))
+        }
+    };
+    let normal = plane
+        .u_slope
+        .cross(plane.v_slope)
+        .normalize()
+        .map_err(GeometryError::from)?;
+    let direction = (direction - normal * direction.dot(normal))
+        .normalize()
+        .map_err(GeometryError::from)?;
+    let side = normal.cross(direction);
+    let on_plane = normal * plane.basis.dot(normal);
+
+    let (min, max) = face_extent(face, side)?;
+    let mut edges = Vec::<Edge>::new();
+    // The lines are placed between the grid lines, so that they do not run exactly through corners on the grid.
+    let first = (min / spacing - 0.5).ceil() as i64;
+    let last = (max / spacing - 0.5).floor() as i64;
+    for (i, k) in (first..=last).enumerate() {
+        let offset = EFloat64::from((k as f64 + 0.5) * spacing);
+        let line = Line::new(on_plane + side * offset, direction)?;
+        if let FaceEdgeIntersection::Edges(mut segments) =
+            face_edge_intersection(face, &Edge::from_curve(Curve::Line(line)))
+        {
+            if i % 2 == 1 {
+                segments = segments.iter().rev().map(|e| e.flip()).collect();
+            }
+            edges.extend(segments);
+        }
+    }
+    Ok(edges)
+}
@@ -0,0 +1,202 @@
+use geop_booleans::split::section::section;
+use geop_geometry::{efloat::EFloat64, point::Point, surfaces::plane::Plane};
+use geop_topology::{
+    operations::offset::offset_face,
+    topology::{contour::Contour, edge::Edge, face::Face, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use super::{infill::rectilinear_infill, settings::SlicerSettings};
+
+// A connected region of a layer, with the lines that are printed to fill it.
+#[derive(Debug, Clone)]
+pub struct Island {
+    pub section: Face,
+    // Perimeters from the outside to the inside. Every perimeter has one contour for the outside and one for every hole.
+    pub perimeters: Vec<Vec<Contour>>,
+    pub infill: Vec<Edge>,
+}
+
+// A layer of the print. The section is taken at the height z, which is in the middle of the layer.
+#[derive(Debug, Clone)]
+pub struct Layer {
+    pub z: f64,
+    pub islands: Vec<Island>,
+}
+
+fn island(section: Face, layer_index: usize, settings: &SlicerSettings) -> TopologyResult<Island> {
+    let width = settings.nozzle_width;
+    let mut perimeters = Vec::<Vec<Contour>>::new();
+    for i in 0..settings.perimeters {
+        // The nozzle runs along the middle of the line, half a width inside of the outer edge of the line.
+        let distance = EFloat64::from((i as f64 + 0.5) * width);
+        match offset_face(&section, distance)? {
+            Some(face) => perimeters.push(face.boundaries),
+            None => break,
+        }
+    }
+
+    // The infill overlaps the innermost perimeter by half a line, so that they stick together.
+    let mut infill = Vec::<Edge>::new();
+    if settings.infill_density > 0.0 && perimeters.len() == settings.perimeters {
+        let distance = EFloat64::from(settings.perimeters as f64 * width);
+        if let Some(inside) = offset_face(&section, distance)? {
+            let angle = (settings.infill_angle + 90.0 * layer_index as f64).to_radians();
+            let direction = Point::from_f64(angle.cos(), angle.sin(), 0.0);
+            infill = rectilinear_infill(&inside, direction, width / settings.infill_density)?;
+        }
+    }
+
+    Ok(Island {
+        section,
+        perimeters,
+        infill,
+    })
+}
+
+// The layer of the volume with the section at height z. The layer index decides about the direction of the infill.
+pub fn slice_layer(
+    volume: &Volume,
+    z: f64,
+    layer_index: usize,
+    settings: &SlicerSettings,
+) -> TopologyResult<Layer> {
+    let plane = Plane::new(
+        Point::from_f64(0.0, 0.0, z),
+        Point::unit_x(),
+        Point::unit_y(),
+    );
+    let islands = section(volume, &plane)?
+        .into_iter()
+        .map(|face| island(face, layer_index, settings))
+        .collect::<TopologyResult<Vec<Island>>>()?;
+    Ok(Layer { z, islands })
+}
+
+// All layers of the volume from the bottom to the top. The print starts at the lowest point of the volume.
+pub fn slice(volume: &Volume, settings: &SlicerSettings) -> TopologyResult<Vec<Layer>> {
+    if settings.layer_height <= 0.0 || settings.nozzle_width <= 0.0 {
+        return Err(TopologyError::new(
+            "Layer height and nozzle width must be positive".to_string(),
+        ));
+    }
+    let bounding_box = volume.bounding_box()?;
+    let (bottom, top) = (bounding_box.min.z.to_f64(), bounding_box.max.z.to_f64());
+    let count = ((top - bottom) / settings.layer_height).round() as usize;
+    (0..count)
+        .map(|i| {
+            let z = bottom + (i as f64 + 0.5) * settings.layer_height;
+            slice_layer(volume, z, i, settings)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        curves::curve::Curve,
+        surfaces::{plane::Plane, surface::Surface},
+    };
+    use geop_topology::{
+        operations::{extrude::extrude, loft::loft},
+        primitive_objects::{
+            edges::circle::primitive_circle, faces::sphere::primitive_sphere,
+            volumes::cube::primitive_cube,
+        },
+        topology::shell::Shell,
+    };
+
+    use super::*;
+
+    #[test]
+    fn test_slice_cube() {
+        let cube = primitive_cube(
+            EFloat64::from(10.0),
+            EFloat64::from(10.0),
+            EFloat64::from(1.0),
+        );
+        let layers = slice(&cube, &SlicerSettings::default()).unwrap();
+        assert_eq!(layers.len(), 5);
+        assert!((layers[0].z + 0.4).abs() < 1e-9);
+        for layer in layers.iter() {
+            assert_eq!(layer.islands.len(), 1);
+            let island = &layer.islands[0];
+            assert_eq!(island.perimeters.len(), 2);
+            // The outer perimeter runs half a line width inside of the section.
+            assert!(island.perimeters[0][0]
+                .all_points()
+                .iter()
+                .any(|p| *p == Point::from_f64(4.8, 4.8, layer.z)));
+            assert!(!island.infill.is_empty());
+        }
+
+        // A layer above the volume is empty.
+        let layer = slice_layer(&cube, 2.0, 0, &SlicerSettings::default()).unwrap();
+        assert!(layer.islands.is_empty());
+    }
+
+    #[test]
+    fn test_slice_tube() {
+        // A tube with an outer radius of 5 and an inner radius of 2, standing on the xy plane.
+        let bottom = Face::new(
+            vec![
+                Contour::new(vec![primitive_circle(
+                    Point::zero(),
+                    -Point::unit_z(),
+                    EFloat64::from(5.0),
+                )]),
+                Contour::new(vec![primitive_circle(
+                    Point::zero(),
+                    Point::unit_z(),
+                    EFloat64::from(2.0),
+                )]),
+            ],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let tube = extrude(bottom, Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let layer = slice_layer(&tube, 1.0, 0, &SlicerSettings::default()).unwrap();
+        assert_eq!(layer.islands.len(), 1);
+        let perimeter = &layer.islands[0].perimeters[0];
+        assert_eq!(perimeter.len(), 2);
+        assert!(perimeter
+            .iter()
+            .flat_map(|contour| contour.edges.iter())
+            .all(|edge| matches!(edge.curve, Curve::Circle(_))));
+        assert!(!layer.islands[0].infill.is_empty());
+    }
+
+    #[test]
+    fn test_slice_curved() {
+        // A frustum of a cone, which gets narrower from a radius of 5 at the bottom to 3 at the top.
+        let circle = |radius: f64, z: f64| {
+            Contour::new(vec![primitive_circle(
+                Point::from_f64(0.0, 0.0, z),
+                Point::unit_z(),
+                EFloat64::from(radius),
+            )])
+        };
+        let frustum = loft(&[circle(5.0, 0.0), circle(3.0, 1.0)], false).unwrap();
+        let layers = slice(&frustum, &SlicerSettings::default()).unwrap();
+        assert_eq!(layers.len(), 5);
+        for layer in layers.iter() {
+            assert_eq!(layer.islands.len(), 1);
+        }
+
+        let sphere = Volume::new(
+            Shell::new(vec![primitive_sphere(Point::zero(), EFloat64::from(5.0))]),
+            vec![],
+        );
+        let layer = slice_layer(&sphere, 3.0, 0, &SlicerSettings::default()).unwrap();
+        assert_eq!(layer.islands.len(), 1);
+        match &layer.islands[0].section.boundaries[0].edges[0].curve {
+            Curve::Circle(circle) => assert!(circle.radius.norm() == 4.0),
+            _ => panic!("The section of a sphere should be a circle"),
+        }
+    }
+}
@@ -0,0 +1,4 @@
+pub mod gcode;
+pub mod infill;
+pub mod layer;
+pub mod settings;
@@ -0,0 +1,40 @@
+use std::f64::consts::PI;
+
+// Settings of the printer and the print. Lengths are in millimeters, speeds in millimeters per second.
+#[derive(Debug, Clone)]
+pub struct SlicerSettings {
+    pub layer_height: f64,
+    pub nozzle_width: f64,
+    pub filament_diameter: f64,
+    // Number of perimeters around every island of a layer.
+    pub perimeters: usize,
+    // Fraction of the inside that is filled, 1.0 places the infill lines next to each other and 0.0 disables the infill.
+    pub infill_density: f64,
+    // Angle of the infill lines in the first layer in degrees. Every following layer is turned by 90 degrees.
+    pub infill_angle: f64,
+    pub print_speed: f64,
+    pub travel_speed: f64,
+}
+
+impl SlicerSettings {
+    // Length of filament that is pushed into the nozzle per millimeter of printed line.
+    pub fn extrusion_per_mm(&self) -> f64 {
+        let filament_radius = self.filament_diameter / 2.0;
+        self.layer_height * self.nozzle_width / (PI * filament_radius * filament_radius)
+    }
+}
+
+impl Default for SlicerSettings {
+    fn default() -> SlicerSettings {
+        SlicerSettings {
+            layer_height: 0.2,
+            nozzle_width: 0.4,
+            filament_diameter: 1.75,
+            perimeters: 2,
+            infill_density: 0.2,
+            infill_angle: 45.0,
+            print_speed: 40.0,
+            travel_speed: 120.0,
+        }
+    }
+}
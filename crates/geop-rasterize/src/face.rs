@@ -13,6 +13,7 @@ use geop_topology::{
 use crate::{
     contour::rasterize_contour_into_line_list,
     edge_buffer::{EdgeBuffer, RenderEdge},
+    mesh_buffer::MeshBuffer,
     triangle_buffer::{RenderTriangle, TriangleBuffer},
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
@@ -89,7 +90,7 @@ pub fn inside_triangle_circumcircle(
 
     // Matrix as in https://en.wikipedia.org/wiki/Delaunay_triangulation
     // For planar Delaunay triangulation, we will check if the point lies inside the circumcircle of the triangle
-    let cross_dir = surface.normal(mid_point);
+    let cross_dir = triangulation_normal(surface, mid_point);
 
     let mat0 = projected_triangle0 + cross_dir * projected_triangle0.norm_sq();
     let mat1 = projected_triangle1 + cross_dir * projected_triangle1.norm_sq();
@@ -103,6 +104,18 @@ pub fn inside_triangle_circumcircle(
     det > 0.0 // Check this is -det < -0.0001, which means it is inside the circumcircle
 }
 
+// The normal used to orient triangles. At the apex of a cone, where the normal is not defined, this is the mean of the normals around it.
+fn triangulation_normal(surface: &Surface, point: Point) -> Point {
+    match (surface, surface_unit_normal(surface, point)) {
+        (_, Some(normal)) => normal,
+        (Surface::Cone(cone), None) => match cone.normal_outwards {
+            true => -cone.extend_dir,
+            false => cone.extend_dir,
+        },
+        (_, None) => surface.normal(point),
+    }
+}
+
 pub fn check_triangle_counter_clockwise(surface: &Surface, triangle: &RenderTriangle) -> bool {
     assert!(surface.on_surface(triangle.a.point()));
     assert!(surface.on_surface(triangle.b.point()));
@@ -120,7 +133,7 @@ pub fn check_triangle_counter_clockwise(surface: &Surface, triangle: &RenderTria
         }
     };
 
-    let normal = surface.normal(triangle.a.point());
+    let normal = triangulation_normal(surface, triangle.a.point());
     let det = determinant(v1, v2, normal);
     return det > 0.0; // Ignore if the triangle is colinear
 }
@@ -252,9 +265,9 @@ pub fn check_triangle(
         edge.end.into(),
         point.into(),
         color,
-        surface.normal(edge.start.point()),
-        surface.normal(edge.end.point()),
-        surface.normal(point.point()),
+        triangulation_normal(surface, edge.start.point()),
+        triangulation_normal(surface, edge.end.point()),
+        triangulation_normal(surface, point.point()),
     );
     // Check if the triangle is clockwise and has area > 0
     if !check_triangle_counter_clockwise(surface, &triangle) {
@@ -422,9 +435,9 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
                 edge.end.into(),
                 point.into(),
                 color,
-                face.surface.normal(edge.start.point()),
-                face.surface.normal(edge.end.point()),
-                face.surface.normal(point.point()),
+                triangulation_normal(&face.surface, edge.start.point()),
+                triangulation_normal(&face.surface, edge.end.point()),
+                triangulation_normal(&face.surface, point.point()),
             ));
             processed_edges.push(RenderEdge::new(point.into(), edge.start.into(), color));
             processed_edges.push(RenderEdge::new(edge.end.into(), point.into(), color));
@@ -448,6 +461,47 @@ pub fn rasterize_face_into_triangle_list(face: &Face, color: Color) -> TriangleB
     return TriangleBuffer::new(triangles);
 }
 
+// The unit normal of the surface at a point, or None where the surface is degenerate (e.g. at the apex of a cone).
+fn surface_unit_normal(surface: &Surface, point: Point) -> Option<Point> {
+    if let Surface::Cone(cone) = surface {
+        if point == cone.basis {
+            return None;
+        }
+    }
+    surface.normal(point).normalize().ok()
+}
+
+// Rasterizes a face into an indexed mesh, with the normals evaluated exactly on the surface of the face.
+// Where the normal of the surface is not defined, the normal of the triangle is used instead.
+pub fn rasterize_face_into_mesh(face: &Face, color: Color) -> MeshBuffer {
+    let mut mesh = MeshBuffer::empty();
+    for triangle in rasterize_face_into_triangle_list(face, color)
+        .triangles
+        .iter()
+    {
+        let points = [triangle.a, triangle.b, triangle.c].map(|v| face.surface.project(v.point()));
+        let normals = points.map(|p| surface_unit_normal(&face.surface, p));
+        let fallback = match normals.iter().any(|n| n.is_none()) {
+            true => match (points[1] - points[0])
+                .cross(points[2] - points[0])
+                .normalize()
+            {
+                Ok(normal) => normal,
+                // The triangle is degenerate as well, so it does not cover any area.
+                Err(_) => continue,
+            },
+            false => Point::zero(),
+        };
+
+        let mut indices = [0u32; 3];
+        for ((index, point), normal) in indices.iter_mut().zip(points).zip(normals) {
+            *index = mesh.add_vertex(point, normal.unwrap_or(fallback), color);
+        }
+        mesh.add_triangle(indices[0], indices[1], indices[2], 0);
+    }
+    mesh
+}
+
 pub fn rasterize_face_into_line_list(face: &Face, color: Color) -> EdgeBuffer {
     let mut buffer = EdgeBuffer::empty();
     for contour in face.boundaries.iter() {
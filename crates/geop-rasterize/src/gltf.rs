@@ -0,0 +1,514 @@
+use std::f64::consts::FRAC_1_SQRT_2;
+
+use geop_geometry::point::Point;
+use geop_topology::topology::scene::{Color, Scene};
+
+use crate::{
+    edge::rasterize_edge_into_line_list,
+    edge_buffer::EdgeBuffer,
+    face::rasterize_face_into_mesh,
+    mesh_buffer::MeshBuffer,
+    volume::{rasterize_volume_into_line_list, rasterize_volume_into_mesh},
+};
+
+// Constants of the glTF 2.0 specification.
+const FLOAT: u32 = 5126;
+const UNSIGNED_INT: u32 = 5125;
+const ARRAY_BUFFER: u32 = 34962;
+const ELEMENT_ARRAY_BUFFER: u32 = 34963;
+const POINTS: u32 = 0;
+const LINES: u32 = 1;
+const TRIANGLES: u32 = 4;
+const GLB_MAGIC: u32 = 0x46546C67;
+const GLB_JSON_CHUNK: u32 = 0x4E4F534A;
+const GLB_BIN_CHUNK: u32 = 0x004E4942;
+
+// geop uses z as the up axis and glTF uses y, so the root node is turned by -90 degrees around x.
+const Z_UP_TO_Y_UP: [f64; 4] = [-FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2];
+
+#[derive(Clone, Copy)]
+struct Material {
+    color: Color,
+    // Lines and points are not shaded, so they have the same color from all sides.
+    lit: bool,
+    // Faces are open surfaces, so their back side is visible as well.
+    double_sided: bool,
+}
+
+impl PartialEq for Material {
+    fn eq(&self, other: &Material) -> bool {
+        let color = |c: Color| [c.r, c.g, c.b, c.a];
+        color(self.color) == color(other.color)
+            && self.lit == other.lit
+            && self.double_sided == other.double_sided
+    }
+}
+
+fn to_f32(p: Point) -> [f32; 3] {
+    [
+        p.x.to_f64() as f32,
+        p.y.to_f64() as f32,
+        p.z.to_f64() as f32,
+    ]
+}
+
+fn middle(min: [f32; 3], max: [f32; 3]) -> [f32; 3] {
+    [
+        (min[0] + max[0]) / 2.0,
+        (min[1] + max[1]) / 2.0,
+        (min[2] + max[2]) / 2.0,
+    ]
+}
+
+fn json_array(values: &[f32]) -> String {
+    let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+    format!("[{}]", values.join(","))
+}
+
+// Collects the JSON objects of the glTF document and the binary buffer that the accessors point into.
+#[derive(Default)]
+struct GltfBuilder {
+    bin: Vec<u8>,
+    buffer_views: Vec<String>,
+    accessors: Vec<String>,
+    materials: Vec<Material>,
+    meshes: Vec<String>,
+    nodes: Vec<String>,
+}
+
+impl GltfBuilder {
+    fn buffer_view(&mut self, bytes: &[u8], target: u32) -> usize {
+        // All accessors hold 4 byte values, so every view starts at a multiple of 4.
+        self.buffer_views.push(format!(
+            "{{\"buffer\":0,\"byteOffset\":{},\"byteLength\":{},\"target\":{}}}",
+            self.bin.len(),
+            bytes.len(),
+            target
+        ));
+        self.bin.extend_from_slice(bytes);
+        self.buffer_views.len() - 1
+    }
+
+    // Positions need their bounds, normals do not.
+    fn vec3_accessor(&mut self, values: &[[f32; 3]], bounds: bool) -> usize {
+        let view = self.buffer_view(bytemuck::cast_slice(values), ARRAY_BUFFER);
+        let mut accessor = format!(
+            "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"VEC3\"",
+            view,
+            FLOAT,
+            values.len()
+        );
+        if bounds {
+            let bound = |f: fn(f32, f32) -> f32, start: f32| {
+                let b = values.iter().fold([start; 3], |b, v| {
+                    [f(b[0], v[0]), f(b[1], v[1]), f(b[2], v[2])]
+                });
+                json_array(&b)
+            };
+            accessor += &format!(
+                ",\"min\":{},\"max\":{}",
+                bound(f32::min, f32::INFINITY),
+                bound(f32::max, f32::NEG_INFINITY)
+            );
+        }
+        self.accessors.push(accessor + "}");
+        self.accessors.len() - 1
+    }
+
+    fn index_accessor(&mut self, indices: &[u32]) -> usize {
+        let view = self.buffer_view(bytemuck::cast_slice(indices), ELEMENT_ARRAY_BUFFER);
+        self.accessors.push(format!(
+            "{{\"bufferView\":{},\"componentType\":{},\"count\":{},\"type\":\"SCALAR\"}}",
+            view,
+            UNSIGNED_INT,
+            indices.len()
+        ));
+        self.accessors.len() - 1
+    }
+
+    // Entries with the same color share their material.
+    fn material(&mut self, material: Material) -> usize {
+        match self.materials.iter().position(|m| *m == material) {
+            Some(i) => i,
+            None => {
+                self.materials.push(material);
+                self.materials.len() - 1
+            }
+        }
+    }
+
+    fn primitive(
+        &mut self,
+        positions: &[[f32; 3]],
+        normals: Option<&[[f32; 3]]>,
+        indices: Option<&[u32]>,
+        mode: u32,
+        material: Material,
+    ) -> Option<String> {
+        if positions.is_empty() {
+            return None;
+        }
+        let mut attributes = format!("\"POSITION\":{}", self.vec3_accessor(positions, true));
+        if let Some(normals) = normals {
+            attributes += &format!(",\"NORMAL\":{}", self.vec3_accessor(normals, false));
+        }
+        let mut primitive = format!("{{\"attributes\":{{{}}}", attributes);
+        if let Some(indices) = indices {
+            primitive += &format!(",\"indices\":{}", self.index_accessor(indices));
+        }
+        primitive += &format!(
+            ",\"mode\":{},\"material\":{}}}",
+            mode,
+            self.material(material)
+        );
+        Some(primitive)
+    }
+
+    fn triangles(&mut self, mesh: &MeshBuffer, material: Material) -> Option<String> {
+        let positions: Vec<[f32; 3]> = mesh
+            .vertices
+            .vertices
+            .iter()
+            .map(|v| middle(v.min_position, v.max_position))
+            .collect();
+        let normals: Vec<[f32; 3]> = mesh
+            .vertices
+            .vertices
+            .iter()
+            .map(|v| to_f32(MeshBuffer::normal_point(v)))
+            .collect();
+        self.primitive(
+            &positions,
+            Some(&normals),
+            Some(&mesh.indices),
+            TRIANGLES,
+            material,
+        )
+    }
+
+    fn lines(&mut self, lines: &EdgeBuffer, material: Material) -> Option<String> {
+        let positions: Vec<[f32; 3]> = lines
+            .edges
+            .iter()
+            .flat_map(|e| [e.start, e.end])
+            .map(|v| middle(v.min_position, v.max_position))
+            .collect();
+        self.primitive(&positions, None, None, LINES, material)
+    }
+
+    // Meshes without any primitive are not allowed, so the node of an empty entry has no mesh.
+    fn mesh(&mut self, name: &str, primitives: Vec<Option<String>>) -> Option<usize> {
+        let primitives: Vec<String> = primitives.into_iter().flatten().collect();
+        if primitives.is_empty() {
+            return None;
+        }
+        self.meshes.push(format!(
+            "{{\"name\":\"{}\",\"primitives\":[{}]}}",
+            name,
+            primitives.join(",")
+        ));
+        Some(self.meshes.len() - 1)
+    }
+
+    fn node(
+        &mut self,
+        name: &str,
+        mesh: Option<usize>,
+        children: Vec<usize>,
+        rotation: Option<[f64; 4]>,
+    ) -> usize {
+        let mut node = format!("{{\"name\":\"{}\"", name);
+        if let Some(mesh) = mesh {
+            node += &format!(",\"mesh\":{}", mesh);
+        }
+        if !children.is_empty() {
+            let children: Vec<String> = children.iter().map(|c| c.to_string()).collect();
+            node += &format!(",\"children\":[{}]", children.join(","));
+        }
+        if let Some(r) = rotation {
+            node += &format!(",\"rotation\":[{},{},{},{}]", r[0], r[1], r[2], r[3]);
+        }
+        self.nodes.push(node + "}");
+        self.nodes.len() - 1
+    }
+
+    // A node with one child per entry, or None if there are no entries.
+    fn group(&mut self, name: &str, children: Vec<usize>) -> Option<usize> {
+        match children.is_empty() {
+            true => None,
+            false => Some(self.node(name, None, children, None)),
+        }
+    }
+
+    fn material_json(material: &Material) -> String {
+        let c = material.color;
+        let mut json = format!(
+            "{{\"pbrMetallicRoughness\":{{\"baseColorFactor\":{},\"metallicFactor\":0,\"roughnessFactor\":1}}",
+            json_array(&[c.r, c.g, c.b, c.a])
+        );
+        if c.a < 1.0 {
+            json += ",\"alphaMode\":\"BLEND\"";
+        }
+        if material.double_sided {
+            json += ",\"doubleSided\":true";
+        }
+        if !material.lit {
+            json += ",\"extensions\":{\"KHR_materials_unlit\":{}}";
+        }
+        json + "}"
+    }
+
+    // The glTF document with the root node. The buffer is embedded with the uri, or stored next to the JSON in a GLB file.
+    fn json(&self, root: usize, uri: Option<String>) -> String {
+        let mut fields = vec![
+            "\"asset\":{\"version\":\"2.0\",\"generator\":\"geop\"}".to_string(),
+            "\"scene\":0".to_string(),
+            format!("\"scenes\":[{{\"nodes\":[{}]}}]", root),
+        ];
+        let mut array = |name: &str, items: &[String]| {
+            if !items.is_empty() {
+                fields.push(format!("\"{}\":[{}]", name, items.join(",")));
+            }
+        };
+        let materials: Vec<String> = self.materials.iter().map(Self::material_json).collect();
+        array("nodes", &self.nodes);
+        array("meshes", &self.meshes);
+        array("materials", &materials);
+        array("accessors", &self.accessors);
+        array("bufferViews", &self.buffer_views);
+        if self.materials.iter().any(|m| !m.lit) {
+            fields.push("\"extensionsUsed\":[\"KHR_materials_unlit\"]".to_string());
+        }
+        if !self.bin.is_empty() {
+            let uri = uri.map_or(String::new(), |uri| format!(",\"uri\":\"{}\"", uri));
+            fields.push(format!(
+                "\"buffers\":[{{\"byteLength\":{}{}}}]",
+                self.bin.len(),
+                uri
+            ));
+        }
+        format!("{{{}}}", fields.join(","))
+    }
+}
+
+// Tessellates the entries of the scene and returns the builder with the index of the root node.
+// The root node has a group for volumes, faces, edges and points, with one node per entry.
+fn build(scene: &Scene) -> (GltfBuilder, usize) {
+    let mut builder = GltfBuilder::default();
+    let edge_color = Color::standard_pallet(false).2;
+    let lit = |color: Color, double_sided: bool| Material {
+        color,
+        lit: true,
+        double_sided,
+    };
+    let unlit = |color: Color| Material {
+        color,
+        lit: false,
+        double_sided: false,
+    };
+
+    let mut volumes = Vec::<usize>::new();
+    for (i, (volume, color)) in scene.volumes.iter().enumerate() {
+        let name = format!("Volume {}", i);
+        let primitives = vec![
+            builder.triangles(
+                &rasterize_volume_into_mesh(volume, *color),
+                lit(*color, false),
+            ),
+            builder.lines(
+                &rasterize_volume_into_line_list(volume, edge_color),
+                unlit(edge_color),
+            ),
+        ];
+        let mesh = builder.mesh(&name, primitives);
+        volumes.push(builder.node(&name, mesh, vec![], None));
+    }
+
+    let mut faces = Vec::<usize>::new();
+    for (i, (face, color)) in scene.faces.iter().enumerate() {
+        let name = format!("Face {}", i);
+        let primitives =
+            vec![builder.triangles(&rasterize_face_into_mesh(face, *color), lit(*color, true))];
+        let mesh = builder.mesh(&name, primitives);
+        faces.push(builder.node(&name, mesh, vec![], None));
+    }
+
+    let mut edges = Vec::<usize>::new();
+    for (i, (edge, color)) in scene.edges.iter().enumerate() {
+        let name = format!("Edge {}", i);
+        let primitives =
+            vec![builder.lines(&rasterize_edge_into_line_list(edge, *color), unlit(*color))];
+        let mesh = builder.mesh(&name, primitives);
+        edges.push(builder.node(&name, mesh, vec![], None));
+    }
+
+    let mut points = Vec::<usize>::new();
+    for (i, (point, color)) in scene.points.iter().enumerate() {
+        let name = format!("Point {}", i);
+        let primitives =
+            vec![builder.primitive(&[to_f32(*point)], None, None, POINTS, unlit(*color))];
+        let mesh = builder.mesh(&name, primitives);
+        points.push(builder.node(&name, mesh, vec![], None));
+    }
+
+    let groups = [
+        builder.group("Volumes", volumes),
+        builder.group("Faces", faces),
+        builder.group("Edges", edges),
+        builder.group("Points", points),
+    ];
+    let root = builder.node(
+        "Scene",
+        None,
+        groups.into_iter().flatten().collect(),
+        Some(Z_UP_TO_Y_UP),
+    );
+    (builder, root)
+}
+
+fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for k in 0..4 {
+            match k <= chunk.len() {
+                true => encoded.push(ALPHABET[(n >> (18 - 6 * k) & 63) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
+
+// The scene as a glTF document, with the binary buffer embedded as a base64 data uri.
+pub fn scene_to_gltf(scene: &Scene) -> String {
+    let (builder, root) = build(scene);
+    let uri = format!(
+        "data:application/octet-stream;base64,{}",
+        base64(&builder.bin)
+    );
+    builder.json(root, Some(uri))
+}
+
+// The scene as a binary GLB file, which holds the glTF document and the binary buffer in one file.
+pub fn scene_to_glb(scene: &Scene) -> Vec<u8> {
+    let (builder, root) = build(scene);
+    let mut json = builder.json(root, None).into_bytes();
+    // Chunks are aligned to 4 bytes. The JSON chunk is padded with spaces, the binary chunk with zeros.
+    json.resize(json.len().next_multiple_of(4), b' ');
+    let mut bin = builder.bin;
+    bin.resize(bin.len().next_multiple_of(4), 0);
+
+    let mut chunks = Vec::<(u32, Vec<u8>)>::new();
+    chunks.push((GLB_JSON_CHUNK, json));
+    if !bin.is_empty() {
+        chunks.push((GLB_BIN_CHUNK, bin));
+    }
+    let length = 12 + chunks.iter().map(|(_, c)| 8 + c.len()).sum::<usize>();
+    let mut glb = Vec::<u8>::with_capacity(length);
+    for word in [GLB_MAGIC, 2, length as u32] {
+        glb.extend_from_slice(&word.to_le_bytes());
+    }
+    for (chunk_type, chunk) in chunks {
+        glb.extend_from_slice(&(chunk.len() as u32).to_le_bytes());
+        glb.extend_from_slice(&chunk_type.to_le_bytes());
+        glb.extend_from_slice(&chunk);
+    }
+    glb
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+    use geop_topology::primitive_objects::{
+        edges::line::primitive_line, faces::rectangle::primitive_rectangle,
+        volumes::cube::primitive_cube,
+    };
+
+    use super::*;
+
+    fn scene() -> Scene {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let square = primitive_rectangle(
+            Point::from_f64(0.0, 0.0, 3.0),
+            Point::unit_x(),
+            Point::unit_y(),
+        );
+        let line = primitive_line(Point::zero(), Point::from_f64(0.0, 0.0, 5.0)).unwrap();
+        Scene::new(
+            vec![(cube.clone(), Color::red()), (cube, Color::red())],
+            vec![(square, Color::new(0.0, 0.0, 1.0, 0.5))],
+            vec![(line, Color::green())],
+            vec![(Point::zero(), Color::black())],
+        )
+    }
+
+    fn u32_at(bytes: &[u8], offset: usize) -> u32 {
+        u32::from_le_bytes(bytes[offset..offset + 4].try_into().unwrap())
+    }
+
+    #[test]
+    fn test_glb_layout() {
+        let glb = scene_to_glb(&scene());
+        assert_eq!(u32_at(&glb, 0), GLB_MAGIC);
+        assert_eq!(u32_at(&glb, 4), 2);
+        assert_eq!(u32_at(&glb, 8) as usize, glb.len());
+
+        let json_length = u32_at(&glb, 12) as usize;
+        assert_eq!(u32_at(&glb, 16), GLB_JSON_CHUNK);
+        let json = std::str::from_utf8(&glb[20..20 + json_length]).unwrap();
+        let bin_length = u32_at(&glb, 20 + json_length) as usize;
+        assert_eq!(u32_at(&glb, 24 + json_length), GLB_BIN_CHUNK);
+        assert_eq!(28 + json_length + bin_length, glb.len());
+        assert!(json.contains(&format!("\"buffers\":[{{\"byteLength\":{}}}]", bin_length)));
+
+        // Root, four groups and five entries.
+        assert!(json.contains("\"scenes\":[{\"nodes\":[9]}]"));
+        assert!(json.contains("{\"name\":\"Scene\",\"children\":[5,6,7,8],\"rotation\""));
+        assert!(json.contains("{\"name\":\"Volumes\",\"children\":[0,1]}"));
+        // The cube is centered at the origin with a size of 2.
+        assert!(json.contains("\"min\":[-1,-1,-1],\"max\":[1,1,1]"));
+        assert!(json.contains("\"NORMAL\""));
+    }
+
+    #[test]
+    fn test_gltf_materials_and_modes() {
+        let gltf = scene_to_gltf(&scene());
+        // Both cubes share the red material, and their edges share the edge color.
+        assert_eq!(gltf.matches("\"baseColorFactor\":[1,0,0,1]").count(), 1);
+        assert!(gltf.contains(
+            "\"baseColorFactor\":[0,0,1,0.5],\"metallicFactor\":0,\"roughnessFactor\":1},\"alphaMode\":\"BLEND\",\"doubleSided\":true}"
+        ));
+        assert!(gltf.contains("\"extensionsUsed\":[\"KHR_materials_unlit\"]"));
+        assert_eq!(gltf.matches("\"mode\":4").count(), 3);
+        assert_eq!(gltf.matches("\"mode\":1").count(), 3);
+        assert_eq!(gltf.matches("\"mode\":0").count(), 1);
+
+        let prefix = "\"uri\":\"data:application/octet-stream;base64,";
+        let start = gltf.find(prefix).unwrap() + prefix.len();
+        let data = &gltf[start..start + gltf[start..].find('"').unwrap()];
+        let length: usize = gltf
+            .split("\"byteLength\":")
+            .last()
+            .unwrap()
+            .split(',')
+            .next()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert_eq!(data.len(), length.div_ceil(3) * 4);
+    }
+
+    #[test]
+    fn test_base64() {
+        assert_eq!(base64(b"geop"), "Z2VvcA==");
+        assert_eq!(base64(b"glb"), "Z2xi");
+        assert_eq!(base64(b"gl"), "Z2w=");
+    }
+}
@@ -0,0 +1,465 @@
+use geop_booleans::intersections::face_edge::{face_edge_intersection, FaceEdgeIntersection};
+use geop_geometry::{
+    curve_curve_intersection::curve_curve::{curve_curve_intersection, CurveCurveIntersection},
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse, line::Line, CurveLike},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::surface::Surface,
+};
+use geop_topology::{
+    contains::edge_point::{edge_point_contains, EdgePointContains},
+    ray_cast::ray_cast,
+    topology::{edge::Edge, face::Face, scene::Color, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+};
+
+use crate::{edge::rasterize_edges_into_line_list, edge_buffer::EdgeBuffer};
+
+// Faces closer than this to a point on an edge are the faces of the edge itself, and do not hide it.
+const OCCLUSION_TOLERANCE: f64 = 1e-7;
+// Rays from a silhouette would touch its surface, so they start this far off the surface instead.
+const SILHOUETTE_CLEARANCE: f64 = 1e-6;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StandardView {
+    Top,
+    Bottom,
+    Front,
+    Back,
+    Right,
+    Left,
+    Iso,
+}
+
+// An orthographic projection. Points are projected along the direction onto the drawing, where right and up are the axes.
+#[derive(Debug, Clone, Copy)]
+pub struct OrthographicView {
+    pub direction: Point,
+    pub up: Point,
+    pub right: Point,
+}
+
+impl OrthographicView {
+    // The up vector is made orthogonal to the direction, so it only has to point roughly upwards.
+    pub fn new(direction: Point, up: Point) -> TopologyResult<OrthographicView> {
+        let direction = direction.normalize().map_err(GeometryError::from)?;
+        let up = (up - direction * up.dot(direction))
+            .normalize()
+            .map_err(|_| TopologyError::new("The up vector is parallel to the view".to_string()))?;
+        Ok(OrthographicView {
+            direction,
+            up,
+            right: direction.cross(up),
+        })
+    }
+
+    // The views of a part with z pointing upwards. The front view looks along y.
+    pub fn standard(view: StandardView) -> OrthographicView {
+        let (direction, up) = match view {
+            StandardView::Top => (-Point::unit_z(), Point::unit_y()),
+            StandardView::Bottom => (Point::unit_z(), Point::unit_y()),
+            StandardView::Front => (Point::unit_y(), Point::unit_z()),
+            StandardView::Back => (-Point::unit_y(), Point::unit_z()),
+            StandardView::Right => (-Point::unit_x(), Point::unit_z()),
+            StandardView::Left => (Point::unit_x(), Point::unit_z()),
+            StandardView::Iso => (Point::from_f64(-1.0, 1.0, -1.0), Point::unit_z()),
+        };
+        OrthographicView::new(direction, up).unwrap()
+    }
+
+    // Coordinates of the point on the drawing.
+    pub fn project(&self, p: Point) -> (f64, f64) {
+        (self.right.dot(p).to_f64(), self.up.dot(p).to_f64())
+    }
+}
+
+// The edges of a view, split into the parts that are visible and hidden.
+#[derive(Debug, Clone)]
+pub struct Drawing {
+    pub view: OrthographicView,
+    pub visible: Vec<Edge>,
+    pub hidden: Vec<Edge>,
+}
+
+impl Drawing {
+    // Line lists of the visible and the hidden edges, e.g. to show the drawing in the viewer.
+    pub fn rasterize_into_line_lists(
+        &self,
+        visible: Color,
+        hidden: Color,
+    ) -> (EdgeBuffer, EdgeBuffer) {
+        (
+            rasterize_edges_into_line_list(&self.visible, visible),
+            rasterize_edges_into_line_list(&self.hidden, hidden),
+        )
+    }
+}
+
+// The curves where the surface of the face turns away from the viewer, clipped to the face.
+// Cylinders have two lines along their axis, and spheres have a circle around their center.
+fn silhouette_edges(face: &Face, view: &OrthographicView) -> TopologyResult<Vec<Edge>> {
+    let curves = match face.surface.as_ref() {
+        Surface::Cylinder(cylinder) => {
+            let side = cylinder.extend_dir.cross(view.direction);
+            // Seen along the axis, the whole cylinder is silhouette, and its outline is given by its edges.
+            if side.norm() == 0.0 {
+                return Ok(vec![]);
+            }
+            let side = side.normalize().map_err(GeometryError::from)? * cylinder.radius.norm();
+            vec![
+                Curve::Line(Line::new(cylinder.basis + side, cylinder.extend_dir)?),
+                Curve::Line(Line::new(cylinder.basis - side, cylinder.extend_dir)?),
+            ]
+        }
+        Surface::Sphere(sphere) => vec![Curve::Circle(Circle::try_new(
+            sphere.basis,
+            view.direction,
+            sphere.radius,
+        )?)],
+        _ => vec![],
+    };
+    let mut edges = Vec::<Edge>::new();
+    for curve in curves {
+        if let FaceEdgeIntersection::Edges(es) =
+            face_edge_intersection(face, &Edge::from_curve(curve))
+        {
+            edges.extend(es);
+        }
+    }
+    Ok(edges)
+}
+
+fn visible(volume: &Volume, p: Point, view: &OrthographicView) -> TopologyResult<bool> {
+    Ok(!ray_cast(volume, p, -view.direction)?
+        .iter()
+        .any(|hit| hit.t > OCCLUSION_TOLERANCE))
+}
+
+// The point projected along the view onto the drawing plane through the origin.
+fn flatten(p: Point, view: &OrthographicView) -> Point {
+    p - view.direction * view.direction.dot(p)
+}
+
+// The curve projected onto the drawing plane. Circles and ellipses become ellipses, or lines if they are seen edge-on.
+// Lines along the view project to a single point and have no projection.
+fn projected_curve(curve: &Curve, view: &OrthographicView) -> TopologyResult<Option<Curve>> {
+    let (basis, a, b) = match curve {
+        Curve::Line(line) => {
+            let direction = flatten(line.direction, view);
+            if direction.norm() == 0.0 {
+                return Ok(None);
+            }
+            let direction = direction.normalize().map_err(GeometryError::from)?;
+            return Ok(Some(Curve::Line(Line::new(
+                flatten(line.basis, view),
+                direction,
+            )?)));
+        }
+        Curve::Circle(circle) => (
+            circle.basis,
+            circle.radius,
+            circle.normal.cross(circle.radius),
+        ),
+        Curve::Ellipse(ellipse) => (ellipse.basis, ellipse.major_radius, ellipse.minor_radius),
+        Curve::Helix(_) => {
+            return Err(TopologyError::new(format!(
+                "Hidden lines are not supported for {:?}",
+                curve
+            )))
+        }
+    };
+    // The radii stay conjugate radii of the projected ellipse.
+    let (basis, a, b) = (flatten(basis, view), flatten(a, view), flatten(b, view));
+    if a.cross(b).norm() == 0.0 {
+        let direction = match b.norm() > a.norm() {
+            true => b,
+            false => a,
+        };
+        let direction = direction.normalize().map_err(GeometryError::from)?;
+        return Ok(Some(Curve::Line(Line::new(basis, direction)?)));
+    }
+    if a.dot(b) == 0.0 && a.norm() == b.norm() {
+        return Ok(Some(Curve::Circle(Circle::try_new(
+            basis,
+            view.direction,
+            a.norm(),
+        )?)));
+    }
+    Ok(Some(Curve::Ellipse(Ellipse::from_conjugate_radii(
+        basis,
+        view.direction,
+        a,
+        b,
+    )?)))
+}
+
+// The points of the curve that project onto the point p of the drawing plane.
+// Circles and ellipses that are seen edge-on have two of them.
+fn lift(curve: &Curve, p: Point, view: &OrthographicView) -> TopologyResult<Vec<Point>> {
+    let (basis, normal) = match curve {
+        Curve::Line(line) => {
+            let direction = flatten(line.direction, view);
+            let s = ((p - flatten(line.basis, view)).dot(direction) / direction.norm_sq())
+                .map_err(GeometryError::from)?;
+            return Ok(vec![line.basis + line.direction * s]);
+        }
+        Curve::Circle(circle) => (circle.basis, circle.normal),
+        Curve::Ellipse(ellipse) => (ellipse.basis, ellipse.normal),
+        Curve::Helix(_) => return Ok(vec![]),
+    };
+    let cos = normal.dot(view.direction);
+    if cos != 0.0 {
+        let t = ((basis - p).dot(normal) / cos).map_err(GeometryError::from)?;
+        return Ok(vec![p + view.direction * t]);
+    }
+    let ray = Curve::Line(Line::new(p, view.direction)?);
+    match curve_curve_intersection(&ray, curve) {
+        CurveCurveIntersection::FinitePoints(points) => Ok(points),
+        _ => Ok(vec![]),
+    }
+}
+
+// The points of the edge where its projection crosses the projection of another edge of the drawing.
+// Only there the edge can disappear behind a face or come out again.
+fn crossings(
+    edge: &Edge,
+    projected: &Curve,
+    outlines: &[(&Edge, Option<Curve>)],
+    view: &OrthographicView,
+) -> TopologyResult<Vec<Point>> {
+    let mut flat = Vec::<Point>::new();
+    for (other, other_projected) in outlines.iter() {
+        let Some(other_projected) = other_projected else {
+            continue;
+        };
+        match curve_curve_intersection(projected, other_projected) {
+            CurveCurveIntersection::FinitePoints(points) => flat.extend(points),
+            // Where the projections overlap, the other edge ends in front of or behind the edge.
+            CurveCurveIntersection::Curve(_) => flat.extend(
+                [other.start, other.end]
+                    .iter()
+                    .flatten()
+                    .map(|p| flatten(*p, view)),
+            ),
+            _ => {}
+        }
+    }
+
+    let mut points = Vec::<Point>::new();
+    for p in flat {
+        for q in lift(&edge.curve, p, view)? {
+            if edge_point_contains(edge, q) == EdgePointContains::Inside && !points.contains(&q) {
+                points.push(q);
+            }
+        }
+    }
+    Ok(points)
+}
+
+// Splits the edge where it disappears behind a face or comes out again.
+// The visibility only changes at the crossings, so it is decided once between every two of them by casting a ray towards the viewer.
+// Silhouettes are given with their face, because rays from a silhouette would touch the curved face.
+fn split_by_visibility(
+    volume: &Volume,
+    edge: &Edge,
+    silhouette_of: Option<&Face>,
+    crossings: Vec<Point>,
+    view: &OrthographicView,
+) -> TopologyResult<Vec<(Edge, bool)>> {
+    let visible_at = |p: Point| match silhouette_of {
+        Some(face) => visible(
+            volume,
+            p + face.normal(p) * EFloat64::from(SILHOUETTE_CLEARANCE),
+            view,
+        ),
+        None => visible(volume, p, view),
+    };
+    let start = edge.start.unwrap_or_else(|| edge.interpolate(0.0));
+    let end = edge.end.unwrap_or_else(|| edge.interpolate(1.0));
+    let crossings: Vec<Point> = crossings
+        .into_iter()
+        .filter(|p| *p != start && *p != end)
+        .collect();
+    if crossings.is_empty() {
+        return Ok(vec![(edge.clone(), visible_at(edge.get_midpoint())?)]);
+    }
+
+    // Circles are sorted by angle, so the points are turned to begin at the start of the edge.
+    let mut cuts = crossings.into_iter().map(Some).collect::<Vec<_>>();
+    cuts.push(Some(start));
+    let mut cuts: Vec<Point> = edge.curve.sort(cuts).into_iter().flatten().collect();
+    let first = cuts.iter().position(|p| *p == start).unwrap();
+    cuts.rotate_left(first);
+    cuts.push(end);
+
+    let mut parts = Vec::<(Point, Point, bool)>::new();
+    for k in 0..cuts.len() - 1 {
+        let midpoint = edge.curve.get_midpoint(Some(cuts[k]), Some(cuts[k + 1]))?;
+        let state = visible_at(midpoint)?;
+        match parts.last_mut() {
+            Some(last) if last.2 == state => last.1 = cuts[k + 1],
+            _ => parts.push((cuts[k], cuts[k + 1], state)),
+        }
+    }
+    // A closed edge can also be joined where it starts.
+    if parts.len() > 1 && start == end && parts[0].2 == parts[parts.len() - 1].2 {
+        let last = parts.pop().unwrap();
+        parts[0].0 = last.0;
+    }
+    if parts.len() == 1 {
+        return Ok(vec![(edge.clone(), parts[0].2)]);
+    }
+
+    Ok(parts
+        .into_iter()
+        .map(|(from, to, state)| (Edge::new(Some(from), Some(to), edge.curve.clone()), state))
+        .collect())
+}
+
+// The drawing of the volume with hidden lines. It contains the edges of the volume and the silhouettes of its curved faces.
+pub fn hidden_line_drawing(volume: &Volume, view: &OrthographicView) -> TopologyResult<Drawing> {
+    let faces = volume.all_faces();
+    let mut edges = Vec::<(Edge, Option<&Face>)>::new();
+    for face in faces.iter() {
+        for edge in face.all_edges() {
+            // Closed edges without endpoints are only equal to their flipped edge when flipped explicitly.
+            if !edges.iter().any(|(e, _)| *e == edge || *e == edge.flip()) {
+                edges.push((edge, None));
+            }
+        }
+        for edge in silhouette_edges(face, view)? {
+            edges.push((edge, Some(face)));
+        }
+    }
+
+    let outlines = edges
+        .iter()
+        .map(|(e, _)| Ok((e, projected_curve(&e.curve, view)?)))
+        .collect::<TopologyResult<Vec<(&Edge, Option<Curve>)>>>()?;
+
+    let mut drawing = Drawing {
+        view: *view,
+        visible: vec![],
+        hidden: vec![],
+    };
+    for ((edge, silhouette_of), (_, projected)) in edges.iter().zip(outlines.iter()) {
+        let Some(projected) = projected else {
+            continue;
+        };
+        if matches!(&edge.curve, Curve::Line(_)) && (edge.start.is_none() || edge.end.is_none()) {
+            return Err(TopologyError::new(
+                "Cannot draw an edge that extends to infinity".to_string(),
+            ));
+        }
+        let crossings = crossings(edge, projected, &outlines, view)?;
+        for (part, visible) in split_by_visibility(volume, edge, *silhouette_of, crossings, view)? {
+            match visible {
+                true => drawing.visible.push(part),
+                false => drawing.hidden.push(part),
+            }
+        }
+    }
+    Ok(drawing)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::surfaces::plane::Plane;
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::contour::Contour,
+    };
+
+    use super::*;
+
+    fn cube() -> Volume {
+        primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        )
+    }
+
+    fn cylinder() -> Volume {
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap()
+    }
+
+    #[test]
+    fn test_cube_views() {
+        let front =
+            hidden_line_drawing(&cube(), &OrthographicView::standard(StandardView::Front)).unwrap();
+        assert_eq!(front.visible.len(), 4);
+        assert_eq!(front.hidden.len(), 4);
+        assert!(front.visible.iter().all(|e| e.get_midpoint().y == -1.0));
+
+        // The three edges at the back corner are hidden.
+        let iso =
+            hidden_line_drawing(&cube(), &OrthographicView::standard(StandardView::Iso)).unwrap();
+        assert_eq!(iso.visible.len(), 9);
+        assert_eq!(iso.hidden.len(), 3);
+        let corner = Point::from_f64(-1.0, 1.0, -1.0);
+        assert!(iso
+            .hidden
+            .iter()
+            .all(|e| e.start == Some(corner) || e.end == Some(corner)));
+    }
+
+    #[test]
+    fn test_cylinder_silhouette() {
+        let cylinder = cylinder();
+
+        let front =
+            hidden_line_drawing(&cylinder, &OrthographicView::standard(StandardView::Front))
+                .unwrap();
+        let silhouettes: Vec<&Edge> = front
+            .visible
+            .iter()
+            .filter(|e| matches!(e.curve, Curve::Line(_)))
+            .collect();
+        assert_eq!(silhouettes.len(), 2);
+        for edge in silhouettes {
+            assert!((edge.get_midpoint().x.to_f64().abs() - 1.0).abs() < 1e-9);
+            assert_eq!(edge.get_midpoint().z, 1.0);
+        }
+
+        // From the top, the bottom circle is hidden behind the top face and there are no silhouettes.
+        let top =
+            hidden_line_drawing(&cylinder, &OrthographicView::standard(StandardView::Top)).unwrap();
+        assert_eq!(top.visible.len(), 1);
+        assert_eq!(top.hidden.len(), 1);
+        assert_eq!(top.visible[0].get_midpoint().z, 2.0);
+    }
+
+    #[test]
+    fn test_circle_visibility() {
+        // Seen from above at an angle, the back half of the bottom circle is hidden, and it disappears exactly where the silhouettes end.
+        let view = OrthographicView::new(Point::from_f64(0.0, 1.0, -1.0), Point::unit_z()).unwrap();
+        let drawing = hidden_line_drawing(&cylinder(), &view).unwrap();
+        assert_eq!(drawing.hidden.len(), 1);
+        let arc = &drawing.hidden[0];
+        assert!(matches!(arc.curve, Curve::Circle(_)));
+        for p in [arc.start.unwrap(), arc.end.unwrap()] {
+            assert!(p.x.abs() == 1.0 && p.y == 0.0 && p.z == 0.0);
+        }
+        assert_eq!(arc.get_midpoint().y, 1.0);
+
+        // The front half and the top circle are visible in one piece, next to the two silhouettes.
+        assert_eq!(drawing.visible.len(), 4);
+    }
+}
@@ -4,6 +4,10 @@ pub mod edge;
 pub mod edge_buffer;
 pub mod face;
 pub mod functions;
+pub mod gltf;
+pub mod hidden_line;
+pub mod mesh_buffer;
+pub mod svg;
 pub mod triangle_buffer;
 pub mod vertex_buffer;
 pub mod vertex_normal_buffer;
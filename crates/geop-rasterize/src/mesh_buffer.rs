@@ -0,0 +1,204 @@
+use std::collections::HashMap;
+
+use geop_geometry::point::Point;
+use geop_topology::topology::scene::Color;
+
+use crate::{
+    triangle_buffer::{RenderTriangle, TriangleBuffer},
+    vertex_normal_buffer::{RenderNormalVertex, VertexNormalBuffer},
+};
+
+// Two render vertices closer than this are considered to be the same position. Render vertices are stored as f32, so this is a few ulps at unit scale.
+const WELD_TOLERANCE: f64 = 1e-5;
+// Two normals with a dot product above this are considered to be the same normal.
+const NORMAL_TOLERANCE: f64 = 1.0 - 1e-6;
+
+// An indexed triangle mesh. Vertices at the same position are welded together, so the boundary between two faces is watertight.
+// Along sharp edges (e.g. between two sides of a cube), a position has one vertex per normal. These vertices share the same position index.
+pub struct MeshBuffer {
+    pub vertices: VertexNormalBuffer,
+    // Three indices into vertices per triangle. The triangles are in counter clockwise order.
+    pub indices: Vec<u32>,
+    // One entry per triangle. Refers to the face the triangle was generated from, e.g. the index in Volume::all_faces.
+    pub face_indices: Vec<usize>,
+    // One entry per vertex. Vertices with the same position have the same position index.
+    pub position_indices: Vec<u32>,
+    position_count: u32,
+    position_lookup: HashMap<(i64, i64, i64), Vec<u32>>,
+}
+
+impl MeshBuffer {
+    pub fn empty() -> Self {
+        MeshBuffer {
+            vertices: VertexNormalBuffer::empty(),
+            indices: Vec::new(),
+            face_indices: Vec::new(),
+            position_indices: Vec::new(),
+            position_count: 0,
+            position_lookup: HashMap::new(),
+        }
+    }
+
+    fn position(vertex: &RenderNormalVertex) -> [f64; 3] {
+        [
+            (vertex.min_position[0] as f64 + vertex.max_position[0] as f64) / 2.0,
+            (vertex.min_position[1] as f64 + vertex.max_position[1] as f64) / 2.0,
+            (vertex.min_position[2] as f64 + vertex.max_position[2] as f64) / 2.0,
+        ]
+    }
+
+    fn normal(vertex: &RenderNormalVertex) -> [f64; 3] {
+        [
+            (vertex.min_normal[0] as f64 + vertex.max_normal[0] as f64) / 2.0,
+            (vertex.min_normal[1] as f64 + vertex.max_normal[1] as f64) / 2.0,
+            (vertex.min_normal[2] as f64 + vertex.max_normal[2] as f64) / 2.0,
+        ]
+    }
+
+    fn cell(position: [f64; 3]) -> (i64, i64, i64) {
+        (
+            (position[0] / WELD_TOLERANCE).floor() as i64,
+            (position[1] / WELD_TOLERANCE).floor() as i64,
+            (position[2] / WELD_TOLERANCE).floor() as i64,
+        )
+    }
+
+    // Adds a vertex, or returns the index of an existing vertex with the same position and normal.
+    pub fn add_vertex(&mut self, point: Point, normal: Point, color: Color) -> u32 {
+        let vertex = RenderNormalVertex::new(point, color, normal);
+        let position = MeshBuffer::position(&vertex);
+        let normal = MeshBuffer::normal(&vertex);
+        let cell = MeshBuffer::cell(position);
+
+        // Neighbouring cells have to be checked as well, since two close points can end up on different sides of a cell border.
+        let mut position_index = None;
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    let Some(candidates) =
+                        self.position_lookup
+                            .get(&(cell.0 + dx, cell.1 + dy, cell.2 + dz))
+                    else {
+                        continue;
+                    };
+                    for &i in candidates.iter() {
+                        let other = &self.vertices.vertices[i as usize];
+                        let other_position = MeshBuffer::position(other);
+                        let distance_sq = (0..3)
+                            .map(|k| (position[k] - other_position[k]).powi(2))
+                            .sum::<f64>();
+                        if distance_sq > WELD_TOLERANCE * WELD_TOLERANCE {
+                            continue;
+                        }
+                        position_index = Some(self.position_indices[i as usize]);
+                        let other_normal = MeshBuffer::normal(other);
+                        let dot = (0..3).map(|k| normal[k] * other_normal[k]).sum::<f64>();
+                        if dot > NORMAL_TOLERANCE && other.color == vertex.color {
+                            return i;
+                        }
+                    }
+                }
+            }
+        }
+
+        let position_index = match position_index {
+            Some(position_index) => position_index,
+            None => {
+                self.position_count += 1;
+                self.position_count - 1
+            }
+        };
+
+        let index = self.vertices.vertices.len() as u32;
+        self.vertices.vertices.push(vertex);
+        self.position_indices.push(position_index);
+        self.position_lookup.entry(cell).or_default().push(index);
+        index
+    }
+
+    pub fn add_triangle(&mut self, a: u32, b: u32, c: u32, face_index: usize) {
+        self.indices.extend_from_slice(&[a, b, c]);
+        self.face_indices.push(face_index);
+    }
+
+    pub fn triangle_count(&self) -> usize {
+        self.face_indices.len()
+    }
+
+    // Number of distinct positions after welding.
+    pub fn position_count(&self) -> usize {
+        self.position_count as usize
+    }
+
+    // A mesh is watertight if every edge between two positions is used by exactly two triangles, once in each direction.
+    pub fn is_watertight(&self) -> bool {
+        let mut half_edges = HashMap::<(u32, u32), i32>::new();
+        for triangle in self.indices.chunks(3) {
+            for k in 0..3 {
+                let from = self.position_indices[triangle[k] as usize];
+                let to = self.position_indices[triangle[(k + 1) % 3] as usize];
+                if from == to {
+                    return false;
+                }
+                *half_edges.entry((from, to)).or_insert(0) += 1;
+            }
+        }
+        half_edges
+            .iter()
+            .all(|(&(from, to), &count)| count == 1 && half_edges.get(&(to, from)) == Some(&1))
+    }
+
+    pub fn index_u8_slice(&self) -> &[u8] {
+        bytemuck::cast_slice(&self.indices)
+    }
+
+    // Expands the mesh into an unindexed triangle list.
+    pub fn to_triangle_buffer(&self) -> TriangleBuffer {
+        let triangles = self
+            .indices
+            .chunks(3)
+            .map(|triangle| RenderTriangle {
+                a: self.vertices.vertices[triangle[0] as usize],
+                b: self.vertices.vertices[triangle[1] as usize],
+                c: self.vertices.vertices[triangle[2] as usize],
+            })
+            .collect();
+        TriangleBuffer::new(triangles)
+    }
+
+    pub fn join(&mut self, other: &MeshBuffer) {
+        let face_offset = self.face_indices.iter().max().map_or(0, |i| i + 1);
+        self.join_with_face_offset(other, face_offset);
+    }
+
+    // Joins another mesh, whose face indices are shifted by face_offset.
+    pub fn join_with_face_offset(&mut self, other: &MeshBuffer, face_offset: usize) {
+        let mut index_map = Vec::with_capacity(other.vertices.vertices.len());
+        for vertex in other.vertices.vertices.iter() {
+            let color = Color::new(
+                vertex.color[0],
+                vertex.color[1],
+                vertex.color[2],
+                vertex.color[3],
+            );
+            index_map.push(self.add_vertex(
+                vertex.point(),
+                MeshBuffer::normal_point(vertex),
+                color,
+            ));
+        }
+        for (triangle, face_index) in other.indices.chunks(3).zip(other.face_indices.iter()) {
+            self.add_triangle(
+                index_map[triangle[0] as usize],
+                index_map[triangle[1] as usize],
+                index_map[triangle[2] as usize],
+                face_offset + face_index,
+            );
+        }
+    }
+
+    pub fn normal_point(vertex: &RenderNormalVertex) -> Point {
+        let normal = MeshBuffer::normal(vertex);
+        Point::from_f64(normal[0], normal[1], normal[2])
+    }
+}
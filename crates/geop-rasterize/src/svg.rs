@@ -0,0 +1,292 @@
+use std::f64::consts::PI;
+
+use geop_geometry::{curves::curve::Curve, efloat::EFloat64, point::Point};
+use geop_topology::topology::{edge::Edge, scene::Color};
+
+use crate::hidden_line::{Drawing, OrthographicView};
+
+// Number of line segments for curves that are not drawn as arcs.
+const POLYLINE_SEGMENTS: usize = 32;
+
+#[derive(Debug, Clone, Copy)]
+pub struct SvgStyle {
+    // Length on the drawing in millimeters for a length of 1 on the part.
+    pub scale: f64,
+    // Space around the drawing in millimeters.
+    pub margin: f64,
+    pub stroke_width: f64,
+    // Length of the dashes and gaps of hidden edges in millimeters.
+    pub dash_length: f64,
+}
+
+impl Default for SvgStyle {
+    fn default() -> Self {
+        SvgStyle {
+            scale: 1.0,
+            margin: 5.0,
+            stroke_width: 0.35,
+            dash_length: 2.0,
+        }
+    }
+}
+
+// SVG coordinates of a point, where y points downwards.
+fn svg_point(view: &OrthographicView, p: Point, style: &SvgStyle) -> (f64, f64) {
+    let (x, y) = view.project(p);
+    (x * style.scale, -y * style.scale)
+}
+
+// SVG coordinates of a vector, e.g. a radius.
+fn svg_vector(view: &OrthographicView, v: Point, style: &SvgStyle) -> (f64, f64) {
+    let (x, y) = svg_point(view, v, style);
+    let (x0, y0) = svg_point(view, Point::zero(), style);
+    (x - x0, y - y0)
+}
+
+fn polyline(view: &OrthographicView, edge: &Edge, style: &SvgStyle) -> String {
+    (0..=POLYLINE_SEGMENTS)
+        .map(|i| {
+            let (x, y) = svg_point(
+                view,
+                edge.interpolate(i as f64 / POLYLINE_SEGMENTS as f64),
+                style,
+            );
+            format!("{}{:.3} {:.3}", if i == 0 { "M" } else { " L" }, x, y)
+        })
+        .collect()
+}
+
+// Circles and ellipses are given by the center and two conjugate radii a and b, with the points center + cos t * a + sin t * b.
+// Their projection is an ellipse, or a circle if the curve is parallel to the drawing. The arc is split in two halves, so that no half is larger than half of the ellipse.
+fn elliptic_arc(
+    view: &OrthographicView,
+    edge: &Edge,
+    center: Point,
+    a: Point,
+    b: Point,
+    style: &SvgStyle,
+) -> String {
+    let (ax, ay) = svg_vector(view, a, style);
+    let (bx, by) = svg_vector(view, b, style);
+    let cross = ax * by - ay * bx;
+    // Seen from the side, the curve is a straight line.
+    if cross.abs() <= 1e-9 * (ax.hypot(ay) * bx.hypot(by)).max(1e-9) {
+        return polyline(view, edge, style);
+    }
+
+    // The axes of the projected ellipse are the longest and the shortest conjugate radii.
+    let theta = 0.5
+        * f64::atan2(
+            2.0 * (ax * bx + ay * by),
+            ax * ax + ay * ay - bx * bx - by * by,
+        );
+    let axis = |t: f64| (ax * t.cos() + bx * t.sin(), ay * t.cos() + by * t.sin());
+    let (rx1, ry1) = axis(theta);
+    let (rx2, ry2) = axis(theta + PI / 2.0);
+    let rotation = ry1.atan2(rx1).to_degrees();
+    let sweep = if cross > 0.0 { 1 } else { 0 };
+
+    let parameter = |p: Point| {
+        let d = p - center;
+        f64::atan2(
+            d.dot(b).to_f64() / b.norm_sq().to_f64(),
+            d.dot(a).to_f64() / a.norm_sq().to_f64(),
+        )
+    };
+    let (t0, t1) = match (edge.start, edge.end) {
+        (Some(start), Some(end)) => {
+            let t0 = parameter(start);
+            let mut t1 = parameter(end);
+            while t1 <= t0 {
+                t1 += 2.0 * PI;
+            }
+            (t0, t1)
+        }
+        _ => (0.0, 2.0 * PI),
+    };
+    let point = |t: f64| {
+        svg_point(
+            view,
+            center + a * EFloat64::from(t.cos()) + b * EFloat64::from(t.sin()),
+            style,
+        )
+    };
+
+    let (x, y) = point(t0);
+    let mut path = format!("M{:.3} {:.3}", x, y);
+    for t in [(t0 + t1) / 2.0, t1] {
+        let (x, y) = point(t);
+        path += &format!(
+            " A{:.3} {:.3} {:.3} 0 {} {:.3} {:.3}",
+            rx1.hypot(ry1),
+            rx2.hypot(ry2),
+            rotation,
+            sweep,
+            x,
+            y
+        );
+    }
+    path
+}
+
+fn edge_path(view: &OrthographicView, edge: &Edge, style: &SvgStyle) -> String {
+    match &edge.curve {
+        Curve::Line(_) => {
+            let (x1, y1) = svg_point(view, edge.interpolate(0.0), style);
+            let (x2, y2) = svg_point(view, edge.interpolate(1.0), style);
+            format!("M{:.3} {:.3} L{:.3} {:.3}", x1, y1, x2, y2)
+        }
+        Curve::Circle(circle) => elliptic_arc(
+            view,
+            edge,
+            circle.basis,
+            circle.radius,
+            circle.normal.cross(circle.radius),
+            style,
+        ),
+        Curve::Ellipse(ellipse) => elliptic_arc(
+            view,
+            edge,
+            ellipse.basis,
+            ellipse.major_radius,
+            ellipse.minor_radius,
+            style,
+        ),
+        _ => polyline(view, edge, style),
+    }
+}
+
+fn group(view: &OrthographicView, edges: &[Edge], style: &SvgStyle, dashed: bool) -> String {
+    let mut svg = format!(
+        "<g fill=\"none\" stroke=\"black\" stroke-width=\"{}\" stroke-linecap=\"round\"",
+        style.stroke_width
+    );
+    if dashed {
+        svg += &format!(
+            " stroke-dasharray=\"{} {}\"",
+            style.dash_length, style.dash_length
+        );
+    }
+    svg += ">\n";
+    for edge in edges.iter() {
+        svg += &format!("<path d=\"{}\"/>\n", edge_path(view, edge, style));
+    }
+    svg + "</g>\n"
+}
+
+// SVG document of the drawing in millimeters. Hidden edges are dashed and drawn below the visible edges.
+// Lines, circles and ellipses are exact, other curves are drawn as polylines.
+pub fn drawing_to_svg(drawing: &Drawing, style: &SvgStyle) -> String {
+    let view = &drawing.view;
+    let (visible, hidden) = drawing.rasterize_into_line_lists(Color::gray(), Color::gray());
+    let mut bounds = (
+        f64::INFINITY,
+        f64::INFINITY,
+        f64::NEG_INFINITY,
+        f64::NEG_INFINITY,
+    );
+    for edge in visible.edges.iter().chain(hidden.edges.iter()) {
+        for vertex in [edge.start, edge.end] {
+            let (x, y) = svg_point(view, vertex.point(), style);
+            bounds = (
+                bounds.0.min(x),
+                bounds.1.min(y),
+                bounds.2.max(x),
+                bounds.3.max(y),
+            );
+        }
+    }
+    if bounds.0 > bounds.2 {
+        bounds = (0.0, 0.0, 0.0, 0.0);
+    }
+    let (x, y) = (bounds.0 - style.margin, bounds.1 - style.margin);
+    let (width, height) = (
+        bounds.2 - bounds.0 + 2.0 * style.margin,
+        bounds.3 - bounds.1 + 2.0 * style.margin,
+    );
+
+    let mut svg = format!(
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{:.3}mm\" height=\"{:.3}mm\" viewBox=\"{:.3} {:.3} {:.3} {:.3}\">\n",
+        width, height, x, y, width, height
+    );
+    svg += &group(view, &drawing.hidden, style, true);
+    svg += &group(view, &drawing.visible, style, false);
+    svg + "</svg>\n"
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::surfaces::{plane::Plane, surface::Surface};
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        topology::{contour::Contour, face::Face, volume::Volume},
+    };
+
+    use super::*;
+    use crate::hidden_line::{hidden_line_drawing, StandardView};
+
+    fn cylinder() -> Volume {
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::from(10.0),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        extrude(disk, Point::from_f64(0.0, 0.0, 20.0)).unwrap()
+    }
+
+    #[test]
+    fn test_svg_cube() {
+        let cube = primitive_cube(
+            EFloat64::from(10.0),
+            EFloat64::from(10.0),
+            EFloat64::from(10.0),
+        );
+        let drawing =
+            hidden_line_drawing(&cube, &OrthographicView::standard(StandardView::Front)).unwrap();
+        let svg = drawing_to_svg(&drawing, &SvgStyle::default());
+        assert!(svg.contains("viewBox=\"-10.000 -10.000 20.000 20.000\""));
+        let (hidden, visible) = svg.split_once("</g>").unwrap();
+        assert!(hidden.contains("stroke-dasharray=\"2 2\""));
+        assert_eq!(hidden.matches("<path").count(), 4);
+        assert_eq!(visible.matches("<path").count(), 4);
+        assert!(
+            visible.contains("M-5.000 -5.000 L5.000 -5.000")
+                || visible.contains("M5.000 -5.000 L-5.000 -5.000")
+        );
+    }
+
+    #[test]
+    fn test_svg_arcs() {
+        // From the top, the circles of the cylinder are circles on the drawing.
+        let drawing =
+            hidden_line_drawing(&cylinder(), &OrthographicView::standard(StandardView::Top))
+                .unwrap();
+        let svg = drawing_to_svg(&drawing, &SvgStyle::default());
+        assert_eq!(svg.matches(" A10.000 10.000 ").count(), 4);
+
+        // From an angle, they are ellipses, and the cylinder has silhouettes on both sides.
+        let view = OrthographicView::new(Point::from_f64(0.0, 1.0, -1.0), Point::unit_z()).unwrap();
+        let drawing = hidden_line_drawing(&cylinder(), &view).unwrap();
+        let svg = drawing_to_svg(&drawing, &SvgStyle::default());
+        let minor = 10.0 / 2.0_f64.sqrt();
+        assert!(svg.contains(&format!(" A10.000 {:.3} ", minor)));
+        assert_eq!(
+            drawing
+                .visible
+                .iter()
+                .filter(|e| matches!(e.curve, Curve::Line(_)))
+                .count(),
+            2
+        );
+    }
+}
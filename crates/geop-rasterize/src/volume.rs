@@ -2,7 +2,10 @@ use geop_topology::topology::{scene::Color, volume::Volume};
 
 use crate::{
     edge_buffer::EdgeBuffer,
-    face::{rasterize_face_into_line_list, rasterize_face_into_triangle_list},
+    face::{
+        rasterize_face_into_line_list, rasterize_face_into_mesh, rasterize_face_into_triangle_list,
+    },
+    mesh_buffer::MeshBuffer,
     triangle_buffer::TriangleBuffer,
     vertex_buffer::{RenderVertex, VertexBuffer},
 };
@@ -27,6 +30,19 @@ pub fn rasterize_volume_into_triangle_list(volume: &Volume, color: Color) -> Tri
     buffer
 }
 
+// Rasterizes a volume into an indexed mesh. The face index of each triangle refers to Volume::all_faces.
+// Vertices on shared edges are welded, and the normals are evaluated exactly on the surface of the face.
+pub fn rasterize_volume_into_mesh(volume: &Volume, color: Color) -> MeshBuffer {
+    let mut mesh = MeshBuffer::empty();
+
+    for (face_index, face) in volume.all_faces().iter().enumerate() {
+        // Faces are meshed with face index 0, so the offset is the index of the face in the volume.
+        mesh.join_with_face_offset(&rasterize_face_into_mesh(face, color), face_index);
+    }
+
+    mesh
+}
+
 pub fn rasterize_volume_into_line_list(volume: &Volume, color: Color) -> EdgeBuffer {
     let mut buffer = EdgeBuffer::empty();
 
@@ -56,3 +72,153 @@ pub fn rasterize_volume_into_vertex_list(volume: &Volume, color: Color) -> Verte
 
     buffer
 }
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use super::*;
+    use geop_geometry::{
+        curves::{circle::Circle, curve::Curve},
+        efloat::EFloat64,
+        point::Point,
+        surfaces::{cone::Cone, plane::Plane, surface::Surface},
+    };
+    use geop_topology::{
+        operations::extrude::extrude,
+        primitive_objects::{
+            edges::{circle::primitive_circle, line::primitive_line},
+            volumes::cube::primitive_cube,
+        },
+        topology::{contour::Contour, edge::Edge, face::Face, shell::Shell},
+    };
+
+    #[test]
+    fn test_cube_mesh_is_watertight() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let mesh = rasterize_volume_into_mesh(&cube, Color::white());
+
+        assert!(mesh.triangle_count() > 0);
+        assert!(mesh.is_watertight());
+        assert!(mesh.face_indices.iter().all(|&i| i < 6));
+
+        // Normals on a cube are axis aligned and point away from the center
+        for vertex in mesh.vertices.vertices.iter() {
+            let p = vertex.point();
+            let n = MeshBuffer::normal_point(vertex);
+            assert!(n.norm() == 1.0);
+            assert!(p.dot(n) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_cylinder_mesh_is_watertight() {
+        // The bottom face points downwards, out of the extruded volume.
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let faces = cylinder.all_faces();
+        let mesh = rasterize_volume_into_mesh(&cylinder, Color::white());
+
+        assert!(mesh.triangle_count() > 0);
+        assert!(mesh.is_watertight());
+
+        // On the side, the normals point straight away from the axis
+        for (triangle, &face_index) in mesh.indices.chunks(3).zip(mesh.face_indices.iter()) {
+            if !matches!(faces[face_index].surface.as_ref(), Surface::Cylinder(_)) {
+                continue;
+            }
+            for &i in triangle.iter() {
+                let vertex = &mesh.vertices.vertices[i as usize];
+                let p = vertex.point();
+                let n = MeshBuffer::normal_point(vertex);
+                assert!((n - Point::from_f64(p.x.lower_bound, p.y.lower_bound, 0.0)).norm() < 1e-5);
+            }
+        }
+    }
+
+    #[test]
+    fn test_cone_apex_mesh() {
+        // Half of a cone, cut along the plane y = 0. The apex is a vertex of the mesh, where the normal of the cone is not defined.
+        let apex = Point::from_f64(0.0, 0.0, 2.0);
+        let x = Point::unit_x();
+        let half_circle = |from: Point, to: Point, normal: Point| {
+            Edge::new(
+                Some(from),
+                Some(to),
+                Curve::Circle(Circle::try_new(Point::zero(), normal, EFloat64::one()).unwrap()),
+            )
+        };
+        let cone = Face::new(
+            vec![Contour::new(vec![
+                primitive_line(apex, x).unwrap(),
+                half_circle(x, -x, Point::unit_z()),
+                primitive_line(-x, apex).unwrap(),
+            ])],
+            Rc::new(Surface::Cone(Cone::new(
+                apex,
+                -Point::unit_z(),
+                EFloat64::from(0.5),
+                true,
+            ))),
+        );
+        let side = Face::new(
+            vec![Contour::new(vec![
+                primitive_line(apex, -x).unwrap(),
+                primitive_line(-x, x).unwrap(),
+                primitive_line(x, apex).unwrap(),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_z(),
+            ))),
+        );
+        let bottom = Face::new(
+            vec![Contour::new(vec![
+                primitive_line(x, -x).unwrap(),
+                half_circle(-x, x, -Point::unit_z()),
+            ])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let volume = Volume::new(Shell::new(vec![cone, side, bottom]), vec![]);
+        let mesh = rasterize_volume_into_mesh(&volume, Color::white());
+
+        assert!(mesh.triangle_count() > 0);
+        for vertex in mesh.vertices.vertices.iter() {
+            assert!((MeshBuffer::normal_point(vertex).norm() - EFloat64::one()).abs() < 1e-5);
+        }
+
+        // On the cone, the normals are perpendicular to the line to the apex, and the apex itself is part of the mesh
+        let mut apex_count = 0;
+        for (triangle, &face_index) in mesh.indices.chunks(3).zip(mesh.face_indices.iter()) {
+            if face_index != 0 {
+                continue;
+            }
+            for &i in triangle.iter() {
+                let vertex = &mesh.vertices.vertices[i as usize];
+                let p = vertex.point();
+                let n = MeshBuffer::normal_point(vertex);
+                assert!(n.dot(p - apex).abs() < 1e-5);
+                if (p - apex).norm() < 1e-5 {
+                    apex_count += 1;
+                }
+            }
+        }
+        assert!(apex_count > 0);
+    }
+}
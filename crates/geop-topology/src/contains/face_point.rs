@@ -1,9 +1,9 @@
 use geop_geometry::{
     curve_curve_intersection::curve_curve::{curve_curve_intersection, CurveCurveIntersection},
-    curves::curve::Curve,
+    curves::{curve::Curve, line::Line},
     efloat::EFloat64,
     point::Point,
-    surfaces::SurfaceLike,
+    surfaces::{surface::Surface, SurfaceLike},
 };
 
 use crate::topology::{edge::Edge, face::Face};
@@ -19,6 +19,40 @@ pub enum FacePointContains {
     NotOnSurface,
 }
 
+// On cylinders, cones, elliptic cylinders and hyperbolic paraboloids, the geodesic to a point on the border is a helix or not a supported curve at all.
+// The straight line through the point on the surface is a line, so it is followed to the closest border in either direction.
+fn ruling_to_boundary(face: &Face, point: Point) -> Option<Edge> {
+    let ruling = match face.surface.as_ref() {
+        Surface::Cylinder(cylinder) => Curve::Line(Line::new(point, cylinder.extend_dir).unwrap()),
+        Surface::Cone(cone) => Curve::Line(cone.generator(point)),
+        Surface::EllipticCylinder(cylinder) => Curve::Line(cylinder.ruling(point)),
+        Surface::HyperbolicParaboloid(patch) => Curve::Line(patch.ruling(point)),
+        _ => return None,
+    };
+    let mut closest: Option<(EFloat64, Point)> = None;
+    for edge in face.all_edges() {
+        let points = match curve_curve_intersection(&edge.curve, &ruling) {
+            CurveCurveIntersection::FinitePoints(points) => points,
+            CurveCurveIntersection::Curve(_) => edge.start.into_iter().chain(edge.end).collect(),
+            _ => vec![],
+        };
+        for p in points {
+            if p == point || edge_point_contains(&edge, p) == EdgePointContains::Outside {
+                continue;
+            }
+            // The normal is not defined at the apex of a cone, so the side of the border cannot be decided there.
+            if matches!(face.surface.as_ref(), Surface::Cone(cone) if p == cone.basis) {
+                continue;
+            }
+            let distance = (p - point).norm();
+            if closest.is_none_or(|(d, _)| distance < d) {
+                closest = Some((distance, p));
+            }
+        }
+    }
+    face.edge_from_to(point, closest?.1).ok()
+}
+
 pub fn face_point_contains(face: &Face, point: Point) -> FacePointContains {
     if !face.surface.on_surface(point) {
         return FacePointContains::NotOnSurface;
@@ -39,7 +73,14 @@ pub fn face_point_contains(face: &Face, point: Point) -> FacePointContains {
             return FacePointContains::Inside;
         }
     };
-    let geodesic = face.edge_from_to(point, q);
+    let geodesic = match ruling_to_boundary(face, point) {
+        Some(ruling) => ruling,
+        None => match face.edge_from_to(point, q) {
+            Ok(geodesic) => geodesic,
+            Err(_) => return FacePointContains::Outside,
+        },
+    };
+    let q = geodesic.end.unwrap();
 
     // Find the closest intersection point and check by using the face normal and the curve tangent if the intersection is from inside or outside.
     let mut closest_distance = face.surface.distance(point, q);
@@ -93,6 +93,10 @@ pub fn volume_point_contains(volume: &Volume, other: Point) -> VolumePointContai
     let normal = volume.boundary_normal(q);
     let mut closest_intersect_from_inside = normal.is_from_inside(curve_dir);
     for point in intersection_points.iter() {
+        // The line also extends behind the point, which does not count
+        if (*point - other).dot(curve_dir) <= 0.0 {
+            continue;
+        }
         let distance = (other - *point).norm();
         if distance < closest_distance.lower_bound {
             let curve_dir = geodesic.curve.tangent(*point).unwrap();
@@ -106,3 +110,59 @@ pub fn volume_point_contains(volume: &Volume, other: Point) -> VolumePointContai
         false => VolumePointContains::Outside,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use super::*;
+    use crate::primitive_objects::volumes::cube::primitive_cube;
+
+    #[test]
+    fn test_volume_point_contains_cube() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        );
+        let contains =
+            |x: f64, y: f64, z: f64| volume_point_contains(&cube, Point::from_f64(x, y, z));
+
+        // The ray towards the first face also crosses the face behind the point, which is closer for some of the points.
+        for axis in [Point::unit_x(), Point::unit_y(), Point::unit_z()] {
+            for sign in [0.9, -0.9] {
+                let p = axis * EFloat64::from(sign);
+                assert!(matches!(
+                    volume_point_contains(&cube, p),
+                    VolumePointContains::Inside
+                ));
+                let p = axis * EFloat64::from(sign * 2.0);
+                assert!(matches!(
+                    volume_point_contains(&cube, p),
+                    VolumePointContains::Outside
+                ));
+            }
+        }
+        assert!(matches!(
+            contains(0.0, 0.0, 0.0),
+            VolumePointContains::Inside
+        ));
+        assert!(matches!(
+            contains(3.0, 2.0, 1.0),
+            VolumePointContains::Outside
+        ));
+
+        assert!(matches!(
+            contains(1.0, 0.5, 0.0),
+            VolumePointContains::OnFace(_)
+        ));
+        assert!(matches!(
+            contains(1.0, 1.0, 0.0),
+            VolumePointContains::OnEdge(_)
+        ));
+        assert!(matches!(
+            contains(1.0, 1.0, -1.0),
+            VolumePointContains::OnPoint(_)
+        ));
+    }
+}
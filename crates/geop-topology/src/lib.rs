@@ -1,10 +1,14 @@
 pub mod topology;
 
 pub mod contains;
+pub mod mass_properties;
+pub mod naming;
 pub mod operations;
 pub mod primitive_objects;
+pub mod ray_cast;
 pub mod topology_error;
 pub mod topology_scene;
+pub mod validation;
 
 // use topology::scene::Scene;
 
@@ -0,0 +1,1026 @@
+use geop_geometry::{
+    color::Category10Color,
+    curves::curve::Curve,
+    efloat::EFloat64,
+    point::Point,
+    surfaces::{hyperbolic_paraboloid::HyperbolicParaboloid, surface::Surface, SurfaceLike},
+};
+
+use crate::{
+    contains::face_point::{face_point_contains, FacePointContains},
+    topology::{contour::Contour, edge::Edge, face::Face, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
+    topology_scene::TopologyScene,
+};
+
+// Mass properties are plain f64 values. For faces bounded by lines, circles and ellipses on planes, cylinders, cones and spheres, the quadrature rules are exact, so the results are exact up to rounding.
+// Other faces are integrated adaptively and are accurate to about 1e-10 relative to the size of the object.
+#[derive(Clone, Debug)]
+pub struct FaceMassProperties {
+    pub area: f64,
+    // Integral of the position over the face.
+    pub first_moment: Point,
+    pub centroid: Point,
+}
+
+#[derive(Clone, Debug)]
+pub struct VolumeMassProperties {
+    pub volume: f64,
+    pub area: f64,
+    pub centroid: Point,
+    // Inertia tensor with respect to the centroid, for a density of 1.
+    pub inertia: [[f64; 3]; 3],
+}
+
+// The quantities that are integrated over every face. With the divergence theorem, the volume integrals are surface integrals of x * n.
+const INTEGRANDS: usize = 14;
+const AREA: usize = 0;
+const FIRST_MOMENT: usize = 1;
+const VOLUME: usize = 4;
+const VOLUME_FIRST_MOMENT: usize = 5;
+const VOLUME_SECOND_MOMENT: usize = 8;
+const VOLUME_PRODUCT_MOMENT: usize = 11;
+
+fn integrands(p: Point, n: Point) -> [f64; INTEGRANDS] {
+    let x = [p.x.to_f64(), p.y.to_f64(), p.z.to_f64()];
+    let n = [n.x.to_f64(), n.y.to_f64(), n.z.to_f64()];
+    let mut f = [0.0; INTEGRANDS];
+    f[AREA] = 1.0;
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+        f[FIRST_MOMENT + i] = x[i];
+        f[VOLUME] += x[i] * n[i] / 3.0;
+        // div(x_i^2 / 2 e_i) = x_i
+        f[VOLUME_FIRST_MOMENT + i] = x[i] * x[i] * n[i] / 2.0;
+        // div(x_i^3 / 3 e_i) = x_i^2
+        f[VOLUME_SECOND_MOMENT + i] = x[i] * x[i] * x[i] * n[i] / 3.0;
+        // div(x_i^2 x_j / 2 e_i) = x_i x_j
+        f[VOLUME_PRODUCT_MOMENT + i] = x[i] * x[i] * x[j] * n[i] / 2.0;
+    }
+    f
+}
+
+// Gauss-Legendre nodes and weights on [-1, 1].
+const GAUSS_NODES: [f64; 8] = [
+    -0.9602898564975363,
+    -0.7966664774136267,
+    -0.525532409916329,
+    -0.1834346424956498,
+    0.1834346424956498,
+    0.525532409916329,
+    0.7966664774136267,
+    0.9602898564975363,
+];
+const GAUSS_WEIGHTS: [f64; 8] = [
+    0.1012285362903763,
+    0.2223810344533745,
+    0.3137066458778873,
+    0.362683783378362,
+    0.362683783378362,
+    0.3137066458778873,
+    0.2223810344533745,
+    0.1012285362903763,
+];
+
+fn gauss_legendre(f: &dyn Fn(f64) -> [f64; INTEGRANDS], a: f64, b: f64) -> [f64; INTEGRANDS] {
+    let mut result = [0.0; INTEGRANDS];
+    let half = (b - a) / 2.0;
+    for (node, weight) in GAUSS_NODES.iter().zip(GAUSS_WEIGHTS.iter()) {
+        let value = f(a + half * (node + 1.0));
+        for k in 0..INTEGRANDS {
+            result[k] += weight * half * value[k];
+        }
+    }
+    result
+}
+
+const MAX_DEPTH: usize = 12;
+const MIN_DEPTH: usize = 2;
+
+// Splits the interval until the estimates of both halves agree with the estimate of the whole interval.
+fn gauss_legendre_adaptive(
+    f: &dyn Fn(f64) -> [f64; INTEGRANDS],
+    a: f64,
+    b: f64,
+    whole: [f64; INTEGRANDS],
+    depth: usize,
+) -> [f64; INTEGRANDS] {
+    let mid = (a + b) / 2.0;
+    let left = gauss_legendre(f, a, mid);
+    let right = gauss_legendre(f, mid, b);
+    let mut sum = [0.0; INTEGRANDS];
+    let mut error = 0.0f64;
+    let mut scale = 1.0f64;
+    for k in 0..INTEGRANDS {
+        sum[k] = left[k] + right[k];
+        error = error.max((sum[k] - whole[k]).abs());
+        scale = scale.max(sum[k].abs());
+    }
+    if depth >= MAX_DEPTH || (depth >= MIN_DEPTH && error < 1e-13 * scale) {
+        return sum;
+    }
+    let left = gauss_legendre_adaptive(f, a, mid, left, depth + 1);
+    let right = gauss_legendre_adaptive(f, mid, b, right, depth + 1);
+    let mut result = [0.0; INTEGRANDS];
+    for k in 0..INTEGRANDS {
+        result[k] = left[k] + right[k];
+    }
+    result
+}
+
+// Number of samples of the trigonometric rule. It is exact for trigonometric polynomials of degree below TRIG_SAMPLES / 2.
+const TRIG_SAMPLES: usize = 16;
+
+// Integral of a trigonometric polynomial from a to b. The Fourier coefficients are exact from equally spaced samples over one period,
+// and every term is integrated in closed form, so the interval does not have to be a full period.
+fn trig_integral(f: &dyn Fn(f64) -> [f64; INTEGRANDS], a: f64, b: f64) -> [f64; INTEGRANDS] {
+    let samples: Vec<(f64, [f64; INTEGRANDS])> = (0..TRIG_SAMPLES)
+        .map(|j| {
+            let x = 2.0 * std::f64::consts::PI * j as f64 / TRIG_SAMPLES as f64;
+            (x, f(x))
+        })
+        .collect();
+    let mut result = [0.0; INTEGRANDS];
+    for (_, value) in samples.iter() {
+        for k in 0..INTEGRANDS {
+            result[k] += value[k] / TRIG_SAMPLES as f64 * (b - a);
+        }
+    }
+    for frequency in 1..TRIG_SAMPLES / 2 {
+        let n = frequency as f64;
+        // int cos(n x) = sin(n x) / n and int sin(n x) = -cos(n x) / n
+        let sin_term = ((n * b).sin() - (n * a).sin()) / n;
+        let cos_term = -((n * b).cos() - (n * a).cos()) / n;
+        for (x, value) in samples.iter() {
+            let (cos, sin) = ((n * x).cos(), (n * x).sin());
+            for k in 0..INTEGRANDS {
+                let weight = 2.0 / TRIG_SAMPLES as f64 * value[k];
+                result[k] += weight * cos * sin_term + weight * sin * cos_term;
+            }
+        }
+    }
+    result
+}
+
+// A parameterization (u, v) of the surface. u is the angle around the axis for cylinders, cones and spheres, and v the height or latitude.
+// Hyperbolic paraboloids use the parameters of the patch, which are not orthogonal.
+enum Chart {
+    Plane {
+        basis: Point,
+        u: Point,
+        v: Point,
+        normal: Point,
+    },
+    Cylinder {
+        basis: Point,
+        axis: Point,
+        e1: Point,
+        e2: Point,
+        radius: f64,
+    },
+    Sphere {
+        basis: Point,
+        e1: Point,
+        e2: Point,
+        axis: Point,
+        radius: f64,
+    },
+    Cone {
+        basis: Point,
+        axis: Point,
+        e1: Point,
+        e2: Point,
+        slope: f64,
+    },
+    EllipticCylinder {
+        basis: Point,
+        axis: Point,
+        major_radius: Point,
+        minor_radius: Point,
+    },
+    HyperbolicParaboloid {
+        patch: HyperbolicParaboloid,
+    },
+}
+
+// Two unit vectors perpendicular to the axis, such that (e1, e2, axis) is right handed.
+fn orthonormal_frame(axis: Point) -> (Point, Point) {
+    let e1 = match Point::unit_x().cross(axis).norm_sq() > Point::unit_y().cross(axis).norm_sq() {
+        true => Point::unit_x().cross(axis).normalize().unwrap(),
+        false => Point::unit_y().cross(axis).normalize().unwrap(),
+    };
+    (e1, axis.cross(e1))
+}
+
+impl Chart {
+    // For spheres, the axis is chosen to keep the poles away from the boundary, since the chart is singular there.
+    fn new(face: &Face) -> Chart {
+        match face.surface.as_ref() {
+            Surface::Plane(plane) => {
+                let normal = plane.u_slope.cross(plane.v_slope).normalize().unwrap();
+                let u = plane.u_slope.normalize().unwrap();
+                Chart::Plane {
+                    basis: plane.basis,
+                    u,
+                    v: normal.cross(u),
+                    normal,
+                }
+            }
+            Surface::Cylinder(cylinder) => {
+                let axis = cylinder.extend_dir.normalize().unwrap();
+                let (e1, e2) = orthonormal_frame(axis);
+                Chart::Cylinder {
+                    basis: cylinder.basis,
+                    axis,
+                    e1,
+                    e2,
+                    radius: cylinder.radius.norm().to_f64(),
+                }
+            }
+            Surface::Cone(cone) => {
+                let (e1, e2) = orthonormal_frame(cone.extend_dir);
+                Chart::Cone {
+                    basis: cone.basis,
+                    axis: cone.extend_dir,
+                    e1,
+                    e2,
+                    slope: cone.slope.to_f64(),
+                }
+            }
+            Surface::EllipticCylinder(cylinder) => Chart::EllipticCylinder {
+                basis: cylinder.basis,
+                axis: cylinder.extend_dir,
+                major_radius: cylinder.major_radius,
+                minor_radius: cylinder.minor_radius,
+            },
+            Surface::HyperbolicParaboloid(patch) => Chart::HyperbolicParaboloid {
+                patch: patch.clone(),
+            },
+            Surface::Sphere(sphere) => {
+                let samples: Vec<Point> = face
+                    .all_edges()
+                    .iter()
+                    .flat_map(|edge| (0..=16).map(|i| edge.interpolate(i as f64 / 16.0)))
+                    .map(|p| (p - sphere.basis).normalize().unwrap())
+                    .collect();
+                let candidates = [
+                    Point::unit_z(),
+                    Point::unit_x(),
+                    Point::unit_y(),
+                    Point::from_f64(1.0, 2.0, 3.0).normalize().unwrap(),
+                    Point::from_f64(-3.0, 1.0, 2.0).normalize().unwrap(),
+                ];
+                let pole_distance = |axis: &Point| {
+                    samples
+                        .iter()
+                        .map(|p| 1.0 - p.dot(*axis).to_f64().abs())
+                        .fold(1.0, f64::min)
+                };
+                let axis = candidates
+                    .into_iter()
+                    .max_by(|a, b| pole_distance(a).total_cmp(&pole_distance(b)))
+                    .unwrap();
+                let (e1, e2) = orthonormal_frame(axis);
+                Chart::Sphere {
+                    basis: sphere.basis,
+                    e1,
+                    e2,
+                    axis,
+                    radius: sphere.radius.to_f64(),
+                }
+            }
+        }
+    }
+
+    fn coordinates(&self, p: Point) -> (f64, f64) {
+        match self {
+            Chart::Plane { basis, u, v, .. } => {
+                ((p - *basis).dot(*u).to_f64(), (p - *basis).dot(*v).to_f64())
+            }
+            Chart::Cylinder {
+                basis,
+                axis,
+                e1,
+                e2,
+                ..
+            }
+            | Chart::Cone {
+                basis,
+                axis,
+                e1,
+                e2,
+                ..
+            } => {
+                let d = p - *basis;
+                let theta = d.dot(*e2).to_f64().atan2(d.dot(*e1).to_f64());
+                (theta, d.dot(*axis).to_f64())
+            }
+            Chart::EllipticCylinder {
+                basis,
+                axis,
+                major_radius,
+                minor_radius,
+            } => {
+                let d = p - *basis;
+                let x = d.dot(*major_radius).to_f64() / major_radius.norm_sq().to_f64();
+                let y = d.dot(*minor_radius).to_f64() / minor_radius.norm_sq().to_f64();
+                (y.atan2(x), d.dot(*axis).to_f64())
+            }
+            Chart::HyperbolicParaboloid { patch } => {
+                let (u, v, _) = patch.coordinates(p);
+                (u.to_f64(), v.to_f64())
+            }
+            Chart::Sphere {
+                basis,
+                e1,
+                e2,
+                axis,
+                radius,
+            } => {
+                let d = p - *basis;
+                let theta = d.dot(*e2).to_f64().atan2(d.dot(*e1).to_f64());
+                let phi = (d.dot(*axis).to_f64() / radius).clamp(-1.0, 1.0).asin();
+                (theta, phi)
+            }
+        }
+    }
+
+    // Derivative of u along a curve through p with derivative dp, by the chain rule.
+    fn u_derivative(&self, p: Point, dp: Point) -> f64 {
+        // For angles around an axis, u = atan2(y, x) in the plane perpendicular to the axis.
+        let angle_derivative = |x: Point, y: Point| {
+            let d = p - self.origin();
+            let (x, y, dx, dy) = (
+                d.dot(x).to_f64(),
+                d.dot(y).to_f64(),
+                dp.dot(x).to_f64(),
+                dp.dot(y).to_f64(),
+            );
+            (x * dy - y * dx) / (x * x + y * y)
+        };
+        match self {
+            Chart::Plane { u, .. } => dp.dot(*u).to_f64(),
+            Chart::Cylinder { e1, e2, .. }
+            | Chart::Cone { e1, e2, .. }
+            | Chart::Sphere { e1, e2, .. } => angle_derivative(*e1, *e2),
+            Chart::EllipticCylinder {
+                major_radius,
+                minor_radius,
+                ..
+            } => angle_derivative(
+                *major_radius * EFloat64::from(1.0 / major_radius.norm_sq().to_f64()),
+                *minor_radius * EFloat64::from(1.0 / minor_radius.norm_sq().to_f64()),
+            ),
+            Chart::HyperbolicParaboloid { patch } => {
+                let (u, v, _) = patch.coordinates(p);
+                let (du, dv) = patch.slopes(u, v);
+                let (uu, uv, vv) = (
+                    du.dot(du).to_f64(),
+                    du.dot(dv).to_f64(),
+                    dv.dot(dv).to_f64(),
+                );
+                let (pu, pv) = (dp.dot(du).to_f64(), dp.dot(dv).to_f64());
+                (pu * vv - pv * uv) / (uu * vv - uv * uv)
+            }
+        }
+    }
+
+    fn origin(&self) -> Point {
+        match self {
+            Chart::Plane { basis, .. }
+            | Chart::Cylinder { basis, .. }
+            | Chart::Sphere { basis, .. }
+            | Chart::Cone { basis, .. }
+            | Chart::EllipticCylinder { basis, .. } => *basis,
+            Chart::HyperbolicParaboloid { patch } => {
+                patch.point_at(EFloat64::zero(), EFloat64::zero())
+            }
+        }
+    }
+
+    // Point, unit normal in the direction of d/du x d/dv, and the area element |d/du x d/dv|.
+    fn evaluate(&self, u: f64, v: f64) -> (Point, Point, f64) {
+        match self {
+            Chart::Plane {
+                basis,
+                u: u_dir,
+                v: v_dir,
+                normal,
+            } => (
+                *basis + *u_dir * EFloat64::from(u) + *v_dir * EFloat64::from(v),
+                *normal,
+                1.0,
+            ),
+            Chart::Cylinder {
+                basis,
+                axis,
+                e1,
+                e2,
+                radius,
+            } => {
+                let radial = *e1 * EFloat64::from(u.cos()) + *e2 * EFloat64::from(u.sin());
+                (
+                    *basis + *axis * EFloat64::from(v) + radial * EFloat64::from(*radius),
+                    radial,
+                    *radius,
+                )
+            }
+            Chart::Cone {
+                basis,
+                axis,
+                e1,
+                e2,
+                slope,
+            } => {
+                let radial = *e1 * EFloat64::from(u.cos()) + *e2 * EFloat64::from(u.sin());
+                let hypotenuse = (1.0 + slope * slope).sqrt();
+                (
+                    *basis + (*axis + radial * EFloat64::from(*slope)) * EFloat64::from(v),
+                    (radial - *axis * EFloat64::from(*slope)) * EFloat64::from(1.0 / hypotenuse),
+                    slope * hypotenuse * v,
+                )
+            }
+            Chart::EllipticCylinder {
+                basis,
+                axis,
+                major_radius,
+                minor_radius,
+            } => {
+                let (cos, sin) = (EFloat64::from(u.cos()), EFloat64::from(u.sin()));
+                let tangent = -sin * *major_radius + cos * *minor_radius;
+                let outwards = tangent.cross(*axis);
+                let area_element = outwards.norm().to_f64();
+                (
+                    *basis + cos * *major_radius + sin * *minor_radius + *axis * EFloat64::from(v),
+                    outwards * EFloat64::from(1.0 / area_element),
+                    area_element,
+                )
+            }
+            Chart::HyperbolicParaboloid { patch } => {
+                let (u, v) = (EFloat64::from(u), EFloat64::from(v));
+                let (du, dv) = patch.slopes(u, v);
+                let normal = du.cross(dv);
+                let area_element = normal.norm().to_f64();
+                (
+                    patch.point_at(u, v),
+                    normal * EFloat64::from(1.0 / area_element),
+                    area_element,
+                )
+            }
+            Chart::Sphere {
+                basis,
+                e1,
+                e2,
+                axis,
+                radius,
+            } => {
+                let radial = (*e1 * EFloat64::from(u.cos()) + *e2 * EFloat64::from(u.sin()))
+                    * EFloat64::from(v.cos())
+                    + *axis * EFloat64::from(v.sin());
+                (
+                    *basis + radial * EFloat64::from(*radius),
+                    radial,
+                    radius * radius * v.cos(),
+                )
+            }
+        }
+    }
+
+    // Lower bound of the integration in v. For spheres, this is the south pole, where the area element vanishes.
+    fn v_start(&self) -> f64 {
+        match self {
+            Chart::Sphere { .. } => -std::f64::consts::FRAC_PI_2,
+            _ => 0.0,
+        }
+    }
+
+    fn north_pole(&self) -> Option<Point> {
+        match self {
+            Chart::Sphere {
+                basis,
+                axis,
+                radius,
+                ..
+            } => Some(*basis + *axis * EFloat64::from(*radius)),
+            _ => None,
+        }
+    }
+}
+
+// P(u, v) = -int_{v_start}^{v} f(x(u, s)) |dx/du x dx/dv| ds. By Green's theorem, the integral of f over a region in the chart is the integral of P du along its boundary.
+fn potential(chart: &Chart, normal_sign: f64, u: f64, v: f64) -> [f64; INTEGRANDS] {
+    let v_start = chart.v_start();
+    let f = |s: f64| {
+        let (p, n, area_element) = chart.evaluate(u, s);
+        let mut value = integrands(p, n * EFloat64::from(normal_sign));
+        for value in value.iter_mut() {
+            *value *= -area_element;
+        }
+        value
+    };
+    match chart {
+        // The integrands times cos(v) are trigonometric polynomials of degree 5 in the latitude.
+        Chart::Sphere { .. } => trig_integral(&f, v_start, v),
+        // The area element is not a polynomial in v, so the interval is split into panels.
+        Chart::HyperbolicParaboloid { .. } => {
+            let panels = 4;
+            let step = (v - v_start) / panels as f64;
+            let mut result = [0.0; INTEGRANDS];
+            for i in 0..panels {
+                let a = v_start + step * i as f64;
+                let panel = gauss_legendre(&f, a, a + step);
+                for k in 0..INTEGRANDS {
+                    result[k] += panel[k];
+                }
+            }
+            result
+        }
+        // Polynomials of degree at most 4 in v, for which Gauss-Legendre with 8 nodes is exact.
+        _ => gauss_legendre(&f, v_start, v),
+    }
+}
+
+// The edge as an explicit curve, such that its derivative is known.
+enum EdgeCurve {
+    // start + t * direction for t from 0 to 1.
+    Line {
+        start: Point,
+        direction: Point,
+    },
+    // center + major * cos(t) + minor * sin(t) for t from start to end. Used for circles and ellipses.
+    Arc {
+        center: Point,
+        major: Point,
+        minor: Point,
+        start: f64,
+        end: f64,
+    },
+    // Helices and unbounded lines, which are interpolated by the edge and differentiated numerically.
+    Interpolated(Edge),
+}
+
+impl EdgeCurve {
+    fn new(edge: &Edge) -> EdgeCurve {
+        let arc = |center: Point, major: Point, minor: Point| {
+            let angle = |p: Point| {
+                let d = p - center;
+                (d.dot(minor) / minor.norm_sq())
+                    .unwrap()
+                    .to_f64()
+                    .atan2((d.dot(major) / major.norm_sq()).unwrap().to_f64())
+            };
+            let two_pi = 2.0 * std::f64::consts::PI;
+            let (start, end) = match (edge.start, edge.end) {
+                (Some(start), Some(end)) => {
+                    let (start, end) = (angle(start), angle(end));
+                    (start, if end <= start { end + two_pi } else { end })
+                }
+                (Some(p), None) | (None, Some(p)) => (angle(p), angle(p) + two_pi),
+                (None, None) => (0.0, two_pi),
+            };
+            EdgeCurve::Arc {
+                center,
+                major,
+                minor,
+                start,
+                end,
+            }
+        };
+        match (&edge.curve, edge.start, edge.end) {
+            (Curve::Line(_), Some(start), Some(end)) => EdgeCurve::Line {
+                start,
+                direction: end - start,
+            },
+            (Curve::Circle(circle), _, _) => arc(
+                circle.basis,
+                circle.radius,
+                circle.normal.cross(circle.radius),
+            ),
+            (Curve::Ellipse(ellipse), _, _) => {
+                arc(ellipse.basis, ellipse.major_radius, ellipse.minor_radius)
+            }
+            _ => EdgeCurve::Interpolated(edge.clone()),
+        }
+    }
+
+    fn domain(&self) -> (f64, f64) {
+        match self {
+            EdgeCurve::Line { .. } | EdgeCurve::Interpolated(_) => (0.0, 1.0),
+            EdgeCurve::Arc { start, end, .. } => (*start, *end),
+        }
+    }
+
+    // Point and derivative at t.
+    fn evaluate(&self, t: f64) -> (Point, Point) {
+        match self {
+            EdgeCurve::Line { start, direction } => {
+                (*start + *direction * EFloat64::from(t), *direction)
+            }
+            EdgeCurve::Arc {
+                center,
+                major,
+                minor,
+                ..
+            } => {
+                let (cos, sin) = (EFloat64::from(t.cos()), EFloat64::from(t.sin()));
+                (
+                    *center + *major * cos + *minor * sin,
+                    *minor * cos - *major * sin,
+                )
+            }
+            EdgeCurve::Interpolated(edge) => {
+                // Five point central difference, which stays inside of the edge.
+                let h = 1e-3f64.min(t / 2.0).min((1.0 - t) / 2.0);
+                let p = |offset: f64| edge.interpolate(t + offset);
+                let derivative = ((p(h) - p(-h)) * EFloat64::from(8.0)
+                    - (p(2.0 * h) - p(-2.0 * h)))
+                    / EFloat64::from(12.0 * h);
+                (edge.interpolate(t), derivative.unwrap())
+            }
+        }
+    }
+
+    // Quadrature rule that is exact for the integral of P du along the curve, if there is one.
+    fn rule(&self, chart: &Chart) -> EdgeRule {
+        match (self, chart) {
+            (EdgeCurve::Interpolated(_), _) | (_, Chart::HyperbolicParaboloid { .. }) => {
+                EdgeRule::Adaptive
+            }
+            // P is a polynomial of degree 4 and du / dt is constant.
+            (EdgeCurve::Line { .. }, Chart::Plane { .. }) => EdgeRule::Polynomial,
+            // Lines on cylinders and cones run along the axis or through the apex, so u is constant.
+            (EdgeCurve::Line { .. }, _) => EdgeRule::Zero,
+            // u and v are trigonometric polynomials of degree 1 in t.
+            (EdgeCurve::Arc { .. }, Chart::Plane { .. }) => EdgeRule::Trigonometric,
+            (
+                EdgeCurve::Arc {
+                    center,
+                    major,
+                    minor,
+                    ..
+                },
+                Chart::Cylinder { basis, e1, e2, .. }
+                | Chart::Cone { basis, e1, e2, .. }
+                | Chart::Sphere { basis, e1, e2, .. },
+            ) => {
+                let project = |d: Point| (d.dot(*e1).to_f64(), d.dot(*e2).to_f64());
+                let (cx, cy) = project(*center - *basis);
+                let (ax, ay) = project(*major);
+                let (bx, by) = project(*minor);
+                let tolerance = 1e-10 * (ax * ax + ay * ay).max(bx * bx + by * by);
+                if cx * cx + cy * cy > tolerance {
+                    return EdgeRule::Adaptive;
+                }
+                // An arc in a plane through the axis, e.g. a meridian of a sphere.
+                if (ax * by - ay * bx).abs() <= tolerance {
+                    return EdgeRule::Zero;
+                }
+                // An arc that winds around the axis, e.g. a circle of latitude or a cut of a cylinder. u = +-t plus a constant.
+                if (ax * bx + ay * by).abs() <= tolerance
+                    && (ax * ax + ay * ay - bx * bx - by * by).abs() <= tolerance
+                {
+                    return EdgeRule::Trigonometric;
+                }
+                EdgeRule::Adaptive
+            }
+            // The area element of elliptic cylinders is not a trigonometric polynomial.
+            (EdgeCurve::Arc { .. }, Chart::EllipticCylinder { .. }) => EdgeRule::Adaptive,
+        }
+    }
+}
+
+enum EdgeRule {
+    // u is constant along the edge.
+    Zero,
+    // The integrand is a polynomial of degree at most 15 in t.
+    Polynomial,
+    // The integrand is a trigonometric polynomial of degree below TRIG_SAMPLES / 2 in t.
+    Trigonometric,
+    Adaptive,
+}
+
+// Integral of P du along the edge.
+fn edge_integral(chart: &Chart, normal_sign: f64, edge: &Edge) -> [f64; INTEGRANDS] {
+    let curve = EdgeCurve::new(edge);
+    let f = |t: f64| {
+        let (p, dp) = curve.evaluate(t);
+        let (u, v) = chart.coordinates(p);
+        let du_dt = chart.u_derivative(p, dp);
+        let mut value = potential(chart, normal_sign, u, v);
+        for value in value.iter_mut() {
+            *value *= du_dt;
+        }
+        value
+    };
+    let (a, b) = curve.domain();
+    match curve.rule(chart) {
+        EdgeRule::Zero => [0.0; INTEGRANDS],
+        EdgeRule::Polynomial => gauss_legendre(&f, a, b),
+        EdgeRule::Trigonometric => trig_integral(&f, a, b),
+        EdgeRule::Adaptive => {
+            let whole = gauss_legendre(&f, a, b);
+            gauss_legendre_adaptive(&f, a, b, whole, 0)
+        }
+    }
+}
+
+// The boundary runs counter-clockwise around the face, seen from the face normal. Where the normal of the chart points the other way, the orientation flips.
+fn normal_sign(face: &Face, chart: &Chart) -> f64 {
+    // Away from the apex of cones, where the normal is not defined.
+    let (point, chart_normal, _) = chart.evaluate(0.0, 1.0);
+    let face_normal = match face.surface.as_ref() {
+        Surface::Plane(plane) => plane.u_slope.cross(plane.v_slope),
+        surface => surface.normal(point),
+    };
+    match chart_normal.dot(face_normal) > 0.0 {
+        true => 1.0,
+        false => -1.0,
+    }
+}
+
+// Area enclosed by a single contour of the face, which is positive if the contour runs counter-clockwise. Only meaningful for planar faces.
+pub(crate) fn contour_signed_area(face: &Face, contour: &Contour) -> f64 {
+    let chart = Chart::new(face);
+    let normal_sign = normal_sign(face, &chart);
+    contour
+        .edges
+        .iter()
+        .map(|edge| normal_sign * edge_integral(&chart, normal_sign, edge)[AREA])
+        .sum()
+}
+
+fn face_integrals(face: &Face) -> [f64; INTEGRANDS] {
+    let chart = Chart::new(face);
+    let normal_sign = normal_sign(face, &chart);
+
+    let mut result = [0.0; INTEGRANDS];
+    for edge in face.all_edges() {
+        let integral = edge_integral(&chart, normal_sign, &edge);
+        for k in 0..INTEGRANDS {
+            result[k] += normal_sign * integral[k];
+        }
+    }
+
+    // P does not vanish at the north pole. If the face contains it, the boundary integral misses -int P(u, pi / 2) du, which is the integral over the whole sphere.
+    if let Some(pole) = chart.north_pole() {
+        match face_point_contains(face, pole) {
+            FacePointContains::Inside
+            | FacePointContains::OnEdge(_)
+            | FacePointContains::OnPoint(_) => {
+                let f = |u: f64| potential(&chart, normal_sign, u, std::f64::consts::FRAC_PI_2);
+                let value = trig_integral(&f, 0.0, 2.0 * std::f64::consts::PI);
+                for k in 0..INTEGRANDS {
+                    result[k] -= value[k];
+                }
+            }
+            FacePointContains::Outside | FacePointContains::NotOnSurface => {}
+        }
+    }
+    result
+}
+
+// Area and first moments of a face, computed from its boundary with Green's theorem in the parameter space of the surface.
+pub fn face_mass_properties(face: &Face) -> TopologyResult<FaceMassProperties> {
+    let integrals = face_integrals(face);
+    let area = integrals[AREA];
+    let first_moment = Point::from_f64(
+        integrals[FIRST_MOMENT],
+        integrals[FIRST_MOMENT + 1],
+        integrals[FIRST_MOMENT + 2],
+    );
+    if area <= 0.0 {
+        return Err(TopologyError::new(format!(
+            "Face has an area of {}, so it has no centroid",
+            area
+        ))
+        .with_context_scene(
+            "Compute mass properties of face".to_string(),
+            TopologyScene::with_faces(vec![(face.clone(), Category10Color::Orange)]),
+        ));
+    }
+    Ok(FaceMassProperties {
+        area,
+        first_moment,
+        centroid: Point::from_f64(
+            integrals[FIRST_MOMENT] / area,
+            integrals[FIRST_MOMENT + 1] / area,
+            integrals[FIRST_MOMENT + 2] / area,
+        ),
+    })
+}
+
+pub fn face_area(face: &Face) -> f64 {
+    face_integrals(face)[AREA]
+}
+
+// Volume, area, centroid and inertia tensor of a volume with a density of 1.
+// The volume integrals are converted to surface integrals over the faces of the boundary and the cavities with the divergence theorem.
+pub fn volume_mass_properties(volume: &Volume) -> TopologyResult<VolumeMassProperties> {
+    let mut integrals = [0.0; INTEGRANDS];
+    for face in volume.all_faces().iter() {
+        let face_integrals = face_integrals(face);
+        for k in 0..INTEGRANDS {
+            integrals[k] += face_integrals[k];
+        }
+    }
+
+    let size = integrals[VOLUME];
+    if size <= 0.0 {
+        return Err(TopologyError::new(format!(
+            "Volume has a size of {}, so it has no centroid. Check that the normals point outwards.",
+            size
+        ))
+        .with_context_scene(
+            "Compute mass properties of volume".to_string(),
+            TopologyScene::with_volumes(vec![(volume.clone(), Category10Color::Orange)]),
+        ));
+    }
+
+    let centroid = [
+        integrals[VOLUME_FIRST_MOMENT] / size,
+        integrals[VOLUME_FIRST_MOMENT + 1] / size,
+        integrals[VOLUME_FIRST_MOMENT + 2] / size,
+    ];
+    // Second moments with respect to the centroid, by the parallel axis theorem.
+    let mut second_moment = [[0.0; 3]; 3];
+    for i in 0..3 {
+        let j = (i + 1) % 3;
+        second_moment[i][i] =
+            integrals[VOLUME_SECOND_MOMENT + i] - size * centroid[i] * centroid[i];
+        second_moment[i][j] =
+            integrals[VOLUME_PRODUCT_MOMENT + i] - size * centroid[i] * centroid[j];
+        second_moment[j][i] = second_moment[i][j];
+    }
+    let trace = second_moment[0][0] + second_moment[1][1] + second_moment[2][2];
+    let mut inertia = [[0.0; 3]; 3];
+    for i in 0..3 {
+        for j in 0..3 {
+            inertia[i][j] = match i == j {
+                true => trace - second_moment[i][i],
+                false => -second_moment[i][j],
+            };
+        }
+    }
+
+    Ok(VolumeMassProperties {
+        volume: size,
+        area: integrals[AREA],
+        centroid: Point::from_f64(centroid[0], centroid[1], centroid[2]),
+        inertia,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{curves::circle::Circle, surfaces::plane::Plane, transforms::Transform};
+
+    use super::*;
+    use crate::{
+        operations::extrude::extrude,
+        primitive_objects::{
+            edges::{circle::primitive_circle, line::primitive_line},
+            faces::sphere::primitive_sphere,
+            volumes::cube::primitive_cube,
+        },
+        topology::contour::Contour,
+    };
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() < 1e-8, "{} != {}", a, b);
+    }
+
+    #[test]
+    fn test_cube_mass_properties() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(4.0),
+            EFloat64::from(6.0),
+        )
+        .transform(Transform::from_translation(Point::from_f64(1.0, 2.0, 3.0)));
+        let properties = volume_mass_properties(&cube).unwrap();
+        assert_close(properties.volume, 48.0);
+        assert_close(properties.area, 88.0);
+        assert_close(properties.centroid.x.to_f64(), 1.0);
+        assert_close(properties.centroid.y.to_f64(), 2.0);
+        assert_close(properties.centroid.z.to_f64(), 3.0);
+        // I_xx = m (b^2 + c^2) / 12
+        assert_close(properties.inertia[0][0], 48.0 * (16.0 + 36.0) / 12.0);
+        assert_close(properties.inertia[1][1], 48.0 * (4.0 + 36.0) / 12.0);
+        assert_close(properties.inertia[2][2], 48.0 * (4.0 + 16.0) / 12.0);
+        assert_close(properties.inertia[0][1], 0.0);
+
+        let face = face_mass_properties(&cube.boundary.faces[0]).unwrap();
+        assert!(face.area > 0.0);
+    }
+
+    #[test]
+    fn test_cylinder_and_sphere_mass_properties() {
+        // The bottom face points downwards, out of the extruded volume.
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::from(1.5));
+        let disk = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let properties = volume_mass_properties(&cylinder).unwrap();
+        let pi = std::f64::consts::PI;
+        assert_close(properties.volume, pi * 1.5 * 1.5 * 2.0);
+        assert_close(properties.area, 2.0 * pi * 1.5 * 1.5 + 2.0 * pi * 1.5 * 2.0);
+        assert_close(properties.centroid.z.to_f64(), 1.0);
+        // I_zz = m r^2 / 2
+        assert_close(
+            properties.inertia[2][2],
+            properties.volume * 1.5 * 1.5 / 2.0,
+        );
+
+        let sphere = primitive_sphere(Point::from_f64(1.0, 0.0, 0.0), EFloat64::from(2.0));
+        let face = face_mass_properties(&sphere).unwrap();
+        assert_close(face.area, 4.0 * pi * 4.0);
+        assert_close(face.centroid.x.to_f64(), 1.0);
+
+        // Spherical caps above and below z = 3. The upper one contains the north pole.
+        let sphere = primitive_sphere(Point::zero(), EFloat64::from(5.0));
+        for (normal, area, centroid_z) in [(1.0, 20.0 * pi, 4.0), (-1.0, 80.0 * pi, -1.0)] {
+            let circle = primitive_circle(
+                Point::from_f64(0.0, 0.0, 3.0),
+                Point::from_f64(0.0, 0.0, normal),
+                EFloat64::from(4.0),
+            );
+            let cap = Face::new(vec![Contour::new(vec![circle])], sphere.surface.clone());
+            let properties = face_mass_properties(&cap).unwrap();
+            assert_close(properties.area, area);
+            assert_close(properties.centroid.z.to_f64(), centroid_z);
+        }
+    }
+
+    #[test]
+    fn test_mass_properties_are_exact_for_lines_and_circles() {
+        let pi = std::f64::consts::PI;
+        let assert_exact = |a: f64, b: f64| {
+            assert!((a - b).abs() < 1e-13 * b.abs().max(1.0), "{} != {}", a, b);
+        };
+
+        // Half disk of radius 2 above the x-axis.
+        let arc = Edge::new(
+            Some(Point::from_f64(2.0, 0.0, 0.0)),
+            Some(Point::from_f64(-2.0, 0.0, 0.0)),
+            Curve::Circle(
+                Circle::try_new(Point::zero(), Point::unit_z(), EFloat64::two()).unwrap(),
+            ),
+        );
+        let line = primitive_line(
+            Point::from_f64(-2.0, 0.0, 0.0),
+            Point::from_f64(2.0, 0.0, 0.0),
+        )
+        .unwrap();
+        let face = Face::new(
+            vec![Contour::new(vec![arc, line])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_x(),
+                Point::unit_y(),
+            ))),
+        );
+        let properties = face_mass_properties(&face).unwrap();
+        assert_exact(properties.area, 2.0 * pi);
+        assert_exact(properties.centroid.y.to_f64(), 8.0 / (3.0 * pi));
+
+        // Cylinder of radius 1.5 and height 2. I_xx = m (3 r^2 + h^2) / 12
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::from(1.5));
+        let disk = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let properties = volume_mass_properties(&cylinder).unwrap();
+        let mass = pi * 1.5 * 1.5 * 2.0;
+        assert_exact(properties.volume, mass);
+        assert_exact(
+            properties.inertia[0][0],
+            mass * (3.0 * 1.5 * 1.5 + 4.0) / 12.0,
+        );
+        assert_exact(properties.inertia[2][2], mass * 1.5 * 1.5 / 2.0);
+
+        // Spherical cap above z = 3 on a sphere of radius 5.
+        let sphere = primitive_sphere(Point::zero(), EFloat64::from(5.0));
+        let circle = primitive_circle(
+            Point::from_f64(0.0, 0.0, 3.0),
+            Point::unit_z(),
+            EFloat64::from(4.0),
+        );
+        let cap = Face::new(vec![Contour::new(vec![circle])], sphere.surface.clone());
+        let properties = face_mass_properties(&cap).unwrap();
+        assert_exact(properties.area, 20.0 * pi);
+        assert_exact(properties.centroid.z.to_f64(), 4.0);
+    }
+}
@@ -0,0 +1,529 @@
+use std::fmt::Display;
+
+use geop_geometry::curves::CurveLike;
+
+use crate::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        face_point::{face_point_contains, FacePointContains},
+    },
+    topology::{edge::Edge, face::Face, store::TopologyStore, volume::Volume},
+};
+
+// Stable name of a face or an edge. Names are derived from the name of the operation and the names of the inputs,
+// so regenerating a model with the same operations gives the same names, e.g. "extrude1/end/sketch" for the top face of an extrusion.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EntityId(pub String);
+
+impl EntityId {
+    pub fn new(name: &str) -> EntityId {
+        EntityId(name.to_string())
+    }
+
+    pub fn child(&self, role: &str) -> EntityId {
+        EntityId(format!("{}/{}", self.0, role))
+    }
+}
+
+impl Display for EntityId {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryKind {
+    // New entities, e.g. the side faces of an extrusion, which are generated from the edges of the profile.
+    Generated,
+    // A single input became a single output with the same name, but possibly different geometry.
+    Modified,
+    // A single input became several outputs.
+    Split,
+    // Several inputs became a single output, which keeps the name of the first input.
+    Merged,
+    // The input has no output.
+    Deleted,
+}
+
+#[derive(Clone, Debug)]
+pub struct HistoryRecord {
+    pub kind: HistoryKind,
+    pub inputs: Vec<EntityId>,
+    pub outputs: Vec<EntityId>,
+}
+
+// Records what every operation did to the faces and edges, in the order of the operations.
+#[derive(Clone, Debug, Default)]
+pub struct History {
+    pub records: Vec<HistoryRecord>,
+}
+
+impl History {
+    pub fn new() -> History {
+        History {
+            records: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, kind: HistoryKind, inputs: Vec<EntityId>, outputs: Vec<EntityId>) {
+        self.records.push(HistoryRecord {
+            kind,
+            inputs,
+            outputs,
+        });
+    }
+
+    // Appends the history of a later operation.
+    pub fn extend(&mut self, other: History) {
+        self.records.extend(other.records);
+    }
+
+    // Direct outputs of the input in any record.
+    pub fn outputs(&self, input: &EntityId) -> Vec<EntityId> {
+        let mut outputs = Vec::<EntityId>::new();
+        for record in self.records.iter().filter(|r| r.inputs.contains(input)) {
+            for output in record.outputs.iter() {
+                if !outputs.contains(output) {
+                    outputs.push(output.clone());
+                }
+            }
+        }
+        outputs
+    }
+
+    // Direct inputs of the output in any record.
+    pub fn inputs(&self, output: &EntityId) -> Vec<EntityId> {
+        let mut inputs = Vec::<EntityId>::new();
+        for record in self.records.iter().filter(|r| r.outputs.contains(output)) {
+            for input in record.inputs.iter() {
+                if !inputs.contains(input) {
+                    inputs.push(input.clone());
+                }
+            }
+        }
+        inputs
+    }
+
+    pub fn kind(&self, output: &EntityId) -> Option<HistoryKind> {
+        self.records
+            .iter()
+            .rev()
+            .find(|r| r.outputs.contains(output))
+            .map(|r| r.kind)
+    }
+
+    pub fn is_deleted(&self, input: &EntityId) -> bool {
+        self.records
+            .iter()
+            .any(|r| r.kind == HistoryKind::Deleted && r.inputs.contains(input))
+    }
+
+    // Follows the entity through all records, in order. Returns the entities it ended up as, which is empty if it was deleted.
+    pub fn trace(&self, input: &EntityId) -> Vec<EntityId> {
+        let mut current = vec![input.clone()];
+        for record in self.records.iter() {
+            if !record.inputs.iter().any(|i| current.contains(i)) {
+                continue;
+            }
+            current.retain(|c| !record.inputs.contains(c) || record.outputs.contains(c));
+            for output in record.outputs.iter() {
+                if !current.contains(output) {
+                    current.push(output.clone());
+                }
+            }
+        }
+        current
+    }
+}
+
+// A face together with the names of the face and its edges.
+#[derive(Clone, Debug)]
+pub struct NamedFace {
+    pub id: EntityId,
+    pub face: Face,
+    // One entry per edge of Face::all_edges(), in the same order.
+    pub edge_ids: Vec<EntityId>,
+}
+
+impl NamedFace {
+    // Names the edges by their position, e.g. "sketch/edge0".
+    pub fn new(id: EntityId, face: Face) -> NamedFace {
+        let edge_ids = (0..face.all_edges().len())
+            .map(|i| id.child(&format!("edge{}", i)))
+            .collect();
+        NamedFace { id, face, edge_ids }
+    }
+
+    // Finds the name of the edge, regardless of its direction.
+    pub fn edge_id(&self, edge: &Edge) -> Option<&EntityId> {
+        self.face
+            .all_edges()
+            .iter()
+            .position(|e| e.same_orientation(edge) || e.opposite_orientation(edge))
+            .map(|i| &self.edge_ids[i])
+    }
+}
+
+// A volume together with the names of its faces and edges. Adjacent faces use the same name for the edge they share.
+#[derive(Clone, Debug)]
+pub struct NamedVolume {
+    pub volume: Volume,
+    // One entry per face of Volume::all_faces(), in the same order.
+    pub faces: Vec<NamedFace>,
+}
+
+impl NamedVolume {
+    // Names faces and edges by their position, e.g. "body/face0" and "body/edge3".
+    pub fn new(id: EntityId, volume: Volume) -> NamedVolume {
+        let store = TopologyStore::from_volume(&volume);
+        let faces = volume
+            .all_faces()
+            .into_iter()
+            .enumerate()
+            .map(|(i, face)| {
+                let edge_ids = face
+                    .all_edges()
+                    .iter()
+                    .map(|e| id.child(&format!("edge{}", store.find_edge(e).unwrap().0)))
+                    .collect();
+                NamedFace {
+                    id: id.child(&format!("face{}", i)),
+                    face,
+                    edge_ids,
+                }
+            })
+            .collect();
+        NamedVolume { volume, faces }
+    }
+
+    pub fn face(&self, id: &EntityId) -> Option<&NamedFace> {
+        self.faces.iter().find(|f| f.id == *id)
+    }
+
+    pub fn edge(&self, id: &EntityId) -> Option<Edge> {
+        self.faces.iter().find_map(|f| {
+            f.edge_ids
+                .iter()
+                .position(|e| e == id)
+                .map(|i| f.face.all_edges()[i].clone())
+        })
+    }
+}
+
+// Checks that the names identify the faces and edges. Every face has its own name, and two edges have the same name exactly if they are the same edge.
+// Returns the first name that breaks this.
+pub fn find_duplicate_id(faces: &[NamedFace]) -> Option<EntityId> {
+    let mut face_ids = Vec::<&EntityId>::new();
+    let mut edges = Vec::<(&EntityId, Edge)>::new();
+    for face in faces.iter() {
+        if face_ids.contains(&&face.id) {
+            return Some(face.id.clone());
+        }
+        face_ids.push(&face.id);
+        for (edge, id) in face.face.all_edges().into_iter().zip(face.edge_ids.iter()) {
+            for (other_id, other) in edges.iter() {
+                let same_edge = other.same_orientation(&edge) || other.opposite_orientation(&edge);
+                if same_edge != (*other_id == id) {
+                    return Some(id.clone());
+                }
+            }
+            edges.push((id, edge));
+        }
+    }
+    None
+}
+
+fn overlaps(a: &Face, b: &Face) -> bool {
+    face_point_contains(a, b.inner_point()) == FacePointContains::Inside
+        || face_point_contains(b, a.inner_point()) == FacePointContains::Inside
+}
+
+// Names the outputs of an operation that cuts and glues faces, like the booleans, and records the history.
+// Output faces are traced back to the input faces that they overlap with, and output edges to the input edges that they are part of.
+pub fn trace_faces(
+    operation: &EntityId,
+    inputs: &[&NamedFace],
+    outputs: Vec<Face>,
+) -> (Vec<NamedFace>, History) {
+    let mut history = History::new();
+
+    let origins: Vec<Vec<usize>> = outputs
+        .iter()
+        .map(|output| {
+            (0..inputs.len())
+                .filter(|i| overlaps(&inputs[*i].face, output))
+                .collect()
+        })
+        .collect();
+
+    let mut face_ids = vec![None::<EntityId>; outputs.len()];
+    for (i, input) in inputs.iter().enumerate() {
+        let own: Vec<usize> = (0..outputs.len())
+            .filter(|o| origins[*o] == vec![i])
+            .collect();
+        let merged = (0..outputs.len()).any(|o| origins[o].len() > 1 && origins[o].contains(&i));
+        match (own.len(), merged) {
+            (0, false) => history.record(HistoryKind::Deleted, vec![input.id.clone()], vec![]),
+            (0, true) => {}
+            (1, false) => {
+                face_ids[own[0]] = Some(input.id.clone());
+                history.record(
+                    HistoryKind::Modified,
+                    vec![input.id.clone()],
+                    vec![input.id.clone()],
+                );
+            }
+            _ => {
+                let ids: Vec<EntityId> = own
+                    .iter()
+                    .enumerate()
+                    .map(|(k, o)| {
+                        let id = input.id.child(&format!("split{}", k));
+                        face_ids[*o] = Some(id.clone());
+                        id
+                    })
+                    .collect();
+                history.record(HistoryKind::Split, vec![input.id.clone()], ids);
+            }
+        }
+    }
+    for (o, origin) in origins.iter().enumerate() {
+        if origin.len() > 1 {
+            let id = inputs[origin[0]].id.clone();
+            face_ids[o] = Some(id.clone());
+            history.record(
+                HistoryKind::Merged,
+                origin.iter().map(|i| inputs[*i].id.clone()).collect(),
+                vec![id],
+            );
+        } else if origin.is_empty() {
+            let id = operation.child(&format!("face{}", o));
+            face_ids[o] = Some(id.clone());
+            history.record(HistoryKind::Generated, vec![], vec![id]);
+        }
+    }
+
+    // Edges keep the name of the input edge, or are split from it, or are new.
+    let input_edges: Vec<(Edge, EntityId)> = inputs
+        .iter()
+        .flat_map(|f| {
+            f.face
+                .all_edges()
+                .into_iter()
+                .zip(f.edge_ids.iter().cloned())
+        })
+        .collect();
+    let mut kept = Vec::<EntityId>::new();
+    let mut splits = Vec::<(EntityId, Vec<Edge>)>::new();
+    let mut generated = Vec::<Edge>::new();
+    let named: Vec<NamedFace> = outputs
+        .into_iter()
+        .zip(face_ids)
+        .map(|(face, id)| {
+            let id = id.expect("Every output face is named");
+            let edge_ids = face
+                .all_edges()
+                .iter()
+                .map(|edge| {
+                    if let Some((_, edge_id)) = input_edges
+                        .iter()
+                        .find(|(e, _)| e.same_orientation(edge) || e.opposite_orientation(edge))
+                    {
+                        if !kept.contains(edge_id) {
+                            kept.push(edge_id.clone());
+                        }
+                        return edge_id.clone();
+                    }
+                    let parent = input_edges.iter().find(|(e, _)| {
+                        (e.curve == edge.curve || e.curve == edge.curve.neg())
+                            && edge_point_contains(e, edge.get_midpoint())
+                                == EdgePointContains::Inside
+                    });
+                    match parent {
+                        Some((_, parent_id)) => {
+                            let pieces = match splits.iter().position(|(p, _)| p == parent_id) {
+                                Some(i) => &mut splits[i].1,
+                                None => {
+                                    splits.push((parent_id.clone(), Vec::new()));
+                                    &mut splits.last_mut().unwrap().1
+                                }
+                            };
+                            let k = match pieces.iter().position(|p| {
+                                p.same_orientation(edge) || p.opposite_orientation(edge)
+                            }) {
+                                Some(k) => k,
+                                None => {
+                                    pieces.push(edge.clone());
+                                    pieces.len() - 1
+                                }
+                            };
+                            parent_id.child(&format!("split{}", k))
+                        }
+                        None => {
+                            let k = match generated.iter().position(|p| {
+                                p.same_orientation(edge) || p.opposite_orientation(edge)
+                            }) {
+                                Some(k) => k,
+                                None => {
+                                    generated.push(edge.clone());
+                                    generated.len() - 1
+                                }
+                            };
+                            operation.child(&format!("edge{}", k))
+                        }
+                    }
+                })
+                .collect();
+            NamedFace { id, face, edge_ids }
+        })
+        .collect();
+
+    for (parent, pieces) in splits.iter() {
+        history.record(
+            HistoryKind::Split,
+            vec![parent.clone()],
+            (0..pieces.len())
+                .map(|k| parent.child(&format!("split{}", k)))
+                .collect(),
+        );
+    }
+    for k in 0..generated.len() {
+        history.record(
+            HistoryKind::Generated,
+            vec![],
+            vec![operation.child(&format!("edge{}", k))],
+        );
+    }
+    for (_, edge_id) in input_edges.iter() {
+        if !kept.contains(edge_id) && !splits.iter().any(|(p, _)| p == edge_id) {
+            history.record(HistoryKind::Deleted, vec![edge_id.clone()], vec![]);
+        }
+    }
+
+    (named, history)
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::point::Point;
+
+    use crate::primitive_objects::faces::rectangle::primitive_rectangle;
+
+    use super::*;
+
+    // The rectangle [x0, x1] x [0, 1] in the xy plane.
+    fn strip(x0: f64, x1: f64) -> Face {
+        primitive_rectangle(
+            Point::from_f64((x0 + x1) / 2.0, 0.5, 0.0),
+            Point::from_f64((x1 - x0) / 2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 0.5, 0.0),
+        )
+    }
+
+    #[test]
+    fn test_history_trace() {
+        let a = EntityId::new("a");
+        let b = EntityId::new("b");
+        let mut history = History::new();
+        history.record(
+            HistoryKind::Split,
+            vec![a.clone()],
+            vec![a.child("split0"), a.child("split1")],
+        );
+        history.record(
+            HistoryKind::Merged,
+            vec![a.child("split1"), b.clone()],
+            vec![a.child("split1")],
+        );
+        history.record(HistoryKind::Deleted, vec![a.child("split0")], vec![]);
+
+        assert_eq!(history.trace(&a), vec![a.child("split1")]);
+        assert_eq!(history.trace(&b), vec![a.child("split1")]);
+        assert_eq!(history.kind(&a.child("split1")), Some(HistoryKind::Merged));
+        assert!(history.is_deleted(&a.child("split0")));
+        assert_eq!(history.inputs(&a.child("split0")), vec![a.clone()]);
+    }
+
+    #[test]
+    fn test_trace_faces_split() {
+        let a = NamedFace::new(EntityId::new("a"), strip(0.0, 2.0));
+        let b = NamedFace::new(EntityId::new("b"), strip(3.0, 4.0));
+        let operation = EntityId::new("cut");
+        let (named, history) = trace_faces(
+            &operation,
+            &[&a, &b],
+            vec![strip(0.0, 1.0), strip(1.0, 2.0)],
+        );
+        assert_eq!(find_duplicate_id(&named), None);
+
+        assert_eq!(named[0].id, EntityId::new("a/split0"));
+        assert_eq!(named[1].id, EntityId::new("a/split1"));
+        assert_eq!(history.kind(&named[0].id), Some(HistoryKind::Split));
+        assert_eq!(
+            history.trace(&a.id),
+            vec![named[0].id.clone(), named[1].id.clone()]
+        );
+        assert!(history.is_deleted(&b.id));
+        assert!(b.edge_ids.iter().all(|id| history.is_deleted(id)));
+
+        // The left edge is kept, the top edge is split in two and the cut in the middle is a new edge, which both faces share
+        assert!(named[0].edge_ids.contains(&EntityId::new("a/edge1")));
+        assert_eq!(
+            history.trace(&EntityId::new("a/edge0")),
+            vec![
+                EntityId::new("a/edge0/split0"),
+                EntityId::new("a/edge0/split1")
+            ]
+        );
+        let cut = EntityId::new("cut/edge0");
+        assert!(named[0].edge_ids.contains(&cut) && named[1].edge_ids.contains(&cut));
+        assert_eq!(history.kind(&cut), Some(HistoryKind::Generated));
+    }
+
+    #[test]
+    fn test_trace_faces_merge() {
+        let a = NamedFace::new(EntityId::new("a"), strip(0.0, 2.0));
+        let b = NamedFace::new(EntityId::new("b"), strip(1.0, 3.0));
+        let (named, history) =
+            trace_faces(&EntityId::new("union"), &[&a, &b], vec![strip(0.0, 3.0)]);
+        assert_eq!(find_duplicate_id(&named), None);
+
+        // The merged face keeps the name of the first input
+        assert_eq!(named.len(), 1);
+        assert_eq!(named[0].id, a.id);
+        assert_eq!(history.kind(&a.id), Some(HistoryKind::Merged));
+        assert_eq!(history.trace(&b.id), vec![a.id.clone()]);
+        assert_eq!(history.inputs(&a.id), vec![a.id.clone(), b.id.clone()]);
+
+        // The outer edges are kept, the edges inside the union are deleted
+        assert!(named[0].edge_ids.contains(&EntityId::new("a/edge1")));
+        assert!(named[0].edge_ids.contains(&EntityId::new("b/edge3")));
+        assert!(history.is_deleted(&EntityId::new("a/edge3")));
+        assert!(history.is_deleted(&EntityId::new("b/edge1")));
+    }
+
+    #[test]
+    fn test_find_duplicate_id() {
+        let a = NamedFace::new(EntityId::new("a"), strip(0.0, 1.0));
+        let b = NamedFace::new(EntityId::new("b"), strip(1.0, 2.0));
+        // The shared edge has a different name in each face
+        assert_eq!(find_duplicate_id(std::slice::from_ref(&a)), None);
+        assert_eq!(
+            find_duplicate_id(&[a.clone(), b.clone()]),
+            Some(EntityId::new("b/edge1"))
+        );
+        assert_eq!(
+            find_duplicate_id(&[a.clone(), a.clone()]),
+            Some(a.id.clone())
+        );
+
+        let mut renamed = b.clone();
+        renamed.edge_ids[1] = EntityId::new("a/edge3");
+        assert_eq!(find_duplicate_id(&[a.clone(), renamed.clone()]), None);
+        renamed.edge_ids[0] = EntityId::new("a/edge0");
+        assert_eq!(
+            find_duplicate_id(&[a, renamed]),
+            Some(EntityId::new("a/edge0"))
+        );
+    }
+}
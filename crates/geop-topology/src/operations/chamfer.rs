@@ -0,0 +1,322 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::curve::Curve,
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{plane::Plane, surface::Surface},
+};
+
+use crate::{
+    operations::edge_trim::{planar_normals, EdgeTrim},
+    primitive_objects::edges::line::primitive_line,
+    topology::{
+        contour::Contour,
+        edge::Edge,
+        face::Face,
+        store::{CoedgeId, EdgeId, FaceId, TopologyStore, VertexId},
+        volume::Volume,
+    },
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+// Intersection of two lines in the same plane, given by a point and a direction.
+fn line_line_point(p1: Point, t1: Point, p2: Point, t2: Point) -> TopologyResult<Point> {
+    let n = t1.cross(t2);
+    let s = ((p2 - p1).cross(t2).dot(n) / n.norm_sq()).map_err(GeometryError::from)?;
+    Ok(p1 + t1 * s)
+}
+
+// Planar face with the points in order. The normal points to the side of outwards.
+fn planar_face(points: &[Point], outwards: Point) -> TopologyResult<Face> {
+    let mut points = points.to_vec();
+    let normal = (points[1] - points[0]).cross(points[2] - points[0]);
+    if normal.dot(outwards) < 0.0 {
+        points.reverse();
+    }
+    let n = points.len();
+    let edges = (0..n)
+        .map(|i| primitive_line(points[i], points[(i + 1) % n]))
+        .collect::<TopologyResult<Vec<Edge>>>()?;
+    let plane = Plane::new(points[0], points[1] - points[0], points[n - 1] - points[0]);
+    Ok(Face::new(
+        vec![Contour::new(edges)],
+        Rc::new(Surface::Plane(plane)),
+    ))
+}
+
+struct Chamfer {
+    store: TopologyStore,
+    // Chamfered edges with the distance on the face of the first and the second coedge.
+    chamfered: Vec<(EdgeId, EFloat64, EFloat64)>,
+    normals: Vec<Point>,
+    // Where the corner of every face is moved to, for vertices that touch a chamfered edge.
+    corners: Vec<Vec<(FaceId, Point)>>,
+}
+
+impl Chamfer {
+    fn new(
+        volume: &Volume,
+        edges: &[Edge],
+        distance1: EFloat64,
+        distance2: EFloat64,
+    ) -> TopologyResult<Chamfer> {
+        let store = TopologyStore::from_volume(volume);
+
+        let normals = planar_normals(&store, "Chamfer")?;
+
+        let mut chamfered = Vec::<(EdgeId, EFloat64, EFloat64)>::new();
+        for edge in edges.iter() {
+            let edge_id = store.find_edge(edge).ok_or_else(|| {
+                TopologyError::new(format!("Edge {} is not part of the volume", edge))
+            })?;
+            let data = store.edge(edge_id);
+            if !matches!(data.edge.curve, Curve::Line(_)) || data.coedges.len() != 2 {
+                return Err(TopologyError::new(format!(
+                    "Chamfer only supports line edges between two faces, but got {}",
+                    edge
+                )));
+            }
+            if chamfered.iter().any(|(e, _, _)| *e == edge_id) {
+                continue;
+            }
+            // distance1 is measured on the face that runs along the edge in the given direction.
+            match store.coedge_edge(data.coedges[0]).same_orientation(edge) {
+                true => chamfered.push((edge_id, distance1, distance2)),
+                false => chamfered.push((edge_id, distance2, distance1)),
+            }
+        }
+
+        let mut chamfer = Chamfer {
+            corners: vec![Vec::new(); store.vertices.len()],
+            store,
+            chamfered,
+            normals,
+        };
+        for vertex_id in chamfer.store.vertex_ids() {
+            chamfer.corners[vertex_id.0] = chamfer.vertex_corners(vertex_id)?;
+        }
+        Ok(chamfer)
+    }
+
+    // The line of the coedge in its face, moved into the face if the edge is chamfered.
+    fn coedge_line(&self, coedge: CoedgeId) -> TopologyResult<(Point, Point)> {
+        let data = self.store.coedge(coedge);
+        let edge = self.store.coedge_edge(coedge);
+        let tangent = normalize(edge.end.unwrap() - edge.start.unwrap())?;
+        let distance = match self.chamfered.iter().find(|(e, _, _)| *e == data.edge) {
+            Some((_, d1, d2)) => match self.store.edge(data.edge).coedges[0] == coedge {
+                true => *d1,
+                false => *d2,
+            },
+            None => return Ok((edge.start.unwrap(), tangent)),
+        };
+        let inwards = self.normals[data.face.0].cross(tangent);
+        Ok((edge.start.unwrap() + inwards * distance, tangent))
+    }
+
+    fn vertex_corners(&self, vertex_id: VertexId) -> TopologyResult<Vec<(FaceId, Point)>> {
+        let edges: Vec<EdgeId> = self.store.edges_of_vertex(vertex_id).collect();
+        let count = edges.iter().filter(|e| self.is_trimmed(**e)).count();
+        if count == 0 {
+            return Ok(Vec::new());
+        }
+        let vertex = self.store.vertex(vertex_id).point;
+        let faces = self.store.faces_of_vertex(vertex_id);
+        if edges.len() != 3 || faces.len() != 3 {
+            return Err(TopologyError::new(format!(
+                "Chamfer only supports vertices where three faces meet, but got {}",
+                vertex
+            )));
+        }
+
+        let mut corners = Vec::<(FaceId, Point)>::new();
+        for face in faces {
+            if !self.has_trimmed_edge_at(face, vertex_id) {
+                continue;
+            }
+            let coedges: Vec<CoedgeId> = self.store.coedges_of_face(face).collect();
+            let incoming = coedges
+                .iter()
+                .find(|c| self.coedge_vertices(**c).1 == vertex_id)
+                .unwrap();
+            let outgoing = self.store.coedge(*incoming).next;
+            let (p1, t1) = self.coedge_line(*incoming)?;
+            let (p2, t2) = self.coedge_line(outgoing)?;
+            corners.push((face, line_line_point(p1, t1, p2, t2)?));
+        }
+
+        // Two chamfers have to meet in a single point on the third edge.
+        if count == 2 {
+            let on_third_edge: Vec<Point> = corners
+                .iter()
+                .filter(|(f, _)| {
+                    self.store
+                        .edges_of_face(*f)
+                        .filter(|e| self.is_trimmed(*e) && edges.contains(e))
+                        .count()
+                        == 1
+                })
+                .map(|(_, p)| *p)
+                .collect();
+            if on_third_edge[0] != on_third_edge[1] {
+                return Err(TopologyError::new(format!(
+                    "Chamfer does not support two different chamfers meeting at {}",
+                    vertex
+                )));
+            }
+        }
+        Ok(corners)
+    }
+
+    fn corner(&self, vertex: VertexId, face: FaceId) -> Point {
+        self.corners[vertex.0]
+            .iter()
+            .find(|(f, _)| *f == face)
+            .unwrap()
+            .1
+    }
+}
+
+impl EdgeTrim for Chamfer {
+    fn name(&self) -> &'static str {
+        "Chamfer"
+    }
+
+    fn store(&self) -> &TopologyStore {
+        &self.store
+    }
+
+    fn trimmed_edges(&self) -> Vec<EdgeId> {
+        self.chamfered.iter().map(|(e, _, _)| *e).collect()
+    }
+
+    fn is_trimmed(&self, edge: EdgeId) -> bool {
+        self.chamfered.iter().any(|(e, _, _)| *e == edge)
+    }
+
+    fn is_moved(&self, vertex: VertexId) -> bool {
+        !self.corners[vertex.0].is_empty()
+    }
+
+    fn moved_corner(&self, vertex: VertexId, face: FaceId) -> Point {
+        self.corner(vertex, face)
+    }
+
+    fn corner_edge(&self, _vertex: VertexId, from: Point, to: Point) -> TopologyResult<Edge> {
+        primitive_line(from, to)
+    }
+
+    fn edge_face(&self, edge_id: EdgeId) -> TopologyResult<Face> {
+        let edge = self.store.edge(edge_id);
+        let face1 = self.store.coedge(edge.coedges[0]).face;
+        let face2 = self.store.coedge(edge.coedges[1]).face;
+        let (start, end) = self.coedge_vertices(edge.coedges[0]);
+        let outwards = self.normals[face1.0] + self.normals[face2.0];
+        planar_face(
+            &[
+                self.corner(end, face1),
+                self.corner(start, face1),
+                self.corner(start, face2),
+                self.corner(end, face2),
+            ],
+            outwards,
+        )
+    }
+
+    // Where three chamfers meet, a triangle closes the corner.
+    fn corner_face(&self, vertex: VertexId) -> TopologyResult<Face> {
+        let corners = &self.corners[vertex.0];
+        let outwards = corners
+            .iter()
+            .fold(Point::zero(), |sum, (f, _)| sum + self.normals[f.0]);
+        let points: Vec<Point> = corners.iter().map(|(_, p)| *p).collect();
+        planar_face(&points, outwards)
+    }
+}
+
+// Bevels the given line edges of a volume with planar faces. The bevel starts at distance1 from the edge on the face
+// that runs along the edge in the given direction, and at distance2 on the other face.
+pub fn chamfer_asymmetric(
+    volume: &Volume,
+    edges: &[Edge],
+    distance1: EFloat64,
+    distance2: EFloat64,
+) -> TopologyResult<Volume> {
+    if distance1 <= 0.0 || distance2 <= 0.0 {
+        return Err(TopologyError::new(
+            "Chamfer distances must be positive".to_string(),
+        ));
+    }
+    Chamfer::new(volume, edges, distance1, distance2)?.trimmed_volume()
+}
+
+pub fn chamfer(volume: &Volume, edges: &[Edge], distance: EFloat64) -> TopologyResult<Volume> {
+    chamfer_asymmetric(volume, edges, distance, distance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        mass_properties::volume_mass_properties, operations::edge_trim::tests::cube,
+        validation::check_volume,
+    };
+
+    #[test]
+    fn test_chamfer_cube() {
+        let cube = cube();
+        let edges: Vec<Edge> = cube
+            .all_faces()
+            .iter()
+            .flat_map(|f| f.all_edges())
+            .collect();
+        let beveled = chamfer(&cube, &edges, EFloat64::from(0.5)).unwrap();
+        // 6 trimmed faces, 12 bevels and 8 corner triangles
+        assert_eq!(beveled.all_faces().len(), 26);
+        let report = check_volume(&beveled);
+        assert!(report.is_valid(), "{}", report);
+
+        // Core, slabs, half edge prisms and a sixth of every corner cube
+        let volume = volume_mass_properties(&beveled).unwrap().volume;
+        let expected = 1.0 + 6.0 * 0.5 + 12.0 * 0.125 + 8.0 * 0.125 / 6.0;
+        assert!(
+            (volume - expected).abs() < 1e-9,
+            "{} != {}",
+            volume,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_chamfer_asymmetric() {
+        let cube = cube();
+        let edge = cube.boundary.faces[0].all_edges()[1].clone();
+        let beveled = chamfer_asymmetric(
+            &cube,
+            std::slice::from_ref(&edge),
+            EFloat64::from(0.5),
+            EFloat64::from(1.0),
+        )
+        .unwrap();
+        assert_eq!(beveled.all_faces().len(), 7);
+        let report = check_volume(&beveled);
+        assert!(report.is_valid(), "{}", report);
+
+        let volume = volume_mass_properties(&beveled).unwrap().volume;
+        assert!((volume - (8.0 - 0.5)).abs() < 1e-9, "{}", volume);
+
+        // The distances are measured on the face that runs along the edge in the given direction
+        let face = &cube.boundary.faces[0];
+        let trimmed = beveled.boundary.faces[0].all_points();
+        let moved = face
+            .all_points()
+            .iter()
+            .filter(|p| !trimmed.contains(p))
+            .count();
+        assert_eq!(moved, 2);
+        let area = crate::mass_properties::face_area(&beveled.boundary.faces[0]);
+        assert!((area - (4.0 - 2.0 * 0.5)).abs() < 1e-9, "{}", area);
+    }
+}
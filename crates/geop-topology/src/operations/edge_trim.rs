@@ -0,0 +1,203 @@
+use geop_geometry::{curves::curve::Curve, point::Point, surfaces::surface::Surface};
+
+use crate::{
+    primitive_objects::edges::line::primitive_line,
+    topology::{
+        contour::Contour,
+        edge::Edge,
+        face::Face,
+        shell::Shell,
+        store::{CoedgeId, EdgeId, FaceId, TopologyStore, VertexId},
+        volume::Volume,
+    },
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+// Outward normal of every face of the store. Operations that cut along edges only support planar faces so far.
+pub(crate) fn planar_normals(store: &TopologyStore, name: &str) -> TopologyResult<Vec<Point>> {
+    let mut normals = Vec::<Point>::new();
+    for face in store.faces.iter() {
+        match face.face.surface.as_ref() {
+            Surface::Plane(plane) => normals.push(normalize(plane.u_slope.cross(plane.v_slope))?),
+            _ => {
+                return Err(TopologyError::new(format!(
+                    "{} only supports volumes with planar faces",
+                    name
+                )))
+            }
+        }
+    }
+    Ok(normals)
+}
+
+// Shared part of operations that replace edges of a volume by new faces, like fillet and chamfer.
+// The faces next to a trimmed edge are cut back, every trimmed edge gets a face that bridges the gap,
+// and where all edges of a vertex are trimmed, a corner face closes the hole.
+pub(crate) trait EdgeTrim {
+    // Name of the operation in error messages.
+    fn name(&self) -> &'static str;
+
+    fn store(&self) -> &TopologyStore;
+
+    fn trimmed_edges(&self) -> Vec<EdgeId>;
+
+    fn is_trimmed(&self, edge: EdgeId) -> bool;
+
+    // Whether the vertex touches a trimmed edge, so the corners of its faces move.
+    fn is_moved(&self, vertex: VertexId) -> bool;
+
+    // Where the corner of the face at the vertex moves to. Only called for faces with a trimmed edge at the vertex.
+    fn moved_corner(&self, vertex: VertexId, face: FaceId) -> Point;
+
+    // Edge that cuts the corner out of a face that ends at the vertex, but has no trimmed edge there.
+    fn corner_edge(&self, vertex: VertexId, from: Point, to: Point) -> TopologyResult<Edge>;
+
+    // Face that replaces the trimmed edge.
+    fn edge_face(&self, edge: EdgeId) -> TopologyResult<Face>;
+
+    // Face at a vertex where all edges are trimmed.
+    fn corner_face(&self, vertex: VertexId) -> TopologyResult<Face>;
+
+    fn coedge_vertices(&self, coedge: CoedgeId) -> (VertexId, VertexId) {
+        let store = self.store();
+        let data = store.coedge(coedge);
+        let edge = store.edge(data.edge);
+        match data.same_direction {
+            true => (edge.start.unwrap(), edge.end.unwrap()),
+            false => (edge.end.unwrap(), edge.start.unwrap()),
+        }
+    }
+
+    fn other_face(&self, coedge: CoedgeId) -> TopologyResult<FaceId> {
+        let store = self.store();
+        match store.mates(coedge).next() {
+            Some(mate) => Ok(store.coedge(mate).face),
+            None => Err(TopologyError::new(format!(
+                "{} needs a closed shell, but edge {} is open",
+                self.name(),
+                store.coedge_edge(coedge)
+            ))),
+        }
+    }
+
+    fn has_trimmed_edge_at(&self, face: FaceId, vertex: VertexId) -> bool {
+        let store = self.store();
+        store
+            .edges_of_face(face)
+            .any(|e| self.is_trimmed(e) && store.vertices_of_edge(e).any(|v| v == vertex))
+    }
+
+    // Where the trimmed coedge of the face starts or ends at the vertex.
+    fn corner_point(
+        &self,
+        face: FaceId,
+        coedge: CoedgeId,
+        vertex: VertexId,
+    ) -> TopologyResult<Point> {
+        if !self.is_moved(vertex) {
+            return Ok(self.store().vertex(vertex).point);
+        }
+        if self.has_trimmed_edge_at(face, vertex) {
+            return Ok(self.moved_corner(vertex, face));
+        }
+        Ok(self.moved_corner(vertex, self.other_face(coedge)?))
+    }
+
+    // A line that replaces an edge. It has to keep the direction, otherwise the trim is too large for the edge.
+    fn trimmed_line(&self, edge: &Edge, start: Point, end: Point) -> TopologyResult<Edge> {
+        let (Some(edge_start), Some(edge_end)) = (edge.start, edge.end) else {
+            return Err(TopologyError::new(format!(
+                "{} only supports edges with endpoints",
+                self.name()
+            )));
+        };
+        if start == end || (end - start).dot(edge_end - edge_start) <= 0.0 {
+            return Err(TopologyError::new(format!(
+                "{} is too large for edge {}",
+                self.name(),
+                edge
+            )));
+        }
+        primitive_line(start, end)
+    }
+
+    fn trimmed_face(&self, face_id: FaceId) -> TopologyResult<Face> {
+        let store = self.store();
+        let data = store.face(face_id);
+        let touched = store
+            .coedges_of_face(face_id)
+            .any(|c| self.is_moved(self.coedge_vertices(c).0));
+        if !touched {
+            return Ok(data.face.clone());
+        }
+
+        let mut contours = Vec::<Contour>::new();
+        for coedges in data.contours.iter() {
+            let mut edges = Vec::<Edge>::new();
+            for (i, coedge) in coedges.iter().enumerate() {
+                let edge = store.coedge_edge(*coedge);
+                if !matches!(edge.curve, Curve::Line(_)) {
+                    return Err(TopologyError::new(format!(
+                        "{} only supports line edges next to the trimmed edges, but got {}",
+                        self.name(),
+                        edge
+                    )));
+                }
+                let (start, end) = self.coedge_vertices(*coedge);
+                let start_point = self.corner_point(face_id, *coedge, start)?;
+                let end_point = self.corner_point(face_id, *coedge, end)?;
+                edges.push(self.trimmed_line(&edge, start_point, end_point)?);
+
+                // A single trimmed edge cuts the corner out of the face at its end.
+                if self.is_moved(end) && !self.has_trimmed_edge_at(face_id, end) {
+                    let next = coedges[(i + 1) % coedges.len()];
+                    let next_point = self.corner_point(face_id, next, end)?;
+                    edges.push(self.corner_edge(end, end_point, next_point)?);
+                }
+            }
+            contours.push(Contour::new(edges));
+        }
+        Ok(Face::new(contours, data.face.surface.clone()))
+    }
+
+    // The volume with the trimmed faces, the edge faces and the corner faces, in the shells of the original faces.
+    fn trimmed_volume(&self) -> TopologyResult<Volume> {
+        let store = self.store();
+        let mut shells = vec![Vec::<Face>::new(); store.shells.len()];
+        for face_id in store.face_ids() {
+            let shell = store.face(face_id).shell;
+            shells[shell.0].push(self.trimmed_face(face_id)?);
+        }
+        for edge_id in self.trimmed_edges() {
+            let coedge = store.edge(edge_id).coedges[0];
+            let shell = store.face(store.coedge(coedge).face).shell;
+            shells[shell.0].push(self.edge_face(edge_id)?);
+        }
+        for vertex_id in store.vertex_ids() {
+            if store.edges_of_vertex(vertex_id).all(|e| self.is_trimmed(e)) {
+                let face = store.faces_of_vertex(vertex_id)[0];
+                let shell = store.face(face).shell;
+                shells[shell.0].push(self.corner_face(vertex_id)?);
+            }
+        }
+
+        let mut shells = shells.into_iter().map(Shell::new);
+        let boundary = shells.next().unwrap();
+        Ok(Volume::new(boundary, shells.collect()))
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use crate::{primitive_objects::volumes::cube::primitive_cube, topology::volume::Volume};
+
+    pub(crate) fn cube() -> Volume {
+        primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        )
+    }
+}
@@ -1,115 +1,526 @@
 use std::rc::Rc;
 
 use geop_geometry::{
-    curves::{curve::Curve, CurveLike},
+    curves::{circle::Circle, curve::Curve, CurveLike},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
     point::Point,
-    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface},
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, elliptic_cylinder::EllipticCylinder, plane::Plane,
+        surface::Surface,
+    },
     transforms::Transform,
 };
 
 use crate::{
+    naming::{EntityId, History, HistoryKind, NamedFace, NamedVolume},
+    operations::sweep::ruled_face,
     primitive_objects::edges::line::primitive_line,
-    topology::{contour::Contour, face::Face, shell::Shell, volume::Volume},
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{TopologyError, TopologyResult},
 };
 
-pub fn extrude(start_face: Face, direction: Point) -> Volume {
-    let end_face = start_face
-        .transform(Transform::from_translation(direction))
-        .flip();
+// The side surface swept by the curve of an edge. Circles and ellipses have to be extruded along their normal.
+fn side_surface(edge: &Edge, direction: Point) -> TopologyResult<Surface> {
+    let midpoint = edge.get_midpoint();
+    let normal_outwards = |basis: Point| -> TopologyResult<bool> {
+        let tangent = edge.curve.tangent(midpoint)?;
+        Ok(direction.cross(tangent).dot(midpoint - basis) > 0.0)
+    };
+    match &edge.curve {
+        Curve::Line(line) => match direction.is_parallel(line.direction) {
+            true => Err(TopologyError::new(format!(
+                "Cannot extrude {} along its own direction {}",
+                edge, direction
+            ))),
+            false => Ok(Surface::Plane(Plane::new(
+                line.basis,
+                direction,
+                line.direction,
+            ))),
+        },
+        Curve::Circle(circle) if direction.is_parallel(circle.normal) => {
+            Ok(Surface::Cylinder(Cylinder::new(
+                circle.basis,
+                circle.normal,
+                circle.radius.norm(),
+                normal_outwards(circle.basis)?,
+            )))
+        }
+        Curve::Ellipse(ellipse) if direction.is_parallel(ellipse.normal) => {
+            Ok(Surface::EllipticCylinder(EllipticCylinder::new(
+                ellipse.basis,
+                ellipse.normal,
+                ellipse.major_radius,
+                ellipse.minor_radius,
+                normal_outwards(ellipse.basis)?,
+            )))
+        }
+        Curve::Circle(_) | Curve::Ellipse(_) => Err(TopologyError::new(format!(
+            "Oblique extrusion of {} along {} is not supported",
+            edge, direction
+        ))),
+        Curve::Helix(_) => Err(TopologyError::new(format!("Cannot extrude helix {}", edge))),
+    }
+}
+
+// The side face between the edge of the start face and its copy in the end face.
+fn side_face(edge: &Edge, end_edge: &Edge, surface: Surface) -> TopologyResult<Face> {
+    let top = edge.flip();
+    let bottom = end_edge.clone();
+    let contours = match (top.start, top.end, bottom.start, bottom.end) {
+        (Some(top_start), Some(top_end), Some(bottom_start), Some(bottom_end)) => {
+            let right = primitive_line(bottom_end, top_start)?;
+            let left = primitive_line(top_end, bottom_start)?;
+            vec![Contour::new(vec![right, top, left, bottom])]
+        }
+        (None, None, None, None) => vec![Contour::new(vec![top]), Contour::new(vec![bottom])],
+        _ => {
+            return Err(TopologyError::new(format!(
+                "Cannot extrude {}, which has only one bound",
+                edge
+            )))
+        }
+    };
+    Ok(Face::new(contours, Rc::new(surface)))
+}
+
+pub fn extrude(start_face: Face, direction: Point) -> TopologyResult<Volume> {
+    let translation = Transform::from_translation(direction);
+    let end_face = start_face.transform(translation).flip();
 
     let mut faces = Vec::<Face>::new();
-    let all_edges = &start_face.all_edges();
-    let end_edges = &end_face.all_edges();
-    let n = all_edges.len();
-    for i in 0..n {
-        match &all_edges[i].curve {
-            Curve::Line(line) => {
-                let top = all_edges[i].flip();
-                let bottom = end_edges
-                    .iter()
-                    .find(|e| {
-                        **e == all_edges[i]
-                            .transform(Transform::from_translation(direction))
-                            .flip()
-                    })
-                    .unwrap()
-                    .flip();
-
-                let right = match (bottom.end, top.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
-                let left = match (top.end, bottom.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
+    for edge in start_face.all_edges().iter() {
+        let surface = side_surface(edge, direction)?;
+        faces.push(side_face(edge, &edge.transform(translation), surface)?);
+    }
+    faces.push(start_face);
+    faces.push(end_face);
 
-                let plane = Surface::Plane(Plane::new(line.basis, direction, line.direction));
-                let contour = Contour::new(
-                    vec![right, Some(top), left, Some(bottom)]
-                        .drain(..)
-                        .filter_map(|f| f)
-                        .collect(),
-                );
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
 
-                let face = Face::new(vec![contour], Rc::new(plane));
-                faces.push(face);
-            }
-            Curve::Circle(circle) => {
-                let top = all_edges[i].flip();
-                let bottom = end_edges
-                    .iter()
-                    .find(|e| {
-                        **e == all_edges[i]
-                            .transform(Transform::from_translation(direction))
-                            .flip()
-                    })
-                    .unwrap()
-                    .flip();
-
-                let right = match (bottom.end, top.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
-                let left = match (top.end, bottom.start) {
-                    (Some(start), Some(end)) => Some(primitive_line(start, end).unwrap()),
-                    _ => None,
-                };
+// Extrudes the face by direction to both sides, so that the start face ends up in the middle of the volume.
+pub fn extrude_symmetric(start_face: Face, direction: Point) -> TopologyResult<Volume> {
+    let start_face = start_face.transform(Transform::from_translation(-direction));
+    extrude(start_face, direction * EFloat64::two())
+}
+
+// The cone between two circles around the same axis with different radii.
+pub(crate) fn frustum_surface(
+    from: &Circle,
+    to: &Circle,
+    normal_outwards: bool,
+) -> TopologyResult<Surface> {
+    let offset = to.basis - from.basis;
+    let (r0, r1) = (from.radius.norm(), to.radius.norm());
+    let apex = from.basis - (offset * r0 / (r1 - r0)).map_err(GeometryError::from)?;
+    let axis = match r1 > r0 {
+        true => offset,
+        false => -offset,
+    };
+    let slope = ((r1 - r0).abs() / offset.norm()).map_err(GeometryError::from)?;
+    Ok(Surface::Cone(Cone::new(apex, axis, slope, normal_outwards)))
+}
 
-                let midpoint = circle.get_midpoint(top.start, top.end).unwrap();
-                let inwards_direction = direction.cross(circle.tangent(midpoint).unwrap());
-                let normal_outwards = inwards_direction.dot(midpoint - circle.basis) > 0.0;
-
-                let cylinder = Surface::Cylinder(Cylinder::new(
-                    circle.basis,
-                    circle.normal,
-                    circle.radius.norm(),
-                    normal_outwards,
-                ));
-
-                match (left, right) {
-                    (Some(left), Some(right)) => {
-                        let contour = Contour::new(vec![right, top, left, bottom]);
-
-                        let face = Face::new(vec![contour], Rc::new(cylinder));
-                        faces.push(face);
-                    }
-                    (None, None) => {
-                        let contour = Contour::new(vec![top]);
-
-                        let face =
-                            Face::new(vec![contour, Contour::new(vec![bottom])], Rc::new(cylinder));
-                        faces.push(face);
-                    }
-                    _ => todo!("Not implemented"),
+// Extrudes a planar face perpendicular to its plane, while the side faces lean inwards by the draft angle.
+// A negative angle leans them outwards. Holes widen where the outer boundary narrows.
+// Every contour has to consist of lines, which give tilted planes, or be a single full circle, which gives a cone.
+pub fn extrude_tapered(
+    start_face: Face,
+    direction: Point,
+    draft_angle: EFloat64,
+) -> TopologyResult<Volume> {
+    if draft_angle == 0.0 {
+        return extrude(start_face, direction);
+    }
+    let Surface::Plane(plane) = start_face.surface.as_ref() else {
+        return Err(TopologyError::new(
+            "Tapered extrusion only supports planar faces".to_string(),
+        ));
+    };
+    let normal = plane
+        .u_slope
+        .cross(plane.v_slope)
+        .normalize()
+        .map_err(GeometryError::from)?;
+    if !direction.is_parallel(normal) {
+        return Err(TopologyError::new(format!(
+            "Tapered extrusion has to be perpendicular to the face, but {} is not parallel to {}",
+            direction, normal
+        )));
+    }
+    // How far the boundary moves inwards.
+    let inset =
+        (direction.norm() * draft_angle.sin() / draft_angle.cos()).map_err(GeometryError::from)?;
+
+    let mut faces = Vec::<Face>::new();
+    let mut end_contours = Vec::<Contour>::new();
+    for contour in start_face.boundaries.iter() {
+        if let [edge] = contour.edges.as_slice() {
+            if let (Curve::Circle(circle), None, None) = (&edge.curve, edge.start, edge.end) {
+                // The normal of the face points into the circle if it runs counter-clockwise.
+                let outer = circle.normal.dot(normal) > 0.0;
+                let radius = match outer {
+                    true => circle.radius.norm() - inset,
+                    false => circle.radius.norm() + inset,
+                };
+                if radius <= 0.0 {
+                    return Err(TopologyError::new(format!(
+                        "Draft angle {} closes {} before the end of the extrusion",
+                        draft_angle, edge
+                    )));
                 }
+                let end_circle = Circle::try_new(circle.basis + direction, circle.normal, radius)?;
+                let end_edge = Edge::new(None, None, Curve::Circle(end_circle.clone()));
+                let surface = frustum_surface(circle, &end_circle, outer)?;
+                faces.push(side_face(edge, &end_edge, surface)?);
+                end_contours.push(Contour::new(vec![end_edge]));
+                continue;
+            }
+        }
+        if let Some(edge) = contour
+            .edges
+            .iter()
+            .find(|e| !matches!(e.curve, Curve::Line(_)))
+        {
+            return Err(TopologyError::new(format!(
+                "Tapered extrusion supports contours of lines or a single full circle, but got {}",
+                edge
+            )));
+        }
+
+        // The edges move inwards, which is to the left of the contour seen from the face normal.
+        let n = contour.edges.len();
+        let inwards = contour
+            .edges
+            .iter()
+            .map(|e| {
+                let tangent = (e.end.unwrap() - e.start.unwrap())
+                    .normalize()
+                    .map_err(GeometryError::from)?;
+                Ok(normal.cross(tangent))
+            })
+            .collect::<TopologyResult<Vec<Point>>>()?;
+        let end_points = (0..n)
+            .map(|i| {
+                let (n1, n2) = (inwards[(i + n - 1) % n], inwards[i]);
+                let corner = ((n1 + n2) * inset / (EFloat64::one() + n1.dot(n2)))
+                    .map_err(GeometryError::from)?;
+                Ok(contour.edges[i].start.unwrap() + direction + corner)
+            })
+            .collect::<TopologyResult<Vec<Point>>>()?;
+        // An end edge that vanishes or runs backwards has been used up by its neighbours.
+        for i in 0..n {
+            let edge = &contour.edges[i];
+            let end_dir = end_points[(i + 1) % n] - end_points[i];
+            if end_dir.dot(edge.end.unwrap() - edge.start.unwrap()) <= 0.0 {
+                return Err(TopologyError::new(format!(
+                    "Draft angle {} closes {} before the end of the extrusion",
+                    draft_angle, edge
+                )));
             }
-            Curve::Ellipse(_) => todo!("Implement this"),
-            Curve::Helix(_) => panic!("Cannot extrude helix"),
         }
+        for i in 0..n {
+            let edge = &contour.edges[i];
+            faces.push(ruled_face(
+                edge.start.unwrap(),
+                edge.end.unwrap(),
+                end_points[i],
+                end_points[(i + 1) % n],
+            )?);
+        }
+        let end_edges = (0..n)
+            .map(|i| primitive_line(end_points[i], end_points[(i + 1) % n]))
+            .collect::<TopologyResult<Vec<Edge>>>()?;
+        end_contours.push(Contour::new(end_edges));
     }
+
+    let end_plane = Plane::new(plane.basis + direction, plane.u_slope, plane.v_slope);
+    let end_face = Face::new(end_contours, Rc::new(Surface::Plane(end_plane))).flip();
     faces.push(start_face);
     faces.push(end_face);
 
-    Volume::new(Shell::new(faces), vec![])
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
+
+// Like extrude, but also names the faces and edges of the volume. The start face keeps its name, the end face is "{name}/end",
+// and the side face of every edge is "{name}/side/{edge}". Translated edges are "{name}/end/{edge}", and the lateral edge
+// starting at the start vertex of an edge is "{name}/lateral/{edge}".
+pub fn extrude_named(
+    start_face: &NamedFace,
+    direction: Point,
+    name: EntityId,
+) -> TopologyResult<(NamedVolume, History)> {
+    let volume = extrude(start_face.face.clone(), direction)?;
+    let translation = Transform::from_translation(direction);
+    let input_edges: Vec<(Edge, EntityId)> = start_face
+        .face
+        .all_edges()
+        .into_iter()
+        .zip(start_face.edge_ids.iter().cloned())
+        .collect();
+    let matches = |a: &Edge, b: &Edge| a.same_orientation(b) || a.opposite_orientation(b);
+
+    let edge_id = |edge: &Edge| -> EntityId {
+        for (input, id) in input_edges.iter() {
+            if matches(input, edge) {
+                return id.clone();
+            }
+            if matches(&input.transform(translation), edge) {
+                return name.child("end").child(&id.0);
+            }
+        }
+        let (_, id) = input_edges
+            .iter()
+            .find(|(input, _)| {
+                input.start.is_some() && (input.start == edge.start || input.start == edge.end)
+            })
+            .expect("Lateral edges start at a vertex of the start face");
+        name.child("lateral").child(&id.0)
+    };
+
+    let mut history = History::new();
+    let n = input_edges.len();
+    let faces: Vec<NamedFace> = volume
+        .all_faces()
+        .into_iter()
+        .enumerate()
+        .map(|(i, face)| {
+            let id = if i < n {
+                name.child("side").child(&input_edges[i].1 .0)
+            } else if i == n {
+                start_face.id.clone()
+            } else {
+                name.child("end")
+            };
+            let edge_ids = face.all_edges().iter().map(&edge_id).collect();
+            NamedFace { id, face, edge_ids }
+        })
+        .collect();
+
+    history.record(
+        HistoryKind::Modified,
+        vec![start_face.id.clone()],
+        vec![start_face.id.clone()],
+    );
+    history.record(
+        HistoryKind::Generated,
+        vec![start_face.id.clone()],
+        vec![name.child("end")],
+    );
+    for (_, id) in input_edges.iter() {
+        history.record(
+            HistoryKind::Generated,
+            vec![id.clone()],
+            vec![name.child("side").child(&id.0)],
+        );
+        history.record(
+            HistoryKind::Generated,
+            vec![id.clone()],
+            vec![name.child("end").child(&id.0)],
+        );
+    }
+    let mut lateral = Vec::<EntityId>::new();
+    for face in faces.iter() {
+        for id in face.edge_ids.iter() {
+            if id.0.starts_with(&name.child("lateral").0) && !lateral.contains(id) {
+                lateral.push(id.clone());
+            }
+        }
+    }
+    for id in lateral {
+        let input = EntityId(id.0[name.child("lateral").0.len() + 1..].to_string());
+        history.record(HistoryKind::Generated, vec![input], vec![id]);
+    }
+
+    Ok((NamedVolume { volume, faces }, history))
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::{
+        contains::{
+            face_point::{face_point_contains, FacePointContains},
+            volume_point::{volume_point_contains, VolumePointContains},
+        },
+        mass_properties::volume_mass_properties,
+        naming::find_duplicate_id,
+        primitive_objects::{
+            edges::{circle::primitive_circle, ellipse::primitive_ellipse},
+            faces::rectangle::primitive_rectangle,
+        },
+        validation::check_volume,
+    };
+
+    // A planar face with a single closed curve, facing downwards.
+    fn disk(edge: Edge) -> Face {
+        Face::new(
+            vec![Contour::new(vec![edge])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        )
+    }
+
+    fn volume(volume: &Volume) -> f64 {
+        volume_mass_properties(volume).unwrap().volume
+    }
+
+    #[test]
+    fn test_extrude_ellipse() {
+        let ellipse = primitive_ellipse(
+            Point::zero(),
+            -Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let cylinder = extrude(disk(ellipse.clone()), Point::from_f64(0.0, 0.0, 3.0)).unwrap();
+        assert!(matches!(
+            cylinder.boundary.faces[0].surface.as_ref(),
+            Surface::EllipticCylinder(_)
+        ));
+        let report = check_volume(&cylinder);
+        assert!(report.is_valid(), "{}", report);
+        let expected = PI * 2.0 * 1.0 * 3.0;
+        assert!((volume(&cylinder) - expected).abs() < 1e-6);
+
+        // Ellipses and circles cannot be extruded obliquely.
+        assert!(extrude(disk(ellipse), Point::from_f64(0.0, 1.0, 1.0)).is_err());
+    }
+
+    #[test]
+    fn test_extrude_ellipse_containment() {
+        let ellipse = primitive_ellipse(
+            Point::zero(),
+            -Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(0.0, 1.0, 0.0),
+        );
+        let cylinder = extrude(disk(ellipse), Point::from_f64(0.0, 0.0, 3.0)).unwrap();
+        let contains =
+            |x: f64, y: f64, z: f64| volume_point_contains(&cylinder, Point::from_f64(x, y, z));
+        assert!(matches!(
+            contains(0.0, 0.0, 1.0),
+            VolumePointContains::Inside
+        ));
+        assert!(matches!(
+            contains(1.9, 0.0, 2.0),
+            VolumePointContains::Inside
+        ));
+        assert!(matches!(
+            contains(0.0, 0.9, 0.5),
+            VolumePointContains::Inside
+        ));
+        assert!(matches!(
+            contains(0.0, 1.1, 1.0),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            contains(1.0, 1.0, 1.0),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            contains(0.0, 0.0, 3.5),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            contains(0.0, -1.0, 1.5),
+            VolumePointContains::OnFace(_)
+        ));
+
+        // Points on the elliptic side face, which are not on the ruling or the cross section of its boundary point.
+        let side = cylinder.boundary.faces[0].clone();
+        let on_side = |angle: f64, z: f64| Point::from_f64(2.0 * angle.cos(), angle.sin(), z);
+        for (angle, z) in [(0.3, 1.0), (2.0, 0.5), (4.0, 2.5)] {
+            assert!(face_point_contains(&side, on_side(angle, z)) == FacePointContains::Inside);
+        }
+        assert!(face_point_contains(&side, on_side(1.0, 4.0)) == FacePointContains::Outside);
+    }
+
+    #[test]
+    fn test_extrude_tapered() {
+        // The side faces lean inwards by 0.5 over the height of 1, so the square shrinks from 2 x 2 to 1 x 1.
+        let square = primitive_rectangle(Point::zero(), Point::unit_y(), Point::unit_x());
+        let angle = EFloat64::from(0.5f64.atan());
+        let frustum = extrude_tapered(square, Point::unit_z(), angle).unwrap();
+        let report = check_volume(&frustum);
+        assert!(report.is_valid(), "{}", report);
+        let expected = (4.0 + 1.0 + 2.0) / 3.0;
+        assert!(
+            (volume(&frustum) - expected).abs() < 1e-9,
+            "{}",
+            volume(&frustum)
+        );
+
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::one());
+        let frustum = extrude_tapered(disk(circle.clone()), Point::unit_z(), angle).unwrap();
+        assert!(matches!(
+            frustum.boundary.faces[0].surface.as_ref(),
+            Surface::Cone(_)
+        ));
+        let expected = PI / 3.0 * (1.0 + 0.5 + 0.25);
+        assert!(
+            (volume(&frustum) - expected).abs() < 1e-6,
+            "{}",
+            volume(&frustum)
+        );
+
+        // The draft closes the circle before the end.
+        assert!(extrude_tapered(disk(circle), Point::from_f64(0.0, 0.0, 5.0), angle).is_err());
+
+        // The draft closes the square before the end, so the end edges would run backwards.
+        let square = primitive_rectangle(Point::zero(), Point::unit_y(), Point::unit_x());
+        let steep = EFloat64::from(60.0f64.to_radians());
+        assert!(extrude_tapered(square.clone(), Point::unit_z(), steep).is_err());
+        // The draft closes the square exactly at the end.
+        let closing = EFloat64::from(1.0f64.atan());
+        assert!(extrude_tapered(square, Point::unit_z(), closing).is_err());
+    }
+
+    #[test]
+    fn test_extrude_symmetric() {
+        let square = primitive_rectangle(Point::zero(), Point::unit_y(), Point::unit_x());
+        let block = extrude_symmetric(square, Point::unit_z()).unwrap();
+        assert!((volume(&block) - 8.0).abs() < 1e-9);
+        let properties = volume_mass_properties(&block).unwrap();
+        assert!(properties.centroid.z.to_f64().abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_extrude_named() {
+        let sketch = primitive_rectangle(Point::zero(), Point::unit_y(), Point::unit_x());
+        let sketch = NamedFace::new(EntityId::new("sketch"), sketch);
+        let (volume, history) =
+            extrude_named(&sketch, Point::unit_z(), EntityId::new("extrude1")).unwrap();
+        assert_eq!(volume.faces.len(), 6);
+        assert_eq!(find_duplicate_id(&volume.faces), None);
+
+        let top = volume.face(&EntityId::new("extrude1/end")).unwrap();
+        assert!(top
+            .face
+            .all_points()
+            .iter()
+            .all(|p| p.z == EFloat64::from(1.0)));
+        assert_eq!(history.inputs(&top.id), vec![EntityId::new("sketch")]);
+
+        // Adjacent faces use the same name for their shared edge
+        let side = volume
+            .face(&EntityId::new("extrude1/side/sketch/edge0"))
+            .unwrap();
+        let shared = EntityId::new("extrude1/end/sketch/edge0");
+        assert!(side.edge_ids.contains(&shared));
+        assert!(top.edge_ids.contains(&shared));
+        assert_eq!(history.outputs(&EntityId::new("sketch/edge0")).len(), 3);
+        assert!(volume
+            .edge(&EntityId::new("extrude1/lateral/sketch/edge1"))
+            .is_some());
+    }
 }
@@ -0,0 +1,481 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, ellipse::Ellipse},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{cylinder::Cylinder, sphere::Sphere, surface::Surface},
+};
+
+use crate::{
+    operations::edge_trim::{planar_normals, EdgeTrim},
+    primitive_objects::edges::line::primitive_line,
+    topology::{
+        contour::Contour,
+        edge::Edge,
+        face::Face,
+        store::{EdgeId, FaceId, TopologyStore, VertexId},
+        volume::Volume,
+    },
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+// Short arc around center from one point to the other. Building both uses of an arc with this function makes them mates.
+fn arc(center: Point, from: Point, to: Point, radius: EFloat64) -> TopologyResult<Edge> {
+    let normal = normalize((from - center).cross(to - center))?;
+    let circle = Circle::try_new(center, normal, radius)?;
+    Ok(Edge::new(Some(from), Some(to), Curve::Circle(circle)))
+}
+
+// At a vertex with two filleted edges, the cylinders of the edges meet in the plane between them, and the third edge stays sharp.
+struct Mitre {
+    // The face between the two filleted edges.
+    face: FaceId,
+    // Where the sharp edge ends. The faces next to it are cut back to this point.
+    point: Point,
+}
+
+struct Fillet {
+    store: TopologyStore,
+    radius: EFloat64,
+    filleted: Vec<EdgeId>,
+    // Outward normal of every face.
+    normals: Vec<Point>,
+    // Center of the rolling ball at every vertex that touches a filleted edge.
+    centers: Vec<Option<Point>>,
+    mitres: Vec<Option<Mitre>>,
+}
+
+impl Fillet {
+    fn new(volume: &Volume, edges: &[Edge], radius: EFloat64) -> TopologyResult<Fillet> {
+        let store = TopologyStore::from_volume(volume);
+
+        let normals = planar_normals(&store, "Fillet")?;
+
+        let mut filleted = Vec::<EdgeId>::new();
+        for edge in edges.iter() {
+            let edge_id = store.find_edge(edge).ok_or_else(|| {
+                TopologyError::new(format!("Edge {} is not part of the volume", edge))
+            })?;
+            if !filleted.contains(&edge_id) {
+                filleted.push(edge_id);
+            }
+        }
+
+        let mut fillet = Fillet {
+            centers: vec![None; store.vertices.len()],
+            mitres: (0..store.vertices.len()).map(|_| None).collect(),
+            store,
+            radius,
+            filleted,
+            normals,
+        };
+        for edge_id in fillet.filleted.clone() {
+            fillet.check_edge(edge_id)?;
+        }
+        for vertex_id in fillet.store.vertex_ids() {
+            fillet.centers[vertex_id.0] = fillet.center(vertex_id)?;
+            fillet.mitres[vertex_id.0] = fillet.mitre(vertex_id)?;
+        }
+        Ok(fillet)
+    }
+
+    fn check_edge(&self, edge_id: EdgeId) -> TopologyResult<()> {
+        let edge = self.store.edge(edge_id);
+        if !matches!(edge.edge.curve, Curve::Line(_)) || edge.coedges.len() != 2 {
+            return Err(TopologyError::new(format!(
+                "Fillet only supports line edges between two faces, but got {}",
+                edge.edge
+            )));
+        }
+        // The first face bends away from the normal of the second face if the edge is convex.
+        let coedge = edge.coedges[0];
+        let e = self.store.coedge_edge(coedge);
+        let tangent = normalize(e.end.unwrap() - e.start.unwrap())?;
+        let inwards = self.normal(self.store.coedge(coedge).face).cross(tangent);
+        let other = self.normal(self.store.coedge(edge.coedges[1]).face);
+        if inwards.dot(other) >= 0.0 {
+            return Err(TopologyError::new(format!(
+                "Fillet only supports convex edges, but got {}",
+                edge.edge
+            )));
+        }
+        Ok(())
+    }
+
+    fn normal(&self, face: FaceId) -> Point {
+        self.normals[face.0]
+    }
+
+    fn center(&self, vertex_id: VertexId) -> TopologyResult<Option<Point>> {
+        let edges: Vec<EdgeId> = self.store.edges_of_vertex(vertex_id).collect();
+        let filleted: Vec<EdgeId> = edges
+            .iter()
+            .copied()
+            .filter(|e| self.is_trimmed(*e))
+            .collect();
+        if filleted.is_empty() {
+            return Ok(None);
+        }
+        let vertex = self.store.vertex(vertex_id).point;
+        let faces = self.store.faces_of_vertex(vertex_id);
+        if edges.len() != 3 || faces.len() != 3 {
+            return Err(TopologyError::new(format!(
+                "Fillet only supports vertices where three faces meet, but got {}",
+                vertex
+            )));
+        }
+        let r = self.radius;
+        match filleted.len() {
+            // The ball touches all three planes. With two filleted edges, this is where the axes of the cylinders meet.
+            2 | 3 => {
+                let n1 = self.normal(faces[0]);
+                let n2 = self.normal(faces[1]);
+                let n3 = self.normal(faces[2]);
+                let det = n1.dot(n2.cross(n3));
+                let sum = n2.cross(n3) + n3.cross(n1) + n1.cross(n2);
+                Ok(Some(vertex - (sum * r / det).map_err(GeometryError::from)?))
+            }
+            // The ball touches the two faces of the edge, and the cylinder ends at the third face.
+            1 => {
+                let edge = self.store.edge(filleted[0]);
+                let n1 = self.normal(self.store.coedge(edge.coedges[0]).face);
+                let n2 = self.normal(self.store.coedge(edge.coedges[1]).face);
+                let third = faces
+                    .iter()
+                    .copied()
+                    .find(|f| !self.store.edges_of_face(*f).any(|e| e == filleted[0]))
+                    .unwrap();
+                let direction = edge.edge.end.unwrap() - edge.edge.start.unwrap();
+                if !self.normal(third).is_parallel(direction) {
+                    return Err(TopologyError::new(format!(
+                        "Fillet only supports faces perpendicular to the filleted edge at {}",
+                        vertex
+                    )));
+                }
+                let offset = ((n1 + n2) * r / (EFloat64::one() + n1.dot(n2)))
+                    .map_err(GeometryError::from)?;
+                Ok(Some(vertex - offset))
+            }
+            _ => unreachable!("A vertex with three edges has at most three filleted edges"),
+        }
+    }
+
+    // Needs the sharp edge to be perpendicular to the face between the filleted edges, such that the cylinders meet it at the same point.
+    fn mitre(&self, vertex_id: VertexId) -> TopologyResult<Option<Mitre>> {
+        let edges: Vec<EdgeId> = self.store.edges_of_vertex(vertex_id).collect();
+        let filleted: Vec<EdgeId> = edges
+            .iter()
+            .copied()
+            .filter(|e| self.is_trimmed(*e))
+            .collect();
+        if filleted.len() != 2 {
+            return Ok(None);
+        }
+        let vertex = self.store.vertex(vertex_id).point;
+        let face = self
+            .store
+            .faces_of_vertex(vertex_id)
+            .into_iter()
+            .find(|f| {
+                filleted
+                    .iter()
+                    .all(|e| self.store.edges_of_face(*f).any(|g| g == *e))
+            })
+            .unwrap();
+        let sharp = edges.iter().find(|e| !self.is_trimmed(**e)).unwrap();
+        let sharp = &self.store.edge(*sharp).edge;
+        let direction = match sharp.start.unwrap() == vertex {
+            true => sharp.end.unwrap() - vertex,
+            false => sharp.start.unwrap() - vertex,
+        };
+        let normal = self.normal(face);
+        if !normal.is_parallel(direction) {
+            return Err(TopologyError::new(format!(
+                "Fillet only supports two filleted edges at {} if the third edge is perpendicular to the face between them",
+                vertex
+            )));
+        }
+        let point = vertex - normal * self.radius;
+        Ok(Some(Mitre { face, point }))
+    }
+
+    // Where the ball at the vertex touches the face. At a mitre, the faces next to the sharp edge end at the sharp edge.
+    fn tangent_point(&self, vertex: VertexId, face: FaceId) -> Point {
+        match &self.mitres[vertex.0] {
+            Some(mitre) if mitre.face != face => mitre.point,
+            _ => self.centers[vertex.0].unwrap() + self.normal(face) * self.radius,
+        }
+    }
+
+    // End of the cylinder of a filleted edge at the vertex.
+    fn cap(&self, vertex: VertexId, from: Point, to: Point) -> TopologyResult<Edge> {
+        let center = self.centers[vertex.0].unwrap();
+        match &self.mitres[vertex.0] {
+            // Both cylinders end at the ellipse where they intersect. It reaches from the face between them to the sharp edge.
+            Some(mitre) => {
+                let normal = normalize((from - center).cross(to - center))?;
+                let ellipse = Ellipse::try_new(
+                    center,
+                    normal,
+                    mitre.point - center,
+                    self.normal(mitre.face) * self.radius,
+                )?;
+                Ok(Edge::new(Some(from), Some(to), Curve::Ellipse(ellipse)))
+            }
+            None => arc(center, from, to, self.radius),
+        }
+    }
+}
+
+impl EdgeTrim for Fillet {
+    fn name(&self) -> &'static str {
+        "Fillet"
+    }
+
+    fn store(&self) -> &TopologyStore {
+        &self.store
+    }
+
+    fn trimmed_edges(&self) -> Vec<EdgeId> {
+        self.filleted.clone()
+    }
+
+    fn is_trimmed(&self, edge: EdgeId) -> bool {
+        self.filleted.contains(&edge)
+    }
+
+    fn is_moved(&self, vertex: VertexId) -> bool {
+        self.centers[vertex.0].is_some()
+    }
+
+    fn moved_corner(&self, vertex: VertexId, face: FaceId) -> Point {
+        self.tangent_point(vertex, face)
+    }
+
+    // The end of a cylinder cuts a corner out of the face.
+    fn corner_edge(&self, vertex: VertexId, from: Point, to: Point) -> TopologyResult<Edge> {
+        arc(self.centers[vertex.0].unwrap(), from, to, self.radius)
+    }
+
+    fn edge_face(&self, edge_id: EdgeId) -> TopologyResult<Face> {
+        let edge = self.store.edge(edge_id);
+        let face1 = self.store.coedge(edge.coedges[0]).face;
+        let face2 = self.store.coedge(edge.coedges[1]).face;
+        let (start, end) = self.coedge_vertices(edge.coedges[0]);
+        let q1_start = self.tangent_point(start, face1);
+        let q1_end = self.tangent_point(end, face1);
+        let q2_start = self.tangent_point(start, face2);
+        let q2_end = self.tangent_point(end, face2);
+        let center_start = self.centers[start.0].unwrap();
+        let center_end = self.centers[end.0].unwrap();
+
+        let contour = Contour::new(vec![
+            primitive_line(q1_end, q1_start)?,
+            self.cap(start, q1_start, q2_start)?,
+            primitive_line(q2_start, q2_end)?,
+            self.cap(end, q2_end, q1_end)?,
+        ]);
+        let cylinder = Cylinder::new(center_start, center_end - center_start, self.radius, true);
+        Ok(Face::new(
+            vec![contour],
+            Rc::new(Surface::Cylinder(cylinder)),
+        ))
+    }
+
+    fn corner_face(&self, vertex: VertexId) -> TopologyResult<Face> {
+        let center = self.centers[vertex.0].unwrap();
+        let faces = self.store.faces_of_vertex(vertex);
+        let mut q: Vec<Point> = faces
+            .iter()
+            .map(|f| self.tangent_point(vertex, *f))
+            .collect();
+        // Counter-clockwise, seen from outside
+        if (q[1] - q[0]).cross(q[2] - q[0]).dot(q[0] - center) < 0.0 {
+            q.swap(1, 2);
+        }
+        let contour = Contour::new(
+            (0..3)
+                .map(|i| arc(center, q[i], q[(i + 1) % 3], self.radius))
+                .collect::<TopologyResult<Vec<Edge>>>()?,
+        );
+        let sphere = Sphere::new(center, self.radius, true);
+        Ok(Face::new(vec![contour], Rc::new(Surface::Sphere(sphere))))
+    }
+}
+
+// Rounds the given line edges of a volume with planar faces with a constant radius.
+// Every filleted edge is replaced by a cylinder, and where three filleted edges meet, the corner is replaced by a sphere.
+// Where two filleted edges meet, the cylinders are joined along the ellipse in the plane between them, and the third edge stays sharp.
+// A vertex with a single filleted edge needs the third face to be perpendicular to the edge,
+// and a vertex with two needs the sharp edge to be perpendicular to the face between them, like at the corners of a cube.
+// Volumes with curved faces (and therefore curved edges) and concave edges return an error.
+pub fn fillet(volume: &Volume, edges: &[Edge], radius: EFloat64) -> TopologyResult<Volume> {
+    if radius <= 0.0 {
+        return Err(TopologyError::new(
+            "Fillet radius must be positive".to_string(),
+        ));
+    }
+    Fillet::new(volume, edges, radius)?.trimmed_volume()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use geop_geometry::surfaces::plane::Plane;
+
+    use super::*;
+    use crate::{
+        mass_properties::volume_mass_properties,
+        operations::{edge_trim::tests::cube, extrude::extrude},
+        primitive_objects::edges::circle::primitive_circle,
+        validation::check_volume,
+    };
+
+    #[test]
+    fn test_fillet_cube() {
+        let cube = cube();
+        let edges: Vec<Edge> = cube
+            .all_faces()
+            .iter()
+            .flat_map(|f| f.all_edges())
+            .collect();
+        let rounded = fillet(&cube, &edges, EFloat64::from(0.5)).unwrap();
+        // 6 trimmed faces, 12 cylinders and 8 spheres
+        assert_eq!(rounded.all_faces().len(), 26);
+        let report = check_volume(&rounded);
+        assert!(report.is_valid(), "{}", report);
+
+        // Core, slabs, quarter cylinders and sphere octants
+        let volume = volume_mass_properties(&rounded).unwrap().volume;
+        let expected = 1.0 + 6.0 * 0.5 + 3.0 * PI * 0.25 + 4.0 / 3.0 * PI * 0.125;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_fillet_single_edge() {
+        let cube = cube();
+        let edge = cube.boundary.faces[0].all_edges()[1].clone();
+        let rounded = fillet(&cube, std::slice::from_ref(&edge), EFloat64::from(0.5)).unwrap();
+        assert_eq!(rounded.all_faces().len(), 7);
+        let report = check_volume(&rounded);
+        assert!(report.is_valid(), "{}", report);
+
+        let volume = volume_mass_properties(&rounded).unwrap().volume;
+        let expected = 8.0 - (1.0 - PI / 4.0) * 0.25 * 2.0;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+
+        assert!(fillet(&cube, &[edge], EFloat64::from(2.5)).is_err());
+    }
+
+    #[test]
+    fn test_fillet_two_edges_at_vertex() {
+        let cube = cube();
+        let r = 0.5;
+        // Where two filleted edges meet, the removed prisms of the edges overlap in the corner.
+        let corner = r * r * r * (5.0 / 3.0 - PI / 2.0);
+        let edge = 2.0 * r * r * (1.0 - PI / 4.0);
+
+        // All edges of the top face, so the four vertical edges stay sharp.
+        let top = cube.boundary.faces[0].all_edges();
+        let rounded = fillet(&cube, &top, EFloat64::from(r)).unwrap();
+        assert_eq!(rounded.all_faces().len(), 10);
+        let report = check_volume(&rounded);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&rounded).unwrap().volume;
+        let expected = 8.0 - 4.0 * edge + 4.0 * corner;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+
+        // Two edges of the top face, which meet at one vertex.
+        let rounded = fillet(&cube, &top[0..2], EFloat64::from(r)).unwrap();
+        let report = check_volume(&rounded);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&rounded).unwrap().volume;
+        let expected = 8.0 - 2.0 * edge + corner;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_fillet_unsupported() {
+        let cube = cube();
+        let edges = cube.boundary.faces[0].all_edges();
+        let error = |result: TopologyResult<Volume>| match result {
+            Ok(_) => panic!("Fillet should fail"),
+            Err(error) => error.to_string(),
+        };
+
+        // At the top corner over the right angle of the wedge, the sharp edge on the slanted face is not perpendicular to the face between the filleted edges
+        let wedge = extrude(
+            Face::new(
+                vec![Contour::new(vec![
+                    primitive_line(Point::zero(), Point::unit_y()).unwrap(),
+                    primitive_line(Point::unit_y(), Point::unit_x()).unwrap(),
+                    primitive_line(Point::unit_x(), Point::zero()).unwrap(),
+                ])],
+                Rc::new(Surface::Plane(Plane::new(
+                    Point::zero(),
+                    Point::unit_y(),
+                    Point::unit_x(),
+                ))),
+            ),
+            Point::unit_z(),
+        )
+        .unwrap();
+        let top = Point::from_f64(1.0, 0.0, 1.0);
+        let edges_at_top: Vec<Edge> = wedge
+            .all_faces()
+            .iter()
+            .flat_map(|f| f.all_edges())
+            .filter(|e| e.start == Some(top) || e.end == Some(top))
+            .filter(|e| e.start.unwrap().y == 0.0 && e.end.unwrap().y == 0.0)
+            .collect();
+        let message = error(fillet(&wedge, &edges_at_top, EFloat64::from(0.1)));
+        assert!(message.contains("third edge"), "{}", message);
+
+        // The circles of a cylinder are curved edges next to a curved face
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::zero(),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::unit_z()).unwrap();
+        let message = error(fillet(
+            &cylinder,
+            &cylinder.boundary.faces[0].all_edges(),
+            EFloat64::from(0.5),
+        ));
+        assert!(message.contains("planar faces"), "{}", message);
+
+        let message = error(fillet(&cube, &edges[0..1], EFloat64::zero()));
+        assert!(message.contains("positive"), "{}", message);
+    }
+}
@@ -0,0 +1,400 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::curve::Curve,
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{cylinder::Cylinder, plane::Plane, surface::Surface, SurfaceLike},
+};
+
+use crate::{
+    operations::{extrude::frustum_surface, sweep::ruled_face},
+    topology::{contour::Contour, face::Face, shell::Shell, volume::Volume},
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+// A planar contour, given by its center and the normal around which it runs counter-clockwise.
+struct Section {
+    contour: Contour,
+    center: Point,
+    normal: Point,
+}
+
+impl Section {
+    fn new(contour: &Contour) -> TopologyResult<Section> {
+        if let [edge] = contour.edges.as_slice() {
+            if let (Curve::Circle(circle), None, None) = (&edge.curve, edge.start, edge.end) {
+                return Ok(Section {
+                    contour: contour.clone(),
+                    center: circle.basis,
+                    normal: circle.normal,
+                });
+            }
+        }
+        if let Some(edge) = contour
+            .edges
+            .iter()
+            .find(|e| !matches!(e.curve, Curve::Line(_)))
+        {
+            return Err(TopologyError::new(format!(
+                "Loft supports contours of lines or a single full circle, but got {}",
+                edge
+            )));
+        }
+
+        // Newell's method: The sum of the cross products is twice the area times the normal.
+        let points = contour.all_points();
+        let n = points.len();
+        let mut normal = Point::zero();
+        let mut center = Point::zero();
+        for i in 0..n {
+            normal = normal + points[i].cross(points[(i + 1) % n]);
+            center = center + points[i];
+        }
+        let normal = normalize(normal)?;
+        let center = (center / EFloat64::from(n as f64)).map_err(GeometryError::from)?;
+        let section = Section {
+            contour: contour.clone(),
+            center,
+            normal,
+        };
+        let plane = section.plane()?;
+        if let Some(point) = points.iter().find(|p| !plane.on_surface(**p)) {
+            return Err(TopologyError::new(format!(
+                "Loft contours have to be planar, but {} is not in the plane of its contour",
+                point
+            )));
+        }
+        Ok(section)
+    }
+
+    fn flip(&self) -> Section {
+        Section {
+            contour: self.contour.flip(),
+            center: self.center,
+            normal: -self.normal,
+        }
+    }
+
+    fn is_circle(&self) -> bool {
+        matches!(self.contour.edges[0].curve, Curve::Circle(_))
+    }
+
+    fn plane(&self) -> TopologyResult<Plane> {
+        let u = match self.contour.all_points().first() {
+            Some(p) => normalize(*p - self.center)?,
+            None => normalize(self.normal.cross(match self.normal.x.abs() < 0.5 {
+                true => Point::unit_x(),
+                false => Point::unit_y(),
+            }))?,
+        };
+        Ok(Plane::new(self.center, u, self.normal.cross(u)))
+    }
+
+    // The cap has the same orientation as the contour.
+    fn cap(&self) -> TopologyResult<Face> {
+        Ok(Face::new(
+            vec![self.contour.clone()],
+            Rc::new(Surface::Plane(self.plane()?)),
+        ))
+    }
+}
+
+// The surface between two circles around the same axis. It is a cylinder for equal radii and a cone otherwise.
+fn circle_surface(from: &Section, to: &Section) -> TopologyResult<Surface> {
+    let (Curve::Circle(c0), Curve::Circle(c1)) =
+        (&from.contour.edges[0].curve, &to.contour.edges[0].curve)
+    else {
+        unreachable!()
+    };
+    let offset = to.center - from.center;
+    if !c0.normal.is_parallel(c1.normal) || !offset.is_parallel(c0.normal) {
+        return Err(TopologyError::new(format!(
+            "Loft between circles needs a common axis, but got {} and {}",
+            from.contour.edges[0], to.contour.edges[0]
+        )));
+    }
+    let (r0, r1) = (c0.radius.norm(), c1.radius.norm());
+    if r0 == r1 {
+        return Ok(Surface::Cylinder(Cylinder::new(
+            from.center,
+            offset,
+            r0,
+            true,
+        )));
+    }
+    frustum_surface(c0, c1, true)
+}
+
+// The side faces between two consecutive contours.
+fn ruled_section(from: &Section, to: &Section) -> TopologyResult<Vec<Face>> {
+    if from.is_circle() {
+        let surface = circle_surface(from, to)?;
+        return Ok(vec![Face::new(
+            vec![
+                Contour::new(vec![from.contour.edges[0].flip()]),
+                to.contour.clone(),
+            ],
+            Rc::new(surface),
+        )]);
+    }
+    let mut faces = Vec::<Face>::new();
+    for (e0, e1) in from.contour.edges.iter().zip(to.contour.edges.iter()) {
+        faces.push(ruled_face(
+            e0.start.unwrap(),
+            e0.end.unwrap(),
+            e1.start.unwrap(),
+            e1.end.unwrap(),
+        )?);
+    }
+    Ok(faces)
+}
+
+// Lofts through the contours in the given order. The edges of consecutive contours are matched by their index, and
+// connected by ruled surfaces: Planes between lines, where they are coplanar, and cylinders or cones between circles
+// around a common axis. Lines that are not coplanar are connected by the hyperbolic paraboloid through both of them.
+// A closed loft connects the last contour with the first one. Otherwise, the first and the last contour are capped.
+pub fn loft(contours: &[Contour], closed: bool) -> TopologyResult<Volume> {
+    let minimum = match closed {
+        true => 3,
+        false => 2,
+    };
+    if contours.len() < minimum {
+        return Err(TopologyError::new(format!(
+            "Loft needs at least {} contours, but got {}",
+            minimum,
+            contours.len()
+        )));
+    }
+    let mut sections = contours
+        .iter()
+        .map(Section::new)
+        .collect::<TopologyResult<Vec<Section>>>()?;
+    let n = sections.len();
+    let kind = (sections[0].is_circle(), sections[0].contour.edges.len());
+    if sections
+        .iter()
+        .any(|s| (s.is_circle(), s.contour.edges.len()) != kind)
+    {
+        return Err(TopologyError::new(
+            "Loft contours have to consist of the same kind and number of edges".to_string(),
+        ));
+    }
+
+    // All contours run clockwise when looking in the direction of the loft, so that the first one is the backwards facing cap.
+    let directions: Vec<Point> = (0..n)
+        .map(|i| match (i + 1 < n, closed) {
+            (true, _) => sections[i + 1].center - sections[i].center,
+            (false, true) => sections[0].center - sections[i].center,
+            (false, false) => sections[i].center - sections[i - 1].center,
+        })
+        .collect();
+    if sections[0].normal.dot(directions[0]) > 0.0 {
+        sections = sections.iter().map(|s| s.flip()).collect();
+    }
+    for i in 0..n {
+        if sections[i].normal.dot(directions[i]) >= 0.0 {
+            return Err(TopologyError::new(format!(
+                "Loft contours have to be oriented the same way along the loft, but contour {} is not",
+                i
+            )));
+        }
+    }
+
+    let mut faces = Vec::<Face>::new();
+    for i in 0..n - 1 {
+        faces.extend(ruled_section(&sections[i], &sections[i + 1])?);
+    }
+    match closed {
+        true => faces.extend(ruled_section(&sections[n - 1], &sections[0])?),
+        false => {
+            faces.push(sections[0].cap()?);
+            faces.push(sections[n - 1].cap()?.flip());
+        }
+    }
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::{
+        contains::{
+            face_point::{face_point_contains, FacePointContains},
+            volume_point::{volume_point_contains, VolumePointContains},
+        },
+        mass_properties::volume_mass_properties,
+        primitive_objects::edges::{circle::primitive_circle, line::primitive_line},
+        topology::edge::Edge,
+        validation::check_volume,
+    };
+
+    fn square(size: f64, z: f64) -> Contour {
+        let h = size / 2.0;
+        let points = [
+            Point::from_f64(-h, -h, z),
+            Point::from_f64(h, -h, z),
+            Point::from_f64(h, h, z),
+            Point::from_f64(-h, h, z),
+        ];
+        Contour::new(
+            (0..4)
+                .map(|i| primitive_line(points[i], points[(i + 1) % 4]).unwrap())
+                .collect::<Vec<Edge>>(),
+        )
+    }
+
+    // The corners of a square turned by the angle around the z axis.
+    fn turned_square_corners(size: f64, z: f64, angle: f64) -> [Point; 4] {
+        let r = size / 2.0 * 2.0_f64.sqrt();
+        [5.0, 7.0, 1.0, 3.0].map(|k: f64| {
+            let phi = angle + k * PI / 4.0;
+            Point::from_f64(r * phi.cos(), r * phi.sin(), z)
+        })
+    }
+
+    fn turned_square(size: f64, z: f64, angle: f64) -> Contour {
+        let points = turned_square_corners(size, z, angle);
+        Contour::new(
+            (0..4)
+                .map(|i| primitive_line(points[i], points[(i + 1) % 4]).unwrap())
+                .collect::<Vec<Edge>>(),
+        )
+    }
+
+    fn circle(radius: f64, z: f64) -> Contour {
+        Contour::new(vec![primitive_circle(
+            Point::from_f64(0.0, 0.0, z),
+            Point::unit_z(),
+            EFloat64::from(radius),
+        )])
+    }
+
+    #[test]
+    fn test_loft_squares() {
+        let frustum = loft(&[square(2.0, 0.0), square(1.0, 1.0)], false).unwrap();
+        let report = check_volume(&frustum);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&frustum).unwrap().volume;
+        assert!((volume - 7.0 / 3.0).abs() < 1e-9, "{}", volume);
+    }
+
+    #[test]
+    fn test_loft_twisted_squares() {
+        let twisted = loft(
+            &[
+                turned_square(2.0, 0.0, 0.0),
+                turned_square(2.0, 1.0, PI / 4.0),
+            ],
+            false,
+        )
+        .unwrap();
+        let sides = &twisted.boundary.faces[..4];
+        assert!(sides
+            .iter()
+            .all(|f| matches!(f.surface.as_ref(), Surface::HyperbolicParaboloid(_))));
+        let report = check_volume(&twisted);
+        assert!(report.is_valid(), "{}", report);
+
+        // The side surfaces are ruled between the edges, so the cross section at every height is the polygon through
+        // the interpolated corners. Its area is quadratic in the height, which Simpson's rule integrates exactly.
+        let area = |points: [Point; 4]| {
+            (0..4)
+                .map(|i| {
+                    let (p, q) = (points[i], points[(i + 1) % 4]);
+                    (p.x * q.y - q.x * p.y).to_f64() / 2.0
+                })
+                .sum::<f64>()
+        };
+        let bottom = turned_square_corners(2.0, 0.0, 0.0);
+        let top = turned_square_corners(2.0, 1.0, PI / 4.0);
+        let middle = [0, 1, 2, 3].map(|i| (bottom[i] + top[i]) * EFloat64::from(0.5));
+        let expected = (area(bottom) + 4.0 * area(middle) + area(top)) / 6.0;
+        let volume = volume_mass_properties(&twisted).unwrap().volume;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+
+        // The middle of a side is on the patch, halfway between the corners of the middle cross section.
+        let inside =
+            |x: f64, y: f64, z: f64| volume_point_contains(&twisted, Point::from_f64(x, y, z));
+        assert!(matches!(inside(0.0, 0.0, 0.5), VolumePointContains::Inside));
+        let side = middle[0] + (middle[1] - middle[0]) * EFloat64::from(0.5);
+        assert!(matches!(
+            volume_point_contains(&twisted, side),
+            VolumePointContains::OnFace(_)
+        ));
+        assert!(matches!(
+            inside(0.0, -1.2, 0.5),
+            VolumePointContains::Outside
+        ));
+    }
+
+    #[test]
+    fn test_loft_circles() {
+        // A cone up to the widest point, then a cylinder.
+        let duct = loft(
+            &[circle(1.0, 0.0), circle(2.0, 2.0), circle(2.0, 3.0)],
+            false,
+        )
+        .unwrap();
+        assert!(matches!(
+            duct.boundary.faces[0].surface.as_ref(),
+            Surface::Cone(_)
+        ));
+        assert!(matches!(
+            duct.boundary.faces[1].surface.as_ref(),
+            Surface::Cylinder(_)
+        ));
+        let report = check_volume(&duct);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&duct).unwrap().volume;
+        let expected = PI * 2.0 / 3.0 * 7.0 + PI * 4.0;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+
+        assert!(loft(&[circle(1.0, 0.0), square(1.0, 1.0)], false).is_err());
+    }
+
+    #[test]
+    fn test_loft_cone_containment() {
+        let frustum = loft(&[circle(1.0, 0.0), circle(2.0, 2.0)], false).unwrap();
+        let inside =
+            |x: f64, y: f64, z: f64| volume_point_contains(&frustum, Point::from_f64(x, y, z));
+        assert!(matches!(inside(0.0, 0.0, 1.0), VolumePointContains::Inside));
+        assert!(matches!(inside(1.4, 0.0, 1.0), VolumePointContains::Inside));
+        assert!(matches!(
+            inside(0.0, -1.6, 1.0),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            inside(0.0, 0.0, 3.0),
+            VolumePointContains::Outside
+        ));
+        assert!(matches!(
+            inside(-1.5, 0.0, 1.0),
+            VolumePointContains::OnFace(_)
+        ));
+
+        // Points on the cone face, on the same and on other generators than its seam.
+        let cone = frustum.boundary.faces[0].clone();
+        assert!(matches!(cone.surface.as_ref(), Surface::Cone(_)));
+        for (x, y) in [(1.5, 0.0), (0.0, 1.5), (-1.5, 0.0), (0.9, -1.2)] {
+            assert!(
+                face_point_contains(&cone, Point::from_f64(x, y, 1.0)) == FacePointContains::Inside
+            );
+        }
+        let above = Point::from_f64(0.0, 3.0, 4.0);
+        assert!(face_point_contains(&cone, above) == FacePointContains::Outside);
+    }
+}
@@ -0,0 +1,163 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::CurveLike,
+    point::Point,
+    surfaces::{plane::Plane, SurfaceLike},
+    transforms::Transform,
+};
+
+use crate::{
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::TopologyResult,
+};
+
+fn mirror_transform(plane: &Plane) -> Transform {
+    Transform::from_mirror(plane.basis, plane.normal(plane.basis))
+}
+
+// The image of the direction v at p.
+fn mirror_direction(transform: Transform, p: Point, v: Point) -> Point {
+    transform * (p + v) - transform * p
+}
+
+// Curves that run around a normal, like circles, keep their normal through the transform, so they would run backwards.
+fn mirror_edge(edge: &Edge, transform: Transform) -> TopologyResult<Edge> {
+    let p = edge.get_midpoint();
+    let expected = mirror_direction(transform, p, edge.curve.tangent(p)?);
+    let curve = edge.curve.transform(transform);
+    let curve = match curve.tangent(transform * p)?.dot(expected) < 0.0 {
+        true => curve.neg(),
+        false => curve,
+    };
+    Ok(Edge::new(
+        edge.start.map(|p| transform * p),
+        edge.end.map(|p| transform * p),
+        curve,
+    ))
+}
+
+fn mirror_face_with(face: &Face, transform: Transform) -> TopologyResult<Face> {
+    let boundaries = face
+        .boundaries
+        .iter()
+        .map(|contour| {
+            let edges = contour
+                .edges
+                .iter()
+                .map(|edge| mirror_edge(edge, transform))
+                .collect::<TopologyResult<Vec<Edge>>>()?;
+            Ok(Contour::new(edges))
+        })
+        .collect::<TopologyResult<Vec<Contour>>>()?;
+    let mirrored = Face {
+        boundaries,
+        surface: Rc::new(face.surface.transform(transform)),
+    };
+
+    // A reflection turns counter-clockwise contours into clockwise ones, so they have to be reversed.
+    // Surfaces like planes are spanned by their directions, and their normal turns around as well, while
+    // spheres and cylinders keep their outward normal.
+    let p = face.inner_point();
+    let expected = mirror_direction(transform, p, face.surface.normal(p));
+    let normal = mirrored.surface.normal(transform * p);
+    Ok(match normal.dot(expected) < 0.0 {
+        true => mirrored.flip(),
+        false => mirrored.neg(),
+    })
+}
+
+fn mirror_shell(shell: &Shell, transform: Transform) -> TopologyResult<Shell> {
+    let faces = shell
+        .faces
+        .iter()
+        .map(|face| mirror_face_with(face, transform))
+        .collect::<TopologyResult<Vec<Face>>>()?;
+    Ok(Shell::new(faces))
+}
+
+// Reflects the face at the plane. The normal of the result is the mirror image of the normal of the face.
+pub fn mirror_face(face: &Face, plane: &Plane) -> TopologyResult<Face> {
+    mirror_face_with(face, mirror_transform(plane))
+}
+
+// Reflects the volume at the plane. Unlike Volume::transform with a reflection, the result is outward oriented again.
+pub fn mirror(volume: &Volume, plane: &Plane) -> TopologyResult<Volume> {
+    let transform = mirror_transform(plane);
+    Ok(Volume::new(
+        mirror_shell(&volume.boundary, transform)?,
+        volume
+            .cavities
+            .iter()
+            .map(|cavity| mirror_shell(cavity, transform))
+            .collect::<TopologyResult<Vec<Shell>>>()?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use geop_geometry::{efloat::EFloat64, surfaces::surface::Surface};
+
+    use super::*;
+    use crate::{
+        mass_properties::volume_mass_properties,
+        operations::extrude::extrude,
+        primitive_objects::{
+            edges::circle::primitive_circle, faces::rectangle::primitive_rectangle,
+        },
+        validation::check_volume,
+    };
+
+    fn plane_x(x: f64) -> Plane {
+        Plane::new(
+            Point::from_f64(x, 0.0, 0.0),
+            Point::unit_y(),
+            Point::unit_z(),
+        )
+    }
+
+    #[test]
+    fn test_mirror_prism() {
+        let square = primitive_rectangle(Point::zero(), Point::unit_y(), Point::unit_x());
+        let prism = extrude(square, Point::from_f64(0.5, 0.0, 2.0)).unwrap();
+        let mirrored = mirror(&prism, &plane_x(3.0)).unwrap();
+        let report = check_volume(&mirrored);
+        assert!(report.is_valid(), "{}", report);
+
+        let properties = volume_mass_properties(&mirrored).unwrap();
+        assert!(
+            (properties.volume - 8.0).abs() < 1e-9,
+            "{}",
+            properties.volume
+        );
+        let expected = Point::from_f64(5.75, 0.0, 1.0);
+        assert!((properties.centroid - expected).norm() < 1e-9);
+    }
+
+    #[test]
+    fn test_mirror_cylinder() {
+        let disk = Face::new(
+            vec![Contour::new(vec![primitive_circle(
+                Point::from_f64(1.0, 0.0, 0.0),
+                -Point::unit_z(),
+                EFloat64::one(),
+            )])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let mirrored = mirror(&cylinder, &plane_x(0.0)).unwrap();
+        let report = check_volume(&mirrored);
+        assert!(report.is_valid(), "{}", report);
+
+        let properties = volume_mass_properties(&mirrored).unwrap();
+        assert!((properties.volume - 2.0 * PI).abs() < 1e-6);
+        let expected = Point::from_f64(-1.0, 0.0, 1.0);
+        assert!((properties.centroid - expected).norm() < 1e-6);
+    }
+}
@@ -1 +1,10 @@
+pub mod chamfer;
+mod edge_trim;
 pub mod extrude;
+pub mod fillet;
+pub mod loft;
+pub mod mirror;
+pub mod offset;
+pub mod pattern;
+pub mod shell;
+pub mod sweep;
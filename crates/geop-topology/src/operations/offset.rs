@@ -0,0 +1,219 @@
+use geop_geometry::{
+    curve_curve_intersection::curve_curve::{curve_curve_intersection, CurveCurveIntersection},
+    curves::{circle::Circle, curve::Curve, line::Line, CurveLike},
+    efloat::EFloat64,
+    point::Point,
+    surfaces::surface::Surface,
+};
+
+use crate::{
+    mass_properties::face_area,
+    topology::{contour::Contour, edge::Edge, face::Face},
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+// The curve moved to the left of its direction by the distance. Circles change their radius, and None is returned if the radius vanishes.
+fn offset_curve(curve: &Curve, normal: Point, distance: EFloat64) -> TopologyResult<Option<Curve>> {
+    match curve {
+        Curve::Line(line) => {
+            let left = normalize(normal.cross(line.direction))?;
+            Ok(Some(Curve::Line(Line::new(
+                line.basis + left * distance,
+                line.direction,
+            )?)))
+        }
+        // A circle that runs counter-clockwise around the normal has its center on the left.
+        Curve::Circle(circle) => {
+            let radius = match circle.normal.dot(normal) > 0.0 {
+                true => circle.radius.norm() - distance,
+                false => circle.radius.norm() + distance,
+            };
+            if radius <= 0.0 {
+                return Ok(None);
+            }
+            Ok(Some(Curve::Circle(Circle::try_new(
+                circle.basis,
+                circle.normal,
+                radius,
+            )?)))
+        }
+        _ => Err(TopologyError::new(
+            "Only lines and circles can be offset".to_string(),
+        )),
+    }
+}
+
+// The point where the offsets of two consecutive edges meet, next to their common vertex.
+fn offset_corner(
+    vertex: Point,
+    (prev, prev_offset): (&Edge, &Curve),
+    (next, next_offset): (&Edge, &Curve),
+    normal: Point,
+    distance: EFloat64,
+) -> TopologyResult<Point> {
+    let from_prev = vertex + normalize(normal.cross(prev.curve.tangent(vertex)?))? * distance;
+    let from_next = vertex + normalize(normal.cross(next.curve.tangent(vertex)?))? * distance;
+    // Tangent edges continue smoothly, so there is no corner to intersect.
+    if from_prev == from_next {
+        return Ok(from_prev);
+    }
+    match curve_curve_intersection(prev_offset, next_offset) {
+        CurveCurveIntersection::FinitePoints(points) => points
+            .into_iter()
+            .min_by(|a, b| {
+                (*a - vertex)
+                    .norm_sq()
+                    .to_f64()
+                    .total_cmp(&(*b - vertex).norm_sq().to_f64())
+            })
+            .ok_or_else(|| TopologyError::new("Offset edges do not meet".to_string())),
+        _ => Err(TopologyError::new(format!(
+            "Offset edges do not meet next to {}",
+            vertex
+        ))),
+    }
+}
+
+// Whether the offset edge still runs the same way as the original one. Otherwise it has been used up by its neighbours.
+pub(crate) fn keeps_direction(edge: &Edge, offset: &Edge) -> bool {
+    match (&edge.curve, edge.start, edge.end, offset.start, offset.end) {
+        (Curve::Line(line), _, _, Some(start), Some(end)) => {
+            (end - start).dot(line.direction) > 0.0
+        }
+        (Curve::Circle(circle), Some(_), Some(_), Some(_), Some(_)) => {
+            (offset.get_midpoint() - circle.basis).dot(edge.get_midpoint() - circle.basis) > 0.0
+        }
+        _ => true,
+    }
+}
+
+// The contour moved to the left of its edges by the distance, within the plane with the given normal.
+// For counter-clockwise contours, positive distances move inwards. None is returned if the contour vanishes.
+// Only lines and circles are supported. Edges that vanish are not removed, instead the whole contour is considered vanished.
+pub fn offset_contour(
+    contour: &Contour,
+    normal: Point,
+    distance: EFloat64,
+) -> TopologyResult<Option<Contour>> {
+    let mut curves = Vec::<Curve>::new();
+    for edge in contour.edges.iter() {
+        match offset_curve(&edge.curve, normal, distance)? {
+            Some(curve) => curves.push(curve),
+            None => return Ok(None),
+        }
+    }
+
+    let n = contour.edges.len();
+    let mut corners = Vec::<Option<Point>>::new();
+    for i in 0..n {
+        let prev = (i + n - 1) % n;
+        corners.push(match contour.edges[i].start {
+            Some(vertex) => Some(offset_corner(
+                vertex,
+                (&contour.edges[prev], &curves[prev]),
+                (&contour.edges[i], &curves[i]),
+                normal,
+                distance,
+            )?),
+            None => None,
+        });
+    }
+
+    let mut edges = Vec::<Edge>::new();
+    for (i, edge) in contour.edges.iter().enumerate() {
+        let (start, end) = (corners[i], corners[(i + 1) % n]);
+        if start.is_some() && start == end {
+            return Ok(None);
+        }
+        let offset = Edge::new(start, end, curves[i].clone());
+        if !keeps_direction(edge, &offset) {
+            return Ok(None);
+        }
+        edges.push(offset);
+    }
+    Ok(Some(Contour::new(edges)))
+}
+
+// The planar face with all boundaries moved into the face by the distance. Negative distances grow the face.
+// Holes that vanish are removed, and None is returned if the face vanishes.
+// Boundaries that start to overlap are not merged, so the distance should be small compared to the features of the face.
+pub fn offset_face(face: &Face, distance: EFloat64) -> TopologyResult<Option<Face>> {
+    let normal = match face.surface.as_ref() {
+        Surface::Plane(plane) => normalize(plane.u_slope.cross(plane.v_slope))?,
+        _ => {
+            return Err(TopologyError::new(
+                "Only planar faces can be offset".to_string(),
+            ))
+        }
+    };
+    let mut boundaries = Vec::<Contour>::new();
+    for contour in face.boundaries.iter() {
+        match offset_contour(contour, normal, distance)? {
+            Some(contour) => boundaries.push(contour),
+            None => {
+                // Outer boundaries run counter-clockwise, so they enclose a positive area on their own.
+                let outer =
+                    face_area(&Face::new(vec![contour.clone()], face.surface.clone())) > 0.0;
+                if outer {
+                    return Ok(None);
+                }
+            }
+        }
+    }
+    Ok(Some(Face::new(boundaries, face.surface.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{f64::consts::PI, rc::Rc};
+
+    use geop_geometry::surfaces::plane::Plane;
+
+    use super::*;
+    use crate::primitive_objects::{
+        edges::circle::primitive_circle, faces::rectangle::primitive_rectangle,
+    };
+
+    #[test]
+    fn test_offset_rectangle() {
+        let square = primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y());
+        let inset = offset_face(&square, EFloat64::from(0.25)).unwrap().unwrap();
+        assert!((face_area(&inset) - 1.5 * 1.5).abs() < 1e-9);
+        assert!(inset
+            .all_points()
+            .contains(&Point::from_f64(0.75, 0.75, 0.0)));
+
+        let outset = offset_face(&square, EFloat64::from(-0.5)).unwrap().unwrap();
+        assert!((face_area(&outset) - 3.0 * 3.0).abs() < 1e-9);
+
+        assert!(offset_face(&square, EFloat64::from(1.5)).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_offset_face_with_hole() {
+        // A disk of radius 2 with a hole of radius 1, where the hole runs clockwise.
+        let plane = Rc::new(Surface::Plane(Plane::new(
+            Point::zero(),
+            Point::unit_x(),
+            Point::unit_y(),
+        )));
+        let outer = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::from(2.0));
+        let hole = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::one());
+        let ring = Face::new(
+            vec![Contour::new(vec![outer]), Contour::new(vec![hole])],
+            plane,
+        );
+        let expected = |outer: f64, inner: f64| PI * (outer * outer - inner * inner);
+
+        let inset = offset_face(&ring, EFloat64::from(0.25)).unwrap().unwrap();
+        assert_eq!(inset.boundaries.len(), 2);
+        assert!((face_area(&inset) - expected(1.75, 1.25)).abs() < 1e-6);
+
+        // Growing the face closes the hole.
+        let outset = offset_face(&ring, EFloat64::from(-1.5)).unwrap().unwrap();
+        assert_eq!(outset.boundaries.len(), 1);
+        assert!((face_area(&outset) - expected(3.5, 0.0)).abs() < 1e-6);
+
+        assert!(offset_face(&ring, EFloat64::from(2.5)).unwrap().is_none());
+    }
+}
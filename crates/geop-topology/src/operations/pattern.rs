@@ -0,0 +1,88 @@
+use geop_geometry::{
+    curves::line::Line, efloat::EFloat64, geometry_error::GeometryError, point::Point,
+    transforms::Transform,
+};
+
+use crate::{
+    topology::volume::Volume,
+    topology_error::{TopologyError, TopologyResult},
+};
+
+fn copies(volume: &Volume, count: usize, transform: impl Fn(usize) -> Transform) -> Vec<Volume> {
+    (0..count).map(|i| volume.transform(transform(i))).collect()
+}
+
+fn check_count(count: usize) -> TopologyResult<()> {
+    match count {
+        0 => Err(TopologyError::new(
+            "A pattern needs at least one instance".to_string(),
+        )),
+        _ => Ok(()),
+    }
+}
+
+// Count copies of the volume, spacing apart along the direction. The first one is the volume itself.
+pub fn linear_pattern(
+    volume: &Volume,
+    direction: Point,
+    count: usize,
+    spacing: EFloat64,
+) -> TopologyResult<Vec<Volume>> {
+    check_count(count)?;
+    let step = direction.normalize().map_err(GeometryError::from)? * spacing;
+    Ok(copies(volume, count, |i| {
+        Transform::from_translation(step * EFloat64::from(i as f64))
+    }))
+}
+
+// Count copies of the volume, rotated by angle around the axis from one to the next. The first one is the volume itself.
+// For copies evenly distributed around the full circle, the angle is 2 pi / count.
+pub fn circular_pattern(
+    volume: &Volume,
+    axis: &Line,
+    count: usize,
+    angle: EFloat64,
+) -> TopologyResult<Vec<Volume>> {
+    check_count(count)?;
+    Ok(copies(volume, count, |i| {
+        Transform::from_axis_angle(axis.basis, axis.direction, angle * EFloat64::from(i as f64))
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::{
+        mass_properties::volume_mass_properties, primitive_objects::volumes::cube::primitive_cube,
+    };
+
+    fn centroid(volume: &Volume) -> Point {
+        volume_mass_properties(volume).unwrap().centroid
+    }
+
+    #[test]
+    fn test_linear_pattern() {
+        let cube = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one());
+        let copies =
+            linear_pattern(&cube, Point::from_f64(2.0, 0.0, 0.0), 3, EFloat64::two()).unwrap();
+        assert_eq!(copies.len(), 3);
+        assert!((centroid(&copies[2]) - Point::from_f64(4.0, 0.0, 0.0)).norm() < 1e-9);
+        assert!(linear_pattern(&cube, Point::unit_x(), 0, EFloat64::one()).is_err());
+        assert!(linear_pattern(&cube, Point::zero(), 2, EFloat64::one()).is_err());
+    }
+
+    #[test]
+    fn test_circular_pattern() {
+        let cube = primitive_cube(EFloat64::one(), EFloat64::one(), EFloat64::one())
+            .transform(Transform::from_translation(Point::from_f64(3.0, 0.0, 0.0)));
+        let axis = Line::new(Point::zero(), Point::unit_z()).unwrap();
+        let copies = circular_pattern(&cube, &axis, 4, EFloat64::from(PI / 2.0)).unwrap();
+        assert_eq!(copies.len(), 4);
+        let c = centroid(&copies[1]);
+        assert!((c - Point::from_f64(0.0, 3.0, 0.0)).norm() < 1e-9);
+        let volume = volume_mass_properties(&copies[3]).unwrap().volume;
+        assert!((volume - 1.0).abs() < 1e-9);
+    }
+}
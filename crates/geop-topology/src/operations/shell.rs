@@ -0,0 +1,441 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{
+        cone::Cone, cylinder::Cylinder, plane::Plane, sphere::Sphere, surface::Surface, SurfaceLike,
+    },
+};
+
+use crate::{
+    operations::offset::keeps_direction,
+    primitive_objects::edges::line::primitive_line,
+    topology::{
+        contour::Contour,
+        edge::Edge,
+        face::Face,
+        shell::Shell,
+        store::{CoedgeId, EdgeId, FaceId, TopologyStore, VertexId},
+        volume::Volume,
+    },
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+fn same_face(a: &Face, b: &Face) -> bool {
+    let edges = b.all_edges();
+    a.surface == b.surface
+        && a.all_edges().len() == edges.len()
+        && a.all_edges()
+            .iter()
+            .all(|e| edges.iter().any(|other| e.same_orientation(other)))
+}
+
+// The surface moved inwards by the distance. Planes move along the normal, spheres and cylinders change their radius.
+fn offset_surface(surface: &Surface, distance: EFloat64) -> TopologyResult<Surface> {
+    let shrink = |radius: EFloat64, normal_outwards: bool| {
+        let radius = match normal_outwards {
+            true => radius - distance,
+            false => radius + distance,
+        };
+        match radius > 0.0 {
+            true => Ok(radius),
+            false => Err(TopologyError::new(format!(
+                "Shell thickness {} is larger than the radius of a face",
+                distance
+            ))),
+        }
+    };
+    match surface {
+        Surface::Plane(plane) => {
+            let normal = normalize(plane.u_slope.cross(plane.v_slope))?;
+            Ok(Surface::Plane(Plane::new(
+                plane.basis - normal * distance,
+                plane.u_slope,
+                plane.v_slope,
+            )))
+        }
+        Surface::Sphere(sphere) => Ok(Surface::Sphere(Sphere::new(
+            sphere.basis,
+            shrink(sphere.radius, sphere.normal_outwards)?,
+            sphere.normal_outwards,
+        ))),
+        Surface::Cylinder(cylinder) => Ok(Surface::Cylinder(Cylinder::new(
+            cylinder.basis,
+            cylinder.extend_dir,
+            shrink(cylinder.radius.norm(), cylinder.normal_outwards)?,
+            cylinder.normal_outwards,
+        ))),
+        // The apex moves along the axis, so that the cone keeps its slope.
+        Surface::Cone(cone) => {
+            let hypotenuse = (EFloat64::one() + cone.slope * cone.slope)
+                .sqrt()
+                .map_err(GeometryError::from)?;
+            let shift = (distance * hypotenuse / cone.slope).map_err(GeometryError::from)?;
+            let shift = match cone.normal_outwards {
+                true => shift,
+                false => -shift,
+            };
+            Ok(Surface::Cone(Cone::new(
+                cone.basis + cone.extend_dir * shift,
+                cone.extend_dir,
+                cone.slope,
+                cone.normal_outwards,
+            )))
+        }
+        // The offset of an ellipse is not an ellipse.
+        Surface::EllipticCylinder(_) => Err(TopologyError::new(
+            "Shell does not support elliptic cylinders".to_string(),
+        )),
+        // The offset of a hyperbolic paraboloid is not ruled.
+        Surface::HyperbolicParaboloid(_) => Err(TopologyError::new(
+            "Shell does not support hyperbolic paraboloids".to_string(),
+        )),
+    }
+}
+
+struct Offset {
+    store: TopologyStore,
+    // Removed faces are not offset, so the walls end in the plane of the opening.
+    distances: Vec<EFloat64>,
+    surfaces: Vec<Surface>,
+    vertices: Vec<Point>,
+}
+
+impl Offset {
+    fn new(store: TopologyStore, distances: Vec<EFloat64>) -> TopologyResult<Offset> {
+        let surfaces = store
+            .faces
+            .iter()
+            .zip(distances.iter())
+            .map(|(face, distance)| offset_surface(&face.face.surface, *distance))
+            .collect::<TopologyResult<Vec<Surface>>>()?;
+        let mut offset = Offset {
+            store,
+            distances,
+            surfaces,
+            vertices: Vec::new(),
+        };
+        for vertex in offset.store.vertex_ids() {
+            let point = offset.offset_vertex(vertex)?;
+            offset.vertices.push(point);
+        }
+        Ok(offset)
+    }
+
+    fn plane_normal(&self, face: FaceId) -> Option<Point> {
+        match self.store.face(face).face.surface.as_ref() {
+            Surface::Plane(plane) => plane.u_slope.cross(plane.v_slope).normalize().ok(),
+            _ => None,
+        }
+    }
+
+    // The vertex where the offset planes of the faces around it meet.
+    fn offset_vertex(&self, vertex_id: VertexId) -> TopologyResult<Point> {
+        let vertex = self.store.vertex(vertex_id).point;
+        let faces = self.store.faces_of_vertex(vertex_id);
+        let planes: Option<Vec<(Point, EFloat64)>> = faces
+            .iter()
+            .map(|f| self.plane_normal(*f).map(|n| (n, self.distances[f.0])))
+            .collect();
+        let Some(planes) = planes else {
+            return Err(TopologyError::new(format!(
+                "Shell only supports vertices between planar faces, but got {}",
+                vertex
+            )));
+        };
+        for i in 0..planes.len() {
+            for j in (i + 1)..planes.len() {
+                for k in (j + 1)..planes.len() {
+                    let (n1, t1) = planes[i];
+                    let (n2, t2) = planes[j];
+                    let (n3, t3) = planes[k];
+                    let det = n1.dot(n2.cross(n3));
+                    if det == 0.0 {
+                        continue;
+                    }
+                    let sum = n2.cross(n3) * t1 + n3.cross(n1) * t2 + n1.cross(n2) * t3;
+                    let point = vertex - (sum / det).map_err(GeometryError::from)?;
+                    if faces.iter().all(|f| self.surfaces[f.0].on_surface(point)) {
+                        return Ok(point);
+                    }
+                }
+            }
+        }
+        Err(TopologyError::new(format!(
+            "Offset faces do not meet in a single point at {}",
+            vertex
+        )))
+    }
+
+    // The offset of the edge, where the offset surfaces of its two faces meet.
+    fn offset_edge(&self, edge_id: EdgeId) -> TopologyResult<Edge> {
+        let data = self.store.edge(edge_id);
+        let faces: Vec<FaceId> = self.store.faces_of_edge(edge_id).collect();
+        match (&data.edge.curve, data.start, data.end) {
+            (Curve::Line(_), Some(start), Some(end)) => {
+                let (start, end) = (self.vertices[start.0], self.vertices[end.0]);
+                // The walls on both sides of the edge meet before the offset edge is reached.
+                let edge = match start == end {
+                    true => None,
+                    false => primitive_line(start, end).ok(),
+                };
+                match edge {
+                    Some(edge) if keeps_direction(&data.edge, &edge) => Ok(edge),
+                    _ => Err(TopologyError::new(format!(
+                        "Shell thickness is larger than the wall at {}",
+                        data.edge
+                    ))),
+                }
+            }
+            // A circle around a cylinder, cut by a plane perpendicular to the axis.
+            (Curve::Circle(circle), None, None) => {
+                let plane = faces
+                    .iter()
+                    .find_map(|f| self.plane_normal(*f).map(|n| (n, *f)));
+                let cylinder = faces.iter().find_map(|f| match &self.surfaces[f.0] {
+                    Surface::Cylinder(cylinder) => Some(cylinder.clone()),
+                    _ => None,
+                });
+                match (plane, cylinder) {
+                    (Some((normal, face)), Some(cylinder)) if normal.is_parallel(circle.normal) => {
+                        let center = circle.basis - normal * self.distances[face.0];
+                        let circle =
+                            Circle::try_new(center, circle.normal, cylinder.radius.norm())?;
+                        Ok(Edge::new(None, None, Curve::Circle(circle)))
+                    }
+                    _ => Err(TopologyError::new(format!(
+                        "Shell only supports circles between a plane and a cylinder, but got {}",
+                        data.edge
+                    ))),
+                }
+            }
+            _ => Err(TopologyError::new(format!(
+                "Shell does not support offsetting edge {}",
+                data.edge
+            ))),
+        }
+    }
+
+    fn offset_coedge(&self, coedge: CoedgeId) -> TopologyResult<Edge> {
+        let data = self.store.coedge(coedge);
+        let edge = self.offset_edge(data.edge)?;
+        match data.same_direction {
+            true => Ok(edge),
+            false => Ok(edge.flip()),
+        }
+    }
+
+    // The offset face, with the same orientation as the original face.
+    fn offset_contours(&self, face_id: FaceId) -> TopologyResult<Vec<Contour>> {
+        let contours = self
+            .store
+            .face(face_id)
+            .contours
+            .iter()
+            .map(|coedges| {
+                let edges = coedges
+                    .iter()
+                    .map(|c| self.offset_coedge(*c))
+                    .collect::<TopologyResult<Vec<Edge>>>()?;
+                Ok(Contour::new(edges))
+            })
+            .collect::<TopologyResult<Vec<Contour>>>()?;
+
+        // Full circles have no vertices that could swap. Instead, the circles of a face swap along the axis if the face is inverted.
+        let centers = |contours: &[Contour]| -> Vec<Option<Point>> {
+            contours
+                .iter()
+                .map(|c| match (c.edges.as_slice(), c.edges[0].start) {
+                    ([edge], None) => match &edge.curve {
+                        Curve::Circle(circle) => Some(circle.basis),
+                        _ => None,
+                    },
+                    _ => None,
+                })
+                .collect()
+        };
+        let face = &self.store.face(face_id).face;
+        let (before, after) = (centers(&face.boundaries), centers(&contours));
+        for i in 0..before.len() {
+            for j in (i + 1)..before.len() {
+                if let (Some(a), Some(b), Some(a_offset), Some(b_offset)) =
+                    (before[i], before[j], after[i], after[j])
+                {
+                    if a != b && (b_offset - a_offset).dot(b - a) <= 0.0 {
+                        return Err(TopologyError::new(format!(
+                            "Shell thickness is larger than the wall of face {}",
+                            face
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(contours)
+    }
+}
+
+// Hollows the volume, so that only walls of the given thickness remain.
+// Without faces to remove, the hollow space becomes a cavity. Otherwise, the removed faces become openings.
+// Inner faces are offset, not re-intersected, so a thickness that inverts or closes a wall returns an error.
+pub fn shell(
+    volume: &Volume,
+    thickness: EFloat64,
+    faces_to_remove: &[Face],
+) -> TopologyResult<Volume> {
+    if thickness <= 0.0 {
+        return Err(TopologyError::new(
+            "Shell thickness must be positive".to_string(),
+        ));
+    }
+    if !volume.cavities.is_empty() {
+        return Err(TopologyError::new(
+            "Shell does not support volumes with cavities yet".to_string(),
+        ));
+    }
+    let mut store = TopologyStore::new();
+    store.add_shell(&volume.boundary);
+
+    let removed: Vec<bool> = store
+        .faces
+        .iter()
+        .map(|f| faces_to_remove.iter().any(|r| same_face(&f.face, r)))
+        .collect();
+    if removed.iter().filter(|r| **r).count() != faces_to_remove.len() {
+        return Err(TopologyError::new(
+            "Faces to remove have to be faces of the volume".to_string(),
+        ));
+    }
+    let distances = removed
+        .iter()
+        .map(|r| match r {
+            true => EFloat64::zero(),
+            false => thickness,
+        })
+        .collect();
+    let offset = Offset::new(store, distances)?;
+
+    let mut outer = Vec::<Face>::new();
+    let mut inner = Vec::<Face>::new();
+    for face_id in offset.store.face_ids() {
+        let face = &offset.store.face(face_id).face;
+        let contours = offset.offset_contours(face_id)?;
+        if removed[face_id.0] {
+            // The opening is a rim between the original contour and the flipped inner contour.
+            let mut boundaries = face.boundaries.clone();
+            boundaries.extend(contours.iter().map(|c| c.flip()));
+            outer.push(Face::new(boundaries, face.surface.clone()));
+        } else {
+            outer.push(face.clone());
+            let surface = Rc::new(offset.surfaces[face_id.0].clone());
+            inner.push(Face::new(contours, surface).flip());
+        }
+    }
+
+    match faces_to_remove.is_empty() {
+        true => Ok(Volume::new(Shell::new(outer), vec![Shell::new(inner)])),
+        false => {
+            outer.extend(inner);
+            Ok(Volume::new(Shell::new(outer), vec![]))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::{
+        mass_properties::volume_mass_properties,
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+        validation::check_volume,
+    };
+
+    #[test]
+    fn test_shell_cube() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        );
+        let hollow = shell(&cube, EFloat64::from(0.25), &[]).unwrap();
+        assert_eq!(hollow.cavities.len(), 1);
+        let report = check_volume(&hollow);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&hollow).unwrap().volume;
+        assert!((volume - (8.0 - 3.375)).abs() < 1e-9, "{}", volume);
+
+        // Open at the top
+        let top = cube.boundary.faces[5].clone();
+        let open = shell(&cube, EFloat64::from(0.25), &[top]).unwrap();
+        assert_eq!(open.cavities.len(), 0);
+        assert_eq!(open.boundary.faces.len(), 11);
+        let report = check_volume(&open);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&open).unwrap().volume;
+        assert!(
+            (volume - (8.0 - 1.5 * 1.5 * 1.75)).abs() < 1e-9,
+            "{}",
+            volume
+        );
+    }
+
+    #[test]
+    fn test_shell_cylinder() {
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::from(1.0));
+        let disk = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let cylinder = extrude(disk, Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let top = cylinder.boundary.faces[2].clone();
+        let cup = shell(&cylinder, EFloat64::from(0.25), &[top]).unwrap();
+        let report = check_volume(&cup);
+        assert!(report.is_valid(), "{}", report);
+        let volume = volume_mass_properties(&cup).unwrap().volume;
+        let expected = PI * 2.0 - PI * 0.75 * 0.75 * 1.75;
+        assert!(
+            (volume - expected).abs() < 1e-6,
+            "{} != {}",
+            volume,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_shell_thicker_than_wall() {
+        // The walls of a plate that is 0.4 thick meet after 0.2, so the offset faces would be inverted.
+        let plate = primitive_cube(
+            EFloat64::from(4.0),
+            EFloat64::from(4.0),
+            EFloat64::from(0.4),
+        );
+        assert!(shell(&plate, EFloat64::from(0.3), &[]).is_err());
+        let top = plate.boundary.faces[5].clone();
+        assert!(shell(&plate, EFloat64::from(0.5), &[top]).is_err());
+        let hollow = shell(&plate, EFloat64::from(0.1), &[]).unwrap();
+        let report = check_volume(&hollow);
+        assert!(report.is_valid(), "{}", report);
+
+        // The circles of the inner cylinder would swap along the axis.
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::from(1.0));
+        let disk = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let coin = extrude(disk, Point::from_f64(0.0, 0.0, 0.4)).unwrap();
+        assert!(shell(&coin, EFloat64::from(0.3), &[]).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+use std::rc::Rc;
+
+use geop_geometry::{
+    curves::{circle::Circle, curve::Curve, helix::Helix},
+    efloat::EFloat64,
+    geometry_error::GeometryError,
+    point::Point,
+    surfaces::{
+        hyperbolic_paraboloid::HyperbolicParaboloid, plane::Plane, surface::Surface, SurfaceLike,
+    },
+};
+
+use crate::{
+    operations::extrude::extrude,
+    primitive_objects::edges::line::primitive_line,
+    topology::{contour::Contour, edge::Edge, face::Face, shell::Shell, volume::Volume},
+    topology_error::{normalize, TopologyError, TopologyResult},
+};
+
+// How the profile is oriented along the path.
+#[derive(Debug, Clone, Copy)]
+pub enum FrameMode {
+    // Follows the curvature of the path. Along a helix, this keeps the profile in the axial plane, like for a screw thread.
+    Frenet,
+    // Keeps the given direction as close to the binormal of the profile as possible.
+    FixedUp(Point),
+}
+
+// Curved path edges are sampled in steps of this angle.
+const STEP_ANGLE: f64 = std::f64::consts::PI / 16.0;
+
+// A planar face with a single contour. The order of the points defines the normal.
+pub(crate) fn polygon_face(points: &[Point]) -> TopologyResult<Face> {
+    let n = points.len();
+    let (u_slope, v_slope) = (points[1] - points[0], points[n - 1] - points[0]);
+    if u_slope.cross(v_slope).is_zero() {
+        return Err(TopologyError::new(format!(
+            "Polygon is degenerate at {}",
+            points[0]
+        )));
+    }
+    let edges = (0..n)
+        .map(|i| primitive_line(points[i], points[(i + 1) % n]))
+        .collect::<TopologyResult<Vec<Edge>>>()?;
+    let plane = Plane::new(points[0], u_slope, v_slope);
+    Ok(Face::new(
+        vec![Contour::new(edges)],
+        Rc::new(Surface::Plane(plane)),
+    ))
+}
+
+// The face between the segment a0 -> b0 and its copy a1 -> b1, oriented so that a0 -> b0 is used backwards.
+// Twisted quads are not planar and span the hyperbolic paraboloid through the two segments.
+// Quads that collapse to a line return an error.
+pub(crate) fn ruled_face(a0: Point, b0: Point, a1: Point, b1: Point) -> TopologyResult<Face> {
+    if a0 == a1 {
+        return polygon_face(&[b0, a0, b1]);
+    }
+    if b0 == b1 {
+        return polygon_face(&[b0, a0, a1]);
+    }
+    if (a0 - b0).cross(b1 - b0).is_zero() {
+        return Err(TopologyError::new(format!(
+            "Ruled face between {} -> {} and {} -> {} is degenerate",
+            a0, b0, a1, b1
+        )));
+    }
+    let plane = Plane::new(b0, a0 - b0, b1 - b0);
+    if plane.on_surface(a1) {
+        return polygon_face(&[b0, a0, a1, b1]);
+    }
+    // The contour runs clockwise in the parameters of the patch, so the normal points against d/du x d/dv.
+    let patch = HyperbolicParaboloid::from_corners(a0, b0, a1, b1, false)?;
+    let edges = vec![
+        primitive_line(b0, a0)?,
+        primitive_line(a0, a1)?,
+        primitive_line(a1, b1)?,
+        primitive_line(b1, b0)?,
+    ];
+    Ok(Face::new(
+        vec![Contour::new(edges)],
+        Rc::new(Surface::HyperbolicParaboloid(patch)),
+    ))
+}
+
+// A frame along the path. It is orthonormal, except at mitre joints, where its axes are sheared into the mitre plane.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    origin: Point,
+    tangent: Point,
+    normal: Point,
+    binormal: Point,
+}
+
+impl Frame {
+    fn new(origin: Point, tangent: Point, normal: Point) -> TopologyResult<Frame> {
+        let normal = normalize(normal - tangent * normal.dot(tangent))?;
+        Ok(Frame {
+            origin,
+            tangent,
+            normal,
+            binormal: tangent.cross(normal),
+        })
+    }
+
+    // Moves a point given relative to the other frame into this frame.
+    fn map(&self, other: &Frame, p: Point) -> Point {
+        self.origin + self.map_direction(other, p - other.origin)
+    }
+
+    fn map_direction(&self, other: &Frame, v: Point) -> Point {
+        self.tangent * v.dot(other.tangent)
+            + self.normal * v.dot(other.normal)
+            + self.binormal * v.dot(other.binormal)
+    }
+
+    // The frame rotated around the origin, so that the tangent turns into the given one by the shortest rotation.
+    fn rotate_to(&self, tangent: Point) -> TopologyResult<Frame> {
+        let axis = self.tangent.cross(tangent);
+        let cos = self.tangent.dot(tangent);
+        let rotate = |v: Point| -> TopologyResult<Point> {
+            let along =
+                (axis * axis.dot(v) / (EFloat64::one() + cos)).map_err(GeometryError::from)?;
+            Ok(v * cos + axis.cross(v) + along)
+        };
+        Ok(Frame {
+            origin: self.origin,
+            tangent,
+            normal: rotate(self.normal)?,
+            binormal: rotate(self.binormal)?,
+        })
+    }
+
+    // The frame moved along its tangent into the plane halfway between its tangent and the given one.
+    // Profiles in the incoming and the rotated outgoing frame both meet this plane in the same section.
+    fn mitre(&self, tangent: Point) -> TopologyResult<Frame> {
+        let plane_normal = self.tangent + tangent;
+        let along = self.tangent.dot(plane_normal);
+        let shear = |v: Point| -> TopologyResult<Point> {
+            Ok(v - (self.tangent * v.dot(plane_normal) / along).map_err(GeometryError::from)?)
+        };
+        Ok(Frame {
+            origin: self.origin,
+            tangent: shear(self.tangent)?,
+            normal: shear(self.normal)?,
+            binormal: shear(self.binormal)?,
+        })
+    }
+}
+
+// Any direction perpendicular to the given one.
+fn perpendicular(v: Point) -> TopologyResult<Point> {
+    let axis = match v.x.abs() < 0.5 {
+        true => Point::unit_x(),
+        false => Point::unit_y(),
+    };
+    normalize(v.cross(axis))
+}
+
+fn frame_at(edge: &Edge, t: f64, frame_mode: FrameMode) -> TopologyResult<Frame> {
+    let h = 1e-6;
+    let origin = edge.interpolate(t);
+    let before = edge.interpolate((t - h).max(0.0));
+    let after = edge.interpolate((t + h).min(1.0));
+    let tangent = normalize(after - before)?;
+    let normal = match frame_mode {
+        FrameMode::Frenet => match &edge.curve {
+            Curve::Circle(circle) => circle.basis - origin,
+            Curve::Helix(helix) => {
+                let axis = normalize(helix.pitch)?;
+                let radial = origin - helix.basis;
+                axis * radial.dot(axis) - radial
+            }
+            _ => perpendicular(tangent)?,
+        },
+        FrameMode::FixedUp(up) => {
+            let binormal = up - tangent * up.dot(tangent);
+            if binormal.norm() == 0.0 {
+                return Err(TopologyError::new(format!(
+                    "Up direction {} is parallel to the path at {}",
+                    up, origin
+                )));
+            }
+            binormal.cross(tangent)
+        }
+    };
+    Frame::new(origin, tangent, normal)
+}
+
+// The frames along the path. Lines keep the frame of their start, so the profile is only translated along them.
+// Where two lines meet at an angle, the frame is rotated onto the next line and the section at the corner lies in the mitre plane.
+fn path_frames(path: &[Edge], frame_mode: FrameMode) -> TopologyResult<Vec<Frame>> {
+    let mut frames = Vec::<Frame>::new();
+    for (i, edge) in path.iter().enumerate() {
+        let (Some(start), Some(end)) = (edge.start, edge.end) else {
+            return Err(TopologyError::new(format!(
+                "Sweep path edges need a start and an end point, but got {}",
+                edge
+            )));
+        };
+        if let Some(frame) = frames.last() {
+            let tangent = normalize(edge.interpolate(1e-6) - start)?;
+            let kink = !(frame.tangent.is_parallel(tangent) && frame.tangent.dot(tangent) > 0.0);
+            let lines = matches!(
+                (&path[i - 1].curve, &edge.curve),
+                (Curve::Line(_), Curve::Line(_))
+            );
+            if kink && (!lines || frame.tangent.is_parallel(tangent)) {
+                return Err(TopologyError::new(format!(
+                    "Sweep path turns between {} and {}, which is only supported between lines that do not reverse",
+                    path[i - 1], edge
+                )));
+            }
+        }
+        match &edge.curve {
+            Curve::Line(_) => {
+                let tangent = normalize(end - start)?;
+                let frame = match frames.pop() {
+                    Some(frame) if frame.tangent.is_parallel(tangent) => {
+                        frames.push(frame);
+                        frame
+                    }
+                    Some(frame) => {
+                        frames.push(frame.mitre(tangent)?);
+                        frame.rotate_to(tangent)?
+                    }
+                    None => {
+                        let frame = frame_at(edge, 0.0, frame_mode)?;
+                        frames.push(frame);
+                        frame
+                    }
+                };
+                frames.push(Frame {
+                    origin: frame.origin + (end - start),
+                    ..frame
+                });
+            }
+            Curve::Circle(Circle { radius, .. }) | Curve::Helix(Helix { radius, .. }) => {
+                let angle =
+                    (edge.length().unwrap() / radius.norm()).map_err(GeometryError::from)?;
+                let steps = (angle.upper_bound / STEP_ANGLE).ceil().max(1.0) as usize;
+                let first = match frames.is_empty() {
+                    true => 0,
+                    false => 1,
+                };
+                for i in first..=steps {
+                    frames.push(frame_at(edge, i as f64 / steps as f64, frame_mode)?);
+                }
+            }
+            _ => {
+                return Err(TopologyError::new(format!(
+                    "Sweep only supports lines, arcs and helices as path, but got {}",
+                    edge
+                )))
+            }
+        }
+    }
+    Ok(frames)
+}
+
+// Sweeps the face along the path, which is a chain of lines, arcs and helices.
+// Along a single line this is an extrusion. Otherwise, the profile has to consist of lines, and the side surfaces,
+// which are in general not analytic, are approximated by ruled surfaces between copies of the profile sampled along the path.
+// Each of them is a plane or a hyperbolic paraboloid, so the approximation passes exactly through the edges of every copy.
+pub fn sweep(face: &Face, path: &[Edge], frame_mode: FrameMode) -> TopologyResult<Volume> {
+    if path.is_empty() {
+        return Err(TopologyError::new("Sweep path is empty".to_string()));
+    }
+    for pair in path.windows(2) {
+        if pair[0].end != pair[1].start {
+            return Err(TopologyError::new(format!(
+                "Sweep path is not connected between {} and {}",
+                pair[0], pair[1]
+            )));
+        }
+    }
+    if path.first().unwrap().start == path.last().unwrap().end {
+        return Err(TopologyError::new(
+            "Sweep does not support closed paths".to_string(),
+        ));
+    }
+    let Surface::Plane(plane) = face.surface.as_ref() else {
+        return Err(TopologyError::new(
+            "Sweep only supports planar profiles".to_string(),
+        ));
+    };
+
+    let frames = path_frames(path, frame_mode)?;
+    let first = frames[0];
+
+    // The start cap has to face backwards.
+    let start_face = match plane.u_slope.cross(plane.v_slope).dot(first.tangent) > 0.0 {
+        true => face.flip(),
+        false => face.clone(),
+    };
+    if let [edge] = path {
+        if let Curve::Line(_) = edge.curve {
+            return extrude(start_face, edge.end.unwrap() - edge.start.unwrap());
+        }
+    }
+    if let Some(edge) = start_face
+        .all_edges()
+        .into_iter()
+        .find(|e| !matches!(e.curve, Curve::Line(_)))
+    {
+        return Err(TopologyError::new(format!(
+            "Sweep along curved paths only supports profiles made of lines, but got {}",
+            edge
+        )));
+    }
+
+    let mut faces = vec![start_face.clone()];
+    for contour in start_face.boundaries.iter() {
+        for edge in contour.edges.iter() {
+            let (a, b) = (edge.start.unwrap(), edge.end.unwrap());
+            for pair in frames.windows(2) {
+                faces.push(ruled_face(
+                    pair[0].map(&first, a),
+                    pair[0].map(&first, b),
+                    pair[1].map(&first, a),
+                    pair[1].map(&first, b),
+                )?);
+            }
+        }
+    }
+
+    let last = frames.last().unwrap();
+    let Surface::Plane(start_plane) = start_face.surface.as_ref() else {
+        unreachable!()
+    };
+    let end_contours = start_face
+        .boundaries
+        .iter()
+        .map(|contour| {
+            let edges = contour
+                .edges
+                .iter()
+                .map(|e| {
+                    primitive_line(
+                        last.map(&first, e.start.unwrap()),
+                        last.map(&first, e.end.unwrap()),
+                    )
+                })
+                .collect::<TopologyResult<Vec<Edge>>>()?;
+            Ok(Contour::new(edges))
+        })
+        .collect::<TopologyResult<Vec<Contour>>>()?;
+    let end_plane = Plane::new(
+        last.map(&first, start_plane.basis),
+        last.map_direction(&first, start_plane.u_slope),
+        last.map_direction(&first, start_plane.v_slope),
+    );
+    faces.push(Face::new(end_contours, Rc::new(Surface::Plane(end_plane))).flip());
+
+    Ok(Volume::new(Shell::new(faces), vec![]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use geop_geometry::efloat::EFloat64;
+
+    use super::*;
+    use crate::{mass_properties::volume_mass_properties, validation::check_volume};
+
+    #[test]
+    fn test_sweep_thread() {
+        let helix = Helix::new(
+            Point::zero(),
+            Point::unit_z(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            true,
+        );
+        let path = Edge::new(
+            Some(helix.point_at_pitch(EFloat64::zero())),
+            Some(helix.point_at_pitch(EFloat64::one())),
+            Curve::Helix(helix),
+        );
+        // A triangle in the axial plane, pointing away from the axis.
+        let profile = polygon_face(&[
+            Point::from_f64(2.0, 0.0, -0.2),
+            Point::from_f64(2.3, 0.0, 0.0),
+            Point::from_f64(2.0, 0.0, 0.2),
+        ])
+        .unwrap();
+        let thread = sweep(&profile, &[path], FrameMode::Frenet).unwrap();
+        let report = check_volume(&thread);
+        assert!(report.is_valid(), "{}", report);
+
+        // Pappus: The area times the distance travelled by the centroid around the axis.
+        let expected = 0.06 * 2.0 * PI * 2.1;
+        let volume = volume_mass_properties(&thread).unwrap().volume;
+        assert!(
+            (volume - expected).abs() < 0.01 * expected,
+            "{} != {}",
+            volume,
+            expected
+        );
+    }
+
+    #[test]
+    fn test_sweep_line() {
+        let profile = polygon_face(&[Point::zero(), Point::unit_y(), Point::unit_x()]).unwrap();
+        let path = primitive_line(Point::zero(), Point::from_f64(0.0, 0.0, 2.0)).unwrap();
+        let prism = sweep(&profile, &[path], FrameMode::Frenet).unwrap();
+        let volume = volume_mass_properties(&prism).unwrap().volume;
+        assert!((volume - 1.0).abs() < 1e-9, "{}", volume);
+    }
+
+    #[test]
+    fn test_sweep_polyline() {
+        // A square around the start of the path, perpendicular to it.
+        let profile = polygon_face(&[
+            Point::from_f64(0.0, -0.5, -0.5),
+            Point::from_f64(0.0, 0.5, -0.5),
+            Point::from_f64(0.0, 0.5, 0.5),
+            Point::from_f64(0.0, -0.5, 0.5),
+        ])
+        .unwrap();
+        let points = [
+            Point::zero(),
+            Point::from_f64(2.0, 0.0, 0.0),
+            Point::from_f64(2.0, 2.0, 0.0),
+            Point::from_f64(2.0, 2.0, 2.0),
+        ];
+        let path: Vec<Edge> = points
+            .windows(2)
+            .map(|pair| primitive_line(pair[0], pair[1]).unwrap())
+            .collect();
+
+        // The mitred corners add as much as they cut off, because the profile is centered on the path.
+        for (edges, expected) in [(2, 4.0), (3, 6.0)] {
+            let pipe = sweep(&profile, &path[..edges], FrameMode::Frenet).unwrap();
+            let report = check_volume(&pipe);
+            assert!(report.is_valid(), "{}", report);
+            let volume = volume_mass_properties(&pipe).unwrap().volume;
+            assert!((volume - expected).abs() < 1e-9, "{}", volume);
+        }
+
+        // A path that reverses cannot be mitred.
+        let back = [
+            path[0].clone(),
+            primitive_line(points[1], Point::from_f64(1.0, 0.0, 0.0)).unwrap(),
+        ];
+        assert!(sweep(&profile, &back, FrameMode::Frenet).is_err());
+    }
+}
@@ -57,5 +57,5 @@ pub fn primitive_cube(size_x: EFloat64, size_y: EFloat64, size_z: EFloat64) -> V
         ))),
     );
 
-    extrude(face, Point::new(EFloat64::zero(), EFloat64::zero(), size_z))
+    extrude(face, Point::new(EFloat64::zero(), EFloat64::zero(), size_z)).unwrap()
 }
@@ -0,0 +1,264 @@
+use geop_geometry::{
+    color::Category10Color,
+    curve_surface_intersection::curve_surface::{
+        curve_surface_intersection, CurveSurfaceIntersection,
+    },
+    curves::{curve::Curve, line::Line, CurveLike},
+    efloat::EFloat64,
+    geometry_error::{ElevateToGeometry, GeometryError},
+    point::Point,
+    surfaces::SurfaceLike,
+};
+
+use crate::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        face_point::{face_point_contains, FacePointContains},
+    },
+    topology::{edge::Edge, face::Face, volume::Volume},
+    topology_error::{ElevateToTopology, TopologyError, TopologyResult},
+    topology_scene::TopologyScene,
+};
+
+#[derive(Clone, Debug)]
+pub struct RayHit {
+    pub face: Face,
+    pub point: Point,
+    // Normal of the surface at the hit point. For the boundary of a volume, this points outwards.
+    pub normal: Point,
+    // Distance from the origin of the ray, e.g. point = origin + t * direction.normalize().
+    pub t: EFloat64,
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeHit {
+    pub edge: Edge,
+    // Closest point on the edge to the ray.
+    pub point: Point,
+    // Distance between the ray and the edge.
+    pub distance: EFloat64,
+    // Distance from the origin of the ray to the point on the ray that is closest to the edge.
+    pub t: EFloat64,
+}
+
+fn ray_direction(origin: Point, direction: Point) -> TopologyResult<Point> {
+    let context = |err: TopologyError| {
+        err.with_context_scene(
+            format!("Ray cast from {} in direction {}", origin, direction),
+            TopologyScene::with_points(vec![
+                (origin, Category10Color::Blue),
+                (origin + direction, Category10Color::Orange),
+            ]),
+        )
+    };
+    let context2 = |err: GeometryError| err.with_context("Normalize ray direction".to_string());
+    direction.normalize().elevate(&context2).elevate(&context)
+}
+
+// Intersects a ray with a face. Hits are sorted by distance from the origin. Hits behind the origin are ignored.
+// If the ray lies within the surface of the face, it does not hit the face.
+pub fn ray_cast_face(face: &Face, origin: Point, direction: Point) -> TopologyResult<Vec<RayHit>> {
+    let direction = ray_direction(origin, direction)?;
+    let line = Line::new(origin, direction)
+        .elevate(&|err| err.with_context("Create line for ray cast".to_string()))?;
+    Ok(ray_cast_face_with_line(face, &line))
+}
+
+fn ray_cast_face_with_line(face: &Face, line: &Line) -> Vec<RayHit> {
+    let mut hits = Vec::<RayHit>::new();
+    let points = match curve_surface_intersection(&Curve::Line(line.clone()), &face.surface) {
+        CurveSurfaceIntersection::Points(points) => points,
+        CurveSurfaceIntersection::None | CurveSurfaceIntersection::Curve(_) => return hits,
+    };
+    for point in points {
+        let t = (point - line.basis).dot(line.direction);
+        if t < 0.0 {
+            continue;
+        }
+        match face_point_contains(face, point) {
+            FacePointContains::Inside
+            | FacePointContains::OnEdge(_)
+            | FacePointContains::OnPoint(_) => hits.push(RayHit {
+                face: face.clone(),
+                point,
+                normal: face.surface.normal(point),
+                t,
+            }),
+            FacePointContains::Outside | FacePointContains::NotOnSurface => {}
+        }
+    }
+    sort_hits(&mut hits);
+    hits
+}
+
+fn sort_hits(hits: &mut [RayHit]) {
+    hits.sort_by(|a, b| a.t.to_f64().total_cmp(&b.t.to_f64()));
+}
+
+// Intersects a ray with all faces of a volume, including the cavities. Hits are sorted by distance from the origin.
+// A ray that passes through an edge or a vertex hits every face that is adjacent to it.
+pub fn ray_cast(volume: &Volume, origin: Point, direction: Point) -> TopologyResult<Vec<RayHit>> {
+    let direction = ray_direction(origin, direction)?;
+    let line = Line::new(origin, direction)
+        .elevate(&|err| err.with_context("Create line for ray cast".to_string()))?;
+    let mut hits = Vec::<RayHit>::new();
+    for face in volume.all_faces().iter() {
+        hits.extend(ray_cast_face_with_line(face, &line));
+    }
+    sort_hits(&mut hits);
+    Ok(hits)
+}
+
+// Distance from the point to the ray, and the parameter of the closest point on the ray.
+fn ray_point_distance(line: &Line, point: Point) -> (EFloat64, EFloat64) {
+    let t = (point - line.basis)
+        .dot(line.direction)
+        .max(EFloat64::zero());
+    ((point - (line.basis + line.direction * t)).norm(), t)
+}
+
+fn ray_edge_closest_point(line: &Line, edge: &Edge) -> EdgeHit {
+    let mut candidates = Vec::<Point>::new();
+    candidates.extend(edge.start);
+    candidates.extend(edge.end);
+
+    // Alternating projections between the ray and the curve converge to a local minimum of the distance.
+    let mut starts = vec![edge.get_midpoint()];
+    starts.extend(edge.start);
+    starts.extend(edge.end);
+    for start in starts {
+        let mut q = start;
+        for _ in 0..64 {
+            let (_, t) = ray_point_distance(line, q);
+            let next = edge.curve.project(line.basis + line.direction * t);
+            let converged = (next - q).norm() < 1e-12;
+            q = next;
+            if converged {
+                break;
+            }
+        }
+        if edge_point_contains(edge, q) != EdgePointContains::Outside {
+            candidates.push(q);
+        }
+    }
+
+    let mut best: Option<EdgeHit> = None;
+    for point in candidates {
+        let (distance, t) = ray_point_distance(line, point);
+        if best
+            .as_ref()
+            .is_none_or(|b| distance.to_f64() < b.distance.to_f64())
+        {
+            best = Some(EdgeHit {
+                edge: edge.clone(),
+                point,
+                distance,
+                t,
+            });
+        }
+    }
+    best.expect("Edge has at least a midpoint")
+}
+
+// Finds the edge of the volume that is closest to the ray, if it is closer than tolerance. Used for picking edges.
+// If several edges are within the tolerance, the one closest to the origin of the ray wins, since it is in front.
+pub fn pick_edge(
+    volume: &Volume,
+    origin: Point,
+    direction: Point,
+    tolerance: f64,
+) -> TopologyResult<Option<EdgeHit>> {
+    let direction = ray_direction(origin, direction)?;
+    let line = Line::new(origin, direction)
+        .elevate(&|err| err.with_context("Create line for edge picking".to_string()))?;
+
+    let mut edges = Vec::<Edge>::new();
+    for face in volume.all_faces().iter() {
+        for edge in face.all_edges() {
+            // Adjacent faces share their edges
+            if !edges.contains(&edge) {
+                edges.push(edge);
+            }
+        }
+    }
+
+    let mut best: Option<EdgeHit> = None;
+    for edge in edges.iter() {
+        let hit = ray_edge_closest_point(&line, edge);
+        if hit.distance.to_f64() > tolerance {
+            continue;
+        }
+        if best.as_ref().is_none_or(|b| hit.t.to_f64() < b.t.to_f64()) {
+            best = Some(hit);
+        }
+    }
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::primitive_objects::volumes::cube::primitive_cube;
+
+    #[test]
+    fn test_ray_cast_cube() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        );
+
+        let hits = ray_cast(
+            &cube,
+            Point::from_f64(-5.0, 0.2, 0.3),
+            Point::from_f64(2.0, 0.0, 0.0),
+        )
+        .unwrap();
+        assert_eq!(hits.len(), 2);
+        assert_eq!(hits[0].point, Point::from_f64(-1.0, 0.2, 0.3));
+        assert_eq!(hits[0].t, 4.0);
+        assert_eq!(hits[0].normal.normalize().unwrap(), -Point::unit_x());
+        assert_eq!(hits[1].point, Point::from_f64(1.0, 0.2, 0.3));
+        assert_eq!(hits[1].normal.normalize().unwrap(), Point::unit_x());
+
+        // From the inside, only the wall in front is hit
+        let hits = ray_cast(&cube, Point::zero(), Point::unit_z()).unwrap();
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].t, 1.0);
+
+        let hits = ray_cast(&cube, Point::from_f64(-5.0, 3.0, 0.0), Point::unit_x()).unwrap();
+        assert!(hits.is_empty());
+        assert!(ray_cast(&cube, Point::zero(), Point::zero()).is_err());
+    }
+
+    #[test]
+    fn test_pick_edge() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        );
+
+        // Passes the top edge at x = 1 at a distance of 0.01. The top edge at x = -1 is just as close, but behind it.
+        let hit = pick_edge(
+            &cube,
+            Point::from_f64(5.0, 0.3, 1.01),
+            -Point::unit_x(),
+            0.05,
+        )
+        .unwrap()
+        .unwrap();
+        assert!((hit.distance.to_f64() - 0.01).abs() < 1e-9);
+        assert_eq!(hit.point, Point::from_f64(1.0, 0.3, 1.0));
+        assert_eq!(hit.t, 4.0);
+
+        let hit = pick_edge(
+            &cube,
+            Point::from_f64(0.0, 0.0, 5.0),
+            -Point::unit_z(),
+            0.05,
+        )
+        .unwrap();
+        assert!(hit.is_none());
+    }
+}
@@ -191,38 +191,27 @@ impl Contour {
         result
     }
 
+    // The whole contour as a chain of edges that starts and ends at the point.
     pub fn get_subcurve_single_point(&self, point: Point) -> Vec<Edge> {
-        let i = match self.get_edge_index(point) {
-            EdgeIndex::OnEdge(i) => i,
-            EdgeIndex::OnCorner(i, _) => i,
+        let n = self.edges.len();
+        let (i, on_edge) = match self.get_edge_index(point) {
+            EdgeIndex::OnEdge(i) => (i, true),
+            // The second edge starts at the corner.
+            EdgeIndex::OnCorner(_, i) => (i, false),
         };
-        let mut result = Vec::<Edge>::new();
-        if Some(point) != self.edges[i].end {
-            result.push(Edge::new(
-                Some(point.clone()),
-                self.edges[i].end.clone(),
-                self.edges[i].curve.clone(),
-            ));
+        if !on_edge {
+            return (0..n).map(|j| self.edges[(i + j) % n].clone()).collect();
         }
-        for j in 1..(self.edges.len() - 1) {
-            let edge = self.edges[(i + j) % self.edges.len()].clone();
-            if edge.end == Some(point) {
-                result.push(edge);
-                break;
-            }
-            result.push(edge);
-        }
-        if Some(point) != self.edges[(i + self.edges.len() - 1) % self.edges.len()].start {
-            result.push(Edge::new(
-                self.edges[(i + self.edges.len() - 1) % self.edges.len()]
-                    .start
-                    .clone(),
-                Some(point.clone()),
-                self.edges[(i + self.edges.len() - 1) % self.edges.len()]
-                    .curve
-                    .clone(),
-            ));
+        let edge = &self.edges[i];
+        let mut result = vec![Edge::new(Some(point.clone()), edge.end, edge.curve.clone())];
+        for j in 1..n {
+            result.push(self.edges[(i + j) % n].clone());
         }
+        result.push(Edge::new(
+            edge.start,
+            Some(point.clone()),
+            edge.curve.clone(),
+        ));
         result
     }
 }
@@ -240,3 +229,69 @@ impl Display for Contour {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use crate::primitive_objects::{
+        edges::circle::primitive_circle, faces::rectangle::primitive_rectangle,
+    };
+
+    use super::*;
+
+    fn square() -> Contour {
+        primitive_rectangle(Point::zero(), Point::unit_x(), Point::unit_y()).boundaries[0].clone()
+    }
+
+    // The edges have to form a chain from the point back to the point.
+    fn assert_chain(edges: &[Edge], point: Point) {
+        assert_eq!(edges[0].start, Some(point));
+        assert_eq!(edges[edges.len() - 1].end, Some(point));
+        for pair in edges.windows(2) {
+            assert_eq!(pair[0].end, pair[1].start);
+        }
+    }
+
+    #[test]
+    fn test_get_subcurve_single_point_on_edge() {
+        let contour = square();
+        let point = Point::from_f64(0.0, 1.0, 0.0);
+        let edges = contour.get_subcurve_single_point(point);
+        assert_eq!(edges.len(), 5);
+        assert_chain(&edges, point);
+        // The edge with the point is split in two, and the other edges are kept in order.
+        assert_eq!(edges[0].end, Some(Point::from_f64(-1.0, 1.0, 0.0)));
+        for (edge, original) in edges[1..4].iter().zip(contour.edges[1..4].iter()) {
+            assert!(edge.same_orientation(original));
+        }
+        assert_eq!(edges[4].start, Some(Point::from_f64(1.0, 1.0, 0.0)));
+    }
+
+    #[test]
+    fn test_get_subcurve_single_point_on_corner() {
+        let contour = square();
+        let corner = Point::from_f64(-1.0, -1.0, 0.0);
+        let edges = contour.get_subcurve_single_point(corner);
+        assert_eq!(edges.len(), 4);
+        assert_chain(&edges, corner);
+        for (i, edge) in edges.iter().enumerate() {
+            assert!(edge.same_orientation(&contour.edges[(i + 2) % 4]));
+        }
+    }
+
+    #[test]
+    fn test_get_subcurve_single_point_on_circle() {
+        // A closed edge is split at the point into the part after and the part before it, which meet where the edge has no end.
+        let contour = Contour::new(vec![primitive_circle(
+            Point::zero(),
+            Point::unit_z(),
+            EFloat64::one(),
+        )]);
+        let point = Point::unit_x();
+        let edges = contour.get_subcurve_single_point(point);
+        assert_eq!(edges.len(), 2);
+        assert_chain(&edges, point);
+        assert_eq!(edges[0].end, None);
+    }
+}
@@ -50,6 +50,16 @@ impl Edge {
         Edge::new(self.end.clone(), self.start.clone(), self.curve.neg())
     }
 
+    // Same edge with the same direction. Equality of edges ignores the direction, except for closed edges without endpoints.
+    pub fn same_orientation(&self, other: &Edge) -> bool {
+        self.start == other.start && self.end == other.end && self.curve == other.curve
+    }
+
+    // Same edge with the opposite direction, e.g. other == self.flip().
+    pub fn opposite_orientation(&self, other: &Edge) -> bool {
+        self.start == other.end && self.end == other.start && self.curve == other.curve.neg()
+    }
+
     pub fn transform(&self, transform: Transform) -> Edge {
         Edge::new(
             transform * self.start,
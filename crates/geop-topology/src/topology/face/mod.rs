@@ -1,17 +1,22 @@
 use std::rc::Rc;
 
 use geop_geometry::{
+    bounding_box::BoundingBox,
     curve_surface_intersection::curve_surface::curve_surface_intersection,
+    curves::curve::Curve,
     efloat::EFloat64,
     point::Point,
     surfaces::{surface::Surface, SurfaceLike},
     transforms::Transform,
 };
 
-use crate::contains::{
-    contour_point::contour_point_contains,
-    edge_point::EdgePointContains,
-    face_point::{face_point_contains, FacePointContains},
+use crate::{
+    contains::{
+        contour_point::contour_point_contains,
+        edge_point::EdgePointContains,
+        face_point::{face_point_contains, FacePointContains},
+    },
+    topology_error::{TopologyError, TopologyResult},
 };
 
 use super::{
@@ -94,7 +99,10 @@ impl Face {
         for e1 in self.boundaries[0].edges.iter() {
             for e2 in self.boundaries[0].edges.iter() {
                 if e1 != e2 {
-                    let geodesic = self.edge_from_to(e1.get_midpoint(), e2.get_midpoint());
+                    let Ok(geodesic) = self.edge_from_to(e1.get_midpoint(), e2.get_midpoint())
+                    else {
+                        continue;
+                    };
                     let p = geodesic.get_midpoint();
                     if face_point_contains(self, p) == FacePointContains::Inside {
                         return Some(p);
@@ -122,7 +130,10 @@ impl Face {
         for e1 in self.all_edges().iter() {
             for e2 in self.all_edges().iter() {
                 if e1 != e2 {
-                    let geodesic = self.edge_from_to(e1.get_midpoint(), e2.get_midpoint());
+                    let Ok(geodesic) = self.edge_from_to(e1.get_midpoint(), e2.get_midpoint())
+                    else {
+                        continue;
+                    };
                     let p = geodesic.get_midpoint();
                     // println!("Checking {:?}", p);
                     if face_point_contains(self, p) == FacePointContains::Inside {
@@ -138,12 +149,13 @@ impl Face {
         panic!("No inner point found");
     }
 
-    pub fn edge_from_to(&self, from: Point, to: Point) -> Edge {
-        Edge::new(
+    // The edge along the geodesic from one point to the other. Fails if the geodesic is not one of the supported curves.
+    pub fn edge_from_to(&self, from: Point, to: Point) -> TopologyResult<Edge> {
+        Ok(Edge::new(
             Some(from.clone()),
             Some(to.clone()),
-            self.surface.geodesic(from, to),
-        )
+            self.surface.geodesic(from, to)?,
+        ))
     }
 
     pub fn get_boundary_point(&self) -> Option<Point> {
@@ -171,6 +183,73 @@ impl Face {
         }
     }
 
+    // A box around the face. It is tight for lines, circles and ellipses, but not for arcs and spheres cut by other faces.
+    // Fails for faces with unbounded edges or edges on other curves.
+    pub fn bounding_box(&self) -> TopologyResult<BoundingBox> {
+        let mut bounding_box: Option<BoundingBox> = None;
+        let mut add = |center: Point, extent: Point| match bounding_box.as_mut() {
+            Some(b) => {
+                b.add_point(center - extent);
+                b.add_point(center + extent);
+            }
+            None => bounding_box = Some(BoundingBox::new(center - extent, center + extent)),
+        };
+        // The extent of a circle along an axis is its radius, times how far the circle is tilted towards the axis.
+        let tilted = |normal: Point, radius: EFloat64| {
+            let extent = |n: EFloat64| {
+                radius
+                    * (EFloat64::one() - n * n)
+                        .max(EFloat64::zero())
+                        .sqrt()
+                        .unwrap()
+            };
+            Point::new(extent(normal.x), extent(normal.y), extent(normal.z))
+        };
+        match self.surface.as_ref() {
+            Surface::Sphere(sphere) => {
+                let r = sphere.radius;
+                add(sphere.basis, Point::new(r, r, r));
+            }
+            // The apex is not on any edge, but sticks out of the circles of the face.
+            Surface::Cone(cone)
+                if face_point_contains(self, cone.basis) != FacePointContains::Outside =>
+            {
+                add(cone.basis, Point::zero());
+            }
+            _ => {}
+        }
+        for edge in self.all_edges() {
+            match (&edge.curve, edge.start, edge.end) {
+                (Curve::Line(_), Some(start), Some(end)) => {
+                    add(start, Point::zero());
+                    add(end, Point::zero());
+                }
+                (Curve::Circle(circle), _, _) => {
+                    add(circle.basis, tilted(circle.normal, circle.radius.norm()))
+                }
+                (Curve::Ellipse(ellipse), _, _) => {
+                    let (major, minor) = (ellipse.major_radius, ellipse.minor_radius);
+                    let extent = |a: EFloat64, b: EFloat64| (a * a + b * b).sqrt().unwrap();
+                    add(
+                        ellipse.basis,
+                        Point::new(
+                            extent(major.x, minor.x),
+                            extent(major.y, minor.y),
+                            extent(major.z, minor.z),
+                        ),
+                    )
+                }
+                _ => {
+                    return Err(TopologyError::new(format!(
+                        "Cannot find the bounding box of the face at {}",
+                        edge
+                    )))
+                }
+            }
+        }
+        bounding_box.ok_or_else(|| TopologyError::new("The face has no edges".to_string()))
+    }
+
     pub fn neg(&self) -> Face {
         Face {
             boundaries: self.boundaries.iter().rev().map(|l| l.flip()).collect(),
@@ -216,6 +295,33 @@ impl std::fmt::Display for Face {
                     }
                 }
             }
+            Surface::Cone(c) => {
+                writeln!(f, "Cone with apex = {:?}, extend_dir = {:?}, slope = {:?} and normal direction = {:?}", c.basis, c.extend_dir, c.slope, c.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::EllipticCylinder(c) => {
+                writeln!(f, "Elliptic cylinder at basis = {:?} with extend_dir = {:?}, major_radius = {:?}, minor_radius = {:?} and normal direction = {:?}", c.basis, c.extend_dir, c.major_radius, c.minor_radius, c.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
+            Surface::HyperbolicParaboloid(h) => {
+                writeln!(f, "Hyperbolic paraboloid at basis = {:?} with u_slope = {:?}, v_slope = {:?}, twist = {:?} and normal direction = {:?}", h.basis, h.u_slope, h.v_slope, h.twist, h.normal_outwards)?;
+                for contour in self.boundaries.iter() {
+                    writeln!(f, "Boundary:")?;
+                    for edge in contour.edges.iter() {
+                        writeln!(f, "  {}", edge)?;
+                    }
+                }
+            }
         };
         Ok(())
     }
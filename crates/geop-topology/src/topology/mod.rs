@@ -3,4 +3,5 @@ pub mod edge;
 pub mod face;
 pub mod scene;
 pub mod shell;
+pub mod store;
 pub mod volume;
@@ -0,0 +1,448 @@
+use std::collections::HashMap;
+
+use geop_geometry::point::Point;
+
+use super::{edge::Edge, face::Face, shell::Shell, volume::Volume};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct VertexId(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct EdgeId(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CoedgeId(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct FaceId(pub usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ShellId(pub usize);
+
+#[derive(Clone, Debug)]
+pub struct VertexData {
+    pub point: Point,
+    pub edges: Vec<EdgeId>,
+}
+
+#[derive(Clone, Debug)]
+pub struct EdgeData {
+    // Oriented like the first coedge that uses it.
+    pub edge: Edge,
+    // None for closed edges without endpoints, like full circles.
+    pub start: Option<VertexId>,
+    pub end: Option<VertexId>,
+    pub coedges: Vec<CoedgeId>,
+}
+
+// The use of an edge by a contour of a face. The coedges of a contour form a loop with next and previous.
+#[derive(Clone, Debug)]
+pub struct CoedgeData {
+    pub edge: EdgeId,
+    // True if the contour runs along the edge in the direction of EdgeData::edge.
+    pub same_direction: bool,
+    pub face: FaceId,
+    pub contour: usize,
+    pub next: CoedgeId,
+    pub previous: CoedgeId,
+}
+
+#[derive(Clone, Debug)]
+pub struct FaceData {
+    pub face: Face,
+    pub shell: ShellId,
+    // Coedges of every contour, in the order of Face::boundaries.
+    pub contours: Vec<Vec<CoedgeId>>,
+}
+
+#[derive(Clone, Debug)]
+pub struct ShellData {
+    pub faces: Vec<FaceId>,
+}
+
+// Side length of the cells of the vertex index. A point is looked up in its own cell and the neighbouring cells,
+// which finds every equal point as long as both are narrower than half a cell. Wider points are always searched.
+const CELL_SIZE: f64 = 1e-6;
+
+// Shared topology of a volume. Every vertex and edge exists once, and is referenced by id from everything that uses it.
+// The value types Face, Contour and Edge are cloned freely, so they cannot answer adjacency queries. This store can.
+#[derive(Clone, Debug)]
+pub struct TopologyStore {
+    pub vertices: Vec<VertexData>,
+    pub edges: Vec<EdgeData>,
+    pub coedges: Vec<CoedgeData>,
+    pub faces: Vec<FaceData>,
+    pub shells: Vec<ShellData>,
+    // Vertices by the cell of their position, see CELL_SIZE.
+    vertex_cells: HashMap<(i64, i64, i64), Vec<VertexId>>,
+    wide_vertices: Vec<VertexId>,
+    // Edges by their vertices, in ascending order. Closed edges, like full circles, have no vertices.
+    edges_by_vertices: HashMap<(Option<VertexId>, Option<VertexId>), Vec<EdgeId>>,
+}
+
+impl TopologyStore {
+    pub fn new() -> TopologyStore {
+        TopologyStore {
+            vertices: Vec::new(),
+            edges: Vec::new(),
+            coedges: Vec::new(),
+            faces: Vec::new(),
+            shells: Vec::new(),
+            vertex_cells: HashMap::new(),
+            wide_vertices: Vec::new(),
+            edges_by_vertices: HashMap::new(),
+        }
+    }
+
+    // The boundary is the first shell, the cavities follow in order.
+    pub fn from_volume(volume: &Volume) -> TopologyStore {
+        let mut store = TopologyStore::new();
+        store.add_shell(&volume.boundary);
+        for cavity in volume.cavities.iter() {
+            store.add_shell(cavity);
+        }
+        store
+    }
+
+    pub fn add_shell(&mut self, shell: &Shell) -> ShellId {
+        let shell_id = ShellId(self.shells.len());
+        self.shells.push(ShellData { faces: Vec::new() });
+        for face in shell.faces.iter() {
+            let face_id = self.add_face(face, shell_id);
+            self.shells[shell_id.0].faces.push(face_id);
+        }
+        shell_id
+    }
+
+    fn add_face(&mut self, face: &Face, shell: ShellId) -> FaceId {
+        let face_id = FaceId(self.faces.len());
+        let mut contours = Vec::<Vec<CoedgeId>>::new();
+        for (contour_index, contour) in face.boundaries.iter().enumerate() {
+            let first = self.coedges.len();
+            let n = contour.edges.len();
+            for (i, edge) in contour.edges.iter().enumerate() {
+                let (edge_id, same_direction) = self.add_edge(edge);
+                let coedge_id = CoedgeId(self.coedges.len());
+                self.coedges.push(CoedgeData {
+                    edge: edge_id,
+                    same_direction,
+                    face: face_id,
+                    contour: contour_index,
+                    next: CoedgeId(first + (i + 1) % n),
+                    previous: CoedgeId(first + (i + n - 1) % n),
+                });
+                self.edges[edge_id.0].coedges.push(coedge_id);
+            }
+            contours.push((first..first + n).map(CoedgeId).collect());
+        }
+        self.faces.push(FaceData {
+            face: face.clone(),
+            shell,
+            contours,
+        });
+        face_id
+    }
+
+    // Returns the existing edge if there is one, and whether it has the same direction.
+    fn add_edge(&mut self, edge: &Edge) -> (EdgeId, bool) {
+        if let Some(edge_id) = self.find_edge(edge) {
+            let same_direction = self.edges[edge_id.0].edge.same_orientation(edge);
+            return (edge_id, same_direction);
+        }
+        let edge_id = EdgeId(self.edges.len());
+        let start = edge.start.map(|p| self.add_vertex(p));
+        let end = edge.end.map(|p| self.add_vertex(p));
+        for vertex in [start, end].into_iter().flatten() {
+            if !self.vertices[vertex.0].edges.contains(&edge_id) {
+                self.vertices[vertex.0].edges.push(edge_id);
+            }
+        }
+        self.edges.push(EdgeData {
+            edge: edge.clone(),
+            start,
+            end,
+            coedges: Vec::new(),
+        });
+        self.edges_by_vertices
+            .entry((start.min(end), start.max(end)))
+            .or_default()
+            .push(edge_id);
+        (edge_id, true)
+    }
+
+    fn add_vertex(&mut self, point: Point) -> VertexId {
+        if let Some(vertex_id) = self.find_vertex(point) {
+            return vertex_id;
+        }
+        let vertex_id = VertexId(self.vertices.len());
+        self.vertices.push(VertexData {
+            point,
+            edges: Vec::new(),
+        });
+        match TopologyStore::cell(point) {
+            Some(cell) => self.vertex_cells.entry(cell).or_default().push(vertex_id),
+            None => self.wide_vertices.push(vertex_id),
+        }
+        vertex_id
+    }
+
+    // The cell of the vertex index that contains the point, or None if the point is wider than half a cell.
+    fn cell(point: Point) -> Option<(i64, i64, i64)> {
+        let mut cell = [0i64; 3];
+        for (index, coordinate) in cell.iter_mut().zip([point.x, point.y, point.z]) {
+            if coordinate.upper_bound - coordinate.lower_bound > CELL_SIZE / 2.0 {
+                return None;
+            }
+            let center = (coordinate.upper_bound + coordinate.lower_bound) / 2.0;
+            *index = (center / CELL_SIZE).floor() as i64;
+        }
+        Some((cell[0], cell[1], cell[2]))
+    }
+
+    // Returns the vertex with the lowest id if the point matches several of them.
+    pub fn find_vertex(&self, point: Point) -> Option<VertexId> {
+        let Some((x, y, z)) = TopologyStore::cell(point) else {
+            return self
+                .vertices
+                .iter()
+                .position(|v| v.point == point)
+                .map(VertexId);
+        };
+        let mut candidates = self.wide_vertices.clone();
+        for dx in -1..=1 {
+            for dy in -1..=1 {
+                for dz in -1..=1 {
+                    if let Some(cell) = self.vertex_cells.get(&(x + dx, y + dy, z + dz)) {
+                        candidates.extend(cell.iter().copied());
+                    }
+                }
+            }
+        }
+        candidates
+            .into_iter()
+            .filter(|v| self.vertex(*v).point == point)
+            .min()
+    }
+
+    // Finds the edge regardless of its direction.
+    pub fn find_edge(&self, edge: &Edge) -> Option<EdgeId> {
+        let start = match edge.start {
+            Some(point) => Some(self.find_vertex(point)?),
+            None => None,
+        };
+        let end = match edge.end {
+            Some(point) => Some(self.find_vertex(point)?),
+            None => None,
+        };
+        self.edges_by_vertices
+            .get(&(start.min(end), start.max(end)))?
+            .iter()
+            .copied()
+            .find(|e| {
+                let other = &self.edge(*e).edge;
+                other.same_orientation(edge) || other.opposite_orientation(edge)
+            })
+    }
+
+    pub fn vertex(&self, id: VertexId) -> &VertexData {
+        &self.vertices[id.0]
+    }
+
+    pub fn edge(&self, id: EdgeId) -> &EdgeData {
+        &self.edges[id.0]
+    }
+
+    pub fn coedge(&self, id: CoedgeId) -> &CoedgeData {
+        &self.coedges[id.0]
+    }
+
+    pub fn face(&self, id: FaceId) -> &FaceData {
+        &self.faces[id.0]
+    }
+
+    pub fn edge_ids(&self) -> impl Iterator<Item = EdgeId> {
+        (0..self.edges.len()).map(EdgeId)
+    }
+
+    pub fn vertex_ids(&self) -> impl Iterator<Item = VertexId> {
+        (0..self.vertices.len()).map(VertexId)
+    }
+
+    pub fn face_ids(&self) -> impl Iterator<Item = FaceId> {
+        (0..self.faces.len()).map(FaceId)
+    }
+
+    // The edge as it is traversed by the coedge.
+    pub fn coedge_edge(&self, id: CoedgeId) -> Edge {
+        let coedge = self.coedge(id);
+        let edge = &self.edge(coedge.edge).edge;
+        match coedge.same_direction {
+            true => edge.clone(),
+            false => edge.flip(),
+        }
+    }
+
+    // The other coedges on the same edge. For a closed manifold shell, there is exactly one.
+    pub fn mates(&self, id: CoedgeId) -> impl Iterator<Item = CoedgeId> + '_ {
+        self.edge(self.coedge(id).edge)
+            .coedges
+            .iter()
+            .copied()
+            .filter(move |c| *c != id)
+    }
+
+    // Faces that meet at the edge. A face appears twice if it uses the edge twice, e.g. at a seam.
+    pub fn faces_of_edge(&self, id: EdgeId) -> impl Iterator<Item = FaceId> + '_ {
+        self.edge(id).coedges.iter().map(|c| self.coedge(*c).face)
+    }
+
+    pub fn edges_of_vertex(&self, id: VertexId) -> impl Iterator<Item = EdgeId> + '_ {
+        self.vertex(id).edges.iter().copied()
+    }
+
+    pub fn vertices_of_edge(&self, id: EdgeId) -> impl Iterator<Item = VertexId> {
+        let edge = self.edge(id);
+        edge.start.into_iter().chain(edge.end)
+    }
+
+    pub fn coedges_of_face(&self, id: FaceId) -> impl Iterator<Item = CoedgeId> + '_ {
+        self.face(id).contours.iter().flatten().copied()
+    }
+
+    pub fn edges_of_face(&self, id: FaceId) -> impl Iterator<Item = EdgeId> + '_ {
+        self.coedges_of_face(id).map(|c| self.coedge(c).edge)
+    }
+
+    // Faces that share an edge with the face, without duplicates and without the face itself.
+    pub fn neighbouring_faces(&self, id: FaceId) -> Vec<FaceId> {
+        let mut faces = Vec::<FaceId>::new();
+        for edge in self.edges_of_face(id) {
+            for face in self.faces_of_edge(edge) {
+                if face != id && !faces.contains(&face) {
+                    faces.push(face);
+                }
+            }
+        }
+        faces
+    }
+
+    // Faces that touch the vertex, without duplicates.
+    pub fn faces_of_vertex(&self, id: VertexId) -> Vec<FaceId> {
+        let mut faces = Vec::<FaceId>::new();
+        for edge in self.edges_of_vertex(id) {
+            for face in self.faces_of_edge(edge) {
+                if !faces.contains(&face) {
+                    faces.push(face);
+                }
+            }
+        }
+        faces
+    }
+}
+
+impl Default for TopologyStore {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use geop_geometry::efloat::EFloat64;
+
+    use super::*;
+    use crate::primitive_objects::{
+        edges::{circle::primitive_circle, line::primitive_line},
+        volumes::cube::primitive_cube,
+    };
+
+    #[test]
+    fn test_cube_adjacency() {
+        let cube = primitive_cube(
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+            EFloat64::from(2.0),
+        );
+        let store = TopologyStore::from_volume(&cube);
+        assert_eq!(store.vertices.len(), 8);
+        assert_eq!(store.edges.len(), 12);
+        assert_eq!(store.coedges.len(), 24);
+        assert_eq!(store.faces.len(), 6);
+
+        for edge in store.edge_ids() {
+            let coedges = &store.edge(edge).coedges;
+            assert_eq!(coedges.len(), 2);
+            // Adjacent faces run along the edge in opposite directions
+            assert_ne!(
+                store.coedge(coedges[0]).same_direction,
+                store.coedge(coedges[1]).same_direction
+            );
+            assert!(store
+                .coedge_edge(coedges[0])
+                .opposite_orientation(&store.coedge_edge(coedges[1])));
+        }
+        for vertex in store.vertex_ids() {
+            assert_eq!(store.edges_of_vertex(vertex).count(), 3);
+            assert_eq!(store.faces_of_vertex(vertex).len(), 3);
+        }
+        for face in store.face_ids() {
+            assert_eq!(store.neighbouring_faces(face).len(), 4);
+            // The coedges of a contour are connected
+            for coedge in store.coedges_of_face(face) {
+                let next = store.coedge(coedge).next;
+                assert_eq!(store.coedge(next).previous, coedge);
+                assert_eq!(store.coedge_edge(coedge).end, store.coedge_edge(next).start);
+            }
+        }
+
+        let edge = cube.boundary.faces[0].all_edges()[0].flip();
+        let edge_id = store.find_edge(&edge).unwrap();
+        assert_eq!(store.faces_of_edge(edge_id).count(), 2);
+    }
+
+    #[test]
+    fn test_find_vertex_and_edge() {
+        let mut store = TopologyStore::new();
+        let a = store.add_vertex(Point::from_f64(CELL_SIZE * 0.999, 0.0, 0.0));
+        let b = store.add_vertex(Point::from_f64(1.0, 2.0, 3.0));
+
+        // Equal points are found across the border of a cell
+        let across = Point::new(
+            EFloat64::new(CELL_SIZE * 1.002, CELL_SIZE * 0.998),
+            EFloat64::zero(),
+            EFloat64::zero(),
+        );
+        assert_eq!(store.find_vertex(across), Some(a));
+        assert_eq!(
+            store.find_vertex(Point::from_f64(CELL_SIZE * 2.0, 0.0, 0.0)),
+            None
+        );
+
+        // Wide points are compared with all vertices, and wide vertices with all points
+        let wide = Point::new(
+            EFloat64::new(1.5, 0.5),
+            EFloat64::from(2.0),
+            EFloat64::from(3.0),
+        );
+        assert_eq!(store.find_vertex(wide), Some(b));
+        let c = store.add_vertex(Point::new(
+            EFloat64::new(5.5, 4.5),
+            EFloat64::zero(),
+            EFloat64::zero(),
+        ));
+        assert_eq!(store.find_vertex(Point::from_f64(5.2, 0.0, 0.0)), Some(c));
+        assert_eq!(store.find_vertex(Point::from_f64(5.7, 0.0, 0.0)), None);
+
+        // Closed edges have no vertices
+        let circle = primitive_circle(Point::zero(), Point::unit_z(), EFloat64::one());
+        let line = primitive_line(Point::zero(), Point::unit_x()).unwrap();
+        let (circle_id, _) = store.add_edge(&circle);
+        let (line_id, _) = store.add_edge(&line);
+        assert_eq!(store.find_edge(&circle.flip()), Some(circle_id));
+        assert_eq!(store.find_edge(&line.flip()), Some(line_id));
+        assert_eq!(
+            store.find_edge(&primitive_line(Point::zero(), Point::unit_y()).unwrap()),
+            None
+        );
+    }
+}
@@ -1,6 +1,9 @@
-use geop_geometry::{point::Point, transforms::Transform};
+use geop_geometry::{bounding_box::BoundingBox, point::Point, transforms::Transform};
 
-use crate::contains::{face_point::FacePointContains, shell_point::shell_point_contains};
+use crate::{
+    contains::{face_point::FacePointContains, shell_point::shell_point_contains},
+    topology_error::{TopologyError, TopologyResult},
+};
 
 use super::{
     face::Face,
@@ -50,4 +53,21 @@ impl Volume {
         }
         panic!("Point is not on boundary");
     }
+
+    // A box around the volume. It is tight for lines, circles and ellipses, but not for arcs and spheres cut by other faces.
+    // Fails for volumes with unbounded edges or edges on other curves.
+    pub fn bounding_box(&self) -> TopologyResult<BoundingBox> {
+        let mut bounding_box: Option<BoundingBox> = None;
+        for face in self.all_faces() {
+            let face_box = face.bounding_box()?;
+            match bounding_box.as_mut() {
+                Some(b) => {
+                    b.add_point(face_box.min);
+                    b.add_point(face_box.max);
+                }
+                None => bounding_box = Some(face_box),
+            }
+        }
+        bounding_box.ok_or_else(|| TopologyError::new("The volume has no faces".to_string()))
+    }
 }
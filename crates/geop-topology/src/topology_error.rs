@@ -1,6 +1,9 @@
 use std::backtrace::Backtrace;
 
-use geop_geometry::geometry_error::{GeometryError, GeometryResult};
+use geop_geometry::{
+    geometry_error::{GeometryError, GeometryResult},
+    point::Point,
+};
 
 use crate::topology_scene::TopologyScene;
 
@@ -101,6 +104,11 @@ impl From<GeometryError> for TopologyError {
 
 pub type TopologyResult<T> = Result<T, TopologyError>;
 
+// The unit vector along p. Fails for zero vectors, like the normal of a degenerate face.
+pub fn normalize(p: Point) -> TopologyResult<Point> {
+    Ok(p.normalize().map_err(GeometryError::from)?)
+}
+
 pub trait WithContext<T> {
     fn with_context(
         self,
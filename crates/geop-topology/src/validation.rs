@@ -0,0 +1,372 @@
+use std::fmt::Display;
+
+use geop_geometry::{
+    color::Category10Color,
+    curve_curve_intersection::curve_curve::{curve_curve_intersection, CurveCurveIntersection},
+    curve_surface_intersection::curve_surface::curve_surface_intersection,
+    surfaces::surface::Surface,
+};
+
+use crate::{
+    contains::{
+        edge_point::{edge_point_contains, EdgePointContains},
+        volume_point::{volume_point_contains, VolumePointContains},
+    },
+    mass_properties::{contour_signed_area, face_area},
+    topology::{
+        contour::Contour,
+        edge::Edge,
+        face::Face,
+        shell::Shell,
+        store::{CoedgeId, TopologyStore},
+        volume::Volume,
+    },
+    topology_error::{TopologyError, TopologyResult},
+    topology_scene::TopologyScene,
+};
+
+pub struct ValidationProblem {
+    pub message: String,
+    // Highlights the offending entities.
+    pub scene: TopologyScene,
+}
+
+pub struct ValidationReport {
+    pub problems: Vec<ValidationProblem>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.problems.is_empty()
+    }
+
+    fn push(&mut self, message: String, scene: TopologyScene) {
+        self.problems.push(ValidationProblem { message, scene });
+    }
+
+    // Turns the first problem into an error, with its scene attached as context.
+    pub fn into_result(mut self) -> TopologyResult<()> {
+        if self.problems.is_empty() {
+            return Ok(());
+        }
+        let problem = self.problems.remove(0);
+        Err(TopologyError::new(format!(
+            "Validation found {} problems",
+            self.problems.len() + 1
+        ))
+        .with_context_scene(problem.message, problem.scene))
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        if self.problems.is_empty() {
+            return writeln!(f, "ValidationReport: valid");
+        }
+        writeln!(f, "ValidationReport: {} problems", self.problems.len())?;
+        for problem in self.problems.iter() {
+            writeln!(f, "  {}", problem.message)?;
+        }
+        Ok(())
+    }
+}
+
+fn edge_scene(face: &Face, edges: Vec<Edge>) -> TopologyScene {
+    let mut scene = TopologyScene::with_faces(vec![(face.clone(), Category10Color::Gray)]);
+    scene
+        .edges
+        .extend(edges.into_iter().map(|e| (e, Category10Color::Red)));
+    scene
+}
+
+fn check_contour(report: &mut ValidationReport, face: &Face, contour: &Contour) {
+    let n = contour.edges.len();
+    for i in 0..n {
+        let edge = &contour.edges[i];
+        let next = &contour.edges[(i + 1) % n];
+        if edge.end != next.start {
+            report.push(
+                format!("Contour is not closed between {} and {}", edge, next),
+                edge_scene(face, vec![edge.clone(), next.clone()]),
+            );
+        }
+    }
+
+    for i in 0..n {
+        for j in (i + 1)..n {
+            let a = &contour.edges[i];
+            let b = &contour.edges[j];
+            let adjacent = j == i + 1 || (i == 0 && j == n - 1);
+            let intersects = match curve_curve_intersection(&a.curve, &b.curve) {
+                CurveCurveIntersection::Curve(_) => {
+                    edge_point_contains(a, b.get_midpoint()) != EdgePointContains::Outside
+                        || edge_point_contains(b, a.get_midpoint()) != EdgePointContains::Outside
+                }
+                CurveCurveIntersection::FinitePoints(points) => points.iter().any(|p| {
+                    let shared = adjacent
+                        && ((a.end == Some(*p) && b.start == Some(*p))
+                            || (b.end == Some(*p) && a.start == Some(*p)));
+                    !shared
+                        && edge_point_contains(a, *p) != EdgePointContains::Outside
+                        && edge_point_contains(b, *p) != EdgePointContains::Outside
+                }),
+                CurveCurveIntersection::InfiniteDiscretePoints(_)
+                | CurveCurveIntersection::None => false,
+            };
+            if intersects {
+                report.push(
+                    format!("Contour intersects itself at {} and {}", a, b),
+                    edge_scene(face, vec![a.clone(), b.clone()]),
+                );
+            }
+        }
+    }
+}
+
+fn check_face(report: &mut ValidationReport, face: &Face) {
+    for edge in face.all_edges() {
+        if !curve_surface_intersection(&edge.curve, &face.surface).is_curve() {
+            report.push(
+                format!("Edge {} does not lie on the surface of its face", edge),
+                edge_scene(face, vec![edge.clone()]),
+            );
+        }
+    }
+
+    for contour in face.boundaries.iter() {
+        check_contour(report, face, contour);
+    }
+
+    // The outer contour runs counter-clockwise and the inner ones clockwise, seen from the normal. Then the area is positive.
+    if face_area(face) <= 0.0 {
+        report.push(
+            "Face has a negative area, the contours are oriented the wrong way".to_string(),
+            TopologyScene::with_faces(vec![(face.clone(), Category10Color::Red)]),
+        );
+    }
+    if let Surface::Plane(_) = face.surface.as_ref() {
+        let areas: Vec<f64> = face
+            .boundaries
+            .iter()
+            .map(|contour| contour_signed_area(face, contour))
+            .collect();
+        let outer = (0..areas.len()).max_by(|a, b| areas[*a].abs().total_cmp(&areas[*b].abs()));
+        for (i, area) in areas.iter().enumerate() {
+            let expected_positive = Some(i) == outer;
+            if (*area > 0.0) != expected_positive {
+                report.push(
+                    format!(
+                        "{} contour is oriented the wrong way",
+                        if expected_positive { "Outer" } else { "Inner" }
+                    ),
+                    edge_scene(face, face.boundaries[i].edges.clone()),
+                );
+            }
+        }
+    }
+}
+
+// Every edge has to be used exactly twice, once in each direction. This makes the shell closed and manifold along its edges.
+fn check_edge_usage(report: &mut ValidationReport, store: &TopologyStore) {
+    for edge_id in store.edge_ids() {
+        let edge = store.edge(edge_id);
+        let forward = edge
+            .coedges
+            .iter()
+            .filter(|c| store.coedge(**c).same_direction)
+            .count();
+        let backward = edge.coedges.len() - forward;
+        if forward != 1 || backward != 1 {
+            let mut scene =
+                TopologyScene::with_edges(vec![(edge.edge.clone(), Category10Color::Red)]);
+            scene.face.extend(
+                store
+                    .faces_of_edge(edge_id)
+                    .map(|f| (store.face(f).face.clone(), Category10Color::Gray)),
+            );
+            report.push(
+                format!(
+                    "Edge {} is used {} times in one direction and {} times in the other, expected once each",
+                    edge.edge, forward, backward
+                ),
+                scene,
+            );
+        }
+    }
+}
+
+// The faces around a vertex have to form a single fan. Otherwise, the shell touches itself at the vertex.
+fn check_vertex_fans(report: &mut ValidationReport, store: &TopologyStore) {
+    for vertex_id in store.vertex_ids() {
+        // Every corner of a face at the vertex is given by the coedge that ends there.
+        let corners: Vec<CoedgeId> = store
+            .edges_of_vertex(vertex_id)
+            .flat_map(|e| store.edge(e).coedges.iter().copied())
+            .filter(|c| {
+                let coedge = store.coedge(*c);
+                let edge = store.edge(coedge.edge);
+                let end = match coedge.same_direction {
+                    true => edge.end,
+                    false => edge.start,
+                };
+                end == Some(vertex_id)
+            })
+            .collect();
+
+        // Walk around the vertex: The mate of the outgoing coedge ends at the vertex in the next face.
+        let mut visited = Vec::<CoedgeId>::new();
+        let mut current = match corners.first() {
+            Some(first) => *first,
+            None => continue,
+        };
+        let closed = loop {
+            if visited.contains(&current) {
+                break true;
+            }
+            visited.push(current);
+            let outgoing = store.coedge(current).next;
+            match store.mates(outgoing).next() {
+                Some(next) => current = next,
+                // Open edges are reported by check_edge_usage
+                None => break false,
+            }
+        };
+        if closed && visited.len() < corners.len() {
+            let vertex = store.vertex(vertex_id).point;
+            report.push(
+                format!("Shell is not manifold at vertex {}", vertex),
+                TopologyScene::with_points(vec![(vertex, Category10Color::Red)]),
+            );
+        }
+    }
+}
+
+fn check_shell(report: &mut ValidationReport, shell: &Shell) {
+    for face in shell.faces.iter() {
+        check_face(report, face);
+    }
+    let mut store = TopologyStore::new();
+    store.add_shell(shell);
+    check_edge_usage(report, &store);
+    check_vertex_fans(report, &store);
+}
+
+// Checks that the volume is a valid solid and reports every problem that is found.
+pub fn check_volume(volume: &Volume) -> ValidationReport {
+    let mut report = ValidationReport {
+        problems: Vec::new(),
+    };
+    check_shell(&mut report, &volume.boundary);
+    for cavity in volume.cavities.iter() {
+        check_shell(&mut report, cavity);
+    }
+
+    let outside = Volume::new(volume.boundary.clone(), vec![]);
+    for cavity in volume.cavities.iter() {
+        let point = cavity
+            .faces
+            .iter()
+            .find_map(|face| face.get_boundary_point());
+        let inside = match point {
+            Some(point) => matches!(
+                volume_point_contains(&outside, point),
+                VolumePointContains::Inside
+            ),
+            None => false,
+        };
+        if !inside {
+            let mut scene = TopologyScene::with_volumes(vec![(
+                Volume::new(cavity.clone(), vec![]),
+                Category10Color::Red,
+            )]);
+            scene.face.extend(
+                volume
+                    .boundary
+                    .faces
+                    .iter()
+                    .map(|f| (f.clone(), Category10Color::Gray)),
+            );
+            report.push("Cavity is not inside of the boundary".to_string(), scene);
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use std::rc::Rc;
+
+    use geop_geometry::{
+        efloat::EFloat64, point::Point, surfaces::plane::Plane, transforms::Transform,
+    };
+
+    use super::*;
+    use crate::{
+        operations::extrude::extrude,
+        primitive_objects::{edges::circle::primitive_circle, volumes::cube::primitive_cube},
+    };
+
+    fn cube(size: f64) -> Volume {
+        primitive_cube(
+            EFloat64::from(size),
+            EFloat64::from(size),
+            EFloat64::from(size),
+        )
+    }
+
+    #[test]
+    fn test_valid_cube() {
+        let report = check_volume(&cube(2.0));
+        assert!(report.is_valid(), "{}", report);
+        assert!(report.into_result().is_ok());
+
+        // The cylinder has faces without vertices
+        let circle = primitive_circle(Point::zero(), -Point::unit_z(), EFloat64::from(1.0));
+        let disk = Face::new(
+            vec![Contour::new(vec![circle])],
+            Rc::new(Surface::Plane(Plane::new(
+                Point::zero(),
+                Point::unit_y(),
+                Point::unit_x(),
+            ))),
+        );
+        let report = check_volume(&extrude(disk, Point::unit_z()).unwrap());
+        assert!(report.is_valid(), "{}", report);
+    }
+
+    #[test]
+    fn test_open_and_flipped_shell() {
+        let mut open = cube(2.0);
+        open.boundary.faces.pop();
+        let report = check_volume(&open);
+        // The four edges of the missing face are used only once
+        assert_eq!(report.problems.len(), 4, "{}", report);
+
+        let mut flipped = cube(2.0);
+        let face = flipped.boundary.faces[0].clone();
+        flipped.boundary.faces[0] = Face::new(
+            face.boundaries.iter().map(|c| c.flip()).collect(),
+            face.surface.clone(),
+        );
+        let report = check_volume(&flipped);
+        assert!(!report.is_valid());
+        assert!(report
+            .problems
+            .iter()
+            .any(|p| p.message.contains("oriented the wrong way")));
+        assert!(report.into_result().is_err());
+    }
+
+    #[test]
+    fn test_cavity_outside() {
+        let mut volume = cube(2.0);
+        let cavity =
+            cube(1.0).transform(Transform::from_translation(Point::from_f64(5.0, 0.0, 0.0)));
+        volume.cavities.push(cavity.boundary);
+        let report = check_volume(&volume);
+        assert!(report
+            .problems
+            .iter()
+            .any(|p| p.message == "Cavity is not inside of the boundary"));
+    }
+}
@@ -1,5 +1,7 @@
 use wgpu::util::DeviceExt;
 
+use crate::orbit_camera::OrbitCamera;
+
 pub struct Camera {
     pub eye: cgmath::Point3<f32>,
     pub target: cgmath::Point3<f32>,
@@ -19,6 +21,22 @@ pub const OPENGL_TO_WGPU_MATRIX: cgmath::Matrix4<f32> = cgmath::Matrix4::new(
 );
 
 impl Camera {
+    pub fn new(aspect: f32) -> Camera {
+        Camera {
+            // position the camera one unit up and 2 units back
+            // +z is out of the screen
+            eye: (0.0, 1.0, 3.0).into(),
+            // have it look at the origin
+            target: (0.0, 0.0, 0.0).into(),
+            // which way is "up"
+            up: cgmath::Vector3::unit_z(),
+            aspect,
+            fovy: 45.0,
+            znear: 0.1,
+            zfar: 100.0,
+        }
+    }
+
     pub fn build_view_projection_matrix(&self) -> cgmath::Matrix4<f32> {
         let view = cgmath::Matrix4::look_at_rh(self.eye, self.target, self.up);
         let proj = cgmath::perspective(cgmath::Deg(self.fovy), self.aspect, self.znear, self.zfar);
@@ -34,15 +52,51 @@ pub struct CameraUniform {
     // We can't use cgmath with bytemuck directly so we'll have
     // to convert the Matrix4 into a 4x4 f32 array
     pub view_proj: [[f32; 4]; 4],
+    // Used for specular highlights. If w is 1, this is the eye position. If w is 0, this is the direction towards the viewer, e.g. for orthographic projections.
+    pub view_position: [f32; 4],
+}
+
+// Directional light plus ambient light. Specular highlights are disabled if specular is 0.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Lighting {
+    pub direction: [f32; 3],
+    pub ambient: f32,
+    pub specular: f32,
+    pub shininess: f32,
+}
+
+impl Default for Lighting {
+    fn default() -> Self {
+        Lighting {
+            direction: [2.0, 1.0, -2.0],
+            ambient: 0.5,
+            specular: 0.0,
+            shininess: 32.0,
+        }
+    }
 }
 
-// lib.rs
 #[repr(C)]
 #[derive(Debug, Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
-struct LightUniform {
+pub struct LightUniform {
     direction: [f32; 3],
+    ambient: f32,
+    specular: f32,
+    shininess: f32,
     // Due to uniforms requiring 16 byte (4 float) spacing, we need to use a padding field here
-    _padding: u32,
+    _padding: [u32; 2],
+}
+
+impl From<&Lighting> for LightUniform {
+    fn from(lighting: &Lighting) -> Self {
+        LightUniform {
+            direction: lighting.direction,
+            ambient: lighting.ambient,
+            specular: lighting.specular,
+            shininess: lighting.shininess,
+            _padding: [0; 2],
+        }
+    }
 }
 
 impl CameraUniform {
@@ -50,11 +104,24 @@ impl CameraUniform {
         use cgmath::SquareMatrix;
         Self {
             view_proj: cgmath::Matrix4::identity().into(),
+            view_position: [0.0, 0.0, 1.0, 0.0],
         }
     }
 
     pub fn update_view_proj(&mut self, camera: &Camera) {
         self.view_proj = camera.build_view_projection_matrix().into();
+        self.view_position = [camera.eye.x, camera.eye.y, camera.eye.z, 1.0];
+    }
+
+    pub fn update_view_orbit(&mut self, camera: &OrbitCamera) {
+        self.view_proj = camera.build_view_projection_matrix().into();
+        self.view_position = if camera.orthographic {
+            let direction = camera.eye() - camera.target;
+            [direction.x, direction.y, direction.z, 0.0]
+        } else {
+            let eye = camera.eye();
+            [eye.x, eye.y, eye.z, 1.0]
+        };
     }
 
     pub fn update_view_ortho(
@@ -67,6 +134,8 @@ impl CameraUniform {
         zfar: f32,
     ) {
         self.view_proj = cgmath::ortho(left, right, bottom, top, znear, zfar).into();
+        // The orthographic camera looks along -z
+        self.view_position = [0.0, 0.0, 1.0, 0.0];
     }
 }
 
@@ -75,25 +144,14 @@ pub struct CameraPipeline {
     pub camera_uniform: CameraUniform,
     pub camera_buffer: wgpu::Buffer,
     pub camera_bind_group: wgpu::BindGroup,
+    pub light_buffer: wgpu::Buffer,
     pub light_bind_group: wgpu::BindGroup,
     pub render_pipeline_layout: wgpu::PipelineLayout,
 }
 
 impl CameraPipeline {
     pub fn new(device: &wgpu::Device, size: winit::dpi::PhysicalSize<u32>) -> CameraPipeline {
-        let camera = Camera {
-            // position the camera one unit up and 2 units back
-            // +z is out of the screen
-            eye: (0.0, 1.0, 3.0).into(),
-            // have it look at the origin
-            target: (0.0, 0.0, 0.0).into(),
-            // which way is "up"
-            up: cgmath::Vector3::unit_z(),
-            aspect: size.width as f32 / size.height as f32,
-            fovy: 45.0,
-            znear: 0.1,
-            zfar: 100.0,
-        };
+        let camera = Camera::new(size.width as f32 / size.height as f32);
 
         let mut camera_uniform = CameraUniform::new();
         camera_uniform.update_view_proj(&camera);
@@ -104,10 +162,7 @@ impl CameraPipeline {
             usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
         });
 
-        let light_uniform = LightUniform {
-            direction: [2.0, 1.0, -2.0],
-            _padding: 0,
-        };
+        let light_uniform = LightUniform::from(&Lighting::default());
 
         // We'll want to update our lights position, so we use COPY_DST
         let light_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
@@ -144,7 +199,7 @@ impl CameraPipeline {
             device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
                 entries: &[wgpu::BindGroupLayoutEntry {
                     binding: 0,
-                    visibility: wgpu::ShaderStages::VERTEX,
+                    visibility: wgpu::ShaderStages::VERTEX | wgpu::ShaderStages::FRAGMENT,
                     ty: wgpu::BindingType::Buffer {
                         ty: wgpu::BufferBindingType::Uniform,
                         has_dynamic_offset: false,
@@ -176,6 +231,7 @@ impl CameraPipeline {
             camera_uniform,
             camera_buffer,
             camera_bind_group,
+            light_buffer,
             light_bind_group,
             render_pipeline_layout,
         };
@@ -0,0 +1,302 @@
+use std::{
+    fmt::{Display, Formatter},
+    path::{Path, PathBuf},
+};
+
+use image::{Rgba, RgbaImage};
+
+// Set this environment variable to overwrite the reference images with the rendered images instead of comparing them.
+pub const UPDATE_REFERENCES_ENV: &str = "GEOP_UPDATE_REFERENCES";
+
+pub struct ImageTolerance {
+    // Maximum perceptual difference (CIE76 delta E) at which two pixels are still considered equal. 2.3 is about the just noticeable difference.
+    pub max_pixel_delta: f64,
+    // Fraction of pixels that may differ before the images are considered different.
+    pub max_mismatch_ratio: f64,
+    // If set, a pixel only counts as different if none of the reference pixels in the 3x3 neighbourhood match it. This ignores edges that moved by one pixel.
+    pub ignore_one_pixel_shift: bool,
+}
+
+impl ImageTolerance {
+    pub fn exact() -> ImageTolerance {
+        ImageTolerance {
+            max_pixel_delta: 0.0,
+            max_mismatch_ratio: 0.0,
+            ignore_one_pixel_shift: false,
+        }
+    }
+}
+
+impl Default for ImageTolerance {
+    fn default() -> Self {
+        ImageTolerance {
+            max_pixel_delta: 2.3,
+            max_mismatch_ratio: 0.001,
+            ignore_one_pixel_shift: true,
+        }
+    }
+}
+
+pub struct ImageComparison {
+    pub mismatched_pixels: usize,
+    pub total_pixels: usize,
+    pub max_delta: f64,
+    // Mismatched pixels in red, the rest is a faded version of the reference.
+    pub diff_image: RgbaImage,
+}
+
+impl ImageComparison {
+    pub fn mismatch_ratio(&self) -> f64 {
+        self.mismatched_pixels as f64 / self.total_pixels.max(1) as f64
+    }
+
+    pub fn is_within(&self, tolerance: &ImageTolerance) -> bool {
+        self.mismatch_ratio() <= tolerance.max_mismatch_ratio
+    }
+}
+
+#[derive(Debug)]
+pub enum GoldenImageError {
+    MissingReference(PathBuf),
+    SizeMismatch {
+        rendered: (u32, u32),
+        reference: (u32, u32),
+    },
+    Mismatch {
+        mismatched_pixels: usize,
+        total_pixels: usize,
+        max_delta: f64,
+        diff_path: PathBuf,
+    },
+    Image(String),
+}
+
+impl Display for GoldenImageError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GoldenImageError::MissingReference(path) => write!(
+                f,
+                "Reference image {} does not exist. Run with {}=1 to create it.",
+                path.display(),
+                UPDATE_REFERENCES_ENV
+            ),
+            GoldenImageError::SizeMismatch {
+                rendered,
+                reference,
+            } => write!(
+                f,
+                "Rendered image is {}x{}, but reference is {}x{}",
+                rendered.0, rendered.1, reference.0, reference.1
+            ),
+            GoldenImageError::Mismatch {
+                mismatched_pixels,
+                total_pixels,
+                max_delta,
+                diff_path,
+            } => write!(
+                f,
+                "{} of {} pixels differ from the reference (max delta E {:.2}). Diff written to {}. Run with {}=1 to accept the new image.",
+                mismatched_pixels,
+                total_pixels,
+                max_delta,
+                diff_path.display(),
+                UPDATE_REFERENCES_ENV
+            ),
+            GoldenImageError::Image(message) => write!(f, "Image error: {}", message),
+        }
+    }
+}
+
+impl From<image::ImageError> for GoldenImageError {
+    fn from(error: image::ImageError) -> Self {
+        GoldenImageError::Image(error.to_string())
+    }
+}
+
+pub type GoldenImageResult<T> = Result<T, GoldenImageError>;
+
+fn srgb_to_linear(c: u8) -> f64 {
+    let c = c as f64 / 255.0;
+    if c <= 0.04045 {
+        c / 12.92
+    } else {
+        ((c + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+// Converts a pixel to CIELAB (D65). Transparent pixels are composited over white first.
+fn to_lab(pixel: &Rgba<u8>) -> [f64; 3] {
+    let a = pixel.0[3] as f64 / 255.0;
+    let [r, g, b] = [0, 1, 2].map(|i| srgb_to_linear(pixel.0[i]) * a + (1.0 - a));
+
+    let x = (0.4124 * r + 0.3576 * g + 0.1805 * b) / 0.95047;
+    let y = 0.2126 * r + 0.7152 * g + 0.0722 * b;
+    let z = (0.0193 * r + 0.1192 * g + 0.9505 * b) / 1.08883;
+
+    let f = |t: f64| {
+        if t > 216.0 / 24389.0 {
+            t.cbrt()
+        } else {
+            (24389.0 / 27.0 * t + 16.0) / 116.0
+        }
+    };
+    let (fx, fy, fz) = (f(x), f(y), f(z));
+    [116.0 * fy - 16.0, 500.0 * (fx - fy), 200.0 * (fy - fz)]
+}
+
+fn delta_e(a: &[f64; 3], b: &[f64; 3]) -> f64 {
+    ((a[0] - b[0]).powi(2) + (a[1] - b[1]).powi(2) + (a[2] - b[2]).powi(2)).sqrt()
+}
+
+// Compares two images of the same size pixel by pixel in CIELAB space.
+pub fn compare_images(
+    rendered: &RgbaImage,
+    reference: &RgbaImage,
+    tolerance: &ImageTolerance,
+) -> GoldenImageResult<ImageComparison> {
+    if rendered.dimensions() != reference.dimensions() {
+        return Err(GoldenImageError::SizeMismatch {
+            rendered: rendered.dimensions(),
+            reference: reference.dimensions(),
+        });
+    }
+    let (width, height) = rendered.dimensions();
+    let reference_lab: Vec<[f64; 3]> = reference.pixels().map(to_lab).collect();
+
+    let mut diff_image = RgbaImage::new(width, height);
+    let mut mismatched_pixels = 0;
+    let mut max_delta: f64 = 0.0;
+    for (x, y, pixel) in rendered.enumerate_pixels() {
+        let lab = to_lab(pixel);
+        let i = (y * width + x) as usize;
+        let mut delta = delta_e(&lab, &reference_lab[i]);
+        if tolerance.ignore_one_pixel_shift && delta > tolerance.max_pixel_delta {
+            for ny in y.saturating_sub(1)..=(y + 1).min(height - 1) {
+                for nx in x.saturating_sub(1)..=(x + 1).min(width - 1) {
+                    let j = (ny * width + nx) as usize;
+                    delta = delta.min(delta_e(&lab, &reference_lab[j]));
+                }
+            }
+        }
+        max_delta = max_delta.max(delta);
+
+        if delta > tolerance.max_pixel_delta {
+            mismatched_pixels += 1;
+            diff_image.put_pixel(x, y, Rgba([255, 0, 0, 255]));
+        } else {
+            let gray = (reference_lab[i][0] / 100.0 * 64.0) as u8 + 191;
+            diff_image.put_pixel(x, y, Rgba([gray, gray, gray, 255]));
+        }
+    }
+
+    Ok(ImageComparison {
+        mismatched_pixels,
+        total_pixels: (width * height) as usize,
+        max_delta,
+        diff_image,
+    })
+}
+
+pub fn update_references() -> bool {
+    std::env::var(UPDATE_REFERENCES_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+// The diff image is written next to the rendered image, e.g. face1.png -> face1.diff.png
+pub fn diff_path(rendered_path: &Path) -> PathBuf {
+    let stem = rendered_path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    rendered_path.with_file_name(format!("{}.diff.png", stem))
+}
+
+// Compares a rendered image against a reference image. On mismatch, a diff image is written next to the rendered image.
+// If GEOP_UPDATE_REFERENCES is set, the reference is overwritten with the rendered image instead.
+pub fn compare_with_reference(
+    rendered_path: &Path,
+    reference_path: &Path,
+    tolerance: &ImageTolerance,
+) -> GoldenImageResult<ImageComparison> {
+    let rendered = image::open(rendered_path)?.to_rgba8();
+
+    if update_references() {
+        if let Some(parent) = reference_path.parent() {
+            std::fs::create_dir_all(parent).map_err(|e| GoldenImageError::Image(e.to_string()))?;
+        }
+        rendered.save(reference_path)?;
+        return compare_images(&rendered, &rendered, tolerance);
+    }
+
+    if !reference_path.exists() {
+        return Err(GoldenImageError::MissingReference(
+            reference_path.to_path_buf(),
+        ));
+    }
+    let reference = image::open(reference_path)?.to_rgba8();
+
+    let comparison = compare_images(&rendered, &reference, tolerance)?;
+    let diff_path = diff_path(rendered_path);
+    if !comparison.is_within(tolerance) {
+        comparison.diff_image.save(&diff_path)?;
+        return Err(GoldenImageError::Mismatch {
+            mismatched_pixels: comparison.mismatched_pixels,
+            total_pixels: comparison.total_pixels,
+            max_delta: comparison.max_delta,
+            diff_path,
+        });
+    }
+    // Remove a stale diff from an earlier failed run
+    if diff_path.exists() {
+        std::fs::remove_file(&diff_path).map_err(|e| GoldenImageError::Image(e.to_string()))?;
+    }
+    Ok(comparison)
+}
+
+pub fn assert_matches_reference(
+    rendered_path: &Path,
+    reference_path: &Path,
+    tolerance: &ImageTolerance,
+) {
+    if let Err(error) = compare_with_reference(rendered_path, reference_path, tolerance) {
+        panic!("{}", error);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn square(offset: u32, color: [u8; 4]) -> RgbaImage {
+        RgbaImage::from_fn(32, 32, |x, y| {
+            if (8 + offset..16 + offset).contains(&x) && (8..16).contains(&y) {
+                Rgba(color)
+            } else {
+                Rgba([255, 255, 255, 255])
+            }
+        })
+    }
+
+    #[test]
+    fn test_compare_images() {
+        let reference = square(0, [0, 0, 0, 255]);
+
+        let same = compare_images(&reference, &reference, &ImageTolerance::exact()).unwrap();
+        assert!(same.mismatched_pixels == 0);
+
+        // A barely visible color change is within the default tolerance
+        let similar = square(0, [1, 1, 1, 255]);
+        let comparison = compare_images(&similar, &reference, &ImageTolerance::default()).unwrap();
+        assert!(comparison.mismatched_pixels == 0);
+        let comparison = compare_images(&similar, &reference, &ImageTolerance::exact()).unwrap();
+        assert!(comparison.mismatched_pixels == 64);
+
+        // A shift by one pixel is ignored, but not a shift by two pixels
+        let shifted = square(1, [0, 0, 0, 255]);
+        let comparison = compare_images(&shifted, &reference, &ImageTolerance::default()).unwrap();
+        assert!(comparison.mismatched_pixels == 0);
+        let shifted = square(2, [0, 0, 0, 255]);
+        let comparison = compare_images(&shifted, &reference, &ImageTolerance::default()).unwrap();
+        assert!(comparison.mismatched_pixels > 0);
+        assert!(comparison.diff_image.get_pixel(17, 10).0 == [255, 0, 0, 255]);
+    }
+}
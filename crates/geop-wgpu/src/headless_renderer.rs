@@ -6,6 +6,7 @@ use geop_rasterize::{
         rasterize_face_into_line_list, rasterize_face_into_triangle_list,
         rasterize_face_into_vertex_list,
     },
+    mesh_buffer::MeshBuffer,
     triangle_buffer::TriangleBuffer,
     vertex_buffer::{RenderVertex, VertexBuffer},
     volume::{
@@ -16,9 +17,18 @@ use geop_rasterize::{
 use geop_topology::topology::scene::{Color, Scene};
 use winit::dpi::PhysicalSize;
 
-use crate::pipeline_manager::PipelineManager;
+use crate::{
+    camera_pipeline::{Camera, CameraUniform, Lighting},
+    pipeline_manager::PipelineManager,
+    software_renderer::SoftwareRenderer,
+};
 
-pub struct HeadlessRenderer {
+enum HeadlessCamera {
+    Orthogonal((f32, f32), (f32, f32)),
+    Perspective(Point),
+}
+
+struct WgpuTarget {
     pipeline_manager: PipelineManager,
     queue: wgpu::Queue,
     device: wgpu::Device,
@@ -29,8 +39,18 @@ pub struct HeadlessRenderer {
     copy_size: wgpu::Extent3d,
 }
 
-impl HeadlessRenderer {
-    pub async fn new() -> Self {
+enum HeadlessBackend {
+    Wgpu(Box<WgpuTarget>),
+    Software(SoftwareRenderer),
+}
+
+pub struct HeadlessRenderer {
+    backend: HeadlessBackend,
+}
+
+impl WgpuTarget {
+    // Returns None if there is no adapter, e.g. on machines without a GPU.
+    async fn new(texture_size: u32) -> Option<Self> {
         let instance = wgpu::Instance::new(wgpu::InstanceDescriptor {
             #[cfg(not(target_arch = "wasm32"))]
             backends: wgpu::Backends::PRIMARY,
@@ -45,13 +65,11 @@ impl HeadlessRenderer {
                 compatible_surface: None,
                 force_fallback_adapter: false,
             })
-            .await
-            .unwrap();
+            .await?;
         let (device, queue) = adapter
             .request_device(&Default::default(), None)
             .await
-            .unwrap();
-        let texture_size = 2048u32;
+            .ok()?;
 
         let texture_format = wgpu::TextureFormat::Rgba8UnormSrgb;
 
@@ -93,10 +111,7 @@ impl HeadlessRenderer {
         };
         let output_buffer = device.create_buffer(&output_buffer_desc);
 
-        // We need to scope the mapping variables so that we can
-        // unmap the buffer
-
-        HeadlessRenderer {
+        Some(WgpuTarget {
             pipeline_manager,
             queue,
             device,
@@ -105,20 +120,18 @@ impl HeadlessRenderer {
             texture,
             texture_size,
             copy_size: texture_desc.size,
-        }
+        })
     }
 
-    pub async fn render_buffers_to_file(
+    async fn render(
         &mut self,
-        vertex_buffer: VertexBuffer,
-        edge_buffer: EdgeBuffer,
-        triangle_buffer: TriangleBuffer,
-        dark_mode: bool,
-        (x_min, x_max): (f32, f32),
-        (y_min, y_max): (f32, f32),
+        vertex_buffer: &VertexBuffer,
+        edge_buffer: &EdgeBuffer,
+        triangle_buffer: &TriangleBuffer,
+        background_color: Color,
+        camera: &HeadlessCamera,
         file_path: &std::path::Path,
     ) {
-        let (background_color, _, _, _) = Color::standard_pallet(dark_mode);
         let u32_size = std::mem::size_of::<u32>() as u32;
         let mut encoder = self
             .device
@@ -152,14 +165,26 @@ impl HeadlessRenderer {
             };
 
             let mut render_pass = encoder.begin_render_pass(&render_pass_desc);
+            match camera {
+                HeadlessCamera::Orthogonal((x_min, x_max), (y_min, y_max)) => {
+                    self.pipeline_manager.update_camera_orthogonal(
+                        &self.queue,
+                        *x_min,
+                        *x_max,
+                        *y_min,
+                        *y_max,
+                    );
+                }
+                HeadlessCamera::Perspective(camera_pos) => {
+                    self.pipeline_manager
+                        .update_camera_pos(&self.queue, *camera_pos);
+                }
+            }
+            self.pipeline_manager.update_edges(&self.queue, edge_buffer);
             self.pipeline_manager
-                .update_camera_orthogonal(&self.queue, x_min, x_max, y_min, y_max);
-            self.pipeline_manager
-                .update_edges(&self.queue, &edge_buffer);
-            self.pipeline_manager
-                .update_triangles(&self.queue, &triangle_buffer);
+                .update_triangles(&self.queue, triangle_buffer);
             self.pipeline_manager
-                .update_vertices(&self.queue, &vertex_buffer);
+                .update_vertices(&self.queue, vertex_buffer);
             self.pipeline_manager.run_pipelines(&mut render_pass);
         }
 
@@ -182,26 +207,133 @@ impl HeadlessRenderer {
         );
         self.queue.submit(Some(encoder.finish()));
 
-        let buffer_slice = self.output_buffer.slice(..);
+        {
+            let buffer_slice = self.output_buffer.slice(..);
+
+            // NOTE: We have to create the mapping THEN device.poll() before await
+            // the future. Otherwise the application will freeze.
+            let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
+            buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
+                tx.send(result).unwrap();
+            });
+            self.device.poll(wgpu::Maintain::Wait);
+            rx.receive().await.unwrap().unwrap();
+
+            let data = buffer_slice.get_mapped_range();
+
+            use image::{ImageBuffer, Rgba};
+            let buffer =
+                ImageBuffer::<Rgba<u8>, _>::from_raw(self.texture_size, self.texture_size, data)
+                    .unwrap();
+            // Create folder if it doesn't exist
+            std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+            buffer.save(file_path).unwrap();
+        }
+        self.output_buffer.unmap();
+    }
+}
 
-        // NOTE: We have to create the mapping THEN device.poll() before await
-        // the future. Otherwise the application will freeze.
-        let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
-        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-            tx.send(result).unwrap();
-        });
-        self.device.poll(wgpu::Maintain::Wait);
-        rx.receive().await.unwrap().unwrap();
-
-        let data = buffer_slice.get_mapped_range();
-
-        use image::{ImageBuffer, Rgba};
-        let buffer =
-            ImageBuffer::<Rgba<u8>, _>::from_raw(self.texture_size, self.texture_size, data)
-                .unwrap();
-        // Create folder if it doesn't exist
-        std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
-        buffer.save(file_path).unwrap();
+impl HeadlessRenderer {
+    // Uses the GPU if there is one, and falls back to the software renderer otherwise.
+    pub async fn new() -> Self {
+        match WgpuTarget::new(2048).await {
+            Some(target) => HeadlessRenderer {
+                backend: HeadlessBackend::Wgpu(Box::new(target)),
+            },
+            None => HeadlessRenderer::new_software(),
+        }
+    }
+
+    // Renders on the CPU only. Does not need a wgpu adapter.
+    pub fn new_software() -> Self {
+        HeadlessRenderer {
+            backend: HeadlessBackend::Software(SoftwareRenderer::new(2048)),
+        }
+    }
+
+    pub fn is_software(&self) -> bool {
+        matches!(self.backend, HeadlessBackend::Software(_))
+    }
+
+    pub fn set_lighting(&mut self, lighting: &Lighting) {
+        match &mut self.backend {
+            HeadlessBackend::Wgpu(target) => target
+                .pipeline_manager
+                .update_lighting(&target.queue, lighting),
+            HeadlessBackend::Software(renderer) => renderer.lighting = *lighting,
+        }
+    }
+
+    async fn render_buffers(
+        &mut self,
+        vertex_buffer: &VertexBuffer,
+        edge_buffer: &EdgeBuffer,
+        triangle_buffer: &TriangleBuffer,
+        dark_mode: bool,
+        camera: HeadlessCamera,
+        file_path: &std::path::Path,
+    ) {
+        let (background_color, _, _, _) = Color::standard_pallet(dark_mode);
+        match &mut self.backend {
+            HeadlessBackend::Wgpu(target) => {
+                target
+                    .render(
+                        vertex_buffer,
+                        edge_buffer,
+                        triangle_buffer,
+                        background_color,
+                        &camera,
+                        file_path,
+                    )
+                    .await;
+            }
+            HeadlessBackend::Software(renderer) => {
+                // Same matrices as the ones PipelineManager writes into the camera uniform.
+                let mut camera_uniform = CameraUniform::new();
+                match camera {
+                    HeadlessCamera::Orthogonal((x_min, x_max), (y_min, y_max)) => {
+                        camera_uniform.update_view_ortho(x_min, x_max, y_min, y_max, -100.0, 100.0);
+                    }
+                    HeadlessCamera::Perspective(camera_pos) => {
+                        let mut camera = Camera::new(1.0);
+                        camera.eye.x = camera_pos.x.lower_bound as f32;
+                        camera.eye.y = camera_pos.y.lower_bound as f32;
+                        camera.eye.z = camera_pos.z.lower_bound as f32;
+                        camera_uniform.update_view_proj(&camera);
+                    }
+                }
+                renderer.view_position = camera_uniform.view_position;
+                renderer.render(
+                    &camera_uniform.view_proj.into(),
+                    background_color,
+                    vertex_buffer,
+                    edge_buffer,
+                    triangle_buffer,
+                );
+                renderer.save(file_path);
+            }
+        }
+    }
+
+    pub async fn render_buffers_to_file(
+        &mut self,
+        vertex_buffer: VertexBuffer,
+        edge_buffer: EdgeBuffer,
+        triangle_buffer: TriangleBuffer,
+        dark_mode: bool,
+        (x_min, x_max): (f32, f32),
+        (y_min, y_max): (f32, f32),
+        file_path: &std::path::Path,
+    ) {
+        self.render_buffers(
+            &vertex_buffer,
+            &edge_buffer,
+            &triangle_buffer,
+            dark_mode,
+            HeadlessCamera::Orthogonal((x_min, x_max), (y_min, y_max)),
+            file_path,
+        )
+        .await;
     }
 
     pub async fn render_buffers_to_file_3d(
@@ -213,90 +345,35 @@ impl HeadlessRenderer {
         camera_pos: Point,
         file_path: &std::path::Path,
     ) {
-        let (background_color, _, _, _) = Color::standard_pallet(dark_mode);
-        let u32_size = std::mem::size_of::<u32>() as u32;
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-        {
-            let render_pass_desc = wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: background_color.r as f64,
-                            g: background_color.g as f64,
-                            b: background_color.b as f64,
-                            a: background_color.a as f64,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: &self.pipeline_manager.depth_texture.view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                    stencil_ops: None,
-                }),
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            };
-
-            let mut render_pass = encoder.begin_render_pass(&render_pass_desc);
-            self.pipeline_manager
-                .update_camera_pos(&self.queue, camera_pos);
-            self.pipeline_manager
-                .update_edges(&self.queue, &edge_buffer);
-            self.pipeline_manager
-                .update_triangles(&self.queue, &triangle_buffer);
-            self.pipeline_manager
-                .update_vertices(&self.queue, &vertex_buffer);
-            self.pipeline_manager.run_pipelines(&mut render_pass);
-        }
-
-        encoder.copy_texture_to_buffer(
-            wgpu::ImageCopyTexture {
-                aspect: wgpu::TextureAspect::All,
-                texture: &self.texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-            },
-            wgpu::ImageCopyBuffer {
-                buffer: &self.output_buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(u32_size * self.texture_size),
-                    rows_per_image: Some(self.texture_size),
-                },
-            },
-            self.copy_size,
-        );
-        self.queue.submit(Some(encoder.finish()));
-
-        let buffer_slice = self.output_buffer.slice(..);
+        self.render_buffers(
+            &vertex_buffer,
+            &edge_buffer,
+            &triangle_buffer,
+            dark_mode,
+            HeadlessCamera::Perspective(camera_pos),
+            file_path,
+        )
+        .await;
+    }
 
-        // NOTE: We have to create the mapping THEN device.poll() before await
-        // the future. Otherwise the application will freeze.
-        let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
-        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-            tx.send(result).unwrap();
-        });
-        self.device.poll(wgpu::Maintain::Wait);
-        rx.receive().await.unwrap().unwrap();
-
-        let data = buffer_slice.get_mapped_range();
-
-        use image::{ImageBuffer, Rgba};
-        let buffer =
-            ImageBuffer::<Rgba<u8>, _>::from_raw(self.texture_size, self.texture_size, data)
-                .unwrap();
-        // Create folder if it doesn't exist
-        std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
-        buffer.save(file_path).unwrap();
+    // The mesh is expanded into a triangle list, which is drawn with the same lit shader as the mesh pipeline of GeopWindow.
+    pub async fn render_mesh_to_file_3d(
+        &mut self,
+        mesh: &MeshBuffer,
+        edge_buffer: EdgeBuffer,
+        dark_mode: bool,
+        camera_pos: Point,
+        file_path: &std::path::Path,
+    ) {
+        self.render_buffers(
+            &VertexBuffer::empty(),
+            &edge_buffer,
+            &mesh.to_triangle_buffer(),
+            dark_mode,
+            HeadlessCamera::Perspective(camera_pos),
+            file_path,
+        )
+        .await;
     }
 
     pub async fn render_to_file(
@@ -307,146 +384,68 @@ impl HeadlessRenderer {
         camera_pos: Point,
         file_path: &std::path::Path,
     ) {
-        let (background_color, face_color, edge_color, point_color) =
-            Color::standard_pallet(dark_mode);
-        let u32_size = std::mem::size_of::<u32>() as u32;
-        let mut encoder = self
-            .device
-            .create_command_encoder(&wgpu::CommandEncoderDescriptor { label: None });
-        {
-            let render_pass_desc = wgpu::RenderPassDescriptor {
-                label: Some("Render Pass"),
-                color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                    view: &self.texture_view,
-                    resolve_target: None,
-                    ops: wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(wgpu::Color {
-                            r: background_color.r as f64,
-                            g: background_color.g as f64,
-                            b: background_color.b as f64,
-                            a: background_color.a as f64,
-                        }),
-                        store: wgpu::StoreOp::Store,
-                    },
-                })],
-                depth_stencil_attachment: Some(wgpu::RenderPassDepthStencilAttachment {
-                    view: &self.pipeline_manager.depth_texture.view,
-                    depth_ops: Some(wgpu::Operations {
-                        load: wgpu::LoadOp::Clear(1.0),
-                        store: wgpu::StoreOp::Store,
-                    }),
-                    stencil_ops: None,
-                }),
-                timestamp_writes: None,
-                occlusion_query_set: None,
-            };
-            let mut vertex_buffer = VertexBuffer::empty();
-            let mut edge_buffer = EdgeBuffer::empty();
-            let mut triangle_buffer = TriangleBuffer::empty();
-
-            for (volume, color) in scene.volumes.iter() {
-                vertex_buffer.join(&rasterize_volume_into_vertex_list(
-                    volume,
-                    *color * point_color,
-                ));
-                edge_buffer.join(&rasterize_volume_into_line_list(
-                    volume,
-                    *color * edge_color,
-                ));
-                triangle_buffer.join(&rasterize_volume_into_triangle_list(
-                    volume,
-                    *color * face_color,
-                ));
-            }
+        let (_, face_color, edge_color, point_color) = Color::standard_pallet(dark_mode);
+        let mut vertex_buffer = VertexBuffer::empty();
+        let mut edge_buffer = EdgeBuffer::empty();
+        let mut triangle_buffer = TriangleBuffer::empty();
+
+        for (volume, color) in scene.volumes.iter() {
+            vertex_buffer.join(&rasterize_volume_into_vertex_list(
+                volume,
+                *color * point_color,
+            ));
+            edge_buffer.join(&rasterize_volume_into_line_list(
+                volume,
+                *color * edge_color,
+            ));
+            triangle_buffer.join(&rasterize_volume_into_triangle_list(
+                volume,
+                *color * face_color,
+            ));
+        }
 
-            for (face, color) in scene.faces.iter() {
-                vertex_buffer.join(&rasterize_face_into_vertex_list(
-                    &face,
-                    *color * point_color,
-                ));
-                edge_buffer.join(&rasterize_face_into_line_list(&face, *color * edge_color));
-                let triangles = rasterize_face_into_triangle_list(face, *color * face_color);
-                if wireframe_mode {
-                    edge_buffer.join(&triangles.to_line_list(*color * edge_color));
-                    for edge in face.all_edges() {
-                        for rasterized_edge in
-                            rasterize_edge_into_line_list(&edge, *color * edge_color)
-                                .edges
-                                .iter()
-                        {
-                            vertex_buffer.vertices.push(rasterized_edge.start.clone());
-                            vertex_buffer.vertices.push(rasterized_edge.end.clone());
-                        }
+        for (face, color) in scene.faces.iter() {
+            vertex_buffer.join(&rasterize_face_into_vertex_list(face, *color * point_color));
+            edge_buffer.join(&rasterize_face_into_line_list(face, *color * edge_color));
+            let triangles = rasterize_face_into_triangle_list(face, *color * face_color);
+            if wireframe_mode {
+                edge_buffer.join(&triangles.to_line_list(*color * edge_color));
+                for edge in face.all_edges() {
+                    for rasterized_edge in rasterize_edge_into_line_list(&edge, *color * edge_color)
+                        .edges
+                        .iter()
+                    {
+                        vertex_buffer.vertices.push(rasterized_edge.start);
+                        vertex_buffer.vertices.push(rasterized_edge.end);
                     }
-                } else {
-                    triangle_buffer.join(&triangles);
                 }
+            } else {
+                triangle_buffer.join(&triangles);
             }
-
-            for (edge, color) in scene.edges.iter() {
-                // vertex_buffer.join(&rasterize_edge_into_vertex_list(edge, *color * point_color));
-                edge_buffer.join(&rasterize_edge_into_line_list(edge, *color * edge_color));
-            }
-
-            vertex_buffer.join(&VertexBuffer::new(
-                scene
-                    .points
-                    .iter()
-                    .map(|(p, color)| RenderVertex::new(p.clone(), *color))
-                    .collect(),
-            ));
-
-            let mut render_pass = encoder.begin_render_pass(&render_pass_desc);
-            self.pipeline_manager
-                .update_camera_pos(&self.queue, camera_pos);
-            self.pipeline_manager
-                .update_edges(&self.queue, &edge_buffer);
-            self.pipeline_manager
-                .update_triangles(&self.queue, &triangle_buffer);
-            self.pipeline_manager
-                .update_vertices(&self.queue, &vertex_buffer);
-            self.pipeline_manager.run_pipelines(&mut render_pass);
         }
 
-        encoder.copy_texture_to_buffer(
-            wgpu::ImageCopyTexture {
-                aspect: wgpu::TextureAspect::All,
-                texture: &self.texture,
-                mip_level: 0,
-                origin: wgpu::Origin3d::ZERO,
-            },
-            wgpu::ImageCopyBuffer {
-                buffer: &self.output_buffer,
-                layout: wgpu::ImageDataLayout {
-                    offset: 0,
-                    bytes_per_row: Some(u32_size * self.texture_size),
-                    rows_per_image: Some(self.texture_size),
-                },
-            },
-            self.copy_size,
-        );
-        self.queue.submit(Some(encoder.finish()));
-
-        let buffer_slice = self.output_buffer.slice(..);
+        for (edge, color) in scene.edges.iter() {
+            // vertex_buffer.join(&rasterize_edge_into_vertex_list(edge, *color * point_color));
+            edge_buffer.join(&rasterize_edge_into_line_list(edge, *color * edge_color));
+        }
 
-        // NOTE: We have to create the mapping THEN device.poll() before await
-        // the future. Otherwise the application will freeze.
-        let (tx, rx) = futures_intrusive::channel::shared::oneshot_channel();
-        buffer_slice.map_async(wgpu::MapMode::Read, move |result| {
-            tx.send(result).unwrap();
-        });
-        self.device.poll(wgpu::Maintain::Wait);
-        rx.receive().await.unwrap().unwrap();
-
-        let data = buffer_slice.get_mapped_range();
-
-        use image::{ImageBuffer, Rgba};
-        let buffer =
-            ImageBuffer::<Rgba<u8>, _>::from_raw(self.texture_size, self.texture_size, data)
-                .unwrap();
-        // Create folder if it doesn't exist
-        std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
-        buffer.save(file_path).unwrap();
+        vertex_buffer.join(&VertexBuffer::new(
+            scene
+                .points
+                .iter()
+                .map(|(p, color)| RenderVertex::new(*p, *color))
+                .collect(),
+        ));
+
+        self.render_buffers(
+            &vertex_buffer,
+            &edge_buffer,
+            &triangle_buffer,
+            dark_mode,
+            HeadlessCamera::Perspective(camera_pos),
+            file_path,
+        )
+        .await;
     }
 }
 
@@ -455,8 +454,9 @@ pub mod tests {
     use super::*;
     use rstest::fixture;
 
+    // The reference images are rendered on the CPU, so the tests do not depend on the GPU of the machine.
     #[fixture]
     pub async fn renderer() -> Box<HeadlessRenderer> {
-        Box::new(HeadlessRenderer::new().await)
+        Box::new(HeadlessRenderer::new_software())
     }
 }
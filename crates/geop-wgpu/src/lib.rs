@@ -1,9 +1,14 @@
 pub mod camera_pipeline;
+pub mod golden_image;
 pub mod headless_renderer;
+pub mod orbit_camera;
+pub mod picking;
 pub mod pipeline_manager;
 pub mod render_pipeline_edge;
+pub mod render_pipeline_mesh;
 pub mod render_pipeline_triangle;
 pub mod render_pipeline_vertex;
+pub mod software_renderer;
 pub mod texture;
 pub mod window;
 pub mod window_state;
@@ -17,6 +22,7 @@ mod tests {
         primitive_objects::volumes::cube::primitive_cube,
         topology::scene::{Color, Scene},
     };
+    use golden_image::{assert_matches_reference, ImageTolerance};
     use headless_renderer::{tests::renderer, HeadlessRenderer};
     use rstest::rstest;
 
@@ -34,6 +40,11 @@ mod tests {
                 std::path::Path::new("test_light.png"),
             )
             .await;
+        assert_matches_reference(
+            std::path::Path::new("test_light.png"),
+            std::path::Path::new("reference_images/test_light.png"),
+            &ImageTolerance::default(),
+        );
     }
 
     #[rstest]
@@ -50,5 +61,10 @@ mod tests {
                 std::path::Path::new("test_dark.png"),
             )
             .await;
+        assert_matches_reference(
+            std::path::Path::new("test_dark.png"),
+            std::path::Path::new("reference_images/test_dark.png"),
+            &ImageTolerance::default(),
+        );
     }
 }
@@ -104,7 +104,7 @@ async fn run() {
 
         let union_face = face_face_difference(&face2, &face1)[1].clone();
 
-        let _object = extrude(union_face.clone(), Point::from_f64(0.0, 0.0, -0.5));
+        let _object = extrude(union_face.clone(), Point::from_f64(0.0, 0.0, -0.5)).unwrap();
 
         let mut sphere = primitive_sphere(Point::zero(), EFloat64::one());
         sphere.boundaries.push(Contour::new(vec![primitive_circle(
@@ -145,7 +145,7 @@ async fn run() {
             ))),
         );
 
-        let shell = extrude(face1, Point::unit_y());
+        let shell = extrude(face1, Point::unit_y()).unwrap();
 
         let mut triangles = TriangleBuffer::empty();
         let mut lines = EdgeBuffer::empty();
@@ -178,11 +178,13 @@ async fn run() {
             &shell,
             Color::from_brightness(0.1),
         ));
-        return (points, lines, triangles);
+        return (points, lines, triangles, shell);
     });
     match result {
-        Ok((points, lines, triangles)) => {
-            let window = GeopWindow::new(points, lines, triangles, &window).await;
+        Ok((points, lines, triangles, shell)) => {
+            let window = GeopWindow::new(points, lines, triangles, &window)
+                .await
+                .with_pickable_volumes(vec![shell]);
             window.show(event_loop);
         }
         Err(e) => {
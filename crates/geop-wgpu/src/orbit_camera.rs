@@ -0,0 +1,249 @@
+use cgmath::{
+    EuclideanSpace, InnerSpace, Matrix4, Point3, SquareMatrix, Transform, Vector3, Vector4,
+};
+use geop_geometry::bounding_box::BoundingBox;
+use geop_rasterize::hidden_line::StandardView;
+
+use crate::camera_pipeline::{Camera, OPENGL_TO_WGPU_MATRIX};
+
+// A camera that orbits around a target point. Angles are in radians.
+// Yaw rotates around the z-axis, pitch is the elevation above the xy-plane. The z-axis is up, like in Camera.
+pub struct OrbitCamera {
+    pub target: Point3<f32>,
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub orthographic: bool,
+    pub aspect: f32,
+    pub fovy: f32,
+}
+
+impl OrbitCamera {
+    pub fn new(aspect: f32) -> OrbitCamera {
+        let mut camera = OrbitCamera {
+            target: Point3::new(0.0, 0.0, 0.0),
+            distance: 3.0,
+            yaw: 0.0,
+            pitch: 0.0,
+            orthographic: false,
+            aspect,
+            fovy: 45.0,
+        };
+        camera.set_view(StandardView::Iso);
+        camera
+    }
+
+    // Unit vector from the target to the eye.
+    fn direction(&self) -> Vector3<f32> {
+        Vector3::new(
+            self.pitch.cos() * self.yaw.cos(),
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+        )
+    }
+
+    // Perpendicular to the view direction. This stays well defined when looking straight down.
+    pub fn up(&self) -> Vector3<f32> {
+        Vector3::new(
+            -self.pitch.sin() * self.yaw.cos(),
+            -self.pitch.sin() * self.yaw.sin(),
+            self.pitch.cos(),
+        )
+    }
+
+    pub fn right(&self) -> Vector3<f32> {
+        (-self.direction()).cross(self.up())
+    }
+
+    pub fn eye(&self) -> Point3<f32> {
+        self.target + self.direction() * self.distance
+    }
+
+    pub fn set_view(&mut self, view: StandardView) {
+        let half_pi = std::f32::consts::FRAC_PI_2;
+        let (yaw, pitch) = match view {
+            StandardView::Top => (-half_pi, half_pi),
+            StandardView::Bottom => (-half_pi, -half_pi),
+            StandardView::Front => (-half_pi, 0.0),
+            StandardView::Back => (half_pi, 0.0),
+            StandardView::Right => (0.0, 0.0),
+            StandardView::Left => (std::f32::consts::PI, 0.0),
+            // Elevation of the space diagonal of a cube
+            StandardView::Iso => (
+                -std::f32::consts::FRAC_PI_4,
+                (1.0f32 / 2.0f32.sqrt()).atan(),
+            ),
+        };
+        self.yaw = yaw;
+        self.pitch = pitch;
+    }
+
+    // dx and dy are in radians.
+    pub fn orbit(&mut self, dx: f32, dy: f32) {
+        let half_pi = std::f32::consts::FRAC_PI_2;
+        self.yaw -= dx;
+        self.pitch = (self.pitch + dy).clamp(-half_pi, half_pi);
+    }
+
+    // dx and dy are fractions of the viewport height, such that the scene follows the mouse.
+    pub fn pan(&mut self, dx: f32, dy: f32) {
+        let height = self.view_height();
+        self.target += self.right() * (-dx * height) + self.up() * (dy * height);
+    }
+
+    // A factor > 1 moves the camera away from the target.
+    pub fn zoom(&mut self, factor: f32) {
+        self.distance = (self.distance * factor).clamp(1e-3, 1e3);
+    }
+
+    pub fn toggle_projection(&mut self) {
+        self.orthographic = !self.orthographic;
+    }
+
+    // Height of the visible area at the target distance.
+    fn view_height(&self) -> f32 {
+        2.0 * self.distance * (self.fovy.to_radians() / 2.0).tan()
+    }
+
+    // Size of a pixel at the target distance, for a view that is height pixels high.
+    pub fn pixel_size(&self, height: u32) -> f32 {
+        self.view_height() / height.max(1) as f32
+    }
+
+    // Centers the bounding box and moves the camera such that the bounding sphere fits into the view.
+    pub fn fit_bounding_box(&mut self, bounding_box: &BoundingBox) {
+        let min = bounding_box.min;
+        let max = bounding_box.max;
+        self.target = Point3::new(
+            ((min.x + max.x).to_f64() / 2.0) as f32,
+            ((min.y + max.y).to_f64() / 2.0) as f32,
+            ((min.z + max.z).to_f64() / 2.0) as f32,
+        );
+        let radius = ((max - min).norm().to_f64() / 2.0).max(1e-3) as f32;
+        // For narrow windows, the horizontal field of view is the limiting one.
+        let half_fovy = self.fovy.to_radians() / 2.0;
+        let half_fov = half_fovy.min((half_fovy.tan() * self.aspect).atan());
+        self.distance = radius / half_fov.sin();
+    }
+
+    // Ray through a point on the screen, e.g. for picking with geop_topology::ray_cast.
+    // x and y are in normalized device coordinates, from -1 (left, bottom) to 1 (right, top).
+    // The ray is computed by unprojecting with the view projection matrix, such that it matches the rendered image exactly.
+    pub fn ray(&self, x: f32, y: f32) -> (Point3<f32>, Vector3<f32>) {
+        let view_proj = self.build_view_projection_matrix();
+        let inverse = view_proj.invert().expect("View projection is invertible");
+        let depth = view_proj.transform_point(self.target).z;
+        let on_target_plane = inverse.transform_point(Point3::new(x, y, depth));
+
+        // The center of projection is the point that is mapped to infinity. For orthographic projections, it is at infinity itself.
+        let center = inverse * Vector4::new(0.0, 0.0, 1.0, 0.0);
+        let forward = -self.direction();
+        if center.w.abs() > 1e-6 * center.truncate().magnitude() {
+            let origin = Point3::from_vec(center.truncate() / center.w);
+            (origin, on_target_plane - origin)
+        } else {
+            (on_target_plane - forward * self.distance, forward)
+        }
+    }
+
+    pub fn build_view_projection_matrix(&self) -> Matrix4<f32> {
+        if self.orthographic {
+            let view = Matrix4::look_at_rh(self.eye(), self.target, self.up());
+            let half_height = self.view_height() / 2.0;
+            let half_width = half_height * self.aspect;
+            // The depth range is large enough to contain everything around the target.
+            let proj = cgmath::ortho(
+                -half_width,
+                half_width,
+                -half_height,
+                half_height,
+                -100.0 * self.distance,
+                100.0 * self.distance,
+            );
+            OPENGL_TO_WGPU_MATRIX * proj * view
+        } else {
+            self.camera().build_view_projection_matrix()
+        }
+    }
+
+    // The perspective camera, as used by CameraPipeline.
+    pub fn camera(&self) -> Camera {
+        let mut camera = Camera::new(self.aspect);
+        camera.eye = self.eye();
+        camera.target = self.target;
+        camera.up = self.up();
+        camera.fovy = self.fovy;
+        camera.znear = self.distance / 100.0;
+        camera.zfar = self.distance * 100.0;
+        camera
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use geop_geometry::point::Point;
+
+    #[test]
+    fn test_standard_views() {
+        let mut camera = OrbitCamera::new(1.0);
+        camera.set_view(StandardView::Top);
+        assert!((camera.eye() - Point3::new(0.0, 0.0, camera.distance)).magnitude() < 1e-5);
+        assert!((camera.up() - Vector3::unit_y()).magnitude() < 1e-5);
+
+        camera.set_view(StandardView::Front);
+        assert!((camera.eye() - Point3::new(0.0, -camera.distance, 0.0)).magnitude() < 1e-5);
+        assert!((camera.right() - Vector3::unit_x()).magnitude() < 1e-5);
+    }
+
+    #[test]
+    fn test_fit_bounding_box() {
+        let mut camera = OrbitCamera::new(1.0);
+        camera.fit_bounding_box(&BoundingBox::new(
+            Point::from_f64(1.0, 1.0, 1.0),
+            Point::from_f64(3.0, 3.0, 3.0),
+        ));
+        assert!((camera.target.to_vec() - Vector3::new(2.0, 2.0, 2.0)).magnitude() < 1e-5);
+
+        // All corners have to be on screen, for both projections
+        for orthographic in [false, true] {
+            camera.orthographic = orthographic;
+            let view_proj = camera.build_view_projection_matrix();
+            for corner in 0..8 {
+                let p = Point3::new(
+                    if corner & 1 == 0 { 1.0 } else { 3.0 },
+                    if corner & 2 == 0 { 1.0 } else { 3.0 },
+                    if corner & 4 == 0 { 1.0 } else { 3.0 },
+                );
+                let p = view_proj.transform_point(p);
+                assert!(p.x.abs() <= 1.0 && p.y.abs() <= 1.0);
+                assert!(p.z >= 0.0 && p.z <= 1.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_ray_hits_projected_point() {
+        let mut camera = OrbitCamera::new(1.5);
+        let p = Point3::new(0.3, -0.2, 0.4);
+        for orthographic in [false, true] {
+            camera.orthographic = orthographic;
+            let screen = camera.build_view_projection_matrix().transform_point(p);
+            let (origin, direction) = camera.ray(screen.x, screen.y);
+            // The point has to be on the ray
+            let v = p - origin;
+            assert!(v.cross(direction.normalize()).magnitude() < 1e-4);
+            assert!(v.dot(direction) > 0.0);
+        }
+    }
+
+    #[test]
+    fn test_orbit_clamps_pitch() {
+        let mut camera = OrbitCamera::new(1.0);
+        camera.orbit(0.0, 10.0);
+        assert!(camera.pitch == std::f32::consts::FRAC_PI_2);
+        camera.zoom(2.0);
+        camera.pan(0.5, 0.0);
+        assert!(camera.target.x != 0.0 || camera.target.y != 0.0);
+    }
+}
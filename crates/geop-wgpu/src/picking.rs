@@ -0,0 +1,118 @@
+use geop_geometry::point::Point;
+use geop_rasterize::{
+    edge::rasterize_edge_into_line_list, edge_buffer::EdgeBuffer,
+    face::rasterize_face_into_triangle_list, triangle_buffer::TriangleBuffer,
+};
+use geop_topology::{
+    ray_cast::{pick_edge, ray_cast},
+    topology::{edge::Edge, face::Face, scene::Color, volume::Volume},
+};
+
+use crate::orbit_camera::OrbitCamera;
+
+// Edges within this many pixels of the cursor are picked instead of the face behind them.
+pub const PICK_PIXELS: f32 = 5.0;
+
+#[derive(Clone, Debug)]
+pub enum Selection {
+    Face(Face),
+    Edge(Box<Edge>),
+}
+
+// Picks the face or edge of the volumes under the point on the screen, in normalized device coordinates like OrbitCamera::ray.
+// pixel_size is the size of a pixel at the target of the camera. An edge wins over a face, unless the face is in front of it.
+pub fn pick(
+    volumes: &[Volume],
+    camera: &OrbitCamera,
+    x: f32,
+    y: f32,
+    pixel_size: f32,
+) -> Option<Selection> {
+    let (origin, direction) = camera.ray(x, y);
+    let origin = Point::from_f64(origin.x as f64, origin.y as f64, origin.z as f64);
+    let direction = Point::from_f64(direction.x as f64, direction.y as f64, direction.z as f64);
+    let tolerance = (PICK_PIXELS * pixel_size) as f64;
+
+    let mut face: Option<(f64, Face)> = None;
+    let mut edge: Option<(f64, Edge)> = None;
+    for volume in volumes.iter() {
+        if let Some(hit) = ray_cast(volume, origin, direction)
+            .ok()
+            .and_then(|hits| hits.into_iter().next())
+        {
+            if face.as_ref().is_none_or(|(t, _)| hit.t.to_f64() < *t) {
+                face = Some((hit.t.to_f64(), hit.face));
+            }
+        }
+        if let Ok(Some(hit)) = pick_edge(volume, origin, direction, tolerance) {
+            if edge.as_ref().is_none_or(|(t, _)| hit.t.to_f64() < *t) {
+                edge = Some((hit.t.to_f64(), hit.edge));
+            }
+        }
+    }
+    match (face, edge) {
+        (Some((face_t, _)), Some((edge_t, edge))) if edge_t <= face_t + tolerance => {
+            Some(Selection::Edge(Box::new(edge)))
+        }
+        (Some((_, face)), _) => Some(Selection::Face(face)),
+        (None, Some((_, edge))) => Some(Selection::Edge(Box::new(edge))),
+        (None, None) => None,
+    }
+}
+
+// The selection drawn in the highlight color. The buffers have to be drawn before the scene,
+// such that they win the depth test against the scene where they cover the same pixels.
+pub fn rasterize_selection(selection: &Selection, color: Color) -> (TriangleBuffer, EdgeBuffer) {
+    match selection {
+        Selection::Face(face) => (
+            rasterize_face_into_triangle_list(face, color),
+            EdgeBuffer::empty(),
+        ),
+        Selection::Edge(edge) => (
+            TriangleBuffer::empty(),
+            rasterize_edge_into_line_list(edge, color),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use cgmath::Transform;
+    use geop_geometry::efloat::EFloat64;
+    use geop_rasterize::hidden_line::StandardView;
+    use geop_topology::primitive_objects::volumes::cube::primitive_cube;
+
+    use super::*;
+
+    #[test]
+    fn test_pick() {
+        let cube = primitive_cube(EFloat64::two(), EFloat64::two(), EFloat64::two());
+        let mut camera = OrbitCamera::new(1.0);
+        camera.distance = 10.0;
+        camera.set_view(StandardView::Top);
+        let pixel_size = camera.pixel_size(1000);
+        let volumes = [cube];
+
+        // The center of the screen is the center of the top face.
+        match pick(&volumes, &camera, 0.0, 0.0, pixel_size) {
+            Some(Selection::Face(face)) => {
+                assert!(face.inner_point().z == 1.0);
+            }
+            other => panic!("Expected the top face, got {:?}", other),
+        }
+
+        // A point next to the edge at x = 1 on the screen picks the edge, even though the face is hit as well.
+        let screen = camera
+            .build_view_projection_matrix()
+            .transform_point(cgmath::Point3::new(0.99, 0.0, 1.0));
+        match pick(&volumes, &camera, screen.x, screen.y, pixel_size) {
+            Some(Selection::Edge(edge)) => {
+                assert!(edge.start.unwrap().x == 1.0 && edge.end.unwrap().x == 1.0);
+            }
+            other => panic!("Expected an edge, got {:?}", other),
+        }
+
+        // Nothing is picked next to the cube.
+        assert!(pick(&volumes, &camera, 0.9, 0.9, pixel_size).is_none());
+    }
+}
@@ -1,11 +1,14 @@
 use geop_rasterize::{
-    edge_buffer::EdgeBuffer, triangle_buffer::TriangleBuffer, vertex_buffer::VertexBuffer,
+    edge_buffer::EdgeBuffer, mesh_buffer::MeshBuffer, triangle_buffer::TriangleBuffer,
+    vertex_buffer::VertexBuffer,
 };
 use wgpu::TextureFormat;
 
 use crate::{
-    camera_pipeline::CameraPipeline,
+    camera_pipeline::{CameraPipeline, LightUniform, Lighting},
+    orbit_camera::OrbitCamera,
     render_pipeline_edge::RenderPipelineEdge,
+    render_pipeline_mesh::RenderPipelineMesh,
     render_pipeline_triangle::RenderPipelineTriangle,
     render_pipeline_vertex::RenderPipelineVertex,
     texture::{self, Texture},
@@ -14,6 +17,7 @@ use crate::{
 pub struct PipelineManager {
     camera_pipeline: CameraPipeline,
     traingle_pipeline: RenderPipelineTriangle,
+    mesh_pipeline: RenderPipelineMesh,
     line_pipeline: RenderPipelineEdge,
     vertex_pipeline: RenderPipelineVertex,
     pub depth_texture: Texture,
@@ -36,6 +40,13 @@ impl PipelineManager {
             &camera_pipeline.render_pipeline_layout,
         );
 
+        let mesh_pipeline = RenderPipelineMesh::new(
+            device,
+            render_texture_format,
+            "Mesh",
+            &camera_pipeline.render_pipeline_layout,
+        );
+
         let line_pipeline = RenderPipelineEdge::new(
             device,
             render_texture_format,
@@ -53,6 +64,7 @@ impl PipelineManager {
         PipelineManager {
             camera_pipeline,
             traingle_pipeline,
+            mesh_pipeline,
             line_pipeline,
             vertex_pipeline,
             depth_texture,
@@ -63,6 +75,10 @@ impl PipelineManager {
         self.traingle_pipeline.update(queue, triangles);
     }
 
+    pub fn update_mesh(&mut self, queue: &wgpu::Queue, mesh: &MeshBuffer) {
+        self.mesh_pipeline.update(queue, mesh);
+    }
+
     pub fn update_edges(&mut self, queue: &wgpu::Queue, edges: &EdgeBuffer) {
         self.line_pipeline.update(queue, edges);
     }
@@ -121,10 +137,31 @@ impl PipelineManager {
         )
     }
 
+    pub fn update_camera_orbit(&mut self, queue: &wgpu::Queue, orbit_camera: &OrbitCamera) {
+        self.camera_pipeline.camera = orbit_camera.camera();
+        self.camera_pipeline
+            .camera_uniform
+            .update_view_orbit(orbit_camera);
+        queue.write_buffer(
+            &self.camera_pipeline.camera_buffer,
+            0,
+            bytemuck::cast_slice(&[self.camera_pipeline.camera_uniform]),
+        )
+    }
+
+    pub fn update_lighting(&mut self, queue: &wgpu::Queue, lighting: &Lighting) {
+        queue.write_buffer(
+            &self.camera_pipeline.light_buffer,
+            0,
+            bytemuck::cast_slice(&[LightUniform::from(lighting)]),
+        )
+    }
+
     pub fn run_pipelines<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
         render_pass.set_bind_group(0, &self.camera_pipeline.camera_bind_group, &[]);
         render_pass.set_bind_group(1, &self.camera_pipeline.light_bind_group, &[]);
         self.traingle_pipeline.render(render_pass);
+        self.mesh_pipeline.render(render_pass);
         self.line_pipeline.render(render_pass);
         self.vertex_pipeline.render(render_pass);
     }
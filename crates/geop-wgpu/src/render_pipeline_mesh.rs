@@ -0,0 +1,69 @@
+use geop_rasterize::{mesh_buffer::MeshBuffer, vertex_normal_buffer::RenderNormalVertex};
+use wgpu::{util::DeviceExt, TextureFormat};
+
+use crate::render_pipeline_triangle::lit_render_pipeline;
+
+// Renders an indexed MeshBuffer with the same lighting as RenderPipelineTriangle.
+pub struct RenderPipelineMesh {
+    vertex_buffer: wgpu::Buffer,
+    index_buffer: wgpu::Buffer,
+    max_num_vertices: usize,
+    max_num_indices: usize,
+    render_indices: u32,
+    render_pipeline: wgpu::RenderPipeline,
+}
+
+impl RenderPipelineMesh {
+    pub fn new(
+        device: &wgpu::Device,
+        texture_format: TextureFormat,
+        label: &str,
+        render_pipeline_layout: &wgpu::PipelineLayout,
+    ) -> RenderPipelineMesh {
+        let max_num_vertices = 16384;
+        let max_num_indices = 3 * 16384;
+
+        let vertex_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{label} Vertex Buffer")),
+            contents: vec![0u8; max_num_vertices * std::mem::size_of::<RenderNormalVertex>()]
+                .as_slice(),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let index_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some(&format!("{label} Index Buffer")),
+            contents: vec![0u8; max_num_indices * std::mem::size_of::<u32>()].as_slice(),
+            usage: wgpu::BufferUsages::INDEX | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let render_pipeline =
+            lit_render_pipeline(device, texture_format, label, render_pipeline_layout);
+
+        RenderPipelineMesh {
+            vertex_buffer,
+            index_buffer,
+            max_num_vertices,
+            max_num_indices,
+            render_indices: 0,
+            render_pipeline,
+        }
+    }
+
+    pub fn update(&mut self, queue: &wgpu::Queue, mesh: &MeshBuffer) {
+        if self.max_num_vertices < mesh.vertices.vertices.len()
+            || self.max_num_indices < mesh.indices.len()
+        {
+            panic!("Mesh is too large to render");
+        }
+        queue.write_buffer(&self.vertex_buffer, 0, mesh.vertices.to_u8_slice());
+        queue.write_buffer(&self.index_buffer, 0, mesh.index_u8_slice());
+        self.render_indices = mesh.indices.len() as u32;
+    }
+
+    pub fn render<'a>(&'a self, render_pass: &mut wgpu::RenderPass<'a>) {
+        render_pass.set_pipeline(&self.render_pipeline);
+        render_pass.set_vertex_buffer(0, self.vertex_buffer.slice(..));
+        render_pass.set_index_buffer(self.index_buffer.slice(..), wgpu::IndexFormat::Uint32);
+        render_pass.draw_indexed(0..self.render_indices, 0, 0..1);
+    }
+}
@@ -1,12 +1,16 @@
 // Vertex shader
 struct Camera {
     view_proj: mat4x4<f32>,
+    view_position: vec4<f32>,
 }
 @group(0) @binding(0)
 var<uniform> camera: Camera;
 
 struct Light {
     direction: vec3<f32>,
+    ambient: f32,
+    specular: f32,
+    shininess: f32,
 }
 @group(1) @binding(0)
 var<uniform> light: Light;
@@ -23,6 +27,7 @@ struct VertexOutput {
     @builtin(position) position: vec4<f32>,
     @location(0) color: vec4<f32>,
     @location(1) normal: vec3<f32>,
+    @location(2) world_position: vec3<f32>,
 };
 
 struct FragmentOutput {
@@ -35,7 +40,8 @@ fn vs_main(
 ) -> VertexOutput {
     var out: VertexOutput;
 
-    out.position = camera.view_proj * vec4<f32>(vec3<f32>(in.min_position + in.max_position) / 2.0, 1.0);
+    out.world_position = vec3<f32>(in.min_position + in.max_position) / 2.0;
+    out.position = camera.view_proj * vec4<f32>(out.world_position, 1.0);
     out.color = in.color;
     out.normal = vec3<f32>(vec3<f32>(in.min_normal + in.max_normal) / 2.0);
     
@@ -46,13 +52,20 @@ fn vs_main(
 fn fs_main(in: VertexOutput) -> FragmentOutput {
     var out: FragmentOutput;
 
-    let ambient_color = 0.5;
-
+    let normal = normalize(in.normal);
     let light_dir = normalize(light.direction);
-    let diffuse_strength = max(dot(in.normal, -light_dir), 0.0);
-    let diffuse_color = diffuse_strength * (1 - ambient_color);
+    let diffuse_strength = max(dot(normal, -light_dir), 0.0);
+    let diffuse_color = diffuse_strength * (1.0 - light.ambient);
+
+    // Blinn-Phong highlight, only on the lit side
+    let view_dir = normalize(camera.view_position.xyz - in.world_position * camera.view_position.w);
+    let half_dir = normalize(view_dir - light_dir);
+    var specular_color = 0.0;
+    if (diffuse_strength > 0.0 && light.specular > 0.0) {
+        specular_color = light.specular * pow(max(dot(normal, half_dir), 0.0), light.shininess);
+    }
 
-    let result = (ambient_color + diffuse_color) * in.color.xyz;
+    let result = (light.ambient + diffuse_color) * in.color.xyz + vec3<f32>(specular_color);
 
     out.color = vec4<f32>(result, in.color.a);
 
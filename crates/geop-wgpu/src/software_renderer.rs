@@ -0,0 +1,535 @@
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+use geop_rasterize::{
+    edge_buffer::EdgeBuffer,
+    triangle_buffer::TriangleBuffer,
+    vertex_buffer::{RenderVertex, VertexBuffer},
+    vertex_normal_buffer::RenderNormalVertex,
+};
+use geop_topology::topology::scene::Color;
+use image::{ImageBuffer, Rgba};
+
+use crate::{camera_pipeline::Lighting, render_pipeline_triangle::FACE_DEPTH_BIAS};
+
+// Size of the cubes that are used to render points. This matches the vertex pipeline.
+const POINT_SIZE: f32 = 0.02;
+
+// A CPU implementation of the triangle, edge and vertex pipelines. It follows the wgpu pipelines as closely as possible,
+// such that images rendered without a GPU look the same as images rendered with one.
+// Colors are blended in linear space and converted to sRGB on output, like an Rgba8UnormSrgb texture.
+pub struct SoftwareRenderer {
+    size: u32,
+    color: Vec<[f32; 4]>,
+    depth: Vec<f32>,
+    pub lighting: Lighting,
+    // Same as CameraUniform::view_position.
+    pub view_position: [f32; 4],
+}
+
+// A vertex after the vertex shader, in clip space.
+#[derive(Clone, Copy)]
+struct ClipVertex {
+    position: Vector4<f32>,
+    color: Vector4<f32>,
+    normal: Vector3<f32>,
+    world_position: Vector3<f32>,
+}
+
+impl ClipVertex {
+    fn lerp(&self, other: &ClipVertex, t: f32) -> ClipVertex {
+        ClipVertex {
+            position: self.position + (other.position - self.position) * t,
+            color: self.color + (other.color - self.color) * t,
+            normal: self.normal + (other.normal - self.normal) * t,
+            world_position: self.world_position + (other.world_position - self.world_position) * t,
+        }
+    }
+}
+
+// A vertex after the perspective divide, in pixel coordinates. z is the depth in [0, 1].
+#[derive(Clone, Copy)]
+struct ScreenVertex {
+    x: f32,
+    y: f32,
+    z: f32,
+    inv_w: f32,
+}
+
+fn mid(min: [f32; 3], max: [f32; 3]) -> Vector3<f32> {
+    Vector3::new(
+        (min[0] + max[0]) / 2.0,
+        (min[1] + max[1]) / 2.0,
+        (min[2] + max[2]) / 2.0,
+    )
+}
+
+fn srgb_encode(c: f32) -> u8 {
+    let c = c.clamp(0.0, 1.0);
+    let s = if c <= 0.0031308 {
+        c * 12.92
+    } else {
+        1.055 * c.powf(1.0 / 2.4) - 0.055
+    };
+    (s * 255.0).round() as u8
+}
+
+// Clips a polygon against the near plane of wgpu clip space (z >= 0).
+fn clip_near(polygon: &[ClipVertex]) -> Vec<ClipVertex> {
+    let mut result = Vec::with_capacity(polygon.len() + 1);
+    for i in 0..polygon.len() {
+        let a = &polygon[i];
+        let b = &polygon[(i + 1) % polygon.len()];
+        let a_inside = a.position.z >= 0.0;
+        let b_inside = b.position.z >= 0.0;
+        if a_inside {
+            result.push(*a);
+        }
+        if a_inside != b_inside {
+            let t = a.position.z / (a.position.z - b.position.z);
+            result.push(a.lerp(b, t));
+        }
+    }
+    result
+}
+
+impl SoftwareRenderer {
+    pub fn new(size: u32) -> SoftwareRenderer {
+        let n = (size * size) as usize;
+        SoftwareRenderer {
+            size,
+            color: vec![[0.0; 4]; n],
+            depth: vec![1.0; n],
+            lighting: Lighting::default(),
+            view_position: [0.0, 0.0, 1.0, 0.0],
+        }
+    }
+
+    pub fn size(&self) -> u32 {
+        self.size
+    }
+
+    pub fn clear(&mut self, background_color: Color) {
+        let c = [
+            background_color.r,
+            background_color.g,
+            background_color.b,
+            background_color.a,
+        ];
+        self.color.iter_mut().for_each(|p| *p = c);
+        self.depth.iter_mut().for_each(|d| *d = 1.0);
+    }
+
+    fn to_screen(&self, v: &ClipVertex) -> ScreenVertex {
+        let inv_w = 1.0 / v.position.w;
+        let size = self.size as f32;
+        ScreenVertex {
+            x: (v.position.x * inv_w + 1.0) / 2.0 * size,
+            y: (1.0 - v.position.y * inv_w) / 2.0 * size,
+            z: v.position.z * inv_w,
+            inv_w,
+        }
+    }
+
+    // Depth test (less) and alpha blending of a single fragment.
+    fn write_fragment(&mut self, x: i64, y: i64, z: f32, color: Vector4<f32>) {
+        if x < 0 || y < 0 || x >= self.size as i64 || y >= self.size as i64 {
+            return;
+        }
+        if !(0.0..=1.0).contains(&z) {
+            return;
+        }
+        let i = (y * self.size as i64 + x) as usize;
+        if z >= self.depth[i] {
+            return;
+        }
+        self.depth[i] = z;
+        let dst = self.color[i];
+        let a = color.w;
+        self.color[i] = [
+            color.x * a + dst[0] * (1.0 - a),
+            color.y * a + dst[1] * (1.0 - a),
+            color.z * a + dst[2] * (1.0 - a),
+            a + dst[3] * (1.0 - a),
+        ];
+    }
+
+    // Same as fs_main in shader_triangle.wgsl.
+    fn shade(
+        &self,
+        color: Vector4<f32>,
+        normal: Vector3<f32>,
+        world_position: Vector3<f32>,
+    ) -> Vector4<f32> {
+        let lighting = &self.lighting;
+        let normal = normal.normalize();
+        let light_dir = Vector3::from(lighting.direction).normalize();
+        let diffuse_strength = normal.dot(-light_dir).max(0.0);
+        let brightness = lighting.ambient + diffuse_strength * (1.0 - lighting.ambient);
+
+        let view_position = Vector4::from(self.view_position);
+        let view_dir = (view_position.truncate() - world_position * view_position.w).normalize();
+        let half_dir = (view_dir - light_dir).normalize();
+        let specular = if diffuse_strength > 0.0 && lighting.specular > 0.0 {
+            lighting.specular * normal.dot(half_dir).max(0.0).powf(lighting.shininess)
+        } else {
+            0.0
+        };
+
+        Vector4::new(
+            color.x * brightness + specular,
+            color.y * brightness + specular,
+            color.z * brightness + specular,
+            color.w,
+        )
+    }
+
+    // Same as FACE_DEPTH_BIAS for a Depth32Float depth buffer: constant * r + slope_scale * max_slope,
+    // where r is the smallest difference that can be represented at the largest depth of the triangle.
+    fn depth_bias(s: &[ScreenVertex; 3], area: f32) -> f32 {
+        let dz_dx =
+            ((s[1].z - s[0].z) * (s[2].y - s[0].y) - (s[2].z - s[0].z) * (s[1].y - s[0].y)) / area;
+        let dz_dy =
+            ((s[2].z - s[0].z) * (s[1].x - s[0].x) - (s[1].z - s[0].z) * (s[2].x - s[0].x)) / area;
+        let max_z = s.iter().map(|v| v.z.abs()).fold(0.0, f32::max);
+        let r = if max_z > 0.0 {
+            2.0f32.powi(max_z.log2().floor() as i32 - 23)
+        } else {
+            0.0
+        };
+        FACE_DEPTH_BIAS.constant as f32 * r
+            + FACE_DEPTH_BIAS.slope_scale * dz_dx.abs().max(dz_dy.abs())
+    }
+
+    fn rasterize_triangle(&mut self, vertices: [ClipVertex; 3]) {
+        let polygon = clip_near(&vertices);
+        if polygon.len() < 3 {
+            return;
+        }
+        for k in 1..polygon.len() - 1 {
+            let tri = [polygon[0], polygon[k], polygon[k + 1]];
+            let s = [
+                self.to_screen(&tri[0]),
+                self.to_screen(&tri[1]),
+                self.to_screen(&tri[2]),
+            ];
+
+            // Counter clockwise in normalized device coordinates is clockwise in pixel coordinates, as y is flipped.
+            let area =
+                (s[1].x - s[0].x) * (s[2].y - s[0].y) - (s[2].x - s[0].x) * (s[1].y - s[0].y);
+            if area >= 0.0 {
+                continue;
+            }
+            let bias = SoftwareRenderer::depth_bias(&s, area);
+
+            let x_min = s
+                .iter()
+                .map(|v| v.x)
+                .fold(f32::INFINITY, f32::min)
+                .floor()
+                .max(0.0) as i64;
+            let x_max = s
+                .iter()
+                .map(|v| v.x)
+                .fold(f32::NEG_INFINITY, f32::max)
+                .ceil()
+                .min(self.size as f32) as i64;
+            let y_min = s
+                .iter()
+                .map(|v| v.y)
+                .fold(f32::INFINITY, f32::min)
+                .floor()
+                .max(0.0) as i64;
+            let y_max = s
+                .iter()
+                .map(|v| v.y)
+                .fold(f32::NEG_INFINITY, f32::max)
+                .ceil()
+                .min(self.size as f32) as i64;
+
+            for y in y_min..y_max {
+                for x in x_min..x_max {
+                    let px = x as f32 + 0.5;
+                    let py = y as f32 + 0.5;
+                    let l0 = ((s[2].x - s[1].x) * (py - s[1].y)
+                        - (s[2].y - s[1].y) * (px - s[1].x))
+                        / area;
+                    let l1 = ((s[0].x - s[2].x) * (py - s[2].y)
+                        - (s[0].y - s[2].y) * (px - s[2].x))
+                        / area;
+                    let l2 = 1.0 - l0 - l1;
+                    if l0 < 0.0 || l1 < 0.0 || l2 < 0.0 {
+                        continue;
+                    }
+                    let z = l0 * s[0].z + l1 * s[1].z + l2 * s[2].z + bias;
+
+                    // Perspective correct interpolation of the attributes
+                    let p0 = l0 * s[0].inv_w;
+                    let p1 = l1 * s[1].inv_w;
+                    let p2 = l2 * s[2].inv_w;
+                    let sum = p0 + p1 + p2;
+                    let (p0, p1, p2) = (p0 / sum, p1 / sum, p2 / sum);
+                    let color = tri[0].color * p0 + tri[1].color * p1 + tri[2].color * p2;
+                    let normal = tri[0].normal * p0 + tri[1].normal * p1 + tri[2].normal * p2;
+                    let world_position = tri[0].world_position * p0
+                        + tri[1].world_position * p1
+                        + tri[2].world_position * p2;
+                    let color = self.shade(color, normal, world_position);
+                    self.write_fragment(x, y, z, color);
+                }
+            }
+        }
+    }
+
+    fn rasterize_line(&mut self, a: ClipVertex, b: ClipVertex) {
+        let (a, b) = match (a.position.z >= 0.0, b.position.z >= 0.0) {
+            (true, true) => (a, b),
+            (true, false) => (a, a.lerp(&b, a.position.z / (a.position.z - b.position.z))),
+            (false, true) => (b.lerp(&a, b.position.z / (b.position.z - a.position.z)), b),
+            (false, false) => return,
+        };
+        let sa = self.to_screen(&a);
+        let sb = self.to_screen(&b);
+        let steps = (sb.x - sa.x).abs().max((sb.y - sa.y).abs()).ceil().max(1.0);
+        // Guard against lines that reach far outside of the image
+        if steps > 16.0 * self.size as f32 {
+            return;
+        }
+        let steps = steps as i64;
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let x = sa.x + (sb.x - sa.x) * t;
+            let y = sa.y + (sb.y - sa.y) * t;
+            let z = sa.z + (sb.z - sa.z) * t;
+            let pa = (1.0 - t) * sa.inv_w;
+            let pb = t * sb.inv_w;
+            let color = (a.color * pa + b.color * pb) / (pa + pb);
+            self.write_fragment(x.floor() as i64, y.floor() as i64, z, color);
+        }
+    }
+
+    fn rasterize_point(&mut self, view_proj: &Matrix4<f32>, vertex: &RenderVertex) {
+        let center = mid(vertex.min_position, vertex.max_position);
+        let color = Vector4::from(vertex.color);
+        let half = POINT_SIZE / 2.0;
+
+        // The sprite covers the screen space bounding box of the cube that the vertex pipeline renders.
+        let mut x_min = f32::INFINITY;
+        let mut x_max = f32::NEG_INFINITY;
+        let mut y_min = f32::INFINITY;
+        let mut y_max = f32::NEG_INFINITY;
+        let mut z_min = f32::INFINITY;
+        for corner in 0..8 {
+            let offset = Vector3::new(
+                if corner & 1 == 0 { -half } else { half },
+                if corner & 2 == 0 { -half } else { half },
+                if corner & 4 == 0 { -half } else { half },
+            );
+            let p = view_proj * (center + offset).extend(1.0);
+            if p.z < 0.0 {
+                return;
+            }
+            let s = self.to_screen(&ClipVertex {
+                position: p,
+                color,
+                normal: Vector3::new(0.0, 0.0, 0.0),
+                world_position: center + offset,
+            });
+            x_min = x_min.min(s.x);
+            x_max = x_max.max(s.x);
+            y_min = y_min.min(s.y);
+            y_max = y_max.max(s.y);
+            z_min = z_min.min(s.z);
+        }
+
+        // At least one pixel is covered, even if the cube is smaller than a pixel
+        let x_start = x_min.round() as i64;
+        let y_start = y_min.round() as i64;
+        let x_end = (x_max.round() as i64).max(x_start + 1);
+        let y_end = (y_max.round() as i64).max(y_start + 1);
+        for y in y_start..y_end {
+            for x in x_start..x_end {
+                self.write_fragment(x, y, z_min, color);
+            }
+        }
+    }
+
+    fn clip_vertex(view_proj: &Matrix4<f32>, vertex: &RenderNormalVertex) -> ClipVertex {
+        let world_position = mid(vertex.min_position, vertex.max_position);
+        ClipVertex {
+            position: view_proj * world_position.extend(1.0),
+            color: Vector4::from(vertex.color),
+            normal: mid(vertex.min_normal, vertex.max_normal),
+            world_position,
+        }
+    }
+
+    pub fn draw_triangles(&mut self, view_proj: &Matrix4<f32>, triangles: &TriangleBuffer) {
+        for triangle in triangles.triangles.iter() {
+            self.rasterize_triangle([
+                SoftwareRenderer::clip_vertex(view_proj, &triangle.a),
+                SoftwareRenderer::clip_vertex(view_proj, &triangle.b),
+                SoftwareRenderer::clip_vertex(view_proj, &triangle.c),
+            ]);
+        }
+    }
+
+    pub fn draw_edges(&mut self, view_proj: &Matrix4<f32>, edges: &EdgeBuffer) {
+        for edge in edges.edges.iter() {
+            let [a, b] = [edge.start, edge.end].map(|v| {
+                let world_position = mid(v.min_position, v.max_position);
+                ClipVertex {
+                    position: view_proj * world_position.extend(1.0),
+                    color: Vector4::from(v.color),
+                    normal: Vector3::new(0.0, 0.0, 0.0),
+                    world_position,
+                }
+            });
+            self.rasterize_line(a, b);
+        }
+    }
+
+    pub fn draw_vertices(&mut self, view_proj: &Matrix4<f32>, vertices: &VertexBuffer) {
+        for vertex in vertices.vertices.iter() {
+            self.rasterize_point(view_proj, vertex);
+        }
+    }
+
+    // Same order as PipelineManager::run_pipelines.
+    pub fn render(
+        &mut self,
+        view_proj: &Matrix4<f32>,
+        background_color: Color,
+        vertex_buffer: &VertexBuffer,
+        edge_buffer: &EdgeBuffer,
+        triangle_buffer: &TriangleBuffer,
+    ) {
+        self.clear(background_color);
+        self.draw_triangles(view_proj, triangle_buffer);
+        self.draw_edges(view_proj, edge_buffer);
+        self.draw_vertices(view_proj, vertex_buffer);
+    }
+
+    pub fn to_image(&self) -> ImageBuffer<Rgba<u8>, Vec<u8>> {
+        let mut data = Vec::<u8>::with_capacity(self.color.len() * 4);
+        for c in self.color.iter() {
+            data.push(srgb_encode(c[0]));
+            data.push(srgb_encode(c[1]));
+            data.push(srgb_encode(c[2]));
+            data.push((c[3].clamp(0.0, 1.0) * 255.0).round() as u8);
+        }
+        ImageBuffer::from_raw(self.size, self.size, data).unwrap()
+    }
+
+    pub fn save(&self, file_path: &std::path::Path) {
+        // Create folder if it doesn't exist
+        std::fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+        self.to_image().save(file_path).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera_pipeline::CameraUniform;
+    use geop_geometry::point::Point;
+    use geop_rasterize::{edge_buffer::RenderEdge, triangle_buffer::RenderTriangle};
+
+    fn triangle(flip: bool) -> TriangleBuffer {
+        let (b, c) = match flip {
+            false => (
+                Point::from_f64(1.0, -1.0, 0.0),
+                Point::from_f64(0.0, 1.0, 0.0),
+            ),
+            true => (
+                Point::from_f64(0.0, 1.0, 0.0),
+                Point::from_f64(1.0, -1.0, 0.0),
+            ),
+        };
+        TriangleBuffer::new(vec![RenderTriangle::new(
+            Point::from_f64(-1.0, -1.0, 0.0),
+            b,
+            c,
+            Color::white(),
+            Point::unit_z(),
+            Point::unit_z(),
+            Point::unit_z(),
+        )])
+    }
+
+    #[test]
+    fn test_software_renderer_culls_back_faces() {
+        let mut view_proj = CameraUniform::new();
+        view_proj.update_view_ortho(-2.0, 2.0, -2.0, 2.0, -100.0, 100.0);
+        let view_proj: Matrix4<f32> = view_proj.view_proj.into();
+
+        let mut renderer = SoftwareRenderer::new(64);
+        renderer.render(
+            &view_proj,
+            Color::black(),
+            &VertexBuffer::empty(),
+            &EdgeBuffer::empty(),
+            &triangle(false),
+        );
+        assert!(renderer.to_image().get_pixel(32, 32).0[0] > 0);
+
+        renderer.render(
+            &view_proj,
+            Color::black(),
+            &VertexBuffer::empty(),
+            &EdgeBuffer::empty(),
+            &triangle(true),
+        );
+        assert!(renderer.to_image().get_pixel(32, 32).0[0] == 0);
+    }
+
+    #[test]
+    fn test_software_renderer_lighting_and_edge_overlay() {
+        // The triangle ends up at a depth of 0.5
+        let mut view_proj = CameraUniform::new();
+        view_proj.update_view_ortho(-2.0, 2.0, -2.0, 2.0, -300.0, 100.0);
+        let view_proj: Matrix4<f32> = view_proj.view_proj.into();
+
+        // The light shines straight onto the triangle, so the highlight is at full strength
+        let mut renderer = SoftwareRenderer::new(64);
+        renderer.lighting.direction = [0.0, 0.0, -1.0];
+        renderer.lighting.ambient = 0.0;
+        let gray = Color::new(0.5, 0.5, 0.5, 1.0);
+        let mut triangles = triangle(false);
+        triangles.triangles[0].a.color = [gray.r, gray.g, gray.b, gray.a];
+        triangles.triangles[0].b.color = [gray.r, gray.g, gray.b, gray.a];
+        triangles.triangles[0].c.color = [gray.r, gray.g, gray.b, gray.a];
+        renderer.render(
+            &view_proj,
+            Color::black(),
+            &VertexBuffer::empty(),
+            &EdgeBuffer::empty(),
+            &triangles,
+        );
+        let diffuse = renderer.to_image().get_pixel(32, 32).0[0];
+        renderer.lighting.specular = 0.5;
+        renderer.render(
+            &view_proj,
+            Color::black(),
+            &VertexBuffer::empty(),
+            &EdgeBuffer::empty(),
+            &triangles,
+        );
+        let specular = renderer.to_image().get_pixel(32, 32).0[0];
+        assert!(specular > diffuse);
+
+        // An edge that lies exactly on a face is drawn on top of it
+        let edge = EdgeBuffer::new(vec![RenderEdge::new(
+            Point::from_f64(-1.0, 0.0, 0.0),
+            Point::from_f64(1.0, 0.0, 0.0),
+            Color::red(),
+        )]);
+        renderer.render(
+            &view_proj,
+            Color::black(),
+            &VertexBuffer::empty(),
+            &edge,
+            &triangles,
+        );
+        let pixel = renderer.to_image().get_pixel(32, 32).0;
+        assert!(pixel[0] == 255 && pixel[1] == 0);
+    }
+}
@@ -1,5 +1,6 @@
 use geop_rasterize::{
-    edge_buffer::EdgeBuffer, triangle_buffer::TriangleBuffer, vertex_buffer::VertexBuffer,
+    edge_buffer::EdgeBuffer, mesh_buffer::MeshBuffer, triangle_buffer::TriangleBuffer,
+    vertex_buffer::VertexBuffer,
 };
 use winit::{
     event::*,
@@ -8,7 +9,9 @@ use winit::{
     window::Window,
 };
 
-use crate::window_state::WindowState;
+use geop_topology::topology::volume::Volume;
+
+use crate::{camera_pipeline::Lighting, window_state::WindowState};
 
 pub struct GeopWindow<'a> {
     state: WindowState<'a>,
@@ -66,6 +69,24 @@ impl<'a> GeopWindow<'a> {
         Self { state }
     }
 
+    // Shows an indexed mesh in addition to the buffers passed to new.
+    pub fn with_mesh(mut self, mesh: &MeshBuffer) -> Self {
+        self.state.update_mesh(mesh);
+        self
+    }
+
+    // Lets a left click highlight the face or edge of the volumes under the cursor.
+    // The volumes should be the ones the buffers passed to new were rasterized from.
+    pub fn with_pickable_volumes(mut self, volumes: Vec<Volume>) -> Self {
+        self.state.set_pickable_volumes(volumes);
+        self
+    }
+
+    pub fn with_lighting(mut self, lighting: &Lighting) -> Self {
+        self.state.update_lighting(lighting);
+        self
+    }
+
     #[cfg_attr(target_arch = "wasm32", wasm_bindgen(start))]
     pub fn show(self, event_loop: EventLoop<()>) {
         let mut state = self.state;
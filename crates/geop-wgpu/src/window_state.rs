@@ -1,14 +1,41 @@
 use std::iter;
 
+use geop_geometry::{bounding_box::BoundingBox, point::Point};
 use geop_rasterize::{
-    edge_buffer::EdgeBuffer, triangle_buffer::TriangleBuffer, vertex_buffer::VertexBuffer,
+    edge_buffer::EdgeBuffer, hidden_line::StandardView, mesh_buffer::MeshBuffer,
+    triangle_buffer::TriangleBuffer, vertex_buffer::VertexBuffer,
+};
+use geop_topology::topology::{scene::Color, volume::Volume};
+use winit::{
+    dpi::PhysicalPosition,
+    event::*,
+    keyboard::{KeyCode, PhysicalKey},
+    window::Window,
 };
-use winit::{event::*, window::Window};
 
 #[cfg(target_arch = "wasm32")]
 use wasm_bindgen::prelude::*;
 
-use crate::pipeline_manager::PipelineManager;
+use crate::{
+    camera_pipeline::Lighting,
+    orbit_camera::OrbitCamera,
+    picking::{pick, rasterize_selection, Selection},
+    pipeline_manager::PipelineManager,
+};
+
+// Radians per pixel of mouse movement when orbiting.
+const ORBIT_SPEED: f32 = 0.01;
+// Zoom factor per line of mouse wheel scrolling.
+const ZOOM_SPEED: f32 = 1.1;
+// A left click that moves the mouse less than this many pixels picks instead of orbiting.
+const CLICK_PIXELS: f64 = 3.0;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum MouseDrag {
+    None,
+    Orbit,
+    Pan,
+}
 
 pub struct WindowState<'a> {
     surface: wgpu::Surface<'a>,
@@ -22,6 +49,49 @@ pub struct WindowState<'a> {
     window: &'a Window,
 
     pipeline_manager: PipelineManager,
+    camera: OrbitCamera,
+    // Bounds of everything in the scene, used to fit the camera. None if the scene is empty.
+    scene_bounds: Option<BoundingBox>,
+    drag: MouseDrag,
+    cursor_position: Option<PhysicalPosition<f64>>,
+    // Where the left mouse button was pressed, to tell clicks from drags.
+    press_position: Option<PhysicalPosition<f64>>,
+
+    // Volumes whose faces and edges can be picked, and the buffers they are shown with besides the selection.
+    volumes: Vec<Volume>,
+    edge_buffer: EdgeBuffer,
+    triangle_buffer: TriangleBuffer,
+    selection: Option<Selection>,
+}
+
+fn scene_bounds(
+    vertex_buffer: &VertexBuffer,
+    edge_buffer: &EdgeBuffer,
+    triangle_buffer: &TriangleBuffer,
+) -> Option<BoundingBox> {
+    let points: Vec<Point> = vertex_buffer
+        .vertices
+        .iter()
+        .map(|v| v.point())
+        .chain(
+            edge_buffer
+                .edges
+                .iter()
+                .flat_map(|e| [e.start.point(), e.end.point()]),
+        )
+        .chain(
+            triangle_buffer
+                .triangles
+                .iter()
+                .flat_map(|t| [t.a.point(), t.b.point(), t.c.point()]),
+        )
+        .collect();
+    let (first, rest) = points.split_first()?;
+    let mut bounding_box = BoundingBox::new(*first, *first);
+    for p in rest {
+        bounding_box.add_point(*p);
+    }
+    Some(bounding_box)
 }
 
 impl<'a> WindowState<'a> {
@@ -100,6 +170,12 @@ impl<'a> WindowState<'a> {
         pipeline_manager.update_triangles(&queue, triangle_buffer);
         pipeline_manager.update_vertices(&queue, vertex_buffer);
 
+        let mut camera = OrbitCamera::new(size.width.max(1) as f32 / size.height.max(1) as f32);
+        let scene_bounds = scene_bounds(vertex_buffer, edge_buffer, triangle_buffer);
+        if let Some(scene_bounds) = &scene_bounds {
+            camera.fit_bounding_box(scene_bounds);
+        }
+
         Self {
             surface,
             device,
@@ -108,9 +184,60 @@ impl<'a> WindowState<'a> {
             size,
             window,
             pipeline_manager,
+            camera,
+            scene_bounds,
+            drag: MouseDrag::None,
+            cursor_position: None,
+            press_position: None,
+            volumes: Vec::new(),
+            edge_buffer: EdgeBuffer::new(edge_buffer.edges.clone()),
+            triangle_buffer: TriangleBuffer::new(triangle_buffer.triangles.clone()),
+            selection: None,
         }
     }
 
+    // The volumes should be the ones the buffers were rasterized from, such that the picked face or edge matches the image.
+    pub fn set_pickable_volumes(&mut self, volumes: Vec<Volume>) {
+        self.volumes = volumes;
+        self.set_selection(None);
+    }
+
+    pub fn selection(&self) -> Option<&Selection> {
+        self.selection.as_ref()
+    }
+
+    // The highlighted face or edge is drawn before the scene, such that it wins the depth test against the scene.
+    fn set_selection(&mut self, selection: Option<Selection>) {
+        let (mut triangles, mut edges) = match &selection {
+            Some(selection) => rasterize_selection(selection, Color::red()),
+            None => (TriangleBuffer::empty(), EdgeBuffer::empty()),
+        };
+        triangles.join(&self.triangle_buffer);
+        edges.join(&self.edge_buffer);
+        self.pipeline_manager
+            .update_triangles(&self.queue, &triangles);
+        self.pipeline_manager.update_edges(&self.queue, &edges);
+        self.selection = selection;
+    }
+
+    fn pick_at(&mut self, position: PhysicalPosition<f64>) {
+        let width = self.size.width.max(1) as f64;
+        let height = self.size.height.max(1) as f64;
+        let x = (2.0 * position.x / width - 1.0) as f32;
+        let y = (1.0 - 2.0 * position.y / height) as f32;
+        let pixel_size = self.camera.pixel_size(self.size.height);
+        let selection = pick(&self.volumes, &self.camera, x, y, pixel_size);
+        self.set_selection(selection);
+    }
+
+    pub fn update_mesh(&mut self, mesh: &MeshBuffer) {
+        self.pipeline_manager.update_mesh(&self.queue, mesh);
+    }
+
+    pub fn update_lighting(&mut self, lighting: &Lighting) {
+        self.pipeline_manager.update_lighting(&self.queue, lighting);
+    }
+
     pub fn window(&self) -> &Window {
         &self.window
     }
@@ -126,27 +253,100 @@ impl<'a> WindowState<'a> {
             self.config.height = new_size.height;
             self.surface.configure(&self.device, &self.config);
             self.pipeline_manager.resize(&self.device, new_size);
+            self.camera.aspect = new_size.width as f32 / new_size.height as f32;
         }
     }
 
-    #[allow(unused_variables)]
+    // Left mouse button orbits, right or middle mouse button pans, the wheel zooms.
+    // A left click without dragging highlights the face or edge under the cursor, if pickable volumes are set.
+    // F fits the scene, 1-4 select the top, front, right and iso view, O toggles between perspective and orthographic projection.
     pub fn input(&mut self, event: &WindowEvent) -> bool {
-        false
+        match event {
+            WindowEvent::MouseInput { state, button, .. } => {
+                match (state, button) {
+                    (ElementState::Pressed, MouseButton::Left) => {
+                        self.press_position = self.cursor_position;
+                    }
+                    (ElementState::Released, MouseButton::Left) => {
+                        if let (Some(press), Some(release)) =
+                            (self.press_position.take(), self.cursor_position)
+                        {
+                            let moved = (release.x - press.x).hypot(release.y - press.y);
+                            if moved < CLICK_PIXELS && !self.volumes.is_empty() {
+                                self.pick_at(release);
+                            }
+                        }
+                    }
+                    _ => {}
+                }
+                self.drag = match (state, button) {
+                    (ElementState::Pressed, MouseButton::Left) => MouseDrag::Orbit,
+                    (ElementState::Pressed, MouseButton::Right | MouseButton::Middle) => {
+                        MouseDrag::Pan
+                    }
+                    _ => MouseDrag::None,
+                };
+                true
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                if let Some(last) = self.cursor_position {
+                    let dx = (position.x - last.x) as f32;
+                    let dy = (position.y - last.y) as f32;
+                    match self.drag {
+                        MouseDrag::Orbit => {
+                            self.camera.orbit(dx * ORBIT_SPEED, dy * ORBIT_SPEED);
+                        }
+                        MouseDrag::Pan => {
+                            let height = self.size.height.max(1) as f32;
+                            self.camera.pan(dx / height, dy / height);
+                        }
+                        MouseDrag::None => {}
+                    }
+                }
+                self.cursor_position = Some(*position);
+                self.drag != MouseDrag::None
+            }
+            WindowEvent::MouseWheel { delta, .. } => {
+                let lines = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y,
+                    MouseScrollDelta::PixelDelta(position) => position.y as f32 / 20.0,
+                };
+                self.camera.zoom(ZOOM_SPEED.powf(-lines));
+                true
+            }
+            WindowEvent::KeyboardInput {
+                event:
+                    KeyEvent {
+                        state: ElementState::Pressed,
+                        physical_key: PhysicalKey::Code(key),
+                        ..
+                    },
+                ..
+            } => {
+                match key {
+                    KeyCode::KeyF => {
+                        if let Some(scene_bounds) = &self.scene_bounds {
+                            self.camera.fit_bounding_box(scene_bounds);
+                        }
+                    }
+                    KeyCode::Digit1 => self.camera.set_view(StandardView::Top),
+                    KeyCode::Digit2 => self.camera.set_view(StandardView::Front),
+                    KeyCode::Digit3 => self.camera.set_view(StandardView::Right),
+                    KeyCode::Digit4 => self.camera.set_view(StandardView::Iso),
+                    KeyCode::KeyO => self.camera.toggle_projection(),
+                    _ => return false,
+                }
+                true
+            }
+            _ => false,
+        }
     }
 
     pub fn update(&mut self) {}
 
     pub fn render(&mut self) -> Result<(), wgpu::SurfaceError> {
-        let time_in_seconds = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_millis();
-        let pi = std::f32::consts::PI;
-        let time_in_seconds = (time_in_seconds % 1000000) as f32 / 1000.0;
-        let rotations_per_second = 0.1;
-        let omega = time_in_seconds * 2.0 * pi * rotations_per_second;
-
-        self.pipeline_manager.update_camera(&self.queue, omega);
+        self.pipeline_manager
+            .update_camera_orbit(&self.queue, &self.camera);
 
         let output = self.surface.get_current_texture()?;
         let view = output
@@ -2,7 +2,7 @@
 mod tests {
     use std::vec;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
     use geop_geometry::curves::bernstein_polynomial::BernsteinPolynomial;
     use geop_geometry::efloat::EFloat64;
     use geop_geometry::{point::Point, transforms::Transform};
@@ -42,9 +42,10 @@ mod tests {
                 false,
                 (-0.1, 1.1),
                 (-0.1, 1.1),
-                std::path::Path::new("src/generated_images/algebra/bernstein.png"),
+                &rendered_image("algebra/bernstein.png"),
             )
             .await;
+        assert_matches_book_image("algebra/bernstein.png");
     }
 
     #[rstest]
@@ -85,9 +86,10 @@ mod tests {
                 false,
                 (-0.5, 1.5),
                 (-0.1, 1.1),
-                std::path::Path::new("src/generated_images/algebra/bernstein_basis.png"),
+                &rendered_image("algebra/bernstein_basis.png"),
             )
             .await;
+        assert_matches_book_image("algebra/bernstein_basis.png");
     }
 
     #[rstest]
@@ -132,9 +134,10 @@ mod tests {
                 false,
                 (-0.5, 1.5),
                 (-0.1, 1.1),
-                std::path::Path::new("src/generated_images/algebra/bezier_curve.png"),
+                &rendered_image("algebra/bezier_curve.png"),
             )
             .await;
+        assert_matches_book_image("algebra/bezier_curve.png");
     }
 
     // #[rstest]
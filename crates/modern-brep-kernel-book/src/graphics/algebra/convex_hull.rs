@@ -2,15 +2,15 @@
 mod tests {
     use std::vec;
 
-    use geop_geometry::primitives::triangle::quickhull;
     use geop_geometry::point::Point;
+    use geop_geometry::primitives::triangle::quickhull;
     use geop_rasterize::{
         edge_buffer::EdgeBuffer, triangle_buffer::TriangleBuffer, vertex_buffer::VertexBuffer,
     };
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_convex_hull(#[future] renderer: Box<HeadlessRenderer>) {
@@ -37,8 +37,9 @@ mod tests {
                 TriangleBuffer::from_geop_triangle_buffer(faces),
                 false,
                 Point::from_f64(2.0, -2.0, 3.0),
-                std::path::Path::new("src/generated_images/algebra/convex_hull.png"),
+                &rendered_image("algebra/convex_hull.png"),
             )
             .await;
+        assert_matches_book_image("algebra/convex_hull.png");
     }
 }
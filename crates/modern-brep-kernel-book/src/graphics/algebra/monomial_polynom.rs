@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
     use geop_geometry::curves::monomial_polynom::MonomialPolynom;
     use geop_geometry::efloat::EFloat64;
     use geop_geometry::point::Point;
@@ -43,8 +43,9 @@ mod tests {
                 false,
                 (-0.1, 1.1),
                 (-0.1, 1.1),
-                std::path::Path::new("src/generated_images/algebra/monomial_polynom.png"),
+                &rendered_image("algebra/monomial_polynom.png"),
             )
             .await;
+        assert_matches_book_image("algebra/monomial_polynom.png");
     }
 }
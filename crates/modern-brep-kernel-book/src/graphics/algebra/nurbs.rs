@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
     use geop_geometry::curves::nurbs_curve::NurbsCurve;
     use geop_geometry::efloat::EFloat64;
     use geop_geometry::{point::Point, transforms::Transform};
@@ -54,9 +54,10 @@ mod tests {
                 false,
                 (-0.5, 8.5),
                 (-0.5, 7.5),
-                std::path::Path::new("src/generated_images/algebra/nurbs_basis.png"),
+                &rendered_image("algebra/nurbs_basis.png"),
             )
             .await;
+        assert_matches_book_image("algebra/nurbs_basis.png");
     }
     #[rstest]
     async fn test_nurbs_basis_with_sharp_border(#[future] renderer: Box<HeadlessRenderer>) {
@@ -99,10 +100,9 @@ mod tests {
                 false,
                 (-0.5, 4.5),
                 (-0.5, 7.5),
-                std::path::Path::new(
-                    "src/generated_images/algebra/nurbs_basis_with_sharp_border.png",
-                ),
+                &rendered_image("algebra/nurbs_basis_with_sharp_border.png"),
             )
             .await;
+        assert_matches_book_image("algebra/nurbs_basis_with_sharp_border.png");
     }
 }
@@ -67,7 +67,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
     #[rstest]
     async fn test_face_boolean_split_points(#[future] renderer: Box<HeadlessRenderer>) {
         let (face1, face2) = generate_scene();
@@ -92,9 +92,10 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/booleans/face_split_points.png"),
+                &rendered_image("booleans/face_split_points.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_split_points.png");
     }
 
     #[rstest]
@@ -126,9 +127,10 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/booleans/face_splits.png"),
+                &rendered_image("booleans/face_splits.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_splits.png");
     }
 
     #[rstest]
@@ -167,9 +169,10 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/booleans/face_splits_remesh.png"),
+                &rendered_image("booleans/face_splits_remesh.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_splits_remesh.png");
     }
 
     #[rstest]
@@ -184,7 +187,7 @@ mod tests {
             scene.edges.push((edge.clone(), Color::white()));
         }
 
-        let intersection_face = face_face_intersection(&face1, &face2);
+        let intersection_face = face_face_intersection(&face1, &face2).unwrap();
         match intersection_face {
             FaceFaceIntersection::Faces(faces) => {
                 assert!(faces.len() == 1);
@@ -210,9 +213,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/booleans/face_intersection.png"),
+                &rendered_image("booleans/face_intersection.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_intersection.png");
     }
 
     #[rstest]
@@ -245,9 +249,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/booleans/face_difference.png"),
+                &rendered_image("booleans/face_difference.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_difference.png");
     }
 
     #[rstest]
@@ -280,8 +285,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/booleans/face_union.png"),
+                &rendered_image("booleans/face_union.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_union.png");
     }
 }
@@ -67,11 +67,11 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
     #[rstest]
     async fn test_volume_split_edges(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
@@ -96,15 +96,16 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(2.0, -4.0, 2.0),
-                std::path::Path::new("src/generated_images/booleans/volume_split_edges.png"),
+                &rendered_image("booleans/volume_split_edges.png"),
             )
             .await;
+        assert_matches_book_image("booleans/volume_split_edges.png");
     }
 
     #[rstest]
     async fn test_face_subdivision(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
@@ -141,20 +142,21 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(2.0, -4.0, 2.0),
-                std::path::Path::new("src/generated_images/booleans/face_subdivions.png"),
+                &rendered_image("booleans/face_subdivions.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_subdivions.png");
     }
 
     #[rstest]
     async fn test_face_classification(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let splits = volume_split(&volume1, &volume2);
+        let splits = volume_split(&volume1, &volume2).unwrap();
         for split in splits {
             let f = split.face();
             let mut midpoint = Point::zero();
@@ -186,20 +188,21 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(2.0, -4.0, 2.0),
-                std::path::Path::new("src/generated_images/booleans/face_classification.png"),
+                &rendered_image("booleans/face_classification.png"),
             )
             .await;
+        assert_matches_book_image("booleans/face_classification.png");
     }
 
     #[rstest]
     async fn test_union_splits(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_1();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let splits = volume_split(&volume1, &volume2);
+        let splits = volume_split(&volume1, &volume2).unwrap();
         let splits = splits.iter().filter(|split| match split {
             VolumeSplit::AinB(_) => false,
             VolumeSplit::AonBSameSide(_) => true,
@@ -241,20 +244,21 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(2.0, -4.0, 2.0),
-                std::path::Path::new("src/generated_images/booleans/volume_union_splits.png"),
+                &rendered_image("booleans/volume_union_splits.png"),
             )
             .await;
+        assert_matches_book_image("booleans/volume_union_splits.png");
     }
 
     #[rstest]
     async fn test_union_splits2(#[future] renderer: Box<HeadlessRenderer>) {
         let (volume1, volume2) = generate_secene_2();
-        let split_edges = volume_split_edges(&volume1, &volume2);
+        let split_edges = volume_split_edges(&volume1, &volume2).unwrap();
         assert!(split_edges.len() == 4);
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
 
-        let splits = volume_split(&volume1, &volume2);
+        let splits = volume_split(&volume1, &volume2).unwrap();
         // let splits = splits.iter().filter(|split| match split {
         //     VolumeSplit::AinB(_) => false,
         //     VolumeSplit::AonBSameSide(_) => true,
@@ -296,8 +300,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(2.0, -4.0, 2.0),
-                std::path::Path::new("src/generated_images/booleans/volume_union_splits2.png"),
+                &rendered_image("booleans/volume_union_splits2.png"),
             )
             .await;
+        assert_matches_book_image("booleans/volume_union_splits2.png");
     }
 }
@@ -9,7 +9,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_ellipse_bb(#[future] renderer: Box<HeadlessRenderer>) {
@@ -43,8 +43,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/primitive_ellipse_bbox.png"),
+                &rendered_image("geometry/primitive_ellipse_bbox.png"),
             )
             .await;
+        assert_matches_book_image("geometry/primitive_ellipse_bbox.png");
     }
 }
@@ -13,7 +13,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_line_line_intersections(#[future] renderer: Box<HeadlessRenderer>) {
@@ -65,9 +65,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/line_line_intersections.png"),
+                &rendered_image("geometry/line_line_intersections.png"),
             )
             .await;
+        assert_matches_book_image("geometry/line_line_intersections.png");
     }
 
     #[rstest]
@@ -124,11 +125,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new(
-                    "src/generated_images/geometry/circle_circle_intersections.png",
-                ),
+                &rendered_image("geometry/circle_circle_intersections.png"),
             )
             .await;
+        assert_matches_book_image("geometry/circle_circle_intersections.png");
     }
 
     #[rstest]
@@ -193,9 +193,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/circle_line_intersections.png"),
+                &rendered_image("geometry/circle_line_intersections.png"),
             )
             .await;
+        assert_matches_book_image("geometry/circle_line_intersections.png");
     }
 
     #[rstest]
@@ -239,10 +240,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new(
-                    "src/generated_images/geometry/ellipse_ellipse_intersection.png",
-                ),
+                &rendered_image("geometry/ellipse_ellipse_intersection.png"),
             )
             .await;
+        assert_matches_book_image("geometry/ellipse_ellipse_intersection.png");
     }
 }
@@ -11,7 +11,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_primitive_line(#[future] renderer: Box<HeadlessRenderer>) {
@@ -27,9 +27,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/primitive_line.png"),
+                &rendered_image("geometry/primitive_line.png"),
             )
             .await;
+        assert_matches_book_image("geometry/primitive_line.png");
     }
 
     #[rstest]
@@ -43,9 +44,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/primitive_circle.png"),
+                &rendered_image("geometry/primitive_circle.png"),
             )
             .await;
+        assert_matches_book_image("geometry/primitive_circle.png");
     }
 
     #[rstest]
@@ -69,9 +71,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/primitive_ellipse.png"),
+                &rendered_image("geometry/primitive_ellipse.png"),
             )
             .await;
+        assert_matches_book_image("geometry/primitive_ellipse.png");
     }
 
     #[rstest]
@@ -90,8 +93,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/geometry/primitive_helix.png"),
+                &rendered_image("geometry/primitive_helix.png"),
             )
             .await;
+        assert_matches_book_image("geometry/primitive_helix.png");
     }
 }
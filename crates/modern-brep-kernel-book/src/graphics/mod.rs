@@ -20,7 +20,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_headless_renderer_light(#[future] renderer: Box<HeadlessRenderer>) {
@@ -37,9 +37,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -2.0, 1.0),
-                std::path::Path::new("src/generated_images/test_light.png"),
+                &rendered_image("test_light.png"),
             )
             .await;
+        assert_matches_book_image("test_light.png");
     }
 
     #[rstest]
@@ -57,9 +58,10 @@ mod tests {
                 true,
                 false,
                 Point::from_f64(0.0, -2.0, 1.0),
-                std::path::Path::new("src/generated_images/test_dark.png"),
+                &rendered_image("test_dark.png"),
             )
             .await;
+        assert_matches_book_image("test_dark.png");
     }
 
     fn try_something_impossible() -> TopologyResult<Edge> {
@@ -155,14 +157,9 @@ mod tests {
         match f() {
             Ok(scene) => {
                 renderer
-                    .render_to_file(
-                        &scene,
-                        false,
-                        false,
-                        camera_pos,
-                        std::path::Path::new(file_name),
-                    )
+                    .render_to_file(&scene, false, false, camera_pos, &rendered_image(file_name))
                     .await;
+                assert_matches_book_image(file_name);
             }
             Err(e) => {
                 renderer
@@ -171,9 +168,10 @@ mod tests {
                         false,
                         false,
                         camera_pos,
-                        std::path::Path::new(file_name),
+                        &rendered_image(file_name),
                     )
                     .await;
+                assert_matches_book_image(file_name);
 
                 TopologyResult::<()>::Err(e).unwrap();
             }
@@ -185,7 +183,7 @@ mod tests {
     async fn test_error_handling(#[future] renderer: Box<HeadlessRenderer>) {
         render_failable_closure(
             &mut *renderer.await,
-            "src/generated_images/test_error_handling.png",
+            "test_error_handling.png",
             Point::from_f64(0.0, -2.0, 1.0),
             || {
                 let line = try_something_impossible()?;
@@ -8,7 +8,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_contour(#[future] renderer: Box<HeadlessRenderer>) {
@@ -41,8 +41,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/topology/contours.png"),
+                &rendered_image("topology/contours.png"),
             )
             .await;
+        assert_matches_book_image("topology/contours.png");
     }
 }
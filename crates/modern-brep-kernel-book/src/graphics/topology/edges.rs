@@ -11,7 +11,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_edges(#[future] renderer: Box<HeadlessRenderer>) {
@@ -59,8 +59,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -3.0, 0.0),
-                std::path::Path::new("src/generated_images/topology/edges.png"),
+                &rendered_image("topology/edges.png"),
             )
             .await;
+        assert_matches_book_image("topology/edges.png");
     }
 }
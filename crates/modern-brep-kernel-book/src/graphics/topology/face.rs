@@ -134,7 +134,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_face1(#[future] renderer: Box<HeadlessRenderer>) {
@@ -146,9 +146,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/topology/face1.png"),
+                &rendered_image("topology/face1.png"),
             )
             .await;
+        assert_matches_book_image("topology/face1.png");
     }
 
     #[rstest]
@@ -161,9 +162,10 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(0.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/topology/face1wire.png"),
+                &rendered_image("topology/face1wire.png"),
             )
             .await;
+        assert_matches_book_image("topology/face1wire.png");
     }
 
     #[rstest]
@@ -176,9 +178,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(4.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/topology/face2.png"),
+                &rendered_image("topology/face2.png"),
             )
             .await;
+        assert_matches_book_image("topology/face2.png");
     }
 
     #[rstest]
@@ -191,9 +194,10 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(4.0, -4.0, 0.0),
-                std::path::Path::new("src/generated_images/topology/face2wire.png"),
+                &rendered_image("topology/face2wire.png"),
             )
             .await;
+        assert_matches_book_image("topology/face2wire.png");
     }
 
     #[rstest]
@@ -206,9 +210,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(3.0, -3.0, 3.0),
-                std::path::Path::new("src/generated_images/topology/face3.png"),
+                &rendered_image("topology/face3.png"),
             )
             .await;
+        assert_matches_book_image("topology/face3.png");
     }
 
     #[rstest]
@@ -221,9 +226,10 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(3.0, -3.0, 3.0),
-                std::path::Path::new("src/generated_images/topology/face3wire.png"),
+                &rendered_image("topology/face3wire.png"),
             )
             .await;
+        assert_matches_book_image("topology/face3wire.png");
     }
 
     #[rstest]
@@ -236,9 +242,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(3.0, -3.0, 3.0),
-                std::path::Path::new("src/generated_images/topology/face4.png"),
+                &rendered_image("topology/face4.png"),
             )
             .await;
+        assert_matches_book_image("topology/face4.png");
     }
 
     #[rstest]
@@ -251,8 +258,9 @@ mod tests {
                 false,
                 true,
                 Point::from_f64(3.0, -3.0, 3.0),
-                std::path::Path::new("src/generated_images/topology/face4wire.png"),
+                &rendered_image("topology/face4wire.png"),
             )
             .await;
+        assert_matches_book_image("topology/face4wire.png");
     }
 }
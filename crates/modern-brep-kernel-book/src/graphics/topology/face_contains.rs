@@ -19,7 +19,7 @@ mod tests {
     use geop_wgpu::headless_renderer::HeadlessRenderer;
     use rstest::rstest;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
 
     #[rstest]
     async fn test_face_contains_sphere(#[future] renderer: Box<HeadlessRenderer>) {
@@ -50,9 +50,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, -2.0, 1.0),
-                std::path::Path::new("src/generated_images/topology/face_contains.png"),
+                &rendered_image("topology/face_contains.png"),
             )
             .await;
+        assert_matches_book_image("topology/face_contains.png");
     }
 
     #[rstest]
@@ -93,9 +94,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, 0.1, 2.20),
-                std::path::Path::new("src/generated_images/topology/face_contains_rectangle.png"),
+                &rendered_image("topology/face_contains_rectangle.png"),
             )
             .await;
+        assert_matches_book_image("topology/face_contains_rectangle.png");
     }
 
     #[rstest]
@@ -140,8 +142,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(0.0, 0.1, 2.20),
-                std::path::Path::new("src/generated_images/topology/face_contains_rectangle2.png"),
+                &rendered_image("topology/face_contains_rectangle2.png"),
             )
             .await;
+        assert_matches_book_image("topology/face_contains_rectangle2.png");
     }
 }
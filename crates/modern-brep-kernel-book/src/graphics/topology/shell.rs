@@ -2,7 +2,7 @@
 mod tests {
     use std::rc::Rc;
 
-    use crate::tests::renderer;
+    use crate::tests::{assert_matches_book_image, rendered_image, renderer};
     use geop_geometry::{
         efloat::EFloat64,
         point::Point,
@@ -38,9 +38,10 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(2.0, -2.0, 2.0),
-                std::path::Path::new("src/generated_images/topology/shell1.png"),
+                &rendered_image("topology/shell1.png"),
             )
             .await;
+        assert_matches_book_image("topology/shell1.png");
     }
 
     #[rstest]
@@ -77,7 +78,7 @@ mod tests {
             ))),
         );
 
-        let shell = extrude(face1, Point::unit_y());
+        let shell = extrude(face1, Point::unit_y()).unwrap();
 
         let mut scene = Scene::new(vec![], vec![], vec![], vec![]);
         scene.volumes.push((shell, Color::light_gray()));
@@ -88,8 +89,9 @@ mod tests {
                 false,
                 false,
                 Point::from_f64(2.0, -2.0, 2.0),
-                std::path::Path::new("src/generated_images/topology/shell2.png"),
+                &rendered_image("topology/shell2.png"),
             )
             .await;
+        assert_matches_book_image("topology/shell2.png");
     }
 }
@@ -2,11 +2,32 @@ mod graphics;
 
 #[cfg(test)]
 pub mod tests {
-    use geop_wgpu::headless_renderer::HeadlessRenderer;
+    use std::path::{Path, PathBuf};
+
+    use geop_wgpu::{
+        golden_image::{assert_matches_reference, ImageTolerance},
+        headless_renderer::HeadlessRenderer,
+    };
     use rstest::fixture;
 
+    // The reference images are rendered on the CPU, so the tests do not depend on the GPU of the machine.
     #[fixture]
     pub async fn renderer() -> Box<HeadlessRenderer> {
-        Box::new(HeadlessRenderer::new().await)
+        Box::new(HeadlessRenderer::new_software())
+    }
+
+    // The images in src/generated_images are shown in the book and are the references of the tests.
+    // Tests render into target/generated_images and compare the result against the image in the book.
+    // Set GEOP_UPDATE_REFERENCES=1 to regenerate the images of the book instead.
+    pub fn rendered_image(name: &str) -> PathBuf {
+        Path::new("target/generated_images").join(name)
+    }
+
+    pub fn assert_matches_book_image(name: &str) {
+        assert_matches_reference(
+            &rendered_image(name),
+            &Path::new("src/generated_images").join(name),
+            &ImageTolerance::default(),
+        );
     }
 }